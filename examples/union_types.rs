@@ -0,0 +1,33 @@
+use rusty_zod::prelude::*;
+use serde_json::json;
+
+fn main() {
+    // Every branch must pass; each branch normalizes its own fields
+    let account = all_of![
+        object().field("username", string().trim().min_length(3)).passthrough(),
+        object().field("email", string().trim().to_lowercase().email()).passthrough(),
+    ];
+
+    let input = json!({"username": "  ferris ", "email": " FERRIS@RUST-LANG.ORG "});
+    match account.validate(&input) {
+        Ok(value) => println!("Account: {}", value),
+        Err(e) => println!("Account error: {}", e),
+    }
+
+    // Report the error of the branch that came closest to matching
+    let id = best_of![
+        string().uuid(),
+        number().integer().min(1.0);
+        |e: &ValidationError| match e.context.code.as_str() {
+            "string.pattern" => 0,
+            _ => 1,
+        }
+    ];
+
+    for value in [json!(42), json!("550e8400-e29b-41d4-a716-446655440000"), json!("not-an-id")] {
+        match id.validate(&value) {
+            Ok(v) => println!("Valid id: {}", v),
+            Err(e) => println!("Invalid id {}: {} ({})", value, e, e.context.code),
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use rusty_zod::{prelude::*, StringSchema};
+use rusty_zod::prelude::*;
 use serde_json::json;
 
 fn main() {
     let user_schema = object!({
         "id" => number()
             .min(1.0)
-            .error_message("number.min", "ID must be a positive number"),
+            .error_message("number.too_small", "ID must be a positive number"),
         "username" => string()
             .min_length(3)
             .max_length(20)
@@ -31,8 +31,8 @@ fn main() {
             .max(150.0)
             .coerce()
             .optional()
-            .error_message("number.min", "User must be at least {min} years old")
-            .error_message("number.max", "Invalid age value"),
+            .error_message("number.too_small", "User must be at least {min} years old")
+            .error_message("number.too_large", "Invalid age value"),
         "is_active" => boolean()
     });
 
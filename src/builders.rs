@@ -0,0 +1,165 @@
+//! Schema constructors, grouped for renamed imports:
+//!
+//! ```
+//! use rusty_zod::builders as z;
+//! use rusty_zod::prelude::*;
+//!
+//! let schema = z::object().field("name", z::string().min_length(1));
+//! assert!(schema.validate(&serde_json::json!({"name": "Ada"})).is_ok());
+//! ```
+
+use crate::schemas::{
+    Schema, UnionSchema, IntoSchemaList,
+    string::StringSchemaImpl,
+    NumberSchema, BooleanSchema, BytesSchema, ArraySchema, ObjectSchema,
+    NeverSchema, NotSchema, AnySchema, SwitchSchema, ValidationContext, ConditionalSchema, LiteralSchema,
+    MoneyPolicy, ClaimsPolicy, EnvelopePolicy,
+};
+#[cfg(feature = "chrono")]
+use crate::schemas::{DateSchema, DateTimeSchema};
+#[cfg(feature = "rust_decimal")]
+use crate::schemas::DecimalSchema;
+
+/// Create a new string schema
+pub fn string() -> StringSchemaImpl {
+    StringSchemaImpl::default()
+}
+
+/// Create a new number schema
+pub fn number() -> NumberSchema {
+    NumberSchema::default()
+}
+
+/// Create a new boolean schema
+pub fn boolean() -> BooleanSchema {
+    BooleanSchema::default()
+}
+
+/// Create a new encoded binary schema (base64 by default)
+pub fn bytes() -> BytesSchema {
+    BytesSchema::default()
+}
+
+/// Create a new array schema
+pub fn array<S: Schema>(schema: S) -> ArraySchema<S> {
+    ArraySchema::new(schema)
+}
+
+/// Create a new array schema that rejects duplicate items
+pub fn set<S: Schema>(schema: S) -> ArraySchema<S> {
+    ArraySchema::new(schema).unique()
+}
+
+/// Create a new object schema
+pub fn object() -> ObjectSchema {
+    ObjectSchema::default()
+}
+
+/// Create a money object schema `{ "amount": ..., "currency": ... }`
+/// accepting any ISO 4217 currency and non-negative amounts
+pub fn money() -> ObjectSchema {
+    MoneyPolicy::default().schema()
+}
+
+/// Create a money object schema with the given [`MoneyPolicy`]
+pub fn money_with(policy: MoneyPolicy) -> ObjectSchema {
+    policy.schema()
+}
+
+/// Create a JWT claims object schema checking `exp` and `nbf` against the
+/// system clock; merge a schema for the application's own claims into it
+pub fn claims_schema() -> ObjectSchema {
+    ClaimsPolicy::default().schema()
+}
+
+/// Create a JWT claims object schema with the given [`ClaimsPolicy`]
+pub fn claims_schema_with(policy: ClaimsPolicy) -> ObjectSchema {
+    policy.schema()
+}
+
+/// Create a webhook envelope schema `{ "id", "timestamp", "type", "data" }`
+/// whose `data` is validated by the schema `payloads` lists for its `type`,
+/// and whose Unix `timestamp` is within five minutes of the system clock
+pub fn envelope<K: Into<String>, S: Schema>(payloads: impl IntoIterator<Item = (K, S)>) -> ObjectSchema {
+    EnvelopePolicy::default().schema(payloads)
+}
+
+/// Create a webhook envelope schema with the given [`EnvelopePolicy`]
+pub fn envelope_with<K: Into<String>, S: Schema>(payloads: impl IntoIterator<Item = (K, S)>, policy: EnvelopePolicy) -> ObjectSchema {
+    policy.schema(payloads)
+}
+
+/// Create an object schema for `page`/`page_size`/`cursor` query
+/// parameters, with page sizes up to `max_page_size`
+pub fn pagination(max_page_size: u32) -> ObjectSchema {
+    crate::schemas::pagination::schema(max_page_size)
+}
+
+/// Create a new calendar date schema (`YYYY-MM-DD`)
+#[cfg(feature = "chrono")]
+pub fn date() -> DateSchema {
+    DateSchema::default()
+}
+
+/// Create a new RFC 3339 date-time schema
+#[cfg(feature = "chrono")]
+pub fn datetime() -> DateTimeSchema {
+    DateTimeSchema::default()
+}
+
+/// Create a new decimal string schema
+#[cfg(feature = "rust_decimal")]
+pub fn decimal() -> DecimalSchema {
+    DecimalSchema::default()
+}
+
+/// Create a schema that accepts any value unchanged
+pub fn any() -> AnySchema {
+    AnySchema::default()
+}
+
+/// Create a schema that rejects every value
+pub fn never() -> NeverSchema {
+    NeverSchema::default()
+}
+
+/// Create a schema that succeeds only when `schema` fails
+pub fn not<S: Schema>(schema: S) -> NotSchema {
+    NotSchema::new(schema)
+}
+
+/// Create a schema that validates with the case `selector` picks from the
+/// current [`ValidationContext`]
+pub fn switch<F, K>(selector: F) -> SwitchSchema
+where
+    F: Fn(&ValidationContext) -> Option<K> + Send + Sync + 'static,
+    K: Into<String>,
+{
+    SwitchSchema::new(selector)
+}
+
+/// Create a schema that validates with `on` while the context enables
+/// `flag`, and with `off` otherwise
+pub fn feature_gated(flag: impl Into<String>, on: impl Schema, off: impl Schema) -> SwitchSchema {
+    let flag = flag.into();
+    SwitchSchema::new(move |ctx| Some(if ctx.is_enabled(&flag) { "on" } else { "off" }))
+        .case("on", on)
+        .case("off", off)
+}
+
+/// Create a schema that accepts only `value`, e.g. `eq("card")`
+pub fn eq(value: impl Into<serde_json::Value>) -> LiteralSchema {
+    LiteralSchema::new(value)
+}
+
+/// Create a schema that validates with `then` when the value passes
+/// `predicate`, and with `otherwise` when it does not
+pub fn conditional(predicate: impl Schema, then: impl Schema, otherwise: impl Schema) -> ConditionalSchema {
+    ConditionalSchema::new(predicate, then, otherwise)
+}
+
+/// Create a new union schema from a `Vec`, array or tuple of schemas;
+/// tuples may mix types, e.g. `union((string(), number()))`
+pub fn union(schemas: impl IntoSchemaList) -> UnionSchema {
+    UnionSchema::new(schemas.into_schema_list())
+}
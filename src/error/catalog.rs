@@ -0,0 +1,129 @@
+use std::{collections::HashMap, sync::RwLock};
+
+use super::ValidationError;
+use super::canonical_code;
+use super::message_check::debug_check_template;
+
+/// Built-in locale of every default message
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Message templates for one locale, keyed by error code
+///
+/// Catalogs are registered process-wide with [`MessageCatalog::load`] and
+/// used by [`Schema::validate_with_locale`](crate::schemas::Schema::validate_with_locale)
+/// and [`ValidationError::localized`]. Templates take the same
+/// `{placeholders}` as `error_message`, filled in when the message is
+/// rendered, with plural forms chosen by the locale's rules (see
+/// [`ValidationError::localized`]). Codes a catalog leaves out keep the built-in English message,
+/// and messages set with `error_message` are never translated.
+#[derive(Clone, Debug, Default)]
+pub struct MessageCatalog {
+    messages: HashMap<String, String>,
+}
+
+static CATALOGS: RwLock<Option<HashMap<String, MessageCatalog>>> = RwLock::new(None);
+
+impl MessageCatalog {
+    /// Register `messages` for `locale`, adding to and overriding whatever was
+    /// loaded for it before. Loading `"en"` overrides the built-in messages.
+    pub fn load<K, V>(locale: impl Into<String>, messages: impl IntoIterator<Item = (K, V)>)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut catalogs = CATALOGS.write().unwrap_or_else(|e| e.into_inner());
+        let catalog = catalogs.get_or_insert_with(HashMap::new).entry(locale.into()).or_default();
+        for (code, template) in messages {
+            let (code, template) = (canonical_code(code.into()), template.into());
+            debug_check_template(&code, &template);
+            catalog.messages.insert(code, template);
+        }
+    }
+
+    /// Remove everything loaded for `locale`
+    pub fn unload(locale: &str) {
+        if let Some(catalogs) = CATALOGS.write().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            catalogs.remove(locale);
+        }
+    }
+
+    /// Template for `code` in `locale`, falling back from a regional locale
+    /// such as `pt-BR` to its language `pt`
+    pub fn template(locale: &str, code: &str) -> Option<String> {
+        let catalogs = CATALOGS.read().unwrap_or_else(|e| e.into_inner());
+        let catalogs = catalogs.as_ref()?;
+        let lookup = |locale: &str| catalogs.get(locale).and_then(|c| c.messages.get(code)).cloned();
+        lookup(locale).or_else(|| {
+            let language = locale.split(['-', '_']).next()?;
+            (language != locale).then(|| lookup(language)).flatten()
+        })
+    }
+}
+
+impl ValidationError {
+    /// Translate the message into `locale` using the loaded catalogs, unless
+    /// it was set with `error_message` or by a per-schema error map
+    ///
+    /// `{name|...}` plural forms in the translation are picked by the
+    /// plural rules of `locale`'s language, listed in CLDR order: one,
+    /// few, many and other for Russian, Ukrainian, Belarusian, Polish,
+    /// Czech and Slovak; zero to other for Arabic; a single form for
+    /// Japanese, Chinese, Korean, Vietnamese, Thai, Indonesian and Malay;
+    /// one (0 and 1) and other for French and Portuguese. Other languages
+    /// use English's one (exactly 1) and other.
+    pub fn localized(mut self, locale: &str) -> Self {
+        if !self.context.custom_message {
+            if let Some(template) = MessageCatalog::template(locale, &self.context.code) {
+                self.context.message = Some(template);
+                self.context.locale = Some(locale.to_string());
+                self.context.custom_message = true;
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{number, object, string, Schema, StringSchema};
+
+    #[test]
+    fn test_validate_with_locale() {
+        MessageCatalog::load("de", [
+            ("string.too_short", "Mindestens {min_length} Zeichen"),
+            ("number.too_large", "Höchstens {max_value}"),
+        ]);
+        let schema = object()
+            .field("name", string().min_length(3))
+            .field("age", number().max(150.0).error_message("number.too_large", "Too old"));
+
+        let err = schema.validate_with_locale(&json!({"name": "al", "age": 1}), "de-AT").unwrap_err();
+        assert_eq!(err.to_string(), "Mindestens 3 Zeichen");
+
+        let err = schema.validate_with_locale(&json!({"name": "alice", "age": 200}), "de").unwrap_err();
+        assert_eq!(err.to_string(), "Too old");
+
+        let err = schema.validate_with_locale(&json!({"name": "al", "age": 1}), "fr").unwrap_err();
+        assert_eq!(err.to_string(), "String must be at least 3 characters long");
+    }
+
+    #[test]
+    fn test_localized_plurals() {
+        MessageCatalog::load("ru", [("string.too_short", "Минимум {min_length|# символ|# символа|# символов}")]);
+        let err = |min| string().min_length(min).validate_with_locale(&json!(""), "ru-RU").unwrap_err().to_string();
+        assert_eq!([1, 2, 5].map(err), ["Минимум 1 символ", "Минимум 2 символа", "Минимум 5 символов"]);
+    }
+
+    #[test]
+    fn test_load_merges_and_unload() {
+        MessageCatalog::load("x-test", [("string.email", "Bad email")]);
+        MessageCatalog::load("x-test", [("string.pattern", "Bad format")]);
+        assert_eq!(MessageCatalog::template("x-test", "string.email").as_deref(), Some("Bad email"));
+        assert_eq!(MessageCatalog::template("x-test", "string.pattern").as_deref(), Some("Bad format"));
+
+        MessageCatalog::unload("x-test");
+        assert_eq!(MessageCatalog::template("x-test", "string.email"), None);
+    }
+}
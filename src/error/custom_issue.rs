@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use super::{ErrorCode, ValidationDetails, ValidationError};
+
+/// Failure reported by a `custom` validator
+///
+/// Validators may return a plain `String` message, or a `CustomIssue` to pick
+/// their own error code and fill details, so that `{placeholders}`, message
+/// catalogs, error maps and clients branching on `code` treat them like
+/// built-in checks.
+#[derive(Debug, Clone, Default)]
+pub struct CustomIssue {
+    /// Error code, the schema's `<type>.custom` (e.g. `number.custom`, or
+    /// `string.custom.<label>`) when unset
+    pub code: Option<String>,
+    pub message: Option<String>,
+    /// Boxed to keep `Result<(), CustomIssue>` small
+    pub details: Box<ValidationDetails>,
+}
+
+impl CustomIssue {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self { code: Some(code.into()), ..Self::default() }
+    }
+
+    /// Message template; without one the code's default message is used
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn with_details(mut self, f: impl FnOnce(&mut ValidationDetails)) -> Self {
+        f(&mut self.details);
+        self
+    }
+
+    /// Error reported under `default_code` unless the issue names its own,
+    /// worded by the schema's `messages` first. A message returned without a
+    /// code is the user's own wording; one returned with a code is that code's
+    /// default, which error maps and catalogs may replace.
+    pub(crate) fn into_error(self, default_code: String, messages: &HashMap<String, String>) -> ValidationError {
+        let has_code = self.code.is_some();
+        let code = self.code.unwrap_or(default_code);
+        let mut err = ValidationError::new(code.as_str());
+        err.context.details = *self.details;
+        match (messages.get(&code), self.message) {
+            (Some(message), _) => err.custom_message(message.clone()),
+            (None, Some(message)) if has_code => err.message(message),
+            (None, Some(message)) => err.custom_message(message),
+            (None, None) => err,
+        }
+    }
+}
+
+impl From<String> for CustomIssue {
+    fn from(message: String) -> Self {
+        Self { message: Some(message), ..Self::default() }
+    }
+}
+
+impl From<&str> for CustomIssue {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+/// Issue under a built-in code, worded by its default message
+impl From<ErrorCode> for CustomIssue {
+    fn from(code: ErrorCode) -> Self {
+        Self::new(code.code()).message(code.default_message())
+    }
+}
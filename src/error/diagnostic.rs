@@ -0,0 +1,140 @@
+//! Terminal diagnostics pointing into the JSON source, via `miette`
+
+use std::fmt;
+use miette::{Diagnostic, GraphicalReportHandler, GraphicalTheme, LabeledSpan, NamedSource, SourceCode};
+
+use super::{ValidationError, ValidationErrors};
+use crate::schemas::spans::attach_span;
+
+/// Validation failures of a JSON document as a [`miette::Diagnostic`], with
+/// one label per failed path
+///
+/// Return it from a `miette::Result` to let the application's report handler
+/// draw it, or call [`SourceDiagnostic::render`] for a plain-text rendering.
+#[derive(Debug)]
+pub struct SourceDiagnostic {
+    source: NamedSource<String>,
+    errors: Vec<ValidationError>,
+}
+
+impl SourceDiagnostic {
+    /// Errors found in `source`, read from the file or stream called `name`.
+    /// Errors without a span are located by their path.
+    pub fn new(name: impl AsRef<str>, source: impl Into<String>, errors: impl Into<ValidationErrors>) -> Self {
+        let source = source.into();
+        let errors = errors.into().into_vec().into_iter().map(|err| attach_span(err, &source)).collect();
+        Self { source: NamedSource::new(name, source), errors }
+    }
+
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    /// Render without colors, e.g. for logs or tests
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+            .render_report(&mut out, self)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+impl fmt::Display for SourceDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.as_slice() {
+            [err] => write!(f, "{}", err),
+            errors => write!(f, "{} validation errors", errors.len()),
+        }
+    }
+}
+
+impl std::error::Error for SourceDiagnostic {}
+
+impl Diagnostic for SourceDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        match self.errors.as_slice() {
+            [err] => Some(Box::new(&err.context.code)),
+            _ => None,
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let labels = self.errors.iter().filter_map(|err| {
+            let span = err.context.details.span.as_ref()?;
+            let label = match err.context.path.is_empty() {
+                true => err.to_string(),
+                false => format!("{}: {}", err.formatted_path(), err),
+            };
+            Some(LabeledSpan::new(Some(label), span.start, span.end - span.start))
+        });
+        Some(Box::new(labels))
+    }
+}
+
+impl ValidationError {
+    /// This error as a diagnostic pointing into `source`, see [`SourceDiagnostic`]
+    pub fn to_diagnostic(&self, name: impl AsRef<str>, source: impl Into<String>) -> SourceDiagnostic {
+        SourceDiagnostic::new(name, source, self.clone())
+    }
+}
+
+impl ValidationErrors {
+    /// These errors as one diagnostic pointing into `source`, see [`SourceDiagnostic`]
+    pub fn to_diagnostic(&self, name: impl AsRef<str>, source: impl Into<String>) -> SourceDiagnostic {
+        SourceDiagnostic::new(name, source, self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{number, object, string};
+    use crate::error::ErrorCode;
+    use crate::schemas::{Schema, StringSchema};
+
+    const CONFIG: &str = "{\n  \"name\": \"\",\n  \"port\": \"8080\"\n}";
+
+    #[test]
+    fn test_diagnostic_labels_each_path() {
+        let errors = ValidationErrors::new(vec![
+            ValidationError::new(ErrorCode::StringTooShort).at("name").message("Name is required"),
+            ValidationError::new(ErrorCode::InvalidType).at("port").message("Expected number"),
+        ]);
+        let diagnostic = errors.to_diagnostic("config.json", CONFIG);
+        assert_eq!(diagnostic.to_string(), "2 validation errors");
+
+        let labels: Vec<_> = diagnostic.labels().unwrap().collect();
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].label(), Some("name: Name is required"));
+        assert_eq!(&CONFIG[labels[1].offset()..labels[1].offset() + labels[1].len()], "\"8080\"");
+
+        let rendered = diagnostic.render();
+        assert!(rendered.contains("config.json:2:"));
+        assert!(rendered.contains("port: Expected number"));
+    }
+
+    #[test]
+    fn test_diagnostic_labels_use_path_formatter() {
+        let errors = ValidationErrors::from(ValidationError::new(ErrorCode::InvalidType).at("port").message("Expected number"))
+            .with_path_formatter(crate::error::PathStyle::JsonPointer);
+        let diagnostic = errors.to_diagnostic("config.json", CONFIG);
+        let labels: Vec<_> = diagnostic.labels().unwrap().collect();
+        assert_eq!(labels[0].label(), Some("/port: Expected number"));
+    }
+
+    #[test]
+    fn test_validate_str_diagnostic() {
+        let schema = object()
+            .field("name", string().min_length(1))
+            .field("port", number());
+        let err = schema.validate_str(CONFIG).unwrap_err();
+        let diagnostic = err.to_diagnostic("config.json", CONFIG);
+        assert_eq!(diagnostic.code().unwrap().to_string(), err.context.code);
+        assert_eq!(diagnostic.errors()[0].context.details.span, err.context.details.span);
+    }
+}
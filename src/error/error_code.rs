@@ -1,31 +1,315 @@
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum ErrorCode {
+/// Declares every built-in code once, with its string form and default message
+macro_rules! error_codes {
+    ($($(#[$attr:meta])* $variant:ident => $code:literal, $message:literal;)*) => {
+        /// Every error code emitted by the built-in schemas
+        ///
+        /// `ValidationError::code` maps an error back to its variant, so
+        /// callers can match exhaustively instead of comparing strings.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($(#[$attr])* $variant,)*
+
+            /// Code `"custom"` carrying its own default message
+            Custom(String),
+            /// Any other code, e.g. `string.custom.<label>` or one raised by
+            /// a user-defined schema
+            Other(String),
+        }
+
+        impl ErrorCode {
+            pub fn default_message(&self) -> String {
+                match self {
+                    $(ErrorCode::$variant => $message.into(),)*
+                    ErrorCode::Custom(msg) => msg.clone(),
+                    ErrorCode::Other(_) => "Validation error".into(),
+                }
+            }
+
+            pub fn code(&self) -> &str {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                    ErrorCode::Custom(_) => "custom",
+                    ErrorCode::Other(code) => code,
+                }
+            }
+
+            /// Variant for a code string, also under the names earlier
+            /// releases emitted it with (`number.min` for `number.too_small`);
+            /// codes that are not built in become [`ErrorCode::Other`]
+            pub fn from_code(code: &str) -> Self {
+                match code {
+                    $($code => ErrorCode::$variant,)*
+                    other => match LEGACY_CODES.iter().find(|(legacy, _)| *legacy == other) {
+                        Some((_, code)) => code.clone(),
+                        None => ErrorCode::Other(other.to_string()),
+                    },
+                }
+            }
+
+            /// Every built-in code in declaration order, with its default
+            /// message template and the detail fields its errors carry
+            ///
+            /// Built-in schemas word their errors with these templates unless
+            /// an `error_message` or catalog entry replaces them. The
+            /// exceptions carry text no template can: parse errors quote the
+            /// parser, exclusive number bounds say "greater than" or "less
+            /// than", a null object is "This field is required", and array
+            /// items are reported as "Item {i} is invalid".
+            ///
+            /// Meant for generating client-side error tables and translation
+            /// files, and for spotting codes added by a crate upgrade.
+            /// Serializes as an array of `{code, template, details}`.
+            pub fn all() -> &'static [ErrorCodeInfo] {
+                static ALL: &[ErrorCodeInfo] = &[
+                    $(ErrorCodeInfo {
+                        code: ErrorCode::$variant,
+                        template: $message,
+                        details: ErrorCode::$variant.detail_fields(),
+                    },)*
+                ];
+                ALL
+            }
+        }
+    };
+}
+
+/// Codes emitted by earlier releases under other names, mapped to the
+/// documented ones. Still accepted as `error_message` and catalog keys.
+const LEGACY_CODES: &[(&str, ErrorCode)] = &[
+    ("number.min", ErrorCode::NumberTooSmall),
+    ("number.max", ErrorCode::NumberTooLarge),
+    ("number.invalid_type", ErrorCode::InvalidNumber),
+    ("array.min_items", ErrorCode::ArrayTooShort),
+    ("array.max_items", ErrorCode::ArrayTooLong),
+    ("array.item", ErrorCode::InvalidArrayItem),
+];
+
+/// `code` as emitted, with legacy spellings replaced by the documented ones
+pub(crate) fn canonical_code(code: String) -> String {
+    match ErrorCode::from_code(&code) {
+        ErrorCode::Other(_) | ErrorCode::Custom(_) => code,
+        known => known.code().to_string(),
+    }
+}
+
+/// A built-in error code as listed by [`ErrorCode::all`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ErrorCodeInfo {
+    pub code: ErrorCode,
+    /// Default message template, with `{placeholders}` as in `error_message`
+    pub template: &'static str,
+    /// [`ValidationDetails`](super::ValidationDetails) fields set on errors
+    /// with this code. Any error may also carry `received`, and errors of
+    /// parsed input `span` and `source_path`.
+    pub details: &'static [&'static str],
+}
+
+impl ErrorCode {
+    /// Detail fields set where errors with this code are raised
+    pub(crate) const fn detail_fields(&self) -> &'static [&'static str] {
+        match self {
+            ErrorCode::StringInvalidType
+            | ErrorCode::BooleanInvalidType
+            | ErrorCode::BytesInvalidType
+            | ErrorCode::ArrayInvalidType
+            | ErrorCode::InvalidType
+            | ErrorCode::InvalidNumber
+            | ErrorCode::DateInvalidType
+            | ErrorCode::DateTimeInvalidType
+            | ErrorCode::DecimalInvalidType => &["expected_type", "actual_type"],
+            ErrorCode::StringTooShort | ErrorCode::BytesTooShort | ErrorCode::ArrayTooShort => &["min_length", "actual_length"],
+            ErrorCode::StringTooLong
+            | ErrorCode::BytesTooLong
+            | ErrorCode::ArrayTooLong
+            | ErrorCode::StringSoftMaxLength => &["max_length", "actual_length"],
+            ErrorCode::PatternMismatch => &["pattern"],
+            ErrorCode::PasswordTooShort => &["min_length"],
+            ErrorCode::NumberTooSmall => &["min_value"],
+            ErrorCode::NumberTooLarge => &["max_value"],
+            ErrorCode::RequiredField | ErrorCode::UnknownField | ErrorCode::DuplicateKey => &["field_name"],
+            // `field_name` of a duplicate item is the `unique_by` path
+            ErrorCode::DuplicateItem => &["expected", "field_name"],
+            ErrorCode::NotMultipleOf
+            | ErrorCode::NumberNotAllowed
+            | ErrorCode::InvalidEncoding
+            | ErrorCode::LiteralMismatch
+            | ErrorCode::MoneyPrecision
+            | ErrorCode::DateTooEarly
+            | ErrorCode::DateTooLate
+            | ErrorCode::DateTimeTooEarly
+            | ErrorCode::DateTimeTooLate
+            | ErrorCode::DecimalScale
+            | ErrorCode::DecimalPrecision
+            | ErrorCode::DecimalTooSmall
+            | ErrorCode::DecimalTooLarge => &["expected"],
+            ErrorCode::InvalidDate | ErrorCode::InvalidDateTime | ErrorCode::InvalidDecimal => &["received"],
+            ErrorCode::InvalidJson | ErrorCode::InvalidYaml | ErrorCode::InvalidToml => &["span"],
+            _ => &[],
+        }
+    }
+}
+
+error_codes! {
     // String errors
-    StringTooShort,
-    StringTooLong,
-    InvalidEmail,
-    PatternMismatch,
-    
+    StringRequired => "string.required", "This field is required";
+    StringInvalidType => "string.invalid_type", "Must be a string";
+    StringTooShort => "string.too_short", "String must be at least {min_length} characters long";
+    StringTooLong => "string.too_long", "String must be at most {max_length} characters long";
+    InvalidEmail => "string.email", "Invalid email address";
+    PatternMismatch => "string.pattern", "String must match pattern: {pattern}";
+    InvalidUrl => "string.url", "Invalid URL format";
+    InvalidUuid => "string.uuid", "Invalid UUID format";
+    InvalidIp => "string.ip", "Invalid IP address format";
+    StringCustom => "string.custom", "Invalid value";
+    NotNumeric => "string.numeric", "Must contain only digits";
+    LeadingZero => "string.leading_zero", "Must not start with a zero";
+    NumericOutOfRange => "string.numeric_range", "Number is too large";
+    ControlChars => "string.control_chars", "Must not contain control characters";
+    NotSingleLine => "string.single_line", "Must be a single line";
+    InvalidFilePath => "string.file_path", "Must be a valid file path";
+    PathNotAbsolute => "string.path_absolute", "Must be an absolute path";
+    PathTraversal => "string.path_traversal", "Must not contain '..' segments";
+    InvalidGlob => "string.glob", "Must be a valid glob pattern";
+    PasswordTooShort => "string.password.min_len", "Password must be at least {min_length} characters";
+    PasswordNoUpper => "string.password.upper", "Password must contain an uppercase letter";
+    PasswordNoLower => "string.password.lower", "Password must contain a lowercase letter";
+    PasswordNoDigit => "string.password.digit", "Password must contain a digit";
+    PasswordNoSymbol => "string.password.symbol", "Password must contain a symbol";
+    PasswordRepeats => "string.password.repeats", "Password repeats a character too many times in a row";
+
     // Number errors
-    NumberTooSmall,
-    NumberTooLarge,
-    InvalidNumber,
-    NotInteger,
-    
+    NumberRequired => "number.required", "This field is required";
+    NumberTooSmall => "number.too_small", "Number must be greater than or equal to {min_value}";
+    NumberTooLarge => "number.too_large", "Number must be less than or equal to {max_value}";
+    InvalidNumber => "number.invalid", "Expected {expected_type}, got {actual_type}";
+    NotInteger => "number.integer", "Must be an integer";
+    NotMultipleOf => "number.multiple_of", "Must be a multiple of {expected}";
+    NumberNotAllowed => "number.one_of", "Must be one of: {expected}";
+    UnsafePrecision => "number.unsafe_precision", "Integer is too large to represent exactly; use integer64()";
+    NumberCustom => "number.custom", "Invalid value";
+
+    // Boolean errors
+    BooleanRequired => "boolean.required", "This field is required";
+    BooleanInvalidType => "boolean.invalid_type", "Must be a boolean value";
+    BooleanCustom => "boolean.custom", "Invalid value";
+
+    // Bytes errors
+    BytesRequired => "bytes.required", "This field is required";
+    BytesInvalidType => "bytes.invalid_type", "Expected {expected_type}, got {actual_type}";
+    InvalidEncoding => "bytes.invalid_encoding", "Must be valid {expected}";
+    BytesTooShort => "bytes.too_short", "Must be at least {min_length} bytes";
+    BytesTooLong => "bytes.too_long", "Must be at most {max_length} bytes";
+
     // Array errors
-    ArrayTooShort,
-    ArrayTooLong,
-    InvalidArrayItem,
-    
+    ArrayRequired => "array.required", "This field is required";
+    ArrayInvalidType => "array.invalid_type", "Must be an array";
+    ArrayTooShort => "array.too_short", "Must have at least {min_items} items";
+    ArrayTooLong => "array.too_long", "Must have at most {max_items} items";
+    InvalidArrayItem => "array.invalid_item", "Invalid array item";
+    DuplicateItem => "array.unique", "Duplicate of item {expected}";
+    ArrayCustom => "array.custom", "Invalid value";
+
     // Object errors
-    RequiredField,
-    UnknownField,
-    InvalidType,
-    
-    // Custom error
-    Custom(String),
+    RequiredField => "object.required", "Field '{field_name}' is required";
+    UnknownField => "object.unknown_field", "Unknown field: {field_name}";
+    InvalidType => "object.invalid_type", "Expected {expected_type}, got {actual_type}";
+    DuplicateKey => "object.duplicate_key", "Duplicate key '{field_name}'";
+    ObjectCustom => "object.custom", "Invalid value";
+    ObjectRule => "object.rule", "Invalid value";
+
+    // Combinator errors
+    UnionNoMatch => "union.no_match", "Value did not match any schema";
+    UnionAmbiguous => "union.ambiguous", "Value matched more than one schema";
+    NeverValid => "never.invalid", "Value is not allowed";
+    NotMatched => "not.matched", "Value must not match the schema";
+    SwitchNoCase => "switch.no_case", "No schema configured for this context";
+    LiteralMismatch => "literal.mismatch", "Must be {expected}";
+    RefineCustom => "refine.custom", "Invalid value";
+
+    // GeoJSON errors, see `geo`
+    GeoLongitude => "geo.longitude", "Longitude must be between -180 and 180";
+    GeoLatitude => "geo.latitude", "Latitude must be between -90 and 90";
+    GeoBbox => "geo.bbox", "Bounding box must be [west, south, east, north] with south not above north";
+    GeoRingNotClosed => "geo.ring_not_closed", "Ring must end at its first position";
+
+    // Money errors, see `money`
+    MoneyCurrency => "money.currency", "Unknown currency";
+    MoneyPrecision => "money.precision", "Amounts in this currency allow at most {expected} decimal places";
+
+    // JWT claim errors, see `claims_schema`
+    JwtExpired => "jwt.expired", "Token has expired";
+    JwtNotBefore => "jwt.not_before", "Token is not valid yet";
+    JwtIssuer => "jwt.issuer", "Token issuer is not accepted";
+    JwtAudience => "jwt.audience", "Token is not intended for this audience";
+
+    // Webhook envelope errors, see `envelope`
+    WebhookTimestamp => "webhook.timestamp", "Timestamp is too far from the current time";
+    WebhookType => "webhook.type", "Unknown event type";
+
+    // Warnings, reported by `validate_with_report` without failing validation
+    Deprecated => "deprecated", "This field is deprecated";
+    StringSoftMaxLength => "string.soft_max_length", "String is longer than the recommended {max_length} characters";
+
+    // Parse errors
+    InvalidJson => "parse.invalid_json", "Invalid JSON";
+    DeserializeFailed => "parse.deserialize", "Failed to deserialize";
+    InvalidYaml => "parse.invalid_yaml", "Invalid YAML";
+    InvalidToml => "parse.invalid_toml", "Invalid TOML";
+    InvalidCsv => "parse.invalid_csv", "Invalid CSV";
+    InvalidBody => "parse.invalid_body", "Invalid request body";
+    UnsupportedMediaType => "parse.unsupported_media_type", "Unsupported content type";
+
+    // Date errors
+    DateRequired => "date.required", "This field is required";
+    DateInvalidType => "date.invalid_type", "Expected {expected_type} string, got {actual_type}";
+    InvalidDate => "date.invalid", "Invalid date: {received}";
+    DateTooEarly => "date.too_early", "Date must be on or after {expected}";
+    DateTooLate => "date.too_late", "Date must be on or before {expected}";
+    DateNotPast => "date.not_past", "Date must be in the past";
+    DateNotFuture => "date.not_future", "Date must be in the future";
+    DateTimeRequired => "datetime.required", "This field is required";
+    DateTimeInvalidType => "datetime.invalid_type", "Expected {expected_type} string, got {actual_type}";
+    InvalidDateTime => "datetime.invalid", "Invalid RFC 3339 date-time: {received}";
+    DateTimeTooEarly => "datetime.too_early", "Date-time must be at or after {expected}";
+    DateTimeTooLate => "datetime.too_late", "Date-time must be at or before {expected}";
+    DateTimeNotPast => "datetime.not_past", "Date-time must be in the past";
+    DateTimeNotFuture => "datetime.not_future", "Date-time must be in the future";
+
+    // Decimal errors
+    DecimalRequired => "decimal.required", "This field is required";
+    DecimalInvalidType => "decimal.invalid_type", "Expected {expected_type} string, got {actual_type}";
+    InvalidDecimal => "decimal.invalid", "Invalid decimal: {received}";
+    DecimalScale => "decimal.scale", "Must have at most {expected} digits after the decimal point";
+    DecimalPrecision => "decimal.precision", "Must have at most {expected} significant digits";
+    DecimalTooSmall => "decimal.too_small", "Must be at least {expected}";
+    DecimalTooLarge => "decimal.too_large", "Must be at most {expected}";
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// As its code string
+impl serde::Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(code: ErrorCode) -> Self {
+        code.code().to_string()
+    }
+}
+
+impl From<&ErrorCode> for String {
+    fn from(code: &ErrorCode) -> Self {
+        code.code().to_string()
+    }
 }
 
 #[cfg(test)]
@@ -44,94 +328,93 @@ mod tests {
     fn test_error_code_default_messages() {
         assert_eq!(
             ErrorCode::StringTooShort.default_message(),
-            "String is too short"
+            "String must be at least {min_length} characters long"
         );
         assert_eq!(
             ErrorCode::InvalidEmail.default_message(),
-            "Invalid email format"
+            "Invalid email address"
         );
         assert_eq!(
             ErrorCode::NumberTooSmall.default_message(),
-            "Number is too small"
+            "Number must be greater than or equal to {min_value}"
         );
     }
 
     #[test]
-    fn test_custom_error_code() {
-        let error = ErrorCode::Custom("custom.error".to_string());
-        assert_eq!(error.code(), "custom");
-        assert_eq!(error.default_message(), "custom.error");
+    fn test_from_code_round_trip() {
+        for code in [ErrorCode::StringRequired, ErrorCode::NumberTooLarge, ErrorCode::UnionNoMatch, ErrorCode::PasswordNoDigit] {
+            assert_eq!(ErrorCode::from_code(code.code()), code);
+        }
+        assert_eq!(ErrorCode::from_code("my.code"), ErrorCode::Other("my.code".into()));
+        assert_eq!(ErrorCode::from_code("number.min"), ErrorCode::NumberTooSmall);
+        assert_eq!(canonical_code("array.min_items".into()), "array.too_short");
     }
-}
 
-impl ErrorCode {
-    pub fn default_message(&self) -> String {
-        match self {
-            // String errors
-            ErrorCode::StringTooShort => "String is too short".into(),
-            ErrorCode::StringTooLong => "String is too long".into(),
-            ErrorCode::InvalidEmail => "Invalid email format".into(),
-            ErrorCode::PatternMismatch => "String does not match pattern".into(),
-            
-            // Number errors
-            ErrorCode::NumberTooSmall => "Number is too small".into(),
-            ErrorCode::NumberTooLarge => "Number is too large".into(),
-            ErrorCode::InvalidNumber => "Invalid number".into(),
-            ErrorCode::NotInteger => "Must be an integer".into(),
-            
-            // Array errors
-            ErrorCode::ArrayTooShort => "Array is too short".into(),
-            ErrorCode::ArrayTooLong => "Array is too long".into(),
-            ErrorCode::InvalidArrayItem => "Invalid array item".into(),
-            
-            // Object errors
-            ErrorCode::RequiredField => "Field is required".into(),
-            ErrorCode::UnknownField => "Unknown field".into(),
-            ErrorCode::InvalidType => "Invalid type".into(),
-            
-            // Custom error
-            ErrorCode::Custom(msg) => msg.clone(),
+    #[test]
+    fn test_all() {
+        let all = ErrorCode::all();
+        let codes: std::collections::HashSet<_> = all.iter().map(|info| info.code.code()).collect();
+        assert_eq!(codes.len(), all.len());
+        for info in all {
+            assert_eq!(ErrorCode::from_code(info.code.code()), info.code);
+            assert_eq!(info.code.default_message(), info.template);
         }
+        let too_short = all.iter().find(|info| info.code == ErrorCode::StringTooShort).unwrap();
+        assert_eq!(too_short.details, ["min_length", "actual_length"]);
+        assert_eq!(serde_json::to_value(too_short).unwrap(), serde_json::json!({
+            "code": "string.too_short",
+            "template": "String must be at least {min_length} characters long",
+            "details": ["min_length", "actual_length"],
+        }));
+        // Placeholders of default templates are filled from listed details
+        let soft = all.iter().find(|info| info.code == ErrorCode::StringSoftMaxLength).unwrap();
+        assert!(soft.details.iter().any(|d| soft.template.contains(&format!("{{{}}}", d))));
     }
 
-    pub fn code(&self) -> &'static str {
-        match self {
-            // String errors
-            ErrorCode::StringTooShort => "string.too_short",
-            ErrorCode::StringTooLong => "string.too_long",
-            ErrorCode::InvalidEmail => "string.email",
-            ErrorCode::PatternMismatch => "string.pattern",
-            
-            // Number errors
-            ErrorCode::NumberTooSmall => "number.too_small",
-            ErrorCode::NumberTooLarge => "number.too_large",
-            ErrorCode::InvalidNumber => "number.invalid",
-            ErrorCode::NotInteger => "number.integer",
-            
-            // Array errors
-            ErrorCode::ArrayTooShort => "array.too_short",
-            ErrorCode::ArrayTooLong => "array.too_long",
-            ErrorCode::InvalidArrayItem => "array.invalid_item",
-            
-            // Object errors
-            ErrorCode::RequiredField => "object.required",
-            ErrorCode::UnknownField => "object.unknown_field",
-            ErrorCode::InvalidType => "object.invalid_type",
-            
-            // Custom error
-            ErrorCode::Custom(_) => "custom",
+    #[test]
+    fn test_schemas_render_all_templates() {
+        use crate::{array, boolean, bytes, eq, never, not, number, object, string, union, Schema, SchemaType, StringSchema};
+        use serde_json::json;
+
+        let errors = [
+            string().validate(&json!(null)),
+            string().min_length(3).validate(&json!("ab")),
+            string().max_length(2).validate(&json!("abc")),
+            string().email().validate(&json!("nope")),
+            string().pattern(r"^\d+$").validate(&json!("abc")),
+            string().url().validate(&json!("nope")),
+            number().validate(&json!("x")),
+            number().min(1.0).validate(&json!(0)),
+            number().max(1.0).validate(&json!(2)),
+            number().integer().validate(&json!(1.5)),
+            number().multiple_of(2.0).validate(&json!(3)),
+            boolean().validate(&json!(1)),
+            bytes().validate(&json!("***")),
+            bytes().min_bytes(4).validate(&json!("AA==")),
+            array(number()).validate(&json!({})),
+            array(number()).min_items(2).validate(&json!([1])),
+            array(number()).max_items(1).validate(&json!([1, 2])),
+            array(number()).unique().validate(&json!([1, 1])),
+            object().field("name", string()).validate(&json!({})),
+            object().strict().validate(&json!({"extra": 1})),
+            object().validate(&json!([])),
+            never().validate(&json!(1)),
+            not(number()).validate(&json!(1)),
+            eq("card").validate(&json!("cash")),
+            union(Vec::<SchemaType>::new()).validate(&json!(1)),
+        ];
+
+        for err in errors {
+            let err = err.unwrap_err();
+            let info = ErrorCode::all().iter().find(|info| info.code.code() == err.context.code).unwrap();
+            assert_eq!(err.context.message.as_deref(), Some(info.template), "{}", err.context.code);
         }
     }
-}
 
-impl fmt::Display for ErrorCode {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.code())
+    #[test]
+    fn test_custom_error_code() {
+        let error = ErrorCode::Custom("custom.error".to_string());
+        assert_eq!(error.code(), "custom");
+        assert_eq!(error.default_message(), "custom.error");
     }
 }
-
-impl From<ErrorCode> for String {
-    fn from(code: ErrorCode) -> Self {
-        code.code().to_string()
-    }
-}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::{fmt, sync::{Arc, RwLock}};
+
+use super::{ErrorCode, ValidationError, ValidationErrorContext};
+
+/// Signature of an error map: a replacement message template for an error,
+/// or `None` to keep the built-in one
+pub type ErrorMapFn = dyn Fn(&ErrorCode, &ValidationErrorContext) -> Option<String> + Send + Sync;
+
+/// Overrides the default messages of built-in errors, Zod style
+///
+/// Installed for the whole process with [`set_error_map`], or for a schema
+/// subtree with [`Schema::error_map`](crate::schemas::Schema::error_map).
+/// Messages configured with `error_message` or `required_message` are never
+/// replaced. Returned messages may use the same `{placeholders}` as
+/// `error_message` templates.
+#[derive(Clone)]
+pub struct ErrorMap(Arc<ErrorMapFn>);
+
+impl ErrorMap {
+    pub fn new(map: impl Fn(&ErrorCode, &ValidationErrorContext) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(map))
+    }
+
+    /// Replace the message of `err` unless it is user-configured or was
+    /// already mapped by a map closer to where it was raised
+    pub(crate) fn apply(&self, err: &mut ValidationError) {
+        if err.context.custom_message {
+            return;
+        }
+        if let Some(message) = (self.0)(&err.code(), &err.context) {
+            err.context.message = Some(message);
+            err.context.custom_message = true;
+        }
+    }
+}
+
+impl fmt::Debug for ErrorMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorMap(..)")
+    }
+}
+
+static GLOBAL: RwLock<Option<ErrorMap>> = RwLock::new(None);
+
+/// Install `map` for every schema, below any per-schema map
+///
+/// Applied when an error's message is rendered, so it sees the error's full
+/// path and details.
+pub fn set_error_map(map: impl Fn(&ErrorCode, &ValidationErrorContext) -> Option<String> + Send + Sync + 'static) {
+    *GLOBAL.write().unwrap_or_else(|e| e.into_inner()) = Some(ErrorMap::new(map));
+}
+
+/// Remove the map installed with [`set_error_map`]
+pub fn clear_error_map() {
+    *GLOBAL.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub(crate) fn global() -> Option<ErrorMap> {
+    GLOBAL.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
@@ -0,0 +1,152 @@
+//! Error bodies for HTTP APIs: RFC 7807 Problem Details and JSON:API
+
+use serde_json::{json, Value};
+
+use super::{ErrorCode, ValidationError, ValidationErrors};
+
+/// HTTP status reported for validation failures
+pub const VALIDATION_STATUS: u16 = 422;
+
+/// HTTP status reported for bodies in a media type that cannot be validated
+const UNSUPPORTED_MEDIA_TYPE_STATUS: u16 = 415;
+
+/// Problem `type` of validation failures; `about:blank` means the status
+/// code alone describes the problem
+const PROBLEM_TYPE: &str = "about:blank";
+
+impl ValidationErrors {
+    /// Status these errors are answered with: `415` when the body's
+    /// content type is not one that can be validated, otherwise
+    /// [`VALIDATION_STATUS`]
+    pub(crate) fn http_status(&self) -> u16 {
+        self.iter().map(ValidationError::http_status).find(|&status| status != VALIDATION_STATUS).unwrap_or(VALIDATION_STATUS)
+    }
+
+    /// RFC 7807 `application/problem+json` body, with one entry per issue in
+    /// the `errors` extension member, located by JSON Pointer. Issues with a
+    /// [`PathFormatter`](super::PathFormatter) also carry their path as it
+    /// renders in `path`; `pointer` stays a JSON Pointer.
+    pub fn to_problem_details(&self) -> Value {
+        let errors: Vec<Value> = self
+            .iter()
+            .map(|err| {
+                let mut entry = json!({
+                    "pointer": err.context.path.to_json_pointer(),
+                    "code": err.context.code,
+                    "detail": err.to_string(),
+                });
+                if err.context.path_formatter.is_some() {
+                    entry["path"] = Value::String(err.formatted_path());
+                }
+                entry
+            })
+            .collect();
+        let status = self.http_status();
+        json!({
+            "type": PROBLEM_TYPE,
+            "title": match status {
+                UNSUPPORTED_MEDIA_TYPE_STATUS => "Unsupported media type",
+                _ => "Validation failed",
+            },
+            "status": status,
+            "detail": match errors.len() {
+                1 => "1 validation issue".to_string(),
+                n => format!("{} validation issues", n),
+            },
+            "errors": errors,
+        })
+    }
+
+    /// JSON:API error document. The validated value is taken to be the
+    /// resource's attributes, so issue paths point below `/data/attributes`.
+    /// Pointers are always JSON Pointers, as JSON:API requires.
+    pub fn to_jsonapi_errors(&self) -> Value {
+        let errors: Vec<Value> = self
+            .iter()
+            .map(|err| {
+                let pointer = match err.context.path.is_empty() {
+                    true => "/data".to_string(),
+                    false => format!("/data/attributes{}", err.context.path.to_json_pointer()),
+                };
+                json!({
+                    "status": err.http_status().to_string(),
+                    "code": err.context.code,
+                    "title": "Invalid attribute",
+                    "detail": err.to_string(),
+                    "source": { "pointer": pointer },
+                })
+            })
+            .collect();
+        json!({ "errors": errors })
+    }
+}
+
+impl ValidationError {
+    fn http_status(&self) -> u16 {
+        match self.code() {
+            ErrorCode::UnsupportedMediaType => UNSUPPORTED_MEDIA_TYPE_STATUS,
+            _ => VALIDATION_STATUS,
+        }
+    }
+
+    /// [`ValidationErrors::to_problem_details`] for a single issue
+    pub fn to_problem_details(&self) -> Value {
+        ValidationErrors::from(self.clone()).to_problem_details()
+    }
+
+    /// [`ValidationErrors::to_jsonapi_errors`] for a single issue
+    pub fn to_jsonapi_errors(&self) -> Value {
+        ValidationErrors::from(self.clone()).to_jsonapi_errors()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::{array, number, object, string, Schema, StringSchema};
+
+    fn errors() -> crate::ValidationErrors {
+        let schema = object()
+            .field("name", string().min_length(3))
+            .field("tags", array(number()));
+        schema.safe_parse::<serde_json::Value>(&json!({"name": "al", "tags": [1, "x"]})).into_result().unwrap_err()
+    }
+
+    #[test]
+    fn test_problem_details() {
+        assert_eq!(errors().to_problem_details(), json!({
+            "type": "about:blank",
+            "title": "Validation failed",
+            "status": 422,
+            "detail": "2 validation issues",
+            "errors": [
+                {"pointer": "/name", "code": "string.too_short", "detail": "String must be at least 3 characters long"},
+                {"pointer": "/tags/1", "code": "number.invalid", "detail": "Item 1 is invalid"},
+            ],
+        }));
+    }
+
+    #[test]
+    fn test_problem_details_unsupported_media_type() {
+        let errors = crate::validate_body_partial("text/plain", b"name=al", &string()).unwrap_err();
+        assert_eq!(errors.http_status(), 415);
+        let body = errors.to_problem_details();
+        assert_eq!((&body["status"], &body["title"]), (&json!(415), &json!("Unsupported media type")));
+        assert_eq!(body["errors"][0]["code"], "parse.unsupported_media_type");
+    }
+
+    #[test]
+    fn test_jsonapi_errors() {
+        let body = errors().to_jsonapi_errors();
+        assert_eq!(body["errors"][1], json!({
+            "status": "422",
+            "code": "number.invalid",
+            "title": "Invalid attribute",
+            "detail": "Item 1 is invalid",
+            "source": {"pointer": "/data/attributes/tags/1"},
+        }));
+
+        let root = number().validate(&json!("x")).unwrap_err().to_jsonapi_errors();
+        assert_eq!(root["errors"][0]["source"]["pointer"], "/data");
+    }
+}
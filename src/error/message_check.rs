@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use super::template::{canonical_placeholder, placeholder_names};
+use super::ErrorCode;
+
+/// Every placeholder `ValidationError::format_message` knows how to fill
+const KNOWN_PLACEHOLDERS: &[&str] = &[
+    "min_length", "min_items", "max_length", "max_items", "actual_length",
+    "min_value", "min", "max_value", "max",
+    "pattern", "field_name", "field", "expected",
+    "expected_type", "actual_type",
+    "received", "value", "code",
+];
+
+/// Placeholders every error can fill
+const ALWAYS_FILLED: &[&str] = &["received", "code"];
+
+/// A custom message template referencing a placeholder its code never fills
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageIssue {
+    /// Path of the schema that owns the template (empty for the root)
+    pub path: String,
+    pub code: String,
+    pub template: String,
+    pub placeholder: String,
+}
+
+/// Placeholders populated for a built-in error code, under the detail
+/// names [`ErrorCode::all`] lists (`{min}` is filled like `{min_value}`), or
+/// `None` when the code is not a built-in one and any known placeholder may
+/// apply
+pub fn placeholders_for(code: &str) -> Option<&'static [&'static str]> {
+    match code {
+        code if code.starts_with("field.") && code.ends_with(".required") => Some(&["field_name"]),
+        code if code.starts_with("string.custom.") => Some(&[]),
+        code => match ErrorCode::from_code(code) {
+            ErrorCode::Other(_) | ErrorCode::Custom(_) => None,
+            code => Some(code.detail_fields()),
+        },
+    }
+}
+
+/// Placeholders in `template` that will not be substituted for `code`
+pub fn unfilled_placeholders(code: &str, template: &str) -> Vec<String> {
+    let allowed = placeholders_for(code).unwrap_or(KNOWN_PLACEHOLDERS);
+    let mut unfilled: Vec<String> = Vec::new();
+    for name in placeholder_names(template) {
+        let canonical = canonical_placeholder(name);
+        let filled = allowed.contains(&canonical) || ALWAYS_FILLED.contains(&canonical);
+        if !filled && !unfilled.iter().any(|p| p == name) {
+            unfilled.push(name.to_string());
+        }
+    }
+    unfilled
+}
+
+/// Check all templates in a schema's message map
+pub fn check_templates(path: &str, messages: &HashMap<String, String>) -> Vec<MessageIssue> {
+    let mut issues: Vec<MessageIssue> = messages
+        .iter()
+        .flat_map(|(code, template)| {
+            unfilled_placeholders(code, template)
+                .into_iter()
+                .map(|placeholder| MessageIssue {
+                    path: path.to_string(),
+                    code: code.clone(),
+                    template: template.clone(),
+                    placeholder,
+                })
+        })
+        .collect();
+    issues.sort_by(|a, b| a.code.cmp(&b.code).then(a.placeholder.cmp(&b.placeholder)));
+    issues
+}
+
+/// Debug-build guard used by the `error_message` builders
+pub(crate) fn debug_check_template(code: &str, template: &str) {
+    let unfilled = unfilled_placeholders(code, template);
+    debug_assert!(
+        unfilled.is_empty(),
+        "error message for '{}' references placeholders that are never filled: {:?}",
+        code,
+        unfilled
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unfilled_placeholders() {
+        assert!(unfilled_placeholders("string.too_short", "At least {min_length}").is_empty());
+        assert_eq!(unfilled_placeholders("string.too_short", "At least {min_len}"), vec!["min_len"]);
+        assert_eq!(unfilled_placeholders("string.too_short", "Between {min_length} and {max_length}"), vec!["max_length"]);
+        assert!(unfilled_placeholders("number.too_small", "At least {min}").is_empty());
+    }
+
+    #[test]
+    fn test_plural_placeholders_checked() {
+        assert!(unfilled_placeholders("string.too_short", "{min_length|# char|# chars}, got '{received}'").is_empty());
+        assert_eq!(unfilled_placeholders("string.too_short", "{min_len|# char|# chars}"), vec!["min_len"]);
+    }
+
+    #[test]
+    fn test_non_placeholder_braces_ignored() {
+        assert!(unfilled_placeholders("string.pattern", r"Must match \d{5} or {pattern}").is_empty());
+        assert!(unfilled_placeholders("string.email", "Use {} braces").is_empty());
+    }
+
+    #[test]
+    fn test_unknown_codes_allow_known_placeholders() {
+        assert!(unfilled_placeholders("my.code", "{field} must be a URL").is_empty());
+        assert_eq!(unfilled_placeholders("my.code", "{typo}"), vec!["typo"]);
+    }
+
+    #[test]
+    fn test_placeholders_follow_error_codes() {
+        for info in ErrorCode::all() {
+            assert!(unfilled_placeholders(info.code.code(), info.template).is_empty(), "{}", info.code);
+        }
+        assert_eq!(placeholders_for("array.too_short"), Some(&["min_length", "actual_length"][..]));
+        assert!(unfilled_placeholders("array.min_items", "At least {min_items}").is_empty());
+        assert_eq!(unfilled_placeholders("string.url", "{field} must be a URL"), vec!["field"]);
+        assert_eq!(placeholders_for("field.name.required"), Some(&["field_name"][..]));
+    }
+}
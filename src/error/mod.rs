@@ -1,7 +1,34 @@
+mod catalog;
+mod custom_issue;
+#[cfg(feature = "diagnostics")]
+mod diagnostic;
 mod error_code;
+mod error_map;
+mod http;
+pub mod message_check;
 mod parse_error;
+mod path;
+pub(crate) mod path_format;
+pub(crate) mod resolved;
+mod span;
+mod template;
 mod validation_error;
+mod validation_errors;
 
-pub use error_code::ErrorCode;
+pub use catalog::{MessageCatalog, DEFAULT_LOCALE};
+pub use custom_issue::CustomIssue;
+#[cfg(feature = "diagnostics")]
+pub use diagnostic::SourceDiagnostic;
+pub use error_code::{ErrorCode, ErrorCodeInfo};
+pub(crate) use error_code::canonical_code;
+pub use error_map::{clear_error_map, set_error_map, ErrorMap, ErrorMapFn};
+pub use http::VALIDATION_STATUS;
+pub use message_check::MessageIssue;
 pub use parse_error::ParseError;
-pub use validation_error::ValidationError;
\ No newline at end of file
+pub use path::{ErrorPath, PathSegment};
+pub use path_format::{DisplayWith, PathFormatter, PathStyle};
+pub use span::SourceSpan;
+pub use validation_error::{ValidationDetails, ValidationError, ValidationErrorContext};
+#[cfg(test)]
+pub(crate) use validation_error::TEMPLATE_LOOKUPS;
+pub use validation_errors::{ErrorTree, FlattenedErrors, ValidationErrors};
\ No newline at end of file
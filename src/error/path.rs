@@ -0,0 +1,243 @@
+use std::fmt;
+
+/// One step into a value: an object key or an array index
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Key(key) => f.write_str(key),
+            PathSegment::Index(i) => write!(f, "{}", i),
+        }
+    }
+}
+
+impl From<&str> for PathSegment {
+    fn from(key: &str) -> Self {
+        PathSegment::Key(key.to_string())
+    }
+}
+
+impl From<&String> for PathSegment {
+    fn from(key: &String) -> Self {
+        PathSegment::Key(key.clone())
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(key: String) -> Self {
+        PathSegment::Key(key)
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        PathSegment::Index(index)
+    }
+}
+
+/// Location of an error inside the validated value, root first.
+///
+/// Displays dot-joined (`items.0.name`); use [`ErrorPath::to_json_pointer`]
+/// or [`ErrorPath::to_bracket_notation`] where keys and indices must stay
+/// distinguishable, or a [`PathFormatter`](super::PathFormatter).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ErrorPath(Vec<PathSegment>);
+
+impl ErrorPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Split a dot-joined path, reading all-digit parts as array indices
+    pub fn parse(dotted: &str) -> Self {
+        if dotted.is_empty() {
+            return Self::new();
+        }
+        Self(dotted
+            .split('.')
+            .map(|part| match part.parse::<usize>() {
+                Ok(i) if part.bytes().all(|b| b.is_ascii_digit()) => PathSegment::Index(i),
+                _ => PathSegment::Key(part.to_string()),
+            })
+            .collect())
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn push(&mut self, segment: impl Into<PathSegment>) {
+        self.0.push(segment.into());
+    }
+
+    /// This path with `segment` appended
+    pub fn child(&self, segment: impl Into<PathSegment>) -> Self {
+        let mut path = self.clone();
+        path.push(segment);
+        path
+    }
+
+    pub fn prepend(&mut self, segment: impl Into<PathSegment>) {
+        self.0.insert(0, segment.into());
+    }
+
+    /// This path below `segment`, if it starts with it
+    pub(crate) fn strip_prefix(&self, segment: &PathSegment) -> Option<Self> {
+        match self.0.split_first() {
+            Some((first, rest)) if first == segment => Some(Self(rest.to_vec())),
+            _ => None,
+        }
+    }
+
+    /// RFC 6901 pointer, e.g. `/items/0/name`; the root is `""`
+    pub fn to_json_pointer(&self) -> String {
+        self.0.iter().fold(String::new(), |mut pointer, segment| {
+            pointer.push('/');
+            match segment {
+                PathSegment::Key(key) => pointer.push_str(&key.replace('~', "~0").replace('/', "~1")),
+                PathSegment::Index(i) => pointer.push_str(&i.to_string()),
+            }
+            pointer
+        })
+    }
+
+    /// JavaScript-style accessor, e.g. `items[0].name`
+    pub fn to_bracket_notation(&self) -> String {
+        let mut out = String::new();
+        for segment in &self.0 {
+            match segment {
+                PathSegment::Key(key) => {
+                    if !out.is_empty() {
+                        out.push('.');
+                    }
+                    out.push_str(key);
+                }
+                PathSegment::Index(i) => out.push_str(&format!("[{}]", i)),
+            }
+        }
+        out
+    }
+
+    /// TOML dotted key with array indices, e.g. `servers[0]."host name"`;
+    /// keys that are not bare keys are quoted
+    pub fn to_toml_key(&self) -> String {
+        let mut out = String::new();
+        for segment in &self.0 {
+            match segment {
+                PathSegment::Key(key) => {
+                    if !out.is_empty() {
+                        out.push('.');
+                    }
+                    let bare = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                    if bare {
+                        out.push_str(key);
+                    } else {
+                        out.push_str(&format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\"")));
+                    }
+                }
+                PathSegment::Index(i) => out.push_str(&format!("[{}]", i)),
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for ErrorPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl serde::Serialize for ErrorPath {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl From<PathSegment> for ErrorPath {
+    fn from(segment: PathSegment) -> Self {
+        Self(vec![segment])
+    }
+}
+
+impl From<Vec<PathSegment>> for ErrorPath {
+    fn from(segments: Vec<PathSegment>) -> Self {
+        Self(segments)
+    }
+}
+
+/// Parsed with [`ErrorPath::parse`]
+impl From<&str> for ErrorPath {
+    fn from(dotted: &str) -> Self {
+        Self::parse(dotted)
+    }
+}
+
+/// Parsed with [`ErrorPath::parse`]
+impl From<String> for ErrorPath {
+    fn from(dotted: String) -> Self {
+        Self::parse(&dotted)
+    }
+}
+
+impl PartialEq<str> for ErrorPath {
+    fn eq(&self, other: &str) -> bool {
+        self.to_string().as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ErrorPath {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_renderers() {
+        let path = ErrorPath::from(vec!["items".into(), 0.into(), "name".into()]);
+
+        assert_eq!(path.to_string(), "items.0.name");
+        assert_eq!(path.to_json_pointer(), "/items/0/name");
+        assert_eq!(path.to_bracket_notation(), "items[0].name");
+        assert_eq!(ErrorPath::parse("items.0.name"), path);
+        assert_eq!(ErrorPath::new().to_json_pointer(), "");
+        assert_eq!(path.to_toml_key(), "items[0].name");
+        let quoted = ErrorPath::from(vec!["servers".into(), 1.into(), "host name".into(), "a\"b".into()]);
+        assert_eq!(quoted.to_toml_key(), r#"servers[1]."host name"."a\"b""#);
+    }
+
+    #[test]
+    fn test_key_and_index_distinct() {
+        let key = ErrorPath::from(vec!["items".into(), "0".into()]);
+        let index = ErrorPath::from(vec!["items".into(), 0.into()]);
+
+        assert_eq!(key, "items.0");
+        assert_ne!(key, index);
+        assert_eq!(key.to_bracket_notation(), "items.0");
+        assert_eq!(index.to_bracket_notation(), "items[0]");
+        assert_eq!(ErrorPath::from(PathSegment::from("a/b~c")).to_json_pointer(), "/a~1b~0c");
+    }
+}
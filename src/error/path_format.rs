@@ -0,0 +1,156 @@
+use std::{fmt, sync::Arc};
+
+use super::{ErrorPath, ValidationErrors};
+
+/// Renders error paths, either for one output, passed to
+/// [`ValidationError::to_json_with`](super::ValidationError::to_json_with),
+/// [`ValidationError::to_kv_with`](super::ValidationError::to_kv_with),
+/// [`ValidationErrors::to_json_with`] or [`ValidationErrors::display_with`],
+/// or for every output of an error, set with
+/// [`ValidationError::with_path_formatter`](super::ValidationError::with_path_formatter)
+/// or for a whole validation with
+/// [`ValidationContext::path_formatter`](crate::ValidationContext::path_formatter)
+///
+/// Closures `Fn(&ErrorPath) -> String` implement it. Without a formatter
+/// paths render dot-joined, as [`PathStyle::Dot`].
+pub trait PathFormatter: Send + Sync {
+    fn format(&self, path: &ErrorPath) -> String;
+}
+
+impl<F> PathFormatter for F
+where
+    F: Fn(&ErrorPath) -> String + Send + Sync,
+{
+    fn format(&self, path: &ErrorPath) -> String {
+        self(path)
+    }
+}
+
+/// Built-in path notations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PathStyle {
+    /// `items.0.name`
+    #[default]
+    Dot,
+    /// `items[0].name`, see [`ErrorPath::to_bracket_notation`]
+    Bracket,
+    /// `/items/0/name`, see [`ErrorPath::to_json_pointer`]
+    JsonPointer,
+}
+
+impl PathFormatter for PathStyle {
+    fn format(&self, path: &ErrorPath) -> String {
+        match self {
+            PathStyle::Dot => path.to_string(),
+            PathStyle::Bracket => path.to_bracket_notation(),
+            PathStyle::JsonPointer => path.to_json_pointer(),
+        }
+    }
+}
+
+/// A [`PathFormatter`] carried by errors and validation contexts
+#[derive(Clone)]
+pub(crate) struct SharedFormatter(pub(crate) Arc<dyn PathFormatter>);
+
+impl fmt::Debug for SharedFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PathFormatter")
+    }
+}
+
+/// [`ValidationErrors`] displayed with paths rendered by a
+/// [`PathFormatter`], see [`ValidationErrors::display_with`]
+pub struct DisplayWith<'a> {
+    pub(crate) errors: &'a ValidationErrors,
+    /// `None` renders each error with its own formatter
+    pub(crate) formatter: Option<&'a dyn PathFormatter>,
+}
+
+impl fmt::Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, err) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            if err.context.path.is_empty() {
+                write!(f, "{}", err)?;
+            } else {
+                let formatter = self.formatter.unwrap_or_else(|| err.path_formatter());
+                write!(f, "{}: {}", formatter.format(&err.context.path), err)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{array, number, object, string, Schema, StringSchema, ValidationContext, ValidationError, ValidationErrors};
+
+    #[test]
+    fn test_path_styles() {
+        let path = ErrorPath::parse("items.0.name");
+        assert_eq!(PathStyle::Dot.format(&path), "items.0.name");
+        assert_eq!(PathStyle::Bracket.format(&path), "items[0].name");
+        assert_eq!(PathStyle::JsonPointer.format(&path), "/items/0/name");
+    }
+
+    #[test]
+    fn test_format_with() {
+        let err = ValidationError::new("string.too_short").at("items.0.name").message("Too short");
+        assert_eq!(err.to_json_with(&PathStyle::Bracket)["context"]["path"], "items[0].name");
+        assert!(err.to_kv_with(&PathStyle::JsonPointer).contains(&("path", "/items/0/name".to_string())));
+        let upper = |path: &ErrorPath| path.to_string().to_uppercase();
+        let errors = ValidationErrors::from(err.clone());
+        assert_eq!(errors.display_with(&upper).to_string(), "ITEMS.0.NAME: Too short");
+        assert_eq!(errors.to_json_with(&upper)[0]["context"]["path"], "ITEMS.0.NAME");
+
+        // Output without a formatter stays dot-joined
+        assert_eq!(err.to_json()["context"]["path"], "items.0.name");
+        assert_eq!(errors.to_string(), "items.0.name: Too short");
+        assert_eq!(errors.to_canonical_json()[0]["path"], "items.0.name");
+    }
+
+    #[test]
+    fn test_error_formatter_reaches_every_output() {
+        let err = ValidationError::new("string.too_short").at("items.0.name").message("Too short")
+            .with_path_formatter(PathStyle::Bracket);
+        assert_eq!(err.formatted_path(), "items[0].name");
+        assert_eq!(err.to_json()["context"]["path"], "items[0].name");
+        assert!(err.to_kv().contains(&("path", "items[0].name".to_string())));
+        // A formatter passed for one output still wins
+        assert_eq!(err.to_json_with(&PathStyle::JsonPointer)["context"]["path"], "/items/0/name");
+
+        let errors = ValidationErrors::from(err);
+        assert_eq!(errors.to_string(), "items[0].name: Too short");
+        assert_eq!(errors.to_json()[0]["context"]["path"], "items[0].name");
+        let problem = &errors.to_problem_details()["errors"][0];
+        assert_eq!((&problem["pointer"], &problem["path"]), (&json!("/items/0/name"), &json!("items[0].name")));
+        assert_eq!(errors.to_canonical_json()[0]["path"], "items.0.name");
+
+        let plain = ValidationErrors::from(ValidationError::new("string.too_short").at("a.0").message("Too short"));
+        assert!(plain.to_problem_details()["errors"][0].get("path").is_none());
+        assert_eq!(plain.with_path_formatter(PathStyle::JsonPointer).to_string(), "/a/0: Too short");
+    }
+
+    #[test]
+    fn test_context_formatter_applies_to_raised_errors() {
+        let schema = object().field("items", array(object().field("name", string().min_length(2))));
+        let ctx = ValidationContext::new().path_formatter(PathStyle::Bracket);
+        let value = json!({"items": [{"name": "A"}]});
+
+        let err = schema.validate_with_context(&value, &ctx).unwrap_err();
+        assert_eq!(err.to_json()["context"]["path"], "items[0].name");
+        assert_eq!(schema.validate(&value).unwrap_err().to_json()["context"]["path"], "items.0.name");
+    }
+
+    #[test]
+    fn test_report_formatter() {
+        let schema = object().field("items", array(object().field("old", number().optional().deprecated("gone"))));
+        let report = schema.validate_with_report(&json!({"items": [{"old": 1}]})).unwrap()
+            .with_path_formatter(PathStyle::JsonPointer);
+        assert_eq!(report.warnings[0].formatted_path(), "/items/0/old");
+    }
+}
@@ -0,0 +1,48 @@
+//! Default message templates resolved ahead of validation by
+//! [`CompiledSchema`](crate::schemas::CompiledSchema)
+
+use std::{cell::RefCell, collections::HashMap, sync::Arc};
+
+use super::ErrorCode;
+
+/// Default template of every built-in code, looked up once when a schema is
+/// compiled instead of each time one of its errors is raised
+#[derive(Debug)]
+pub(crate) struct ResolvedMessages {
+    templates: HashMap<&'static str, &'static str>,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<Arc<ResolvedMessages>>> = const { RefCell::new(None) };
+}
+
+/// Puts back the messages that were active before, also when validation
+/// panics
+struct Restore(Option<Arc<ResolvedMessages>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| *active.borrow_mut() = self.0.take());
+    }
+}
+
+impl ResolvedMessages {
+    pub(crate) fn new() -> Self {
+        Self {
+            templates: ErrorCode::all().iter().map(|info| (info.code.code(), info.template)).collect(),
+        }
+    }
+
+    /// Run `f` with errors raised on this thread taking their default
+    /// templates from `messages`
+    pub(crate) fn with<R>(messages: &Arc<Self>, f: impl FnOnce() -> R) -> R {
+        let _restore = Restore(ACTIVE.with(|active| active.replace(Some(Arc::clone(messages)))));
+        f()
+    }
+}
+
+/// Pre-resolved template for `code` while a compiled schema validates on
+/// this thread
+pub(crate) fn template(code: &str) -> Option<&'static str> {
+    ACTIVE.with(|active| active.borrow().as_ref()?.templates.get(code).copied())
+}
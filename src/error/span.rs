@@ -0,0 +1,37 @@
+/// Location of a value in the JSON text it was parsed from
+///
+/// Offsets are in bytes, `end` exclusive; `line` and `column` are 1-based,
+/// the column counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceSpan {
+    /// Span of `text[start..end]`
+    pub fn from_offsets(text: &str, start: usize, end: usize) -> Self {
+        let before = &text[..start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            start,
+            end,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Empty span at a 1-based line and column, as reported by `serde_json`
+    /// (which uses column 0 for the start of a line)
+    pub fn at_line_column(text: &str, line: usize, column: usize) -> Self {
+        let column = column.max(1);
+        let line_start: usize = text.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum();
+        let offset = text[line_start..]
+            .char_indices()
+            .nth(column.saturating_sub(1))
+            .map_or(text.len(), |(i, _)| line_start + i);
+        Self { start: offset, end: offset, line, column }
+    }
+}
@@ -0,0 +1,219 @@
+//! The message template language used by default messages, `error_message`,
+//! error maps and message catalogs
+//!
+//! `{name}` is replaced by the named value from the error. `{name|one|other}`
+//! picks a plural form by that value, with `#` standing for the value:
+//! `"At least {min_length|# character|# characters}"`. Braces around
+//! anything that is not a known, filled placeholder are left as they are,
+//! so regex fragments like `\d{5}` survive.
+//!
+//! The form is chosen by the plural rules of the catalog locale the message
+//! came from, English for other messages. Forms are listed in CLDR order
+//! (zero, one, two, few, many, other), one per category the language has:
+//! `{n|# символ|# символа|# символов}` in Russian, `{n|#文字}` in Japanese.
+//! A missing trailing form falls back to the last one given. Languages
+//! without built-in rules use English's `one` and `other`.
+
+use serde_json::Value;
+
+use super::{ValidationErrorContext, DEFAULT_LOCALE};
+
+/// A value a placeholder can be filled with
+enum Arg<'a> {
+    Count(f64),
+    Text(&'a str),
+    Json(&'a Value),
+}
+
+impl Arg<'_> {
+    fn render(&self) -> String {
+        match self {
+            Arg::Count(n) => n.to_string(),
+            Arg::Text(s) => s.to_string(),
+            Arg::Json(Value::String(s)) => s.clone(),
+            Arg::Json(value) => value.to_string(),
+        }
+    }
+
+    fn count(&self) -> Option<f64> {
+        match self {
+            Arg::Count(n) => Some(*n),
+            Arg::Json(value) => value.as_f64(),
+            Arg::Text(_) => None,
+        }
+    }
+}
+
+/// Value of placeholder `name` for `ctx`, `None` when it is unknown or unset
+fn lookup<'a>(name: &str, ctx: &'a ValidationErrorContext) -> Option<Arg<'a>> {
+    let d = &ctx.details;
+    let count = |n: Option<usize>| n.map(|n| Arg::Count(n as f64));
+    match canonical_placeholder(name) {
+        "min_length" => count(d.min_length),
+        "max_length" => count(d.max_length),
+        "actual_length" => count(d.actual_length),
+        "min_value" => d.min_value.map(Arg::Count),
+        "max_value" => d.max_value.map(Arg::Count),
+        "pattern" => d.pattern.as_deref().map(Arg::Text),
+        "expected_type" => d.expected_type.as_deref().map(Arg::Text),
+        "actual_type" => d.actual_type.as_deref().map(Arg::Text),
+        "field_name" => d.field_name.as_deref().map(Arg::Text),
+        "expected" => d.expected.as_deref().map(Arg::Text),
+        "received" => d.received.as_ref().map(Arg::Json),
+        "code" => Some(Arg::Text(&ctx.code)),
+        _ => None,
+    }
+}
+
+/// Index of the plural form of `n` among the forms a template lists for
+/// `locale`'s language, following CLDR
+fn plural_index(locale: &str, n: f64) -> usize {
+    let n = n.abs();
+    let int = (n.fract() == 0.0).then_some(n as u64);
+    let few = |i: u64| (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100));
+    let (language, region) = locale.split_once(['-', '_']).unwrap_or((locale, ""));
+    match language {
+        // other
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => 0,
+        // one (0 and 1), other
+        "fr" => usize::from(n >= 2.0),
+        "pt" if !region.eq_ignore_ascii_case("pt") => usize::from(n >= 2.0),
+        // one, few, many, other
+        "ru" | "uk" | "be" => match int {
+            Some(i) if i % 10 == 1 && i % 100 != 11 => 0,
+            Some(i) if few(i) => 1,
+            Some(_) => 2,
+            None => 3,
+        },
+        "pl" => match int {
+            Some(1) => 0,
+            Some(i) if few(i) => 1,
+            Some(_) => 2,
+            None => 3,
+        },
+        "cs" | "sk" => match int {
+            Some(1) => 0,
+            Some(2..=4) => 1,
+            None => 2,
+            Some(_) => 3,
+        },
+        // zero, one, two, few, many, other
+        "ar" => match int {
+            Some(0) => 0,
+            Some(1) => 1,
+            Some(2) => 2,
+            Some(i) if (3..=10).contains(&(i % 100)) => 3,
+            Some(i) if (11..=99).contains(&(i % 100)) => 4,
+            _ => 5,
+        },
+        // one, other
+        _ => usize::from(n != 1.0),
+    }
+}
+
+/// Fill every placeholder of `template` from `ctx`
+pub(crate) fn render(template: &str, ctx: &ValidationErrorContext) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let inner = &rest[start + 1..start + len];
+        let mut parts = inner.split('|');
+        let name = parts.next().unwrap_or_default();
+        let forms: Vec<&str> = parts.collect();
+        let replacement = lookup(name, ctx).and_then(|arg| match forms.as_slice() {
+            [] => Some(arg.render()),
+            forms => {
+                let locale = ctx.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+                let index = plural_index(locale, arg.count()?).min(forms.len() - 1);
+                Some(forms[index].replace('#', &arg.render()))
+            }
+        });
+        match replacement {
+            Some(text) => out.push_str(&text),
+            None => out.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Detail `name` is a shorter spelling of, e.g. `min_value` for `{min}`;
+/// other names are returned unchanged
+pub(crate) fn canonical_placeholder(name: &str) -> &str {
+    match name {
+        "min_items" => "min_length",
+        "max_items" => "max_length",
+        "min" => "min_value",
+        "max" => "max_value",
+        "field" => "field_name",
+        "value" => "received",
+        other => other,
+    }
+}
+
+/// Placeholder names referenced by `template`, plural forms included
+pub(crate) fn placeholder_names(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|part| {
+        let inner = &part[..part.find('}')?];
+        let name = inner.split('|').next().unwrap_or_default();
+        let is_name = !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+        is_name.then_some(name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::error::{ErrorCode, ValidationError};
+
+    fn render_for(template: &str, err: ValidationError) -> String {
+        render(template, &err.context)
+    }
+
+    #[test]
+    fn test_plural_forms() {
+        let template = "At least {min_length|# character|# characters}";
+        let one = ValidationError::new(ErrorCode::StringTooShort).with_details(|d| d.min_length = Some(1));
+        let three = ValidationError::new(ErrorCode::StringTooShort).with_details(|d| d.min_length = Some(3));
+        assert_eq!(render_for(template, one), "At least 1 character");
+        assert_eq!(render_for(template, three), "At least 3 characters");
+    }
+
+    #[test]
+    fn test_plural_forms_follow_locale() {
+        let render_in = |locale: &str, template: &str, n: usize| {
+            let mut err = ValidationError::new(ErrorCode::StringTooShort).with_details(|d| d.min_length = Some(n));
+            err.context.locale = Some(locale.to_string());
+            render(template, &err.context)
+        };
+        let ru = "{min_length|# символ|# символа|# символов}";
+        let forms: Vec<_> = [1, 3, 5, 11, 21, 22, 112].map(|n| render_in("ru", ru, n)).into();
+        assert_eq!(forms, ["1 символ", "3 символа", "5 символов", "11 символов", "21 символ", "22 символа", "112 символов"]);
+
+        let pl = "{min_length|# znak|# znaki|# znaków}";
+        assert_eq!([1, 2, 5, 22, 25].map(|n| render_in("pl-PL", pl, n)), ["1 znak", "2 znaki", "5 znaków", "22 znaki", "25 znaków"]);
+        assert_eq!([0, 1, 2].map(|n| render_in("fr", "{min_length|# caractère|# caractères}", n)), ["0 caractère", "1 caractère", "2 caractères"]);
+        assert_eq!(render_in("ja", "{min_length|#文字}", 3), "3文字");
+        assert_eq!([0, 1].map(|n| render_in("pt-PT", "{min_length|# carácter|# caracteres}", n)), ["0 caracteres", "1 carácter"]);
+        assert_eq!(render_in("xx", "{min_length|# thing|# things}", 0), "0 things");
+    }
+
+    #[test]
+    fn test_received_and_unknown_placeholders() {
+        let err = ValidationError::new(ErrorCode::PatternMismatch).with_details(|d| {
+            d.received = Some(json!("abc"));
+            d.pattern = Some(r"^\d{5}$".to_string());
+        });
+        assert_eq!(
+            render_for(r"'{received}' must match {pattern}, like \d{5} {typo} {}", err),
+            r"'abc' must match ^\d{5}$, like \d{5} {typo} {}",
+        );
+    }
+}
@@ -1,18 +1,34 @@
 use std::fmt;
+use std::sync::Arc;
+use super::{error_map, resolved, template, ErrorCode, ErrorPath, PathFormatter, PathSegment, PathStyle, SourceSpan};
+use super::path_format::SharedFormatter;
+use crate::schemas::ValidationContext;
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ValidationError {
-    pub context: ValidationErrorContext,
+    pub context: Box<ValidationErrorContext>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ValidationErrorContext {
     pub code: String,
-    #[serde(skip_serializing_if = "String::is_empty")]
-    pub path: String,
+    #[serde(skip_serializing_if = "ErrorPath::is_empty")]
+    pub path: ErrorPath,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
     #[serde(skip_serializing_if = "ValidationDetails::is_empty")]
     pub details: ValidationDetails,
+    /// Whether `message` was configured by the user (`error_message`,
+    /// `required_message`, a refinement's error), which error maps keep
+    #[serde(skip)]
+    pub(crate) custom_message: bool,
+    /// How outputs of this error render `path`, taken from the
+    /// [`ValidationContext`] it was raised under
+    #[serde(skip)]
+    pub(crate) path_formatter: Option<SharedFormatter>,
+    /// Locale of the catalog `message` came from, whose plural rules it is
+    /// rendered with
+    #[serde(skip)]
+    pub(crate) locale: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, serde::Serialize)]
@@ -22,6 +38,8 @@ pub struct ValidationDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_length: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub min_value: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_value: Option<f64>,
@@ -33,61 +51,77 @@ pub struct ValidationDetails {
     pub actual_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub field_name: Option<String>,
+    /// What the value had to be, as text, where no other detail says it: a
+    /// date or decimal bound, a step, the allowed values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Truncated copy of the value that failed, filled in for values
+    /// validated through [`SchemaType`](crate::SchemaType) (nested fields,
+    /// array items, union branches). `"[REDACTED]"` under a sensitive schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub received: Option<serde_json::Value>,
+    /// Where the value sits in the JSON text, for errors from
+    /// [`Schema::validate_str`](crate::Schema::validate_str)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
+    /// Path in the notation of the source format, e.g. `servers[0].host`,
+    /// for errors from `validate_yaml` and `validate_toml`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
 }
 
 impl ValidationDetails {
     pub fn is_empty(&self) -> bool {
         self.min_length.is_none() &&
         self.max_length.is_none() &&
+        self.actual_length.is_none() &&
         self.min_value.is_none() &&
         self.max_value.is_none() &&
         self.pattern.is_none() &&
         self.expected_type.is_none() &&
         self.actual_type.is_none() &&
-        self.field_name.is_none()
+        self.field_name.is_none() &&
+        self.expected.is_none() &&
+        self.received.is_none() &&
+        self.span.is_none() &&
+        self.source_path.is_none()
     }
 }
 
 impl ValidationError {
     pub fn new(code: impl Into<String>) -> Self {
         let code = code.into();
-        let message = match code.as_str() {
-            "string.too_short" => "String must be at least {min_length} characters long",
-            "string.too_long" => "String must be at most {max_length} characters long",
-            "string.email" => "Invalid email address",
-            "string.pattern" => "String must match pattern: {pattern}",
-            "number.too_small" => "Number must be greater than or equal to {min_value}",
-            "number.too_large" => "Number must be less than or equal to {max_value}",
-            "object.required" => "Field '{field_name}' is required",
-            "object.unknown_field" => "Unknown field: {field_name}",
-            "object.invalid_type" => "Expected {expected_type}, got {actual_type}",
-            "array.min_items" => "Must have at least {min_items} items",
-            "array.max_items" => "Must have at most {max_items} items",
-            "array.type" => "Must be an array",
-            "boolean.type" => "Must be a boolean value",
-            "number.type" => "Must be a number",
-            "number.integer" => "Must be an integer",
-            "object.type" => "Must be an object",
-            _ => "Validation error"
-        }.to_string();
+        let message = match resolved::template(&code) {
+            Some(template) => template.to_string(),
+            None => default_template(&code),
+        };
 
         Self {
-            context: ValidationErrorContext {
+            context: Box::new(ValidationErrorContext {
                 code,
-                path: String::new(),
+                path: ErrorPath::new(),
                 message: Some(message),
                 details: ValidationDetails::default(),
-            },
+                custom_message: false,
+                path_formatter: ValidationContext::current_path_formatter(),
+                locale: None,
+            }),
         }
     }
 
-    pub fn at(mut self, path: impl Into<String>) -> Self {
+    /// Typed code for exhaustive matching; `context.code` holds its string form
+    pub fn code(&self) -> ErrorCode {
+        ErrorCode::from_code(&self.context.code)
+    }
+
+    pub fn at(mut self, path: impl Into<ErrorPath>) -> Self {
         self.context.path = path.into();
         self
     }
 
     pub fn message(mut self, message: impl Into<String>) -> Self {
         self.context.message = Some(message.into());
+        self.context.locale = None;
         self
     }
 
@@ -95,13 +129,34 @@ impl ValidationError {
         self.message(message)
     }
 
-    pub fn with_path_prefix(mut self, prefix: impl Into<String>) -> Self {
-        let prefix = prefix.into();
-        if self.context.path.is_empty() {
-            self.context.path = prefix;
-        } else {
-            self.context.path = format!("{}.{}", prefix, self.context.path);
+    /// Set a user-configured message, which error maps never replace
+    pub(crate) fn custom_message(mut self, message: impl Into<String>) -> Self {
+        self.context.message = Some(message.into());
+        self.context.locale = None;
+        self.context.custom_message = true;
+        self
+    }
+
+    /// [`custom_message`](Self::custom_message) when the schema has one
+    /// configured, otherwise the code's default template
+    pub(crate) fn message_or_default(self, custom: Option<impl Into<String>>) -> Self {
+        match custom {
+            Some(message) => self.custom_message(message),
+            None => self,
+        }
+    }
+
+    /// [`custom_message`](Self::custom_message) when the schema has one
+    /// configured, otherwise the built-in `default`
+    pub(crate) fn message_or(self, custom: Option<impl Into<String>>, default: impl Into<String>) -> Self {
+        match custom {
+            Some(message) => self.custom_message(message),
+            None => self.message(default),
         }
+    }
+
+    pub fn with_path_prefix(mut self, prefix: impl Into<PathSegment>) -> Self {
+        self.context.path.prepend(prefix);
         self
     }
 
@@ -120,6 +175,15 @@ impl ValidationError {
         self
     }
 
+    /// Record `value` as the received value, unless one is already set or
+    /// the error belongs to a nested location
+    pub(crate) fn with_received(mut self, value: &serde_json::Value) -> Self {
+        if self.context.path.is_empty() && self.context.details.received.is_none() {
+            self.context.details.received = Some(received_preview(value));
+        }
+        self
+    }
+
     pub fn with_type_info(mut self, expected: impl Into<String>, actual: impl Into<String>) -> Self {
         self.context.details.expected_type = Some(expected.into());
         self.context.details.actual_type = Some(actual.into());
@@ -127,72 +191,117 @@ impl ValidationError {
     }
 
     pub fn format_message(&mut self) -> String {
-        let msg = if let Some(ref message) = self.context.message {
-            message.clone()
-        } else {
-            match self.context.code.as_str() {
-                "string.too_short" => "String must be at least {min_length} characters long",
-                "string.too_long" => "String must be at most {max_length} characters long",
-                "string.email" => "Invalid email address",
-                "string.pattern" => "String must match pattern: {pattern}",
-                "number.too_small" => "Number must be greater than or equal to {min_value}",
-                "number.too_large" => "Number must be less than or equal to {max_value}",
-                "object.required" => "Field '{field_name}' is required",
-                "object.unknown_field" => "Unknown field: {field_name}",
-                "object.invalid_type" => "Expected {expected_type}, got {actual_type}",
-                "array.min_items" => "Must have at least {min_items} items",
-                "array.max_items" => "Must have at most {max_items} items",
-                "array.type" => "Must be an array",
-                "boolean.type" => "Must be a boolean value",
-                "number.type" => "Must be a number",
-                "number.integer" => "Must be an integer",
-                "object.type" => "Must be an object",
-                _ => "Validation error"
-            }.to_string()
+        if let Some(map) = error_map::global() {
+            map.apply(self);
+        }
+
+        let template = match &self.context.message {
+            Some(message) => message.clone(),
+            None => default_template(&self.context.code),
         };
+        self.context.message = Some(template.clone());
 
-        // Update the message
-        self.context.message = Some(msg.clone());
+        template::render(&template, &self.context)
+    }
 
-        // Replace placeholders with actual values
-        let mut formatted_msg = msg.clone();
-        if let Some(min) = self.context.details.min_length {
-            formatted_msg = formatted_msg.replace("{min_length}", &min.to_string());
-            formatted_msg = formatted_msg.replace("{min_items}", &min.to_string());
-        }
-        if let Some(max) = self.context.details.max_length {
-            formatted_msg = formatted_msg.replace("{max_length}", &max.to_string());
-            formatted_msg = formatted_msg.replace("{max_items}", &max.to_string());
-        }
-        if let Some(min) = self.context.details.min_value {
-            formatted_msg = formatted_msg.replace("{min_value}", &min.to_string());
-            formatted_msg = formatted_msg.replace("{min}", &min.to_string());
-        }
-        if let Some(max) = self.context.details.max_value {
-            formatted_msg = formatted_msg.replace("{max_value}", &max.to_string());
-            formatted_msg = formatted_msg.replace("{max}", &max.to_string());
+    /// Render the path with `formatter` in every output of this error:
+    /// JSON, key-value pairs, display of [`ValidationErrors`](super::ValidationErrors),
+    /// problem details and diagnostics
+    pub fn with_path_formatter(self, formatter: impl PathFormatter + 'static) -> Self {
+        self.with_shared_formatter(SharedFormatter(Arc::new(formatter)))
+    }
+
+    pub(crate) fn with_shared_formatter(mut self, formatter: SharedFormatter) -> Self {
+        self.context.path_formatter = Some(formatter);
+        self
+    }
+
+    /// The path as rendered by this error's formatter, dot-joined without one
+    pub fn formatted_path(&self) -> String {
+        self.path_formatter().format(&self.context.path)
+    }
+
+    pub(crate) fn path_formatter(&self) -> &dyn PathFormatter {
+        match &self.context.path_formatter {
+            Some(formatter) => formatter.0.as_ref(),
+            None => &PathStyle::Dot,
         }
-        if let Some(ref pattern) = self.context.details.pattern {
-            formatted_msg = formatted_msg.replace("{pattern}", pattern);
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        self.to_json_with(self.path_formatter())
+    }
+
+    /// [`ValidationError::to_json`] with the path rendered by `formatter`
+    pub fn to_json_with(&self, formatter: &dyn PathFormatter) -> serde_json::Value {
+        let mut json = serde_json::to_value(self).unwrap();
+        if !self.context.path.is_empty() {
+            json["context"]["path"] = serde_json::Value::String(formatter.format(&self.context.path));
         }
-        if let Some(ref field) = self.context.details.field_name {
-            formatted_msg = formatted_msg.replace("{field_name}", field);
-            formatted_msg = formatted_msg.replace("{field}", field);
+        json
+    }
+
+    /// Flat fields for structured loggers (`tracing`, `slog`): `code`,
+    /// `path`, `message`, and when known `expected`, `actual` and `limit`
+    pub fn to_kv(&self) -> Vec<(&'static str, String)> {
+        self.to_kv_with(self.path_formatter())
+    }
+
+    /// [`ValidationError::to_kv`] with the path rendered by `formatter`
+    pub fn to_kv_with(&self, formatter: &dyn PathFormatter) -> Vec<(&'static str, String)> {
+        let d = &self.context.details;
+        let mut kv = vec![("code", self.context.code.clone())];
+        if !self.context.path.is_empty() {
+            kv.push(("path", formatter.format(&self.context.path)));
         }
-        if let (Some(ref expected), Some(ref actual)) = (
-            self.context.details.expected_type.as_ref(),
-            self.context.details.actual_type.as_ref()
-        ) {
-            formatted_msg = formatted_msg.replace("{expected_type}", expected);
-            formatted_msg = formatted_msg.replace("{actual_type}", actual);
+        kv.push(("message", self.to_string()));
+
+        let expected = d.expected_type.clone().or_else(|| d.pattern.clone()).or_else(|| d.expected.clone());
+        let actual = d.actual_type.clone()
+            .or_else(|| d.actual_length.map(|n| n.to_string()))
+            .or_else(|| d.received.as_ref().map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            }));
+        let limit = d.min_length.or(d.max_length).map(|n| n.to_string())
+            .or_else(|| d.min_value.or(d.max_value).map(|n| n.to_string()));
+        for (key, value) in [("expected", expected), ("actual", actual), ("limit", limit)] {
+            if let Some(value) = value {
+                kv.push((key, value));
+            }
         }
-
-        // Return the formatted message
-        formatted_msg
+        kv
     }
+}
 
-    pub fn to_json(&self) -> serde_json::Value {
-        serde_json::to_value(self).unwrap()
+/// Built-in message template for `code`, before placeholders are filled,
+/// as listed by [`ErrorCode::all`]
+fn default_template(code: &str) -> String {
+    #[cfg(test)]
+    TEMPLATE_LOOKUPS.with(|n| n.set(n.get() + 1));
+    ErrorCode::from_code(code).default_message()
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Calls of `default_template` on this thread
+    pub(crate) static TEMPLATE_LOOKUPS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Longest string kept in `received`, in characters
+const RECEIVED_MAX_CHARS: usize = 64;
+
+/// Copy of `value` small enough to attach to an error: long strings are cut
+/// and arrays and objects summarized by size
+fn received_preview(value: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::String(s) if s.chars().count() > RECEIVED_MAX_CHARS => {
+            Value::String(format!("{}…", s.chars().take(RECEIVED_MAX_CHARS).collect::<String>()))
+        }
+        Value::Array(items) => Value::String(format!("<array of {} items>", items.len())),
+        Value::Object(map) => Value::String(format!("<object with {} keys>", map.len())),
+        _ => value.clone(),
     }
 }
 
@@ -213,6 +322,13 @@ mod tests {
     use error_code::ErrorCode;
     use serde_json::json;
 
+    #[test]
+    fn test_error_is_pointer_sized() {
+        assert_eq!(std::mem::size_of::<ValidationError>(), std::mem::size_of::<usize>());
+        let error = ValidationError::new(ErrorCode::StringTooShort).at("name");
+        assert_eq!(error.context.path, "name");
+    }
+
     #[test]
     fn test_string_length_error() {
         let error = ValidationError::new(ErrorCode::StringTooShort)
@@ -507,4 +623,38 @@ mod tests {
             "Custom error message"
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_code_accessor() {
+        let from_enum = ValidationError::new(ErrorCode::RequiredField);
+        let from_str = ValidationError::new("object.required");
+
+        assert_eq!(from_enum.code(), ErrorCode::RequiredField);
+        assert_eq!(from_enum.code(), from_str.code());
+        assert_eq!(ValidationError::new("string.custom.no_spaces").code(), ErrorCode::Other("string.custom.no_spaces".into()));
+        assert_eq!(from_enum.context.message, from_str.context.message);
+    }
+
+    #[test]
+    fn test_to_kv() {
+        let error = ValidationError::new(ErrorCode::StringTooShort)
+            .at("user.name")
+            .with_details(|d| {
+                d.min_length = Some(3);
+                d.actual_length = Some(1);
+            });
+        assert_eq!(error.to_kv(), vec![
+            ("code", "string.too_short".to_string()),
+            ("path", "user.name".to_string()),
+            ("message", "String must be at least 3 characters long".to_string()),
+            ("actual", "1".to_string()),
+            ("limit", "3".to_string()),
+        ]);
+
+        let error = ValidationError::new(ErrorCode::InvalidType).with_type_info("number", "string");
+        let kv = error.to_kv();
+        assert!(kv.contains(&("expected", "number".to_string())));
+        assert!(kv.contains(&("actual", "string".to_string())));
+        assert!(!kv.iter().any(|(key, _)| *key == "path"));
+    }
+}
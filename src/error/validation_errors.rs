@@ -0,0 +1,263 @@
+use std::{collections::BTreeMap, fmt, sync::Arc};
+use super::{DisplayWith, PathFormatter, ValidationError};
+use super::path_format::SharedFormatter;
+
+/// Every error found while validating one value
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors(Vec<ValidationError>);
+
+impl ValidationErrors {
+    pub fn new(errors: Vec<ValidationError>) -> Self {
+        Self(errors)
+    }
+
+    pub fn issues(&self) -> &[ValidationError] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ValidationError> {
+        self.0.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<ValidationError> {
+        self.0
+    }
+
+    /// Translate every message, see [`ValidationError::localized`]
+    pub fn localized(self, locale: &str) -> Self {
+        Self(self.0.into_iter().map(|err| err.localized(locale)).collect())
+    }
+
+    /// Messages grouped by top-level field, with root-level issues kept
+    /// apart in `form_errors`
+    pub fn flatten(&self) -> FlattenedErrors {
+        let mut flat = FlattenedErrors::default();
+        for err in &self.0 {
+            match err.context.path.segments().first() {
+                None => flat.form_errors.push(err.to_string()),
+                Some(field) => flat.field_errors.entry(field.to_string()).or_default().push(err.to_string()),
+            }
+        }
+        flat
+    }
+
+    /// Messages nested along each issue's full path
+    pub fn tree(&self) -> ErrorTree {
+        let mut tree = ErrorTree::default();
+        for err in &self.0 {
+            let node = err.context.path.segments().iter().fold(&mut tree, |node, segment| {
+                node.properties.entry(segment.to_string()).or_default()
+            });
+            node.errors.push(err.to_string());
+        }
+        tree
+    }
+
+    /// JSON array with one entry per issue, as produced by [`ValidationError::to_json`]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(self.0.iter().map(ValidationError::to_json).collect())
+    }
+
+    /// [`ValidationErrors::to_json`] with paths rendered by `formatter`
+    pub fn to_json_with(&self, formatter: &dyn PathFormatter) -> serde_json::Value {
+        serde_json::Value::Array(self.0.iter().map(|err| err.to_json_with(formatter)).collect())
+    }
+
+    /// [`ValidationError::with_path_formatter`] for every issue
+    pub fn with_path_formatter(self, formatter: impl PathFormatter + 'static) -> Self {
+        let formatter = SharedFormatter(Arc::new(formatter));
+        Self(self.0.into_iter().map(|err| err.with_shared_formatter(formatter.clone())).collect())
+    }
+
+    /// Display one issue per line, with paths rendered by `formatter`
+    pub fn display_with<'a>(&'a self, formatter: &'a dyn PathFormatter) -> DisplayWith<'a> {
+        DisplayWith { errors: self, formatter: Some(formatter) }
+    }
+
+    /// JSON array for snapshot tests of an API's error contract
+    ///
+    /// Each issue is `{code, details?, message, path}` with the rendered
+    /// message and `path` always present (`""` at the root). Issues are
+    /// sorted by path, code and message, and object keys at every level.
+    /// Paths are dot-joined whatever formatter the errors carry, and source
+    /// spans are left out, as both depend on how the output or input was
+    /// formatted rather than on what it contained.
+    pub fn to_canonical_json(&self) -> serde_json::Value {
+        let mut issues: Vec<_> = self.0.iter().map(|err| {
+            let mut details = serde_json::to_value(&err.context.details).unwrap();
+            if let Some(obj) = details.as_object_mut() {
+                obj.remove("span");
+            }
+            let mut issue = serde_json::json!({
+                "code": err.context.code,
+                "message": err.to_string(),
+                "path": err.context.path.to_string(),
+            });
+            if details.as_object().is_some_and(|d| !d.is_empty()) {
+                issue["details"] = details;
+            }
+            sort_keys(issue)
+        }).collect();
+        issues.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+        serde_json::Value::Array(issues)
+    }
+}
+
+fn sort_key(issue: &serde_json::Value) -> [Option<&str>; 3] {
+    [issue["path"].as_str(), issue["code"].as_str(), issue["message"].as_str()]
+}
+
+/// `value` with object keys inserted in sorted order, so it serializes the
+/// same whether or not `serde_json` preserves insertion order
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(obj) => {
+            let sorted: BTreeMap<_, _> = obj.into_iter().collect();
+            serde_json::Value::Object(sorted.into_iter().map(|(k, v)| (k, sort_keys(v))).collect())
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// Field→messages view from [`ValidationErrors::flatten`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct FlattenedErrors {
+    /// Issues on the value itself rather than one of its fields
+    pub form_errors: Vec<String>,
+    pub field_errors: BTreeMap<String, Vec<String>>,
+}
+
+/// Nested view from [`ValidationErrors::tree`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ErrorTree {
+    pub errors: Vec<String>,
+    /// Children keyed by object field or array index
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, ErrorTree>,
+}
+
+impl From<ValidationError> for ValidationErrors {
+    fn from(err: ValidationError) -> Self {
+        Self(vec![err])
+    }
+}
+
+impl From<Vec<ValidationError>> for ValidationErrors {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        Self(errors)
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = ValidationError;
+    type IntoIter = std::vec::IntoIter<ValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ValidationErrors {
+    type Item = &'a ValidationError;
+    type IntoIter = std::slice::Iter<'a, ValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayWith { errors: self, formatter: None }.fmt(f)
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn errors() -> ValidationErrors {
+        ValidationErrors::new(vec![
+            ValidationError::new("object.custom").message("Passwords must match"),
+            ValidationError::new("string.too_short").at("name").message("Too short"),
+            ValidationError::new("string.email").at("contacts.0.email").message("Bad email"),
+            ValidationError::new("object.required").at("contacts.1.phone").message("Phone required"),
+        ])
+    }
+
+    #[test]
+    fn test_flatten() {
+        let flat = errors().flatten();
+
+        assert_eq!(flat.form_errors, ["Passwords must match"]);
+        assert_eq!(flat.field_errors["name"], ["Too short"]);
+        assert_eq!(flat.field_errors["contacts"], ["Bad email", "Phone required"]);
+    }
+
+    #[test]
+    fn test_tree() {
+        let tree = serde_json::to_value(errors().tree()).unwrap();
+
+        assert_eq!(tree, json!({
+            "errors": ["Passwords must match"],
+            "properties": {
+                "name": {"errors": ["Too short"]},
+                "contacts": {"errors": [], "properties": {
+                    "0": {"errors": [], "properties": {"email": {"errors": ["Bad email"]}}},
+                    "1": {"errors": [], "properties": {"phone": {"errors": ["Phone required"]}}},
+                }},
+            },
+        }));
+    }
+
+    #[test]
+    fn test_display_and_json() {
+        let errors = errors();
+
+        assert_eq!(
+            errors.to_string(),
+            "Passwords must match\nname: Too short\ncontacts.0.email: Bad email\ncontacts.1.phone: Phone required"
+        );
+        assert_eq!(errors.to_json().as_array().unwrap().len(), 4);
+        assert_eq!(errors.into_iter().count(), 4);
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        let errors = ValidationErrors::new(vec![
+            ValidationError::new("string.too_short").at("name").message("Too short")
+                .with_details(|d| {
+                    d.min_length = Some(2);
+                    d.span = Some(crate::error::SourceSpan { start: 3, end: 5, line: 1, column: 4 });
+                }),
+            ValidationError::new("object.custom").message("Passwords must match"),
+            ValidationError::new("object.required").at("age").message("Age required"),
+        ]);
+        let canonical = errors.to_canonical_json();
+        assert_eq!(canonical, json!([
+            {"code": "object.custom", "message": "Passwords must match", "path": ""},
+            {"code": "object.required", "message": "Age required", "path": "age"},
+            {"code": "string.too_short", "details": {"min_length": 2}, "message": "Too short", "path": "name"},
+        ]));
+        assert_eq!(
+            canonical[2].to_string(),
+            r#"{"code":"string.too_short","details":{"min_length":2},"message":"Too short","path":"name"}"#
+        );
+
+        let reversed = ValidationErrors::new(errors.into_vec().into_iter().rev().collect());
+        assert_eq!(reversed.to_canonical_json(), canonical);
+    }
+}
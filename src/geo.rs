@@ -0,0 +1,189 @@
+//! Ready-made schemas for GeoJSON ([RFC 7946](https://www.rfc-editor.org/rfc/rfc7946))
+//! payloads:
+//!
+//! ```
+//! use rusty_zod::prelude::*;
+//! use rusty_zod::geo;
+//! use serde_json::json;
+//!
+//! let place = object()
+//!     .field("name", string())
+//!     .field("area", geo::geojson_geometry());
+//! assert!(place.validate(&json!({
+//!     "name": "Plaza",
+//!     "area": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]},
+//! })).is_ok());
+//! ```
+//!
+//! Coordinates are `[longitude, latitude]` with an optional altitude, and a
+//! longitude or latitude out of range fails as `geo.longitude` or
+//! `geo.latitude` on the position holding it.
+
+use serde_json::Value;
+
+use crate::error::{CustomIssue, ErrorCode};
+use crate::schemas::{ArraySchema, NumberSchema, ObjectSchema, UnionSchema};
+use crate::{array, eq, number, object, union};
+
+/// `type` of every geometry but `GeometryCollection`
+const SIMPLE_TYPES: [&str; 6] = ["Point", "MultiPoint", "LineString", "MultiLineString", "Polygon", "MultiPolygon"];
+
+/// A position: `[longitude, latitude]` or `[longitude, latitude, altitude]`
+pub fn point() -> ArraySchema<NumberSchema> {
+    array(number())
+        .len_range(2..=3)
+        .custom(|items| {
+            check_longitude(&items[0])?;
+            check_latitude(&items[1])
+        })
+}
+
+/// A bounding box: `[west, south, east, north]`, or with altitudes
+/// `[west, south, min_altitude, east, north, max_altitude]`
+///
+/// `west` may exceed `east` for a box crossing the antimeridian; `south`
+/// may not exceed `north`.
+pub fn bbox() -> ArraySchema<NumberSchema> {
+    array(number())
+        .custom(|items| {
+            let (south, north) = match items.len() {
+                4 => (1, 3),
+                6 => (1, 4),
+                _ => return Err(CustomIssue::from(ErrorCode::GeoBbox)),
+            };
+            let half = items.len() / 2;
+            check_longitude(&items[0])?;
+            check_longitude(&items[half])?;
+            check_latitude(&items[south])?;
+            check_latitude(&items[north])?;
+            if items[south].as_f64() > items[north].as_f64() {
+                return Err(CustomIssue::from(ErrorCode::GeoBbox));
+            }
+            Ok(())
+        })
+}
+
+/// A GeoJSON geometry object, any of `Point`, `MultiPoint`, `LineString`,
+/// `MultiLineString`, `Polygon`, `MultiPolygon` and `GeometryCollection`
+///
+/// Line strings need two positions; polygon rings need four and must end
+/// where they start (`geo.ring_not_closed`). Collections may not nest, as
+/// RFC 7946 advises.
+pub fn geojson_geometry() -> ObjectSchema {
+    let collection = simple_geometry()
+        .when("type", eq("GeometryCollection"), |s| s.field("geometries", array(simple_geometry())));
+    collection.field("type", geometry_type(SIMPLE_TYPES.into_iter().chain(["GeometryCollection"])))
+}
+
+fn simple_geometry() -> ObjectSchema {
+    let line_string = || array(point()).min_items(2);
+    let polygon = || array(ring());
+    object()
+        .field("type", geometry_type(SIMPLE_TYPES))
+        .optional_field("bbox", bbox())
+        .when("type", eq("Point"), |s| s.field("coordinates", point()))
+        .when("type", eq("MultiPoint"), |s| s.field("coordinates", array(point())))
+        .when("type", eq("LineString"), |s| s.field("coordinates", line_string()))
+        .when("type", eq("MultiLineString"), |s| s.field("coordinates", array(line_string())))
+        .when("type", eq("Polygon"), |s| s.field("coordinates", polygon()))
+        .when("type", eq("MultiPolygon"), |s| s.field("coordinates", array(polygon())))
+}
+
+/// One of `types`; a union reports its last branch's error, so each literal
+/// carries the message
+fn geometry_type<'a>(types: impl IntoIterator<Item = &'a str>) -> UnionSchema {
+    let literals = types.into_iter().map(|t| eq(t).error_message("literal.mismatch", "Unknown geometry type"));
+    union(literals.collect::<Vec<_>>())
+}
+
+/// A linear ring: four or more positions, the last equal to the first
+fn ring() -> ArraySchema<ArraySchema<NumberSchema>> {
+    array(point())
+        .min_items(4)
+        .custom(|positions| match (positions.first(), positions.last()) {
+            (Some(first), Some(last)) if !same_position(first, last) => {
+                Err(CustomIssue::from(ErrorCode::GeoRingNotClosed))
+            }
+            _ => Ok(()),
+        })
+}
+
+/// Equal coordinates, whether written as `1` or `1.0`
+fn same_position(a: &Value, b: &Value) -> bool {
+    match (a.as_array(), b.as_array()) {
+        (Some(a), Some(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.as_f64() == b.as_f64()),
+        _ => false,
+    }
+}
+
+fn check_longitude(value: &Value) -> Result<(), CustomIssue> {
+    check_range(value, 180.0, ErrorCode::GeoLongitude)
+}
+
+fn check_latitude(value: &Value) -> Result<(), CustomIssue> {
+    check_range(value, 90.0, ErrorCode::GeoLatitude)
+}
+
+fn check_range(value: &Value, limit: f64, code: ErrorCode) -> Result<(), CustomIssue> {
+    match value.as_f64() {
+        Some(n) if (-limit..=limit).contains(&n) => Ok(()),
+        _ => Err(code.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::schemas::Schema;
+
+    #[test]
+    fn test_point_and_bbox() {
+        assert!(point().validate(&json!([13.4, 52.5])).is_ok());
+        assert!(point().validate(&json!([13.4, 52.5, 34])).is_ok());
+        assert_eq!(point().validate(&json!([13.4])).unwrap_err().context.code, "array.too_short");
+        assert_eq!(point().validate(&json!([181, 0])).unwrap_err().context.code, "geo.longitude");
+        let err = point().validate(&json!([0, -90.5])).unwrap_err();
+        assert_eq!(err.context.code, "geo.latitude");
+        assert_eq!(err.to_string(), "Latitude must be between -90 and 90");
+
+        assert!(bbox().validate(&json!([170, -10, -170, 10])).is_ok());
+        assert!(bbox().validate(&json!([0, 0, -5, 1, 1, 5])).is_ok());
+        assert_eq!(bbox().validate(&json!([0, 10, 1, 5])).unwrap_err().context.code, "geo.bbox");
+        assert_eq!(bbox().validate(&json!([0, 0, 1, 1, 2])).unwrap_err().context.code, "geo.bbox");
+        assert_eq!(bbox().validate(&json!([0, 0, 1, 91])).unwrap_err().context.code, "geo.latitude");
+    }
+
+    #[test]
+    fn test_geojson_geometry() {
+        let schema = geojson_geometry();
+        let square = json!([[0, 0], [1, 0], [1, 1], [0, 1], [0.0, 0.0]]);
+        assert!(schema.validate(&json!({"type": "Point", "coordinates": [1, 2]})).is_ok());
+        assert!(schema.validate(&json!({"type": "LineString", "coordinates": [[1, 2], [3, 4]]})).is_ok());
+        assert!(schema.validate(&json!({"type": "Polygon", "coordinates": [square], "bbox": [0, 0, 1, 1]})).is_ok());
+        assert!(schema.validate(&json!({"type": "MultiPolygon", "coordinates": [[square], [square]]})).is_ok());
+        assert!(schema.validate(&json!({"type": "GeometryCollection", "geometries": [
+            {"type": "Point", "coordinates": [1, 2]},
+            {"type": "MultiPoint", "coordinates": [[1, 2], [3, 4]]},
+        ]})).is_ok());
+
+        let err = schema.validate(&json!({"type": "Polygon", "coordinates": [
+            [[0, 0], [1, 0], [1, 1], [0, 1]],
+        ]})).unwrap_err();
+        assert_eq!(err.context.code, "geo.ring_not_closed");
+        assert_eq!(err.context.path, "coordinates.0");
+
+        let err = schema.validate(&json!({"type": "MultiPoint", "coordinates": [[1, 2], [200, 4]]})).unwrap_err();
+        assert_eq!(err.context.code, "geo.longitude");
+        assert_eq!(err.context.path, "coordinates.1");
+
+        let err = schema.validate(&json!({"type": "Circle", "coordinates": [1, 2]})).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown geometry type");
+        // Coordinates of one type are unknown to another
+        assert!(schema.validate(&json!({"type": "Point", "geometries": []})).is_err());
+        // Collections do not nest
+        assert!(schema.validate(&json!({"type": "GeometryCollection", "geometries": [
+            {"type": "GeometryCollection", "geometries": []},
+        ]})).is_err());
+    }
+}
@@ -1,69 +1,93 @@
+pub mod builders;
 pub mod error;
+pub mod geo;
+pub mod prelude;
 pub mod schemas;
-
-pub use error::ValidationError;
+pub mod testing;
+#[cfg(any(feature = "actix-web", feature = "warp", feature = "tower"))]
+pub mod web;
+
+pub use builders::{string, number, boolean, bytes, array, set, object, any, never, not, switch, feature_gated, eq, conditional, union, money, money_with, claims_schema, claims_schema_with, envelope, envelope_with, pagination};
+#[cfg(feature = "chrono")]
+pub use builders::{date, datetime};
+#[cfg(feature = "rust_decimal")]
+pub use builders::decimal;
+pub use error::{ValidationError, ValidationErrors};
+pub use schemas::body::{validate_body, validate_body_partial};
+pub use schemas::typed::{from_str_validated, from_value_validated, double_option};
 pub use schemas::{
-    Schema, SchemaType,
+    Schema, SchemaType, HasErrorMessages, Presence, IntoSchemaList,
+    InvalidFields, PartialOutput, SafeParseResult, PasswordPolicy, MoneyPolicy, ClaimsPolicy, EnvelopePolicy,
+    BatchOptions, BatchReport, ValidationReport, ValidationSession, NdjsonLines, StreamingArrayValidator,
+    LenientOutput, Repair, RepairKind,
     UnionSchema, UnionStrategy,
     string::{StringSchema, StringSchemaImpl},
-    NumberSchema, BooleanSchema, ArraySchema, ObjectSchema,
-    transform::Transformable,
+    NumberSchema, BooleanSchema, BytesSchema, ArraySchema, ObjectSchema, FieldSet, OverlaySpec, OverlayError, JsonSchemaError,
+    NeverSchema, NotSchema, AnySchema, CompiledSchema, Sensitive, ErrorMapped, Cached, Deprecated, Catch, Pipe, MapOutput,
+    Refined, Refinement, RefinementContext, LintFinding, LintKind, Explanation, Unsatisfiable, UnsatisfiableKind,
+    SwitchSchema, ValidationContext, ConditionalSchema, LiteralSchema,
+    transform::{Transformable, WithTransform},
 };
+#[cfg(feature = "chrono")]
+pub use schemas::{DateSchema, DateTimeSchema};
+#[cfg(feature = "csv")]
+pub use schemas::{CsvRows, CsvValidator};
+#[cfg(feature = "rust_decimal")]
+pub use schemas::DecimalSchema;
 
-pub mod prelude {
-    pub use crate::{
-        string, number, boolean, array, object,
-        union, union_best,
-        Schema, StringSchema,
-    };
-}
-
-/// Create a new string schema
-pub fn string() -> StringSchemaImpl {
-    StringSchemaImpl::default()
-}
-
-/// Create a new number schema
-pub fn number() -> NumberSchema {
-    NumberSchema::default()
-}
-
-/// Create a new boolean schema
-pub fn boolean() -> BooleanSchema {
-    BooleanSchema::default()
-}
-
-/// Create a new array schema
-pub fn array<S: Schema>(schema: S) -> ArraySchema {
-    ArraySchema::new(schema)
-}
-
-/// Create a new object schema
-pub fn object() -> ObjectSchema {
-    ObjectSchema::default()
+#[macro_export]
+macro_rules! union {
+    ($($schema:expr),+ $(,)?) => {{
+        let schemas = vec![$($crate::Schema::into_schema_type($schema)),+];
+        $crate::UnionSchema::new(schemas)
+    }};
 }
 
-/// Create a new union schema
-pub fn union<S: Schema>(schemas: Vec<S>) -> UnionSchema {
-    UnionSchema::new(schemas.into_iter().map(|s| s.into_schema_type()).collect())
+#[macro_export]
+macro_rules! union_best {
+    ($error_score:expr, $($schema:expr),+ $(,)?) => {{
+        let schemas = vec![$($crate::Schema::into_schema_type($schema)),+];
+        $crate::UnionSchema::new(schemas).strategy($crate::UnionStrategy::Best { error_score: ::std::sync::Arc::new($error_score) })
+    }};
 }
 
+/// Intersection: every schema must validate, outputs are merged
 #[macro_export]
-macro_rules! union {
+macro_rules! all_of {
     ($($schema:expr),+ $(,)?) => {{
-        let schemas = vec![$($schema),+];
-        UnionSchema::new(schemas)
+        let schemas = vec![$($crate::Schema::into_schema_type($schema)),+];
+        $crate::UnionSchema::new(schemas).strategy($crate::UnionStrategy::All)
     }};
 }
 
+/// Union reporting the lowest-scored error: `best_of!(schemas...; scorer)`
 #[macro_export]
-macro_rules! union_best {
-    ($error_score:expr, $($schema:expr),+ $(,)?) => {{
-        let schemas = vec![$($schema),+];
-        UnionSchema::new(schemas).strategy(UnionStrategy::Best { error_score: std::sync::Arc::new($error_score) })
+macro_rules! best_of {
+    ($($schema:expr),+ ; $error_score:expr $(,)?) => {{
+        let schemas = vec![$($crate::Schema::into_schema_type($schema)),+];
+        $crate::UnionSchema::new(schemas).strategy($crate::UnionStrategy::Best { error_score: ::std::sync::Arc::new($error_score) })
     }};
 }
 
+/// Build an [`ObjectSchema`] from a field list, in either syntax:
+///
+/// ```
+/// use rusty_zod::prelude::*;
+///
+/// // String keys; fields whose schema is `.optional()` are optional
+/// let a = object!({
+///     "name" => string().min_length(1),
+///     "email" => string().email().optional(),
+/// });
+///
+/// // Identifier keys; a trailing `?` marks the field optional
+/// let b = object! {
+///     name: string().min_length(1),
+///     email: string().email()?,
+///     tags: array(string())?,
+/// };
+/// # let _ = (a, b);
+/// ```
 #[macro_export]
 macro_rules! object {
     () => {
@@ -71,19 +95,51 @@ macro_rules! object {
     };
 
     ({ $($key:tt => $value:expr),* $(,)? }) => {{
+        #[allow(unused_mut)]
         let mut schema = $crate::object();
-        $(
-            let value = $value;
-            let is_optional = match &value {
-                s if s.is_optional() => true,
-                _ => false,
-            };
-            if is_optional {
-                schema = schema.optional_field($key, value);
-            } else {
-                schema = schema.field($key, value);
-            }
-        )*
+        $( $crate::object!(@insert schema; $key; $value); )*
+        schema
+    }};
+
+    ($($key:tt => $value:expr),+ $(,)?) => {
+        $crate::object!({ $($key => $value),+ })
+    };
+
+    ({ $($tokens:tt)* }) => {
+        $crate::object!($($tokens)*)
+    };
+
+    // Field with a value chosen by its own `is_optional()`
+    (@insert $schema:ident; $key:expr; $value:expr) => {
+        let value = $value;
+        $schema = if $crate::Schema::is_optional(&value) {
+            $schema.optional_field($key, value)
+        } else {
+            $schema.field($key, value)
+        };
+    };
+
+    // `key: schema` / `key: schema?` list, munched one field at a time
+    (@fields $schema:ident;) => {};
+    (@fields $schema:ident; $key:ident : $($rest:tt)+) => {
+        $crate::object!(@value $schema; $key; []; $($rest)+)
+    };
+    (@value $schema:ident; $key:ident; [$($value:tt)+]; ? $(, $($rest:tt)*)?) => {
+        $schema = $schema.optional_field(stringify!($key), $($value)+);
+        $crate::object!(@fields $schema; $($($rest)*)?);
+    };
+    (@value $schema:ident; $key:ident; [$($value:tt)+]; $(, $($rest:tt)*)?) => {
+        $crate::object!(@insert $schema; stringify!($key); $($value)+);
+        $crate::object!(@fields $schema; $($($rest)*)?);
+    };
+    (@value $schema:ident; $key:ident; [$($value:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::object!(@value $schema; $key; [$($value)* $next]; $($rest)*)
+    };
+
+    ($($tokens:tt)+) => {{
+        #[allow(unused_mut)]
+        let mut schema = $crate::object();
+        $crate::object!(@fields schema; $($tokens)+);
         schema
     }};
 }
@@ -131,6 +187,75 @@ mod tests {
         assert!(schema.validate(&invalid_data).is_err());
     }
 
+    #[test]
+    fn test_object_macro_ident_keys() {
+        let schema = object! {
+            name: string(),
+            age: number()?,
+            email: string().email()?
+        };
+
+        assert!(schema.validate(&json!({"name": "John"})).is_ok());
+        assert!(schema.validate(&json!({
+            "name": "John",
+            "age": 30,
+            "email": "john@example.com"
+        })).is_ok());
+        assert!(schema.validate(&json!({"age": 30})).is_err());
+        assert!(schema.validate(&json!({"name": "John", "email": "nope"})).is_err());
+    }
+
+    #[test]
+    fn test_object_macro_ident_keys_nested() {
+        let schema = object! {
+            name: string(),
+            age: number(),
+            email: string()?,
+            tags: array(string().min_length(1)).max_items(3)?,
+            address: object! {
+                street: string(),
+                city: string(),
+                country: string()?,
+                postal_code: string()?,
+            },
+        };
+
+        assert!(schema.validate(&json!({
+            "name": "John",
+            "age": 30,
+            "address": {"street": "123 Main St", "city": "New York"}
+        })).is_ok());
+
+        assert!(schema.validate(&json!({
+            "name": "John",
+            "age": 30,
+            "email": "john@example.com",
+            "tags": ["a"],
+            "address": {
+                "street": "123 Main St",
+                "city": "New York",
+                "country": "USA",
+                "postal_code": "10001"
+            }
+        })).is_ok());
+
+        let err = schema.validate(&json!({
+            "name": "John",
+            "age": 30,
+            "address": {"street": "123 Main St"}
+        })).unwrap_err();
+        assert_eq!(err.context.path, "address.city");
+    }
+
+    #[test]
+    fn test_object_macro_braced_string_keys() {
+        let schema = object! { "name" => string(), "nick" => string().optional() };
+        assert!(schema.validate(&json!({"name": "John"})).is_ok());
+
+        let schema = object!({ name: string() });
+        assert!(schema.validate(&json!({"name": "John"})).is_ok());
+    }
+
     #[test]
     fn test_object_macro_with_string_keys() {
         let schema = object! ({
@@ -309,6 +434,59 @@ mod tests {
         assert!(schema.validate(&invalid_data).is_err());
     }
 
+    #[test]
+    fn test_union_heterogeneous() {
+        let schema = union((string().email(), number().min(0.0), boolean()));
+        assert!(schema.validate(&json!("a@b.co")).is_ok());
+        assert!(schema.validate(&json!(3)).is_ok());
+        assert!(schema.validate(&json!(true)).is_ok());
+        assert!(schema.validate(&json!(-1)).is_err());
+
+        let schema = union![string(), number()];
+        assert!(schema.validate(&json!(1)).is_ok());
+        assert!(schema.validate(&json!(null)).is_err());
+
+        let schema = union(vec![string().min_length(2), string().email()]);
+        assert!(schema.validate(&json!("ab")).is_ok());
+    }
+
+    #[test]
+    fn test_all_of_and_best_of() {
+        let schema = all_of![string().min_length(2), string().max_length(4)];
+        assert!(schema.validate(&json!("abc")).is_ok());
+        assert!(schema.validate(&json!("abcde")).is_err());
+
+        let schema = best_of![
+            string().min_length(5),
+            number();
+            |e: &ValidationError| if e.context.code == "string.too_short" { 0 } else { 1 }
+        ];
+        assert!(schema.validate(&json!(1)).is_ok());
+        assert_eq!(schema.validate(&json!("abc")).unwrap_err().context.code, "string.too_short");
+    }
+
+    #[test]
+    fn test_prelude_covers_macros_and_traits() {
+        mod only_prelude {
+            use crate::prelude::*;
+            use serde_json::json;
+
+            pub fn check() {
+                let schema = union_best!(
+                    |e: &ValidationError| if e.context.code == "string.too_short" { 0 } else { 1 },
+                    string().min_length(5).into_schema_type(),
+                    number().into_schema_type()
+                );
+                let err = schema.validate(&json!("abc")).unwrap_err();
+                assert_eq!(err.context.code, "string.too_short");
+
+                let schema = object().field("tag", string().trim().min_length(1));
+                assert!(schema.check_messages().is_empty());
+            }
+        }
+        only_prelude::check();
+    }
+
     #[test]
     fn test_object_macro_with_boolean_fields() {
         let schema = object! ({
@@ -1,13 +1,22 @@
+//! Everything needed for typical usage: `use rusty_zod::prelude::*;`
+//!
+//! Covers the core schema constructors, the traits their methods come from
+//! and the error types. Niche entry points such as [`validate_body`](crate::validate_body)
+//! or [`money`](crate::builders::money) are reached through the crate root
+//! or [`builders`](crate::builders).
+
 pub use crate::{
-    array,
-    boolean,
-    number,
-    object,
-    string,
-    Schema,
-    ValidationError,
-    StringSchema,
-    NumberSchema,
-    ArraySchema,
-    ObjectSchema,
-};
\ No newline at end of file
+    string, number, boolean, bytes, array, object,
+    union, union_best, all_of, best_of,
+    Schema, SchemaType, HasErrorMessages, SafeParseResult,
+    StringSchema, StringSchemaImpl,
+    NumberSchema, BooleanSchema, BytesSchema, ArraySchema, ObjectSchema,
+    UnionSchema, UnionStrategy,
+    Transformable, WithTransform,
+    ValidationError, ValidationErrors,
+};
+pub use crate::error::ErrorCode;
+#[cfg(feature = "chrono")]
+pub use crate::{date, datetime, DateSchema, DateTimeSchema};
+#[cfg(feature = "rust_decimal")]
+pub use crate::{decimal, DecimalSchema};
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation};
+
+/// Schema that accepts every value unchanged, for free-form subtrees
+#[derive(Clone, Default)]
+pub struct AnySchema {
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl AnySchema {
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+}
+
+impl HasErrorMessages for AnySchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+}
+
+impl Schema for AnySchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        vec![Explanation::typed("any value", self.presence)]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        Ok(value.clone())
+    }
+
+    fn validate_ref(&self, _value: &Value) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    fn preserves_input(&self) -> bool {
+        true
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Any(self)
+    }
+}
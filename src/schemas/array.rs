@@ -1,138 +1,560 @@
-use std::collections::HashMap;
+use std::{collections::{hash_map::RandomState, HashMap}, hash::BuildHasher, ops::RangeBounds, sync::Arc};
 use serde_json::Value;
 
-use crate::error::ValidationError;
-use super::{Schema, SchemaType, HasErrorMessages, get_type_name, validate_schema_type};
+use crate::error::{ValidationError, MessageIssue, PathSegment, ErrorCode, CustomIssue, canonical_code};
+use crate::error::message_check::{check_templates, debug_check_template};
+use super::{string::length_bounds, Schema, Presence, SchemaType, HasErrorMessages, get_type_name, nest_issues, InvalidFields, PartialOutput, LintFinding, lint::nest_findings, Explanation, explain::{self, nest_explanations}, Unsatisfiable, satisfiable::{self, nest_unsatisfiable}, nested, refine, compat, parallel};
 
+/// How array items are compared when uniqueness is enforced
+#[derive(Clone, Debug, PartialEq)]
+pub enum Uniqueness {
+    /// Items must be distinct as whole values
+    Value,
+    /// Items must be distinct by the value at a dotted path inside each item
+    By(String),
+}
+
+/// Which items of an array [`ArraySchema::validate_sample`] checks; the
+/// first and last item are always checked
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sample {
+    /// Each item with this probability, drawn anew on every validation
+    Rate(f64),
+    /// About this many items, evenly spaced through the array
+    Count(usize),
+}
+
+impl From<f64> for Sample {
+    fn from(rate: f64) -> Self {
+        Sample::Rate(rate)
+    }
+}
+
+impl From<usize> for Sample {
+    fn from(count: usize) -> Self {
+        Sample::Count(count)
+    }
+}
+
+/// Picks the sampled items of one array
+struct Sampler {
+    sample: Sample,
+    len: usize,
+    state: RandomState,
+}
+
+impl Sampler {
+    fn picks(&self, i: usize) -> bool {
+        if i == 0 || i + 1 == self.len {
+            return true;
+        }
+        match self.sample {
+            Sample::Rate(rate) => (self.state.hash_one(i) as f64 / u64::MAX as f64) < rate,
+            Sample::Count(0) => false,
+            Sample::Count(count) => i.is_multiple_of((self.len / count).max(1)),
+        }
+    }
+}
+
+type CustomValidator = Arc<dyn Fn(&[Value]) -> Result<(), CustomIssue> + Send + Sync>;
+
+/// Array schema over items validated by `S`
+///
+/// The item schema keeps its concrete type, so it can still be configured
+/// after construction. `ArraySchema` without a parameter is the type-erased
+/// form used inside [`SchemaType`].
 #[derive(Clone)]
-pub struct ArraySchema {
-    item_schema: Box<SchemaType>,
+pub struct ArraySchema<S = SchemaType> {
+    item_schema: Box<S>,
     min_items: Option<usize>,
     max_items: Option<usize>,
-    optional: bool,
+    unique: Option<Uniqueness>,
+    presence: Presence,
     error_messages: HashMap<String, String>,
+    custom_validators: Vec<CustomValidator>,
+    sample: Option<Sample>,
+    parallel: Option<usize>,
 }
 
 impl ArraySchema {
-    pub fn new(schema: impl Schema) -> Self {
+    /// Type-erased constructor, for item schemas chosen at runtime
+    pub fn dynamic(schema: impl Schema) -> Self {
+        ArraySchema::new(schema.into_schema_type())
+    }
+
+    /// Rewrite the item schema in place
+    pub(crate) fn map_items(&mut self, f: impl FnOnce(SchemaType) -> SchemaType) {
+        let taken = std::mem::replace(self.item_schema.as_mut(), SchemaType::Never(Default::default()));
+        *self.item_schema = f(taken);
+    }
+
+    /// Configured `(min_items, max_items)`
+    pub(crate) fn item_limits(&self) -> (Option<usize>, Option<usize>) {
+        (self.min_items, self.max_items)
+    }
+
+    /// Whether [`Schema::validate_owned`] can move `items` through the item
+    /// schema one by one: nothing checks them as a whole or defers to the
+    /// document, and their count is within bounds
+    pub(crate) fn moves_items(&self, items: &[Value]) -> bool {
+        self.unique.is_none()
+            && self.custom_validators.is_empty()
+            && self.sample.is_none()
+            && !refine::defers(&self.item_schema)
+            && self.threads_for(items).is_none()
+            && self.check_len(items).is_ok()
+    }
+
+    /// Validate `items` by value, see [`ArraySchema::moves_items`]
+    pub(crate) fn validate_items_owned(&self, items: Vec<Value>) -> Result<Value, ValidationError> {
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| nested(i, || self.item_schema.validate_owned(item)).map_err(|e| self.item_error(i, e)))
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
+    }
+
+    /// Whether every value `other` accepts meets this schema's checks, see
+    /// [`Schema::accepts`]
+    pub(crate) fn covers(&self, other: &Self) -> bool {
+        compat::covers_null(self.presence, other.presence)
+            && compat::covers_min(self.min_items, other.min_items)
+            && compat::covers_max(self.max_items, other.max_items)
+            && self.unique.as_ref().is_none_or(|u| other.unique.as_ref() == Some(u))
+            && self.custom_validators.is_empty()
+            // A sampled array may hold items that were never checked
+            && other.sample.is_none()
+            && compat::accepts(&self.item_schema, &other.item_schema)
+    }
+}
+
+impl<S: Schema> ArraySchema<S> {
+    pub fn new(schema: S) -> Self {
         Self {
-            item_schema: Box::new(schema.into_schema_type()),
+            item_schema: Box::new(schema),
             min_items: None,
             max_items: None,
-            optional: false,
+            unique: None,
+            presence: Presence::Required,
             error_messages: HashMap::new(),
+            custom_validators: Vec::new(),
+            sample: None,
+            parallel: None,
+        }
+    }
+
+    /// The item schema
+    pub fn item(&self) -> &S {
+        &self.item_schema
+    }
+
+    /// Reconfigure the item schema, e.g. `.with_item(|s| s.min_length(1))`
+    pub fn with_item<T: Schema>(self, f: impl FnOnce(S) -> T) -> ArraySchema<T> {
+        ArraySchema {
+            item_schema: Box::new(f(*self.item_schema)),
+            min_items: self.min_items,
+            max_items: self.max_items,
+            unique: self.unique,
+            presence: self.presence,
+            error_messages: self.error_messages,
+            custom_validators: self.custom_validators,
+            sample: self.sample,
+            parallel: self.parallel,
         }
     }
 
     pub fn min_items(mut self, count: usize) -> Self {
+        self.set_min_items(count);
+        self
+    }
+
+    /// In-place form of [`ArraySchema::min_items`]
+    pub fn set_min_items(&mut self, count: usize) -> &mut Self {
         self.min_items = Some(count);
-        self.error_messages.insert("array.min_items".to_string(), format!("Must have at least {} items", count));
         self
     }
 
     pub fn max_items(mut self, count: usize) -> Self {
+        self.set_max_items(count);
+        self
+    }
+
+    /// In-place form of [`ArraySchema::max_items`]
+    pub fn set_max_items(&mut self, count: usize) -> &mut Self {
         self.max_items = Some(count);
-        self.error_messages.insert("array.max_items".to_string(), format!("Must have at most {} items", count));
+        self
+    }
+
+    /// Set both item count bounds from a Rust range, e.g. `1..=10`
+    pub fn len_range<R: RangeBounds<usize>>(mut self, range: R) -> Self {
+        let (min, max) = length_bounds(&range);
+        if let Some(min) = min {
+            self = self.min_items(min);
+        }
+        if let Some(max) = max {
+            self = self.max_items(max);
+        }
+        self
+    }
+
+    /// Reject arrays containing duplicate items
+    pub fn unique(mut self) -> Self {
+        self.unique = Some(Uniqueness::Value);
+        self
+    }
+
+    /// Reject arrays where two items share the same value at `path`
+    /// (e.g. `"id"` or `"user.id"`)
+    pub fn unique_by(mut self, path: impl Into<String>) -> Self {
+        self.unique = Some(Uniqueness::By(path.into()));
+        self
+    }
+
+    /// Validate only a sample of the items, e.g. `0.01` for about one in a
+    /// hundred at random or `100` for a hundred evenly spaced ones, always
+    /// including the first and last
+    ///
+    /// Meant for arrays too large to check in full, where catching a change
+    /// in shape is enough. Items left out are passed through untransformed;
+    /// item counts, uniqueness and custom checks still see every item.
+    pub fn validate_sample(mut self, sample: impl Into<Sample>) -> Self {
+        self.sample = Some(sample.into());
+        self
+    }
+
+    /// Validate the items on several threads once the array holds at
+    /// least `min_items` of them
+    ///
+    /// Worth it for large arrays of costly items. The output and errors are
+    /// those of validating in order: the error reported is that of the first
+    /// invalid item, and partial validation lists item errors by index.
+    #[cfg(feature = "rayon")]
+    pub fn parallel(mut self, min_items: usize) -> Self {
+        self.parallel = Some(min_items);
         self
     }
 
     pub fn optional(mut self) -> Self {
-        self.optional = true;
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.set_presence(presence);
+        self
+    }
+
+    /// In-place form of [`ArraySchema::presence`]
+    pub fn set_presence(&mut self, presence: Presence) -> &mut Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Custom check over the validated items, reported as `array.custom`
+    /// unless it returns a [`CustomIssue`] with its own code
+    pub fn custom<F, E>(mut self, validator: F) -> Self
+    where
+        F: Fn(&[Value]) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
+    {
+        self.custom_validators.push(Arc::new(move |items| validator(items).map_err(Into::into)));
         self
     }
 
     pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
-        self.error_messages.insert(code.into(), message.into());
+        self.set_error_message(code, message);
         self
     }
+
+    /// In-place form of [`ArraySchema::error_message`]
+    pub fn set_error_message(&mut self, code: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::ArrayRequired, message)
+    }
+}
+
+impl<S> ArraySchema<S> {
+    /// Picker of the items to validate in `arr`, `None` unless sampling
+    fn sampler(&self, arr: &[Value]) -> Option<Sampler> {
+        self.sample.map(|sample| Sampler { sample, len: arr.len(), state: RandomState::new() })
+    }
+
+    fn check_len(&self, arr: &[Value]) -> Result<(), ValidationError> {
+        if let Some(min_items) = self.min_items {
+            if arr.len() < min_items {
+                let mut err = ValidationError::new(ErrorCode::ArrayTooShort)
+                    .with_details(|d| {
+                        d.min_length = Some(min_items);
+                        d.actual_length = Some(arr.len());
+                    });
+                if let Some(msg) = self.error_messages.get("array.too_short") {
+                    err = err.custom_message(msg.clone());
+                }
+                return Err(err);
+            }
+        }
+
+        if let Some(max_items) = self.max_items {
+            if arr.len() > max_items {
+                let mut err = ValidationError::new(ErrorCode::ArrayTooLong)
+                    .with_details(|d| {
+                        d.max_length = Some(max_items);
+                        d.actual_length = Some(arr.len());
+                    });
+                if let Some(msg) = self.error_messages.get("array.too_long") {
+                    err = err.custom_message(msg.clone());
+                }
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Threads to validate `items` on, if enough for [`ArraySchema::parallel`]
+    fn threads_for(&self, items: &[Value]) -> Option<usize> {
+        self.parallel.filter(|&min| items.len() >= min).map(|_| parallel::available_threads())
+    }
+
+    fn item_error(&self, i: usize, e: ValidationError) -> ValidationError {
+        let err = e.with_path_prefix(i);
+        if let Some(msg) = self.error_messages.get("array.invalid_item") {
+            err.custom_message(msg.clone())
+        } else {
+            err.message(format!("Item {} is invalid", i))
+        }
+    }
+
+    fn check_custom(&self, items: &[Value]) -> Result<(), ValidationError> {
+        for validator in &self.custom_validators {
+            validator(items).map_err(|issue| issue.into_error(ErrorCode::ArrayCustom.to_string(), &self.error_messages))?;
+        }
+        Ok(())
+    }
+
+    fn check_unique(&self, unique: &Uniqueness, items: &[Value]) -> Result<(), ValidationError> {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            let key = match unique {
+                Uniqueness::Value => Some(item),
+                Uniqueness::By(path) => path.split('.').try_fold(item, |v, key| v.get(key)),
+            };
+            // Items without the key cannot collide
+            let Some(key) = key else { continue };
+            let key = key.to_string();
+
+            if let Some(first) = seen.get(&key) {
+                let err = ValidationError::new(ErrorCode::DuplicateItem)
+                    .at(PathSegment::Index(i))
+                    .with_details(|d| {
+                        d.expected = Some(first.to_string());
+                        if let Uniqueness::By(path) = unique {
+                            d.field_name = Some(path.clone());
+                        }
+                    });
+                return Err(err.message_or_default(self.error_messages.get("array.unique")));
+            }
+            seen.insert(key, i);
+        }
+        Ok(())
+    }
 }
 
-impl HasErrorMessages for ArraySchema {
+impl<S: HasErrorMessages> HasErrorMessages for ArraySchema<S> {
     fn error_messages(&self) -> &HashMap<String, String> {
         &self.error_messages
     }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = check_templates("", &self.error_messages);
+        issues.extend(nest_issues("*", self.item_schema.check_messages()));
+        issues
+    }
 }
 
-impl Schema for ArraySchema {
-    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
-        match value {
-            Value::Array(arr) => {
-                if let Some(min_items) = self.min_items {
-                    if arr.len() < min_items {
-                        let mut err = ValidationError::new("array.min_items")
-                            .with_details(|d| {
-                                d.min_length = Some(min_items);
-                            });
-                        if let Some(msg) = self.error_messages.get("array.min_items") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message("less than minimum".to_string());
-                        }
-                        return Err(err);
-                    }
+impl<S: Schema> Schema for ArraySchema<S> {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("array.required").cloned()
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        let mut issues: Vec<_> = satisfiable::check_bounds("item count", self.min_items, self.max_items).into_iter().collect();
+        issues.extend(nest_unsatisfiable("*", self.item_schema.check_satisfiable()));
+        issues
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("array", self.presence);
+        for rule in explain::bounds(self.min_items, self.max_items, "item") {
+            explanation.rule(rule);
+        }
+        match &self.unique {
+            Some(Uniqueness::Value) => explanation.rule("items unique"),
+            Some(Uniqueness::By(path)) => explanation.rule(format!("items unique by `{}`", path)),
+            None => {}
+        }
+        for _ in &self.custom_validators {
+            explanation.rule("custom check");
+        }
+        let mut explanations = vec![explanation];
+        explanations.extend(nest_explanations("*", self.item_schema.explain()));
+        explanations
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let Value::Array(arr) = value else {
+            return PartialOutput::from_result(self.validate(value), value, invalid);
+        };
+        refine::root_partial(value, || {
+            if let Err(err) = self.check_len(arr) {
+                return PartialOutput::from_result(Err(err), value, invalid);
+            }
+
+            let sampler = self.sampler(arr);
+            let validate_item = |i, item: &Value| {
+                if sampler.as_ref().is_some_and(|s| !s.picks(i)) {
+                    return PartialOutput { value: Some(item.clone()), errors: Vec::new() };
                 }
+                nested(i, || self.item_schema.validate_partial_with(item, invalid))
+            };
+            let outputs = parallel::map_indexed(arr, self.threads_for(arr).unwrap_or(1), validate_item);
 
-                if let Some(max_items) = self.max_items {
-                    if arr.len() > max_items {
-                        let mut err = ValidationError::new("array.max_items")
-                            .with_details(|d| {
-                                d.max_length = Some(max_items);
-                            });
-                        if let Some(msg) = self.error_messages.get("array.max_items") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message(format!("Must have at most {} items", max_items));
-                        }
-                        return Err(err);
-                    }
+            let mut result = Vec::new();
+            let mut errors = Vec::new();
+            for (i, output) in outputs.into_iter().enumerate() {
+                let mut item_errors = output.errors.into_iter();
+                if let Some(first) = item_errors.next() {
+                    errors.push(self.item_error(i, first));
+                    errors.extend(item_errors.map(|e| e.with_path_prefix(i)));
                 }
+                result.extend(output.value);
+            }
 
-                let mut result = Vec::new();
-                for (i, item) in arr.iter().enumerate() {
-                    match validate_schema_type(self.item_schema.as_ref(), item) {
-                        Ok(validated) => result.push(validated),
-                        Err(e) => {
-                            let mut err = e.with_path_prefix(&i.to_string());
-                            if let Some(msg) = self.error_messages.get("array.item") {
-                                err = err.message(msg.clone());
-                            } else {
-                                err = err.message(format!("Item {} is invalid", i));
-                            }
-                            return Err(err);
-                        }
-                    }
+            if let Some(unique) = &self.unique {
+                if let Err(err) = self.check_unique(unique, &result) {
+                    errors.push(err);
                 }
+            }
 
-                Ok(Value::Array(result))
+            if errors.is_empty() {
+                if let Err(err) = self.check_custom(&result) {
+                    errors.push(err);
+                }
             }
-            Value::Null if self.optional => Ok(value.clone()),
+
+            PartialOutput { value: Some(Value::Array(result)), errors }
+        })
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        match value {
+            Value::Array(items) => Value::Array(items.iter().map(|item| self.item_schema.redact(item)).collect()),
+            _ => value.clone(),
+        }
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        nest_findings("*", self.item_schema.lint())
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        match output {
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| self.item_schema.canonical(item)).collect()),
+            other => other,
+        }
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::Array(arr) => refine::root(value, || {
+                    self.check_len(arr)?;
+
+                    let sampler = self.sampler(arr);
+                    let validate_item = |i, item: &Value| {
+                        if sampler.as_ref().is_some_and(|s| !s.picks(i)) {
+                            return Ok(item.clone());
+                        }
+                        nested(i, || self.item_schema.validate(item)).map_err(|e| self.item_error(i, e))
+                    };
+                    // The first invalid item fails the array, on one thread or several
+                    let result = match self.threads_for(arr) {
+                        Some(threads) => parallel::map_indexed(arr, threads, validate_item).into_iter().collect(),
+                        None => arr.iter().enumerate().map(|(i, item)| validate_item(i, item)).collect::<Result<Vec<_>, _>>(),
+                    }?;
+
+                    if let Some(unique) = &self.unique {
+                        self.check_unique(unique, &result)?;
+                    }
+                    self.check_custom(&result)?;
+
+                    Ok(Value::Array(result))
+            }),
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
             Value::Null => {
-                let mut err = ValidationError::new("array.required");
+                let mut err = ValidationError::new(ErrorCode::ArrayRequired);
                 if let Some(msg) = self.error_messages.get("array.required") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message("This field is required");
+                    err = err.custom_message(msg.clone());
                 }
                 Err(err)
             }
             _ => {
-                let mut err = ValidationError::new("array.invalid_type")
+                let mut err = ValidationError::new(ErrorCode::ArrayInvalidType)
                     .with_details(|d| {
                         d.expected_type = Some("array".to_string());
                         d.actual_type = Some(get_type_name(value).to_string());
                     });
                 if let Some(msg) = self.error_messages.get("array.invalid_type") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message("Must be an array");
+                    err = err.custom_message(msg.clone());
                 }
                 Err(err)
             }
         }
     }
 
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        match value {
+            // Uniqueness and custom checks then see the items as given
+            Value::Array(arr) if self.item_schema.preserves_input() && self.threads_for(arr).is_none() => refine::root(value, || {
+                self.check_len(arr)?;
+                let sampler = self.sampler(arr);
+                for (i, item) in arr.iter().enumerate() {
+                    if sampler.as_ref().is_some_and(|s| !s.picks(i)) {
+                        continue;
+                    }
+                    nested(i, || self.item_schema.validate_ref(item)).map_err(|e| self.item_error(i, e))?;
+                }
+                if let Some(unique) = &self.unique {
+                    self.check_unique(unique, arr)?;
+                }
+                self.check_custom(arr)
+            }),
+            _ => self.validate(value).map(drop),
+        }
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.item_schema.preserves_input()
+    }
+
     fn into_schema_type(self) -> SchemaType {
-        SchemaType::Array(Box::new(self))
+        SchemaType::Array(Box::new(self.with_item(Schema::into_schema_type)))
     }
 }
 
@@ -153,12 +575,12 @@ mod tests {
         assert!(schema.validate(&json!(["a", "b", "c"])).is_ok());
         
         let err = schema.validate(&json!(["a"])).unwrap_err();
-        assert_eq!(err.context.code, "array.min_items");
+        assert_eq!(err.context.code, "array.too_short");
         assert_eq!(err.context.details.min_length, Some(2));
         assert!(err.to_string().contains("Must have at least 2 items"));
 
         let err = schema.validate(&json!(["a", "b", "c", "d", "e"])).unwrap_err();
-        assert_eq!(err.context.code, "array.max_items");
+        assert_eq!(err.context.code, "array.too_long");
         assert_eq!(err.context.details.max_length, Some(4));
         assert!(err.to_string().contains("Must have at most 4 items"));
     }
@@ -170,7 +592,7 @@ mod tests {
         assert!(schema.validate(&json!([1, 50, 100])).is_ok());
         
         let err = schema.validate(&json!([1, -1, 50])).unwrap_err();
-        assert!(err.context.path.contains("1"));
+        assert!(err.context.path.to_string().contains("1"));
         assert_eq!(err.to_string(), "Item 1 is invalid");
     }
 
@@ -195,6 +617,41 @@ mod tests {
         assert!(err.to_string().contains("Must be an array"));
     }
 
+    #[test]
+    fn test_array_len_range() {
+        let schema = ArraySchema::new(NumberSchema::default()).len_range(1..3);
+
+        assert!(schema.validate(&json!([1, 2])).is_ok());
+        assert_eq!(schema.validate(&json!([])).unwrap_err().context.code, "array.too_short");
+        assert_eq!(schema.validate(&json!([1, 2, 3])).unwrap_err().context.code, "array.too_long");
+    }
+
+    #[test]
+    fn test_array_unique() {
+        let schema = ArraySchema::new(StringSchemaImpl::default()).unique();
+
+        assert!(schema.validate(&json!(["a", "b", "c"])).is_ok());
+
+        let err = schema.validate(&json!(["a", "b", "a", "b"])).unwrap_err();
+        assert_eq!(err.context.code, "array.unique");
+        assert_eq!(err.context.path, "2");
+        assert_eq!(err.to_string(), "Duplicate of item 0");
+    }
+
+    #[test]
+    fn test_array_unique_by() {
+        let schema = ArraySchema::new(crate::schemas::ObjectSchema::default()
+            .field("id", NumberSchema::default())
+            .optional_field("name", StringSchemaImpl::default()))
+            .unique_by("id");
+
+        assert!(schema.validate(&json!([{"id": 1}, {"id": 2, "name": "x"}])).is_ok());
+
+        let err = schema.validate(&json!([{"id": 1}, {"id": 2}, {"id": 1, "name": "x"}])).unwrap_err();
+        assert_eq!(err.context.path, "2");
+        assert_eq!(err.context.details.field_name.as_deref(), Some("id"));
+    }
+
     #[test]
     fn test_nested_array_validation() {
         let inner_schema = ArraySchema::new(NumberSchema::default().integer());
@@ -203,4 +660,97 @@ mod tests {
         assert!(schema.validate(&json!([[1, 2], [3, 4]])).is_ok());
         assert!(schema.validate(&json!([[1, 2.5]])).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_array_keeps_item_type() {
+        use crate::schemas::string::StringSchema;
+
+        let schema = ArraySchema::new(StringSchemaImpl::default())
+            .max_items(3)
+            .with_item(|s| s.min_length(2));
+
+        assert!(schema.validate(&json!(["ab", "cd"])).is_ok());
+        assert_eq!(schema.validate(&json!(["ab", "c"])).unwrap_err().context.path, "1");
+        assert!(schema.item().validate(&json!("xy")).is_ok());
+
+        let dynamic = ArraySchema::dynamic(StringSchemaImpl::default());
+        assert!(matches!(dynamic.item(), SchemaType::String(_)));
+    }
+
+    #[test]
+    fn test_array_custom() {
+        let ascending = ArraySchema::new(NumberSchema::default())
+            .custom(|items| match items.windows(2).all(|w| w[0].as_f64() <= w[1].as_f64()) {
+                true => Ok(()),
+                false => Err("Must be in ascending order"),
+            });
+        assert!(ascending.validate(&json!([1, 2, 3])).is_ok());
+
+        let err = ascending.validate(&json!([3, 1])).unwrap_err();
+        assert_eq!(err.context.code, "array.custom");
+        assert_eq!(err.to_string(), "Must be in ascending order");
+
+        let output = ascending.validate_partial(&json!([2, 1]));
+        assert_eq!(output.errors[0].context.code, "array.custom");
+    }
+
+    #[test]
+    fn test_validate_sample() {
+        let mut items = vec![json!(1); 1000];
+        items[501] = json!("drift");
+        let value = Value::Array(items);
+
+        // 10 items spaced 100 apart miss the bad one at 501
+        let schema = ArraySchema::new(NumberSchema::default()).validate_sample(10);
+        assert_eq!(schema.validate(&value).unwrap(), value);
+        let schema = ArraySchema::new(NumberSchema::default()).validate_sample(1000);
+        assert_eq!(schema.validate(&value).unwrap_err().context.path, "501");
+
+        // First and last are checked even at rate 0
+        let schema = ArraySchema::new(NumberSchema::default()).validate_sample(0.0);
+        assert!(schema.validate(&value).is_ok());
+        assert_eq!(schema.validate(&json!([1, 2, "x"])).unwrap_err().context.path, "2");
+        assert_eq!(schema.validate_partial(&json!(["x", 2, 3])).errors[0].context.path, "0");
+
+        let schema = ArraySchema::new(NumberSchema::default()).validate_sample(1.0);
+        assert!(schema.validate(&value).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_parallel() {
+        use crate::{array, object, string};
+
+        let item = || object().field("name", string().soft_max_length(5).trim());
+        let mut items: Vec<_> = (0..1000).map(|i| json!({"name": format!(" n{} ", i % 10)})).collect();
+        let value = Value::Array(items.clone());
+        let sequential = array(item());
+        let parallel = array(item()).parallel(100);
+        assert_eq!(parallel.validate(&value).unwrap(), sequential.validate(&value).unwrap());
+        assert!(parallel.validate(&json!([1])).is_err());
+
+        items[40] = json!({"name": "long name"});
+        items[700] = json!({"name": 7});
+        items[300] = json!({});
+        let value = Value::Array(items);
+        let err = parallel.validate(&value).unwrap_err();
+        assert_eq!((err.context.path.to_string(), err.context.code), ("300.name".to_string(), "object.required".to_string()));
+        let paths = |output: PartialOutput| output.errors.iter().map(|e| e.context.path.to_string()).collect::<Vec<_>>();
+        assert_eq!(paths(parallel.validate_partial(&value)), ["300.name", "700.name"]);
+        assert_eq!(paths(parallel.validate_partial(&value)), paths(sequential.validate_partial(&value)));
+
+        // Warnings and refinements come back from the worker threads
+        let value = value.as_array().unwrap()[..200].to_vec().into();
+        let schema = array(item()).parallel(10);
+        let report = schema.validate_with_report(&value).unwrap();
+        assert_eq!(report.warnings.iter().map(|w| w.context.path.to_string()).collect::<Vec<_>>(), ["40.name"]);
+        let schema = array(string().refine(|_: &Value, ctx: &mut crate::RefinementContext| {
+            if ctx.root().as_array().is_some_and(|all| all.len() != 3) {
+                ctx.issue("not in the document");
+            }
+        }))
+        .parallel(2);
+        assert!(schema.validate(&json!(["a", "b", "c"])).is_ok());
+        assert_eq!(schema.validate(&json!(["a", "b", "c", "d"])).unwrap_err().context.path, "0");
+    }
+}
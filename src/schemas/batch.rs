@@ -0,0 +1,185 @@
+use std::{collections::HashMap, num::NonZeroUsize};
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use super::{parallel, Schema};
+
+/// How [`Schema::validate_batch_with`] runs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchOptions {
+    threads: Option<NonZeroUsize>,
+    max_errors: Option<usize>,
+}
+
+impl BatchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate on up to `threads` threads; `0` uses the available parallelism
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = NonZeroUsize::new(threads)
+            .or_else(|| NonZeroUsize::new(parallel::available_threads()));
+        self
+    }
+
+    /// Stop validating once `max_errors` values have failed; later values
+    /// are counted as skipped. With several threads, values are validated
+    /// in rounds of a few per thread, so the round that reaches the cap may
+    /// validate some values past it, which are left out of the report.
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+}
+
+/// Per-value results of a batch validation with aggregate statistics
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    /// Result for each validated value, in input order. Shorter than the
+    /// input when the error cap was reached.
+    pub results: Vec<Result<Value, ValidationError>>,
+    pub passed: usize,
+    pub failed: usize,
+    /// Values not validated because [`BatchOptions::max_errors`] was reached
+    pub skipped: usize,
+    error_counts: HashMap<String, usize>,
+}
+
+impl BatchReport {
+    fn from_results(results: Vec<Result<Value, ValidationError>>, total: usize) -> Self {
+        let mut error_counts = HashMap::new();
+        for err in results.iter().filter_map(|r| r.as_ref().err()) {
+            *error_counts.entry(err.context.code.clone()).or_insert(0) += 1;
+        }
+        let failed = error_counts.values().sum();
+        Self {
+            passed: results.len() - failed,
+            failed,
+            skipped: total - results.len(),
+            results,
+            error_counts,
+        }
+    }
+
+    /// Whether every value was validated and passed
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0 && self.skipped == 0
+    }
+
+    /// Index and error of every failed value
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &ValidationError)> {
+        self.results.iter().enumerate().filter_map(|(i, r)| r.as_ref().err().map(|e| (i, e)))
+    }
+
+    /// The `n` most frequent error codes with their counts, most frequent first
+    pub fn top_error_codes(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut counts: Vec<_> = self.error_counts.iter().map(|(code, &count)| (code.as_str(), count)).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts.truncate(n);
+        counts
+    }
+}
+
+/// Values each thread validates between checks of [`BatchOptions::max_errors`]
+const VALUES_PER_ROUND: usize = 16;
+
+pub(crate) fn validate_batch<S: Schema + ?Sized>(schema: &S, values: &[Value], options: BatchOptions) -> BatchReport {
+    let threads = options.threads.map_or(1, NonZeroUsize::get);
+    let validate = |_, value: &Value| schema.validate(value);
+    let Some(max_errors) = options.max_errors else {
+        return BatchReport::from_results(parallel::map_indexed(values, threads, validate), values.len());
+    };
+
+    let round = match threads {
+        1 => 1,
+        threads => threads * VALUES_PER_ROUND,
+    };
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for chunk in values.chunks(round) {
+        if failed == max_errors {
+            break;
+        }
+        for result in parallel::map_indexed(chunk, threads, validate) {
+            failed += usize::from(result.is_err());
+            results.push(result);
+            if failed == max_errors {
+                break;
+            }
+        }
+    }
+    BatchReport::from_results(results, values.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{number, object, string};
+
+    fn rows() -> Vec<Value> {
+        (0..40)
+            .map(|i| match i % 4 {
+                0 => json!({"id": i, "name": "ok"}),
+                1 => json!({"id": i}),
+                2 => json!({"id": -1, "name": "ok"}),
+                _ => json!({"id": i, "name": "fine"}),
+            })
+            .collect()
+    }
+
+    fn schema() -> impl Schema {
+        object().field("id", number().min(0.0)).field("name", string())
+    }
+
+    #[test]
+    fn test_validate_batch_statistics() {
+        let report = schema().validate_batch(&rows());
+        assert_eq!(report.results.len(), 40);
+        assert_eq!((report.passed, report.failed, report.skipped), (20, 20, 0));
+        assert!(!report.all_passed());
+        assert_eq!(report.errors().next().map(|(i, _)| i), Some(1));
+        assert_eq!(report.top_error_codes(5), vec![("number.too_small", 10), ("object.required", 10)]);
+    }
+
+    #[test]
+    fn test_validate_batch_parallel_matches_sequential() {
+        let rows = rows();
+        let sequential = schema().validate_batch(&rows);
+        let parallel = schema().validate_batch_with(&rows, BatchOptions::new().threads(4));
+        let codes = |report: &BatchReport| report.errors().map(|(i, e)| (i, e.context.code.clone())).collect::<Vec<_>>();
+        assert_eq!(codes(&sequential), codes(&parallel));
+        assert_eq!(parallel.passed, 20);
+    }
+
+    #[test]
+    fn test_validate_batch_error_cap() {
+        let report = schema().validate_batch_with(&rows(), BatchOptions::new().threads(3).max_errors(3));
+        assert_eq!(report.failed, 3);
+        assert_eq!(report.results.len(), 6);
+        assert_eq!(report.skipped, 34);
+        assert!(schema().validate_batch(&[]).all_passed());
+    }
+
+    #[test]
+    fn test_validate_batch_stops_at_error_cap() {
+        use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let schema = number().custom(move |n| {
+            counted.fetch_add(1, Ordering::Relaxed);
+            if n < 0.0 { Err("negative") } else { Ok(()) }
+        });
+        let values: Vec<_> = (0..1000).map(|i| json!(if i % 10 == 9 { -1 } else { i })).collect();
+
+        let report = schema.validate_batch_with(&values, BatchOptions::new().max_errors(2));
+        assert_eq!((report.passed, report.failed, report.skipped), (18, 2, 980));
+        assert_eq!(calls.swap(0, Ordering::Relaxed), 20);
+
+        let report = schema.validate_batch_with(&values, BatchOptions::new().threads(2).max_errors(2));
+        assert_eq!((report.passed, report.failed, report.skipped), (18, 2, 980));
+        assert!(calls.load(Ordering::Relaxed) <= 2 * VALUES_PER_ROUND);
+    }
+}
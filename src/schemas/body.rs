@@ -0,0 +1,208 @@
+use serde_json::Value;
+
+use crate::error::{ErrorCode, ValidationError, ValidationErrors};
+use super::{spans, Schema};
+
+/// Request body formats [`validate_body`] parses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+    UrlEncoded,
+    MessagePack,
+}
+
+impl Format {
+    /// Format of the MIME type `content_type`, ignoring parameters such as
+    /// `charset` and recognizing structured suffixes like `+json`
+    fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        let format = match mime.as_str() {
+            "application/json" | "text/json" => Format::Json,
+            "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => Format::Yaml,
+            "application/x-www-form-urlencoded" => Format::UrlEncoded,
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => Format::MessagePack,
+            mime if mime.ends_with("+json") => Format::Json,
+            mime if mime.ends_with("+yaml") => Format::Yaml,
+            _ => return None,
+        };
+        Some(format)
+    }
+}
+
+/// Parse `body` in the format its `content_type` names and validate it
+/// with `schema`, so every supported format goes through one entry point
+///
+/// JSON is always supported, and its errors carry source spans as with
+/// [`Schema::validate_str`]. YAML, form-urlencoded and MessagePack bodies
+/// need the `yaml`, `urlencoded` and `msgpack` features. Form fields are
+/// strings; a field repeated or named with a `[]` suffix (`tag[]=a`)
+/// becomes an array. An unknown or disabled content type fails as
+/// `parse.unsupported_media_type`, a body that does not parse as
+/// `parse.invalid_json`, `parse.invalid_yaml` or `parse.invalid_body`.
+pub fn validate_body<S: Schema + ?Sized>(content_type: &str, body: &[u8], schema: &S) -> Result<Value, ValidationError> {
+    match Format::from_content_type(content_type) {
+        Some(Format::Json) => schema.validate_str(json_text(body)?),
+        Some(format) => schema.validate(&parse(format, content_type, body)?),
+        None => Err(unsupported(content_type)),
+    }
+}
+
+/// [`validate_body`] reporting every issue, as [`Schema::validate_partial`]
+/// does, instead of the first; a body that does not parse is its only issue
+pub fn validate_body_partial<S: Schema + ?Sized>(content_type: &str, body: &[u8], schema: &S) -> Result<Value, ValidationErrors> {
+    let (value, json) = match Format::from_content_type(content_type) {
+        Some(Format::Json) => {
+            let text = json_text(body)?;
+            (serde_json::from_str(text).map_err(|e| spans::invalid_json(e, text))?, Some(text))
+        }
+        Some(format) => (parse(format, content_type, body)?, None),
+        None => return Err(unsupported(content_type).into()),
+    };
+    let output = schema.validate_partial(&value);
+    if output.is_valid() {
+        return Ok(output.value.unwrap_or(Value::Null));
+    }
+    let errors = output.errors.into_iter().map(|err| match json {
+        Some(text) => spans::attach_span(err, text),
+        None => err,
+    });
+    Err(errors.collect::<Vec<_>>().into())
+}
+
+fn json_text(body: &[u8]) -> Result<&str, ValidationError> {
+    std::str::from_utf8(body)
+        .map_err(|e| ValidationError::new(ErrorCode::InvalidJson).message(format!("Invalid JSON: {}", e)))
+}
+
+/// Value of a `body` in a format other than JSON, if its feature is enabled
+#[cfg_attr(not(any(feature = "yaml", feature = "urlencoded", feature = "msgpack")), allow(unused_variables))]
+fn parse(format: Format, content_type: &str, body: &[u8]) -> Result<Value, ValidationError> {
+    match format {
+        #[cfg(feature = "yaml")]
+        Format::Yaml => match std::str::from_utf8(body) {
+            Ok(text) => super::source_formats::from_yaml(text),
+            Err(e) => Err(ValidationError::new(ErrorCode::InvalidYaml).message(format!("Invalid YAML: {}", e))),
+        },
+        #[cfg(feature = "urlencoded")]
+        Format::UrlEncoded => Ok(form_value(body)),
+        #[cfg(feature = "msgpack")]
+        Format::MessagePack => rmp_serde::from_slice(body).map_err(invalid_body),
+        _ => Err(unsupported(content_type)),
+    }
+}
+
+fn unsupported(content_type: &str) -> ValidationError {
+    ValidationError::new(ErrorCode::UnsupportedMediaType)
+        .message(format!("Unsupported content type: {}", content_type))
+}
+
+#[cfg(feature = "msgpack")]
+fn invalid_body(e: rmp_serde::decode::Error) -> ValidationError {
+    ValidationError::new(ErrorCode::InvalidBody).message(format!("Invalid MessagePack: {}", e))
+}
+
+/// Form fields as an object of strings, repeated and `[]`-suffixed fields
+/// collected into arrays
+#[cfg(feature = "urlencoded")]
+fn form_value(body: &[u8]) -> Value {
+    let mut obj = serde_json::Map::new();
+    for (key, value) in form_urlencoded::parse(body) {
+        let value = Value::String(value.into_owned());
+        let (name, list) = match key.strip_suffix("[]") {
+            Some(name) => (name.to_string(), true),
+            None => (key.into_owned(), false),
+        };
+        match obj.get_mut(&name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+            None if list => {
+                obj.insert(name, Value::Array(vec![value]));
+            }
+            None => {
+                obj.insert(name, value);
+            }
+        }
+    }
+    Value::Object(obj)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{array, object, string, StringSchema};
+
+    fn schema() -> crate::ObjectSchema {
+        object().field("name", string()).optional_field("tags", array(string()))
+    }
+
+    #[test]
+    fn test_validate_body_json() {
+        let body = br#"{"name": "Ada", "tags": ["x"]}"#;
+        assert_eq!(validate_body("application/json; charset=utf-8", body, &schema()).unwrap()["name"], "Ada");
+        assert!(validate_body("application/vnd.api+json", body, &schema()).is_ok());
+
+        let err = validate_body("application/json", br#"{"name": 1}"#, &schema()).unwrap_err();
+        assert_eq!(err.context.path, "name");
+        assert!(err.context.details.span.is_some());
+        assert_eq!(validate_body("application/json", b"{", &schema()).unwrap_err().context.code, "parse.invalid_json");
+        assert_eq!(validate_body("application/json", b"\xff", &schema()).unwrap_err().context.code, "parse.invalid_json");
+
+        let err = validate_body("text/plain", body, &schema()).unwrap_err();
+        assert_eq!(err.context.code, "parse.unsupported_media_type");
+        assert_eq!(err.to_string(), "Unsupported content type: text/plain");
+    }
+
+    #[test]
+    fn test_validate_body_partial() {
+        let schema = object().field("name", string().min_length(2)).field("tags", array(string()));
+        let body = br#"{"name": "A", "tags": [1]}"#;
+        let errors = validate_body_partial("application/json", body, &schema).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|err| err.context.details.span.is_some()));
+
+        assert_eq!(validate_body_partial("application/json", b"{", &schema).unwrap_err().len(), 1);
+        let body = br#"{"name": "Ada", "tags": []}"#;
+        assert_eq!(validate_body_partial("application/json", body, &schema).unwrap()["name"], "Ada");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_validate_body_yaml() {
+        use serde_json::json;
+        let body = b"name: Ada\ntags:\n  - x\n";
+        assert_eq!(validate_body("application/yaml", body, &schema()).unwrap(), json!({"name": "Ada", "tags": ["x"]}));
+        assert_eq!(validate_body("text/yaml", b"name: [", &schema()).unwrap_err().context.code, "parse.invalid_yaml");
+    }
+
+    #[cfg(feature = "urlencoded")]
+    #[test]
+    fn test_validate_body_urlencoded() {
+        use serde_json::json;
+        use crate::number;
+        let schema = schema().optional_field("age", number().coerce());
+        let output = validate_body("application/x-www-form-urlencoded", b"name=Ada+L&tags[]=x&age=36", &schema).unwrap();
+        assert_eq!(output, json!({"name": "Ada L", "tags": ["x"], "age": 36.0}));
+        let output = validate_body("application/x-www-form-urlencoded", b"name=Ada&tags=x&tags=y", &schema).unwrap();
+        assert_eq!(output["tags"], json!(["x", "y"]));
+        assert!(validate_body("application/x-www-form-urlencoded", b"tags=x", &schema).is_err());
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_validate_body_msgpack() {
+        use serde_json::json;
+        let body = rmp_serde::to_vec(&json!({"name": "Ada", "tags": ["x"]})).unwrap();
+        assert!(validate_body("application/msgpack", &body, &schema()).is_ok());
+        let body = rmp_serde::to_vec(&json!({"name": 1})).unwrap();
+        assert_eq!(validate_body("application/x-msgpack", &body, &schema()).unwrap_err().context.path, "name");
+        assert_eq!(validate_body("application/msgpack", b"\xc1", &schema()).unwrap_err().context.code, "parse.invalid_body");
+    }
+
+    #[cfg(not(feature = "yaml"))]
+    #[test]
+    fn test_validate_body_disabled_format() {
+        let err = validate_body("application/yaml", b"name: Ada", &schema()).unwrap_err();
+        assert_eq!(err.context.code, "parse.unsupported_media_type");
+    }
+}
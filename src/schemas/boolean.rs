@@ -1,25 +1,71 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use serde_json::Value;
 
-use crate::error::ValidationError;
-use super::{Schema, SchemaType, HasErrorMessages, get_type_name};
+use crate::error::{ValidationError, ErrorCode, CustomIssue, canonical_code};
+use crate::error::message_check::debug_check_template;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, compat, get_type_name};
+
+type CustomValidator = Arc<dyn Fn(bool) -> Result<(), CustomIssue> + Send + Sync>;
 
 #[derive(Clone, Default)]
 pub struct BooleanSchema {
-    optional: bool,
+    presence: Presence,
     error_messages: HashMap<String, String>,
+    custom_validators: Vec<CustomValidator>,
 }
 
 impl BooleanSchema {
     pub fn optional(mut self) -> Self {
-        self.optional = true;
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.set_presence(presence);
+        self
+    }
+
+    /// In-place form of [`BooleanSchema::presence`]
+    pub fn set_presence(&mut self, presence: Presence) -> &mut Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Custom check, reported as `boolean.custom` unless it returns a
+    /// [`CustomIssue`] with its own code
+    pub fn custom<F, E>(mut self, validator: F) -> Self
+    where
+        F: Fn(bool) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
+    {
+        self.custom_validators.push(Arc::new(move |b| validator(b).map_err(Into::into)));
         self
     }
 
     pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
-        self.error_messages.insert(code.into(), message.into());
+        self.set_error_message(code, message);
         self
     }
+
+    /// In-place form of [`BooleanSchema::error_message`]
+    pub fn set_error_message(&mut self, code: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::BooleanRequired, message)
+    }
+
+    /// Whether every value `other` accepts meets this schema's checks, see
+    /// [`Schema::accepts`]
+    pub(crate) fn covers(&self, other: &Self) -> bool {
+        compat::covers_null(self.presence, other.presence) && self.custom_validators.is_empty()
+    }
 }
 
 impl HasErrorMessages for BooleanSchema {
@@ -29,22 +75,49 @@ impl HasErrorMessages for BooleanSchema {
 }
 
 impl Schema for BooleanSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn preserves_input(&self) -> bool {
+        true
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("boolean.required").cloned()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("boolean", self.presence);
+        for _ in &self.custom_validators {
+            explanation.rule("custom check");
+        }
+        vec![explanation]
+    }
+
     fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
         match value {
-            Value::Bool(_) => Ok(value.clone()),
-            Value::Null if self.optional => Ok(value.clone()),
-            Value::Null => Err(ValidationError::new("boolean.required")
-                .message("This field is required")),
+            Value::Bool(b) => {
+                for validator in &self.custom_validators {
+                    validator(*b).map_err(|issue| issue.into_error(ErrorCode::BooleanCustom.to_string(), &self.error_messages))?;
+                }
+                Ok(value.clone())
+            }
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
+            Value::Null => Err(ValidationError::new(ErrorCode::BooleanRequired)
+                .message_or_default(self.error_messages.get("boolean.required"))),
             _ => {
-                let mut err = ValidationError::new("boolean.invalid_type")
+                let mut err = ValidationError::new(ErrorCode::BooleanInvalidType)
                     .with_details(|d| {
                         d.expected_type = Some("boolean".to_string());
                         d.actual_type = Some(get_type_name(value).to_string());
                     });
                 if let Some(msg) = self.error_messages.get("boolean.invalid_type") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message("Must be a boolean value");
+                    err = err.custom_message(msg.clone());
                 }
                 Err(err)
             }
@@ -92,4 +165,15 @@ mod tests {
         assert_eq!(err.context.code, "boolean.required");
         assert!(err.to_string().contains("This field is required"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_boolean_custom() {
+        let accepted = BooleanSchema::default()
+            .custom(|b| if b { Ok(()) } else { Err("You must accept the terms") });
+        assert!(accepted.validate(&json!(true)).is_ok());
+
+        let err = accepted.validate(&json!(false)).unwrap_err();
+        assert_eq!(err.context.code, "boolean.custom");
+        assert_eq!(err.to_string(), "You must accept the terms");
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{ValidationError, ErrorCode, canonical_code};
+use crate::error::message_check::debug_check_template;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, explain, Unsatisfiable, satisfiable, compat, get_type_name};
+
+/// Text encoding of a binary value
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BytesEncoding {
+    /// RFC 4648 base64 (`+` and `/`), padding optional
+    #[default]
+    Base64,
+    /// RFC 4648 URL-safe base64 (`-` and `_`), padding optional
+    Base64Url,
+    /// Hexadecimal, either case
+    Hex,
+}
+
+impl BytesEncoding {
+    fn name(self) -> &'static str {
+        match self {
+            BytesEncoding::Base64 => "base64",
+            BytesEncoding::Base64Url => "base64url",
+            BytesEncoding::Hex => "hex",
+        }
+    }
+
+    /// Decoded length in bytes, or `None` if `s` is not validly encoded
+    pub fn decoded_len(self, s: &str) -> Option<usize> {
+        match self {
+            BytesEncoding::Hex => {
+                (s.len().is_multiple_of(2) && s.bytes().all(|b| b.is_ascii_hexdigit())).then_some(s.len() / 2)
+            }
+            BytesEncoding::Base64 | BytesEncoding::Base64Url => {
+                let (extra_a, extra_b) = if self == BytesEncoding::Base64 { (b'+', b'/') } else { (b'-', b'_') };
+                let data = s.trim_end_matches('=');
+                let padding = s.len() - data.len();
+                if padding > 2 || (padding > 0 && !s.len().is_multiple_of(4)) || data.len() % 4 == 1 {
+                    return None;
+                }
+                data.bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == extra_a || b == extra_b)
+                    .then_some(data.len() * 3 / 4)
+            }
+        }
+    }
+}
+
+/// Binary data carried as an encoded string
+#[derive(Clone, Default)]
+pub struct BytesSchema {
+    encoding: BytesEncoding,
+    min_bytes: Option<usize>,
+    max_bytes: Option<usize>,
+    report_length: bool,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl BytesSchema {
+    pub fn base64(mut self) -> Self {
+        self.encoding = BytesEncoding::Base64;
+        self
+    }
+
+    pub fn base64_url(mut self) -> Self {
+        self.encoding = BytesEncoding::Base64Url;
+        self
+    }
+
+    pub fn hex(mut self) -> Self {
+        self.encoding = BytesEncoding::Hex;
+        self
+    }
+
+    /// Minimum decoded size in bytes
+    pub fn min_bytes(mut self, count: usize) -> Self {
+        self.min_bytes = Some(count);
+        self
+    }
+
+    /// Maximum decoded size in bytes
+    pub fn max_bytes(mut self, count: usize) -> Self {
+        self.max_bytes = Some(count);
+        self
+    }
+
+    /// Include the decoded length as `actual_length` in size error details
+    pub fn report_length(mut self) -> Self {
+        self.report_length = true;
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.set_presence(presence);
+        self
+    }
+
+    /// In-place form of [`BytesSchema::presence`]
+    pub fn set_presence(&mut self, presence: Presence) -> &mut Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        self.set_error_message(code, message);
+        self
+    }
+
+    /// In-place form of [`BytesSchema::error_message`]
+    pub fn set_error_message(&mut self, code: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::BytesRequired, message)
+    }
+
+    fn error(&self, code: ErrorCode) -> ValidationError {
+        let custom = self.error_messages.get(code.code());
+        ValidationError::new(code).message_or_default(custom)
+    }
+
+    fn check(&self, s: &str) -> Result<(), ValidationError> {
+        let len = self.encoding.decoded_len(s).ok_or_else(|| {
+            self.error(ErrorCode::InvalidEncoding)
+                .with_details(|d| d.expected = Some(self.encoding.name().to_string()))
+        })?;
+        let actual = self.report_length.then_some(len);
+
+        if let Some(min) = self.min_bytes {
+            if len < min {
+                return Err(self.error(ErrorCode::BytesTooShort)
+                    .with_details(|d| {
+                        d.min_length = Some(min);
+                        d.actual_length = actual;
+                    }));
+            }
+        }
+
+        if let Some(max) = self.max_bytes {
+            if len > max {
+                return Err(self.error(ErrorCode::BytesTooLong)
+                    .with_details(|d| {
+                        d.max_length = Some(max);
+                        d.actual_length = actual;
+                    }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether every value `other` accepts meets this schema's checks, see
+    /// [`Schema::accepts`]
+    pub(crate) fn covers(&self, other: &Self) -> bool {
+        self.encoding == other.encoding
+            && compat::covers_null(self.presence, other.presence)
+            && compat::covers_min(self.min_bytes, other.min_bytes)
+            && compat::covers_max(self.max_bytes, other.max_bytes)
+    }
+}
+
+impl HasErrorMessages for BytesSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+}
+
+impl Schema for BytesSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("bytes.required").cloned()
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        satisfiable::check_bounds("byte length", self.min_bytes, self.max_bytes).into_iter().collect()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let encoding = match self.encoding {
+            BytesEncoding::Base64 => "base64",
+            BytesEncoding::Base64Url => "URL-safe base64",
+            BytesEncoding::Hex => "hex",
+        };
+        let mut explanation = Explanation::typed(format!("{} bytes", encoding), self.presence);
+        for rule in explain::bounds(self.min_bytes, self.max_bytes, "byte") {
+            explanation.rule(rule);
+        }
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
+            Value::Null => Err(self.error(ErrorCode::BytesRequired)),
+            Value::String(s) => self.check(s).map(|_| value.clone()),
+            _ => Err(self.error(ErrorCode::BytesInvalidType)
+                .with_type_info(format!("{} string", self.encoding.name()), get_type_name(value))),
+        }
+    }
+
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        match value {
+            Value::String(s) => self.check(s).map(drop),
+            _ => self.validate(value).map(drop),
+        }
+    }
+
+    fn preserves_input(&self) -> bool {
+        true
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Bytes(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bytes_base64() {
+        let schema = BytesSchema::default();
+
+        assert!(schema.validate(&json!("aGVsbG8=")).is_ok());
+        assert!(schema.validate(&json!("aGVsbG8")).is_ok());
+        assert!(schema.validate(&json!("+/+/")).is_ok());
+        assert_eq!(schema.validate(&json!("-_-_")).unwrap_err().context.code, "bytes.invalid_encoding");
+        assert!(schema.validate(&json!("aGVsbG8==")).is_err());
+        assert!(schema.validate(&json!("a")).is_err());
+
+        let url = BytesSchema::default().base64_url();
+        assert!(url.validate(&json!("-_-_")).is_ok());
+        assert!(url.validate(&json!("+/+/")).is_err());
+    }
+
+    #[test]
+    fn test_bytes_hex() {
+        let schema = BytesSchema::default().hex();
+
+        assert!(schema.validate(&json!("DEADbeef")).is_ok());
+        assert!(schema.validate(&json!("abc")).is_err());
+        assert!(schema.validate(&json!("zz")).is_err());
+    }
+
+    #[test]
+    fn test_bytes_size_limits() {
+        let schema = BytesSchema::default().hex().min_bytes(2).max_bytes(4).report_length();
+
+        assert!(schema.validate(&json!("0011")).is_ok());
+
+        let err = schema.validate(&json!("00")).unwrap_err();
+        assert_eq!(err.context.code, "bytes.too_short");
+        assert_eq!(err.context.details.min_length, Some(2));
+        assert_eq!(err.context.details.actual_length, Some(1));
+
+        let err = schema.validate(&json!("0011223344")).unwrap_err();
+        assert_eq!(err.context.code, "bytes.too_long");
+        assert_eq!(err.context.details.actual_length, Some(5));
+
+        let quiet = BytesSchema::default().base64().max_bytes(1);
+        assert_eq!(quiet.validate(&json!("aGVsbG8=")).unwrap_err().context.details.actual_length, None);
+    }
+}
@@ -0,0 +1,288 @@
+use std::{collections::{HashMap, VecDeque}, sync::{Arc, Mutex}};
+use serde_json::Value;
+
+use crate::error::{MessageIssue, ValidationError};
+use super::refine::{self, Deferred};
+use super::hooks;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput};
+
+/// Longest serialized input, in bytes, whose result is cached
+pub const MAX_CACHED_INPUT_LEN: usize = 256;
+
+/// Memoizes the results of a schema for small inputs
+///
+/// Created with [`Schema::cached`]. Useful for enum-like reference data
+/// validated over and over, e.g. country codes in an ETL job. Inputs are
+/// keyed by their serialized JSON; larger ones than
+/// [`MAX_CACHED_INPUT_LEN`] are validated as usual. Once `capacity` results
+/// are stored the oldest is evicted. Clones share one cache.
+///
+/// Only cache schemas whose result depends on the input alone, not on a
+/// [`ValidationContext`](super::ValidationContext) or on mutable state in
+/// custom validators. Checks that look at the rest of the document, such as
+/// [`Schema::refine`] and [`NumberSchema::scaled_by_field`](super::NumberSchema::scaled_by_field),
+/// are not cached and run again on every hit. Warnings for [`Schema::validate_with_report`] are
+/// only raised on a cache miss. Inside an object with
+/// [`on_field`](super::ObjectSchema::on_field) hooks the cache is bypassed,
+/// so the hooks see every nested field.
+#[derive(Clone)]
+pub struct Cached<S> {
+    pub schema: S,
+    cache: Arc<Mutex<ResultCache>>,
+}
+
+/// A result along with the document-level checks it deferred
+type CachedResult = (Result<Value, ValidationError>, Deferred);
+
+struct ResultCache {
+    capacity: usize,
+    results: HashMap<String, CachedResult>,
+    order: VecDeque<String>,
+}
+
+impl ResultCache {
+    fn get(&self, key: &str) -> Option<CachedResult> {
+        self.results.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: String, result: CachedResult) {
+        if self.capacity == 0 || self.results.contains_key(&key) {
+            return;
+        }
+        if self.results.len() == self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.results.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.results.insert(key, result);
+    }
+}
+
+impl<S> Cached<S> {
+    pub fn new(schema: S, capacity: usize) -> Self {
+        Self {
+            schema,
+            cache: Arc::new(Mutex::new(ResultCache {
+                capacity,
+                results: HashMap::with_capacity(capacity.min(1024)),
+                order: VecDeque::new(),
+            })),
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// Number of results currently cached
+    pub fn len(&self) -> usize {
+        self.lock().results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every cached result
+    pub fn clear(&self) {
+        let mut cache = self.lock();
+        cache.results.clear();
+        cache.order.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ResultCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Cache key for `value`, or `None` when it is too large to be worth caching
+fn cache_key(value: &Value) -> Option<String> {
+    let small = match value {
+        Value::String(s) => s.len() <= MAX_CACHED_INPUT_LEN,
+        Value::Array(items) => items.len() <= 16,
+        Value::Object(map) => map.len() <= 16,
+        _ => true,
+    };
+    if !small {
+        return None;
+    }
+    serde_json::to_string(value).ok().filter(|key| key.len() <= MAX_CACHED_INPUT_LEN)
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for Cached<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for Cached<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<super::Explanation> {
+        self.schema.explain()
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        // Hooks of an enclosing object must see the fields below this one
+        let key = cache_key(value).filter(|_| !hooks::is_active());
+        let Some(key) = key else {
+            return self.schema.validate(value);
+        };
+        if let Some((result, deferred)) = self.lock().get(&key) {
+            return refine::replay(result, &deferred, value);
+        }
+        // Validate without holding the lock, so nested cached schemas and
+        // other threads are not blocked. Deferred checks are recorded rather
+        // than run, as they depend on more than the cached input.
+        let (result, deferred) = refine::detach(|| self.schema.validate(value));
+        let deferred = if result.is_ok() { deferred } else { Deferred::default() };
+        self.lock().insert(key, (result.clone(), deferred.clone()));
+        refine::replay(result, &deferred, value)
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.schema.preserves_input()
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        self.schema.validate_partial_with(value, invalid)
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<super::LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        let schema = self.schema.into_schema_type();
+        SchemaType::Cached(Box::new(Cached { schema, cache: self.cache }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::{string, object, StringSchema};
+
+    #[test]
+    fn test_cached_reuses_results() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let schema = string()
+            .custom(move |s| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                if s.len() == 2 { Ok(()) } else { Err("Must be a two-letter code".to_string()) }
+            })
+            .cached(8);
+
+        for _ in 0..3 {
+            assert_eq!(schema.validate(&json!("de")).unwrap(), json!("de"));
+            assert_eq!(schema.validate(&json!("xyz")).unwrap_err().to_string(), "Must be a two-letter code");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(schema.len(), 2);
+
+        schema.clear();
+        assert!(schema.is_empty());
+    }
+
+    #[test]
+    fn test_cached_evicts_oldest_and_skips_large_inputs() {
+        let schema = string().cached(2);
+        schema.validate(&json!("a")).unwrap();
+        schema.validate(&json!("b")).unwrap();
+        schema.validate(&json!("c")).unwrap();
+        assert_eq!(schema.len(), 2);
+
+        schema.validate(&json!("x".repeat(MAX_CACHED_INPUT_LEN + 1))).unwrap();
+        assert_eq!(schema.len(), 2);
+    }
+
+    #[test]
+    fn test_cached_nested_field() {
+        let schema = object().field("country", string().min_length(2).max_length(2).cached(16));
+        assert!(schema.validate(&json!({"country": "fr"})).is_ok());
+        let err = schema.validate(&json!({"country": "fra"})).unwrap_err();
+        assert_eq!(err.context.path, "country");
+        let err = schema.validate(&json!({"country": "fra"})).unwrap_err();
+        assert_eq!(err.context.path, "country");
+    }
+
+    #[test]
+    fn test_cached_reruns_document_checks() {
+        let schema = object()
+            .field("password", string())
+            .field("confirm", string()
+                .refine(|v, ctx| if ctx.root().get("password") != Some(v) { ctx.issue("Passwords must match") })
+                .cached(8));
+        assert!(schema.validate(&json!({"password": "a", "confirm": "a"})).is_ok());
+        let err = schema.validate(&json!({"password": "b", "confirm": "a"})).unwrap_err();
+        assert_eq!(err.context.path, "confirm");
+        assert_eq!(err.to_string(), "Passwords must match");
+
+        // Alone the refined value is its own document
+        let confirm = string().refine(|v, ctx| if v == "x" { ctx.issue("No x") }).cached(8);
+        for _ in 0..2 {
+            assert_eq!(confirm.validate(&json!("x")).unwrap_err().to_string(), "No x");
+        }
+    }
+
+    #[test]
+    fn test_cached_bypassed_under_field_hooks() {
+        let user = object().field("password", string().min_length(8)).cached(8);
+        assert!(user.validate(&json!({"password": "short"})).is_err());
+        assert_eq!(user.len(), 1);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let schema = object().field("user", user.clone()).on_field(move |path, outcome| {
+            if outcome.is_err() {
+                *outcome = Err(ValidationError::new("masked"));
+            }
+            log.lock().unwrap().push(path.to_string());
+        });
+        for _ in 0..2 {
+            let err = schema.validate(&json!({"user": {"password": "short"}})).unwrap_err();
+            assert_eq!(err.context.code, "masked");
+        }
+        assert_eq!(*seen.lock().unwrap(), ["user.password", "user", "user.password", "user"]);
+
+        // Results shaped by the hooks are not cached for other callers
+        let err = user.validate(&json!({"password": "short"})).unwrap_err();
+        assert_eq!(err.context.code, "string.too_short");
+        assert_eq!(user.len(), 1);
+    }
+
+}
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+use serde_json::{Map, Number, Value};
+
+/// Largest integer an `f64` holds exactly
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// `value` with object keys sorted and numbers normalized at every level,
+/// for values no schema says more about
+pub(crate) fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(obj) => sorted(obj.into_iter().map(|(k, v)| (k, normalize(v)))),
+        Value::Array(items) => Value::Array(items.into_iter().map(normalize).collect()),
+        Value::Number(n) => Value::Number(normalize_number(n)),
+        other => other,
+    }
+}
+
+/// Object of `entries` with keys inserted in sorted order, so it serializes
+/// the same whether or not `serde_json` preserves insertion order
+pub(crate) fn sorted(entries: impl Iterator<Item = (String, Value)>) -> Value {
+    let entries: BTreeMap<_, _> = entries.collect();
+    Value::Object(entries.into_iter().collect::<Map<_, _>>())
+}
+
+/// Integral floats such as `1.0` or `-0.0` as the integers they equal
+fn normalize_number(n: Number) -> Number {
+    match n.as_f64() {
+        Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() <= MAX_SAFE_INTEGER => Number::from(f as i64),
+        _ => n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{any, array, number, object, string};
+    use crate::schemas::{Schema, StringSchema};
+
+    #[test]
+    fn test_normalize() {
+        let value = normalize(json!({"b": [1.0, -0.0, 2.5], "a": {"d": 1e3, "c": 1}}));
+        assert_eq!(value.to_string(), r#"{"a":{"c":1,"d":1000},"b":[1,0,2.5]}"#);
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let schema = object()
+            .field("name", string().trim())
+            .optional_field("nickname", string().optional())
+            .field("scores", array(number()))
+            .field("meta", any());
+        let a = schema.canonicalize(&json!({
+            "scores": [1.0, 2], "name": " Ada ", "nickname": null, "meta": {"z": 1, "y": 2.0},
+        })).unwrap();
+        let b = schema.canonicalize(&json!({
+            "name": "Ada", "meta": {"y": 2, "z": 1}, "scores": [1, 2.0],
+        })).unwrap();
+        assert_eq!(a.to_string(), r#"{"meta":{"y":2,"z":1},"name":"Ada","scores":[1,2]}"#);
+        assert_eq!(a.to_string(), b.to_string());
+
+        assert!(schema.canonicalize(&json!({"name": 1})).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use std::{collections::HashMap, sync::Arc};
+use serde_json::Value;
+
+use crate::error::{MessageIssue, ValidationError};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput, LintFinding, Explanation, explain, branch, warnings};
+
+type Fallback = Arc<dyn Fn(&ValidationError) -> Value + Send + Sync>;
+
+/// A schema whose invalid values are replaced by a fallback instead of
+/// failing validation (Zod's `catch`)
+///
+/// Created with [`Schema::catch`] or [`Schema::catch_with`]. The swallowed
+/// errors are still reported as warnings by
+/// [`Schema::validate_with_report`]. Only present values are caught: a
+/// missing required field is reported as usual.
+#[derive(Clone)]
+pub struct Catch<S> {
+    pub schema: S,
+    pub fallback: Fallback,
+}
+
+impl<S> Catch<S> {
+    /// Replace invalid values with whatever `fallback` computes from the
+    /// first error
+    pub fn new<F>(schema: S, fallback: F) -> Self
+    where
+        F: Fn(&ValidationError) -> Value + Send + Sync + 'static,
+    {
+        Self { schema, fallback: Arc::new(fallback) }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for Catch<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for Catch<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        explain::with_rules(self.schema.explain(), ["invalid values replaced by a fallback".to_string()])
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        match branch(|| self.schema.validate(value)) {
+            Ok(output) => Ok(output),
+            Err(err) => {
+                let fallback = (self.fallback)(&err);
+                warnings::warn(err);
+                Ok(fallback)
+            }
+        }
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let output = self.schema.validate_partial_with(value, invalid);
+        let Some(first) = output.errors.first() else {
+            return output;
+        };
+        let fallback = (self.fallback)(first);
+        output.errors.into_iter().for_each(warnings::warn);
+        PartialOutput { value: Some(fallback), errors: Vec::new() }
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Catch(Box::new(Catch { schema: self.schema.into_schema_type(), fallback: self.fallback }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{array, number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[test]
+    fn test_catch_replaces_invalid_values() {
+        let schema = object()
+            .field("name", string())
+            .field("age", number().min(0.0).catch(json!(null)))
+            .field("tags", array(string()).catch(json!([])));
+
+        let output = schema.validate(&json!({"name": "Ada", "age": -5, "tags": "x"})).unwrap();
+        assert_eq!(output, json!({"name": "Ada", "age": null, "tags": []}));
+        // Valid values pass through
+        let output = schema.validate(&json!({"name": "Ada", "age": 36, "tags": ["a"]})).unwrap();
+        assert_eq!(output["age"], 36);
+        // Other fields still fail the record
+        assert!(schema.validate(&json!({"name": 1, "age": 36, "tags": []})).is_err());
+    }
+
+    #[test]
+    fn test_catch_with_reports_warnings() {
+        let schema = object().field(
+            "code",
+            string().max_length(3).catch_with(|err| json!(format!("invalid: {}", err.context.code))),
+        );
+        let report = schema.validate_with_report(&json!({"code": "ABCD"})).unwrap();
+        assert_eq!(report.value, json!({"code": "invalid: string.too_long"}));
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].context.path, "code");
+
+        let output = schema.validate_partial(&json!({"code": "ABCD"}));
+        assert!(output.errors.is_empty());
+        assert_eq!(output.value.unwrap()["code"], "invalid: string.too_long");
+    }
+}
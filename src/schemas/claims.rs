@@ -0,0 +1,165 @@
+use std::{sync::Arc, time::{SystemTime, UNIX_EPOCH}};
+use serde_json::Value;
+
+use crate::error::{CustomIssue, ErrorCode};
+use super::{Schema, ArraySchema, NumberSchema, ObjectSchema, UnionSchema, string::{StringSchema, StringSchemaImpl}};
+
+/// Options for [`claims_schema_with`](crate::claims_schema_with)
+///
+/// Times are JWT NumericDates, seconds since the Unix epoch. A token is
+/// expired from its `exp` on and valid from its `nbf` on, each moved by
+/// `leeway` seconds in the token's favor.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClaimsPolicy {
+    /// Time to check `exp` and `nbf` against; the system clock at
+    /// validation when `None`
+    pub now: Option<i64>,
+    /// Seconds of clock skew tolerated
+    pub leeway: i64,
+    /// Audiences accepted; when set, `aud` is required and must name one
+    pub audiences: Vec<String>,
+    /// Issuers accepted; when set, `iss` is required and must be one
+    pub issuers: Vec<String>,
+    pub require_exp: bool,
+}
+
+/// Seconds since the Unix epoch by the system clock
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)
+}
+
+impl ClaimsPolicy {
+    fn now(&self) -> f64 {
+        self.now.unwrap_or_else(unix_now) as f64
+    }
+
+    /// Object schema for the registered claims of RFC 7519; other claims
+    /// pass through unless declared by merging a schema for them
+    pub(crate) fn schema(self) -> ObjectSchema {
+        let policy = Arc::new(self);
+        let expiry = policy.clone();
+        let exp = NumberSchema::default().custom(move |exp| {
+            if expiry.now() - expiry.leeway as f64 >= exp {
+                return Err(CustomIssue::from(ErrorCode::JwtExpired));
+            }
+            Ok(())
+        });
+        let start = policy.clone();
+        let nbf = NumberSchema::default().custom(move |nbf| {
+            if nbf > start.now() + start.leeway as f64 {
+                return Err(CustomIssue::from(ErrorCode::JwtNotBefore));
+            }
+            Ok(())
+        });
+        let issuers = policy.clone();
+        let iss = StringSchemaImpl::default().custom(move |iss| {
+            if !issuers.issuers.is_empty() && !issuers.issuers.iter().any(|i| i == iss) {
+                return Err(CustomIssue::from(ErrorCode::JwtIssuer));
+            }
+            Ok(())
+        });
+        let aud = UnionSchema::new(vec![
+            StringSchemaImpl::default().into_schema_type(),
+            ArraySchema::new(StringSchemaImpl::default()).into_schema_type(),
+        ]);
+
+        let mut schema = ObjectSchema::default()
+            .optional_field("iat", NumberSchema::default())
+            .optional_field("sub", StringSchemaImpl::default())
+            .optional_field("jti", StringSchemaImpl::default())
+            .passthrough();
+        if policy.require_exp {
+            schema.set_field("exp", exp);
+        } else {
+            schema.set_optional_field("exp", exp);
+        }
+        schema.set_optional_field("nbf", nbf);
+        if policy.issuers.is_empty() {
+            schema.set_optional_field("iss", iss);
+        } else {
+            schema.set_field("iss", iss);
+        }
+        if policy.audiences.is_empty() {
+            return schema.optional_field("aud", aud);
+        }
+        schema.field("aud", aud).rule(["aud"], move |obj| {
+            let accepted = |aud: &Value| aud.as_str().is_some_and(|a| policy.audiences.iter().any(|p| p == a));
+            let found = match &obj["aud"] {
+                Value::Array(auds) => auds.iter().any(accepted),
+                aud => accepted(aud),
+            };
+            if !found {
+                return Err(CustomIssue::from(ErrorCode::JwtAudience));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{claims_schema, claims_schema_with, object, string};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn policy() -> ClaimsPolicy {
+        ClaimsPolicy {
+            now: Some(NOW),
+            leeway: 30,
+            audiences: vec!["api".into()],
+            issuers: vec!["https://auth.example.com".into()],
+            require_exp: true,
+        }
+    }
+
+    #[test]
+    fn test_claims_time_window() {
+        let schema = claims_schema_with(ClaimsPolicy { audiences: vec![], issuers: vec![], ..policy() });
+        assert!(schema.validate(&json!({"exp": NOW + 60, "nbf": NOW - 60, "iat": NOW - 60})).is_ok());
+        // Within the leeway either way
+        assert!(schema.validate(&json!({"exp": NOW - 10, "nbf": NOW + 10})).is_ok());
+
+        let err = schema.validate(&json!({"exp": NOW - 30})).unwrap_err();
+        assert_eq!(err.context.code, "jwt.expired");
+        assert_eq!(err.context.path, "exp");
+        assert_eq!(err.to_string(), "Token has expired");
+        let err = schema.validate(&json!({"exp": NOW + 60, "nbf": NOW + 31})).unwrap_err();
+        assert_eq!(err.context.code, "jwt.not_before");
+        assert_eq!(schema.validate(&json!({})).unwrap_err().context.code, "object.required");
+
+        // The preset checks against the system clock
+        assert_eq!(claims_schema().validate(&json!({"exp": NOW})).unwrap_err().context.code, "jwt.expired");
+        assert!(claims_schema().validate(&json!({"sub": "u1"})).is_ok());
+    }
+
+    #[test]
+    fn test_claims_audience_and_issuer() {
+        let schema = claims_schema_with(policy());
+        let claims = json!({"exp": NOW + 60, "iss": "https://auth.example.com", "aud": ["web", "api"]});
+        assert!(schema.validate(&claims).is_ok());
+        assert!(schema.validate(&json!({"exp": NOW + 60, "iss": "https://auth.example.com", "aud": "api"})).is_ok());
+
+        let err = schema.validate(&json!({"exp": NOW + 60, "iss": "https://auth.example.com", "aud": "web"})).unwrap_err();
+        assert_eq!(err.context.code, "jwt.audience");
+        assert_eq!(err.context.path, "aud");
+        let err = schema.validate(&json!({"exp": NOW + 60, "iss": "https://evil.example.com", "aud": "api"})).unwrap_err();
+        assert_eq!(err.context.code, "jwt.issuer");
+        assert!(schema.validate(&json!({"exp": NOW + 60, "aud": "api"})).is_err());
+    }
+
+    #[test]
+    fn test_claims_merge_custom() {
+        let schema = claims_schema_with(policy())
+            .merge(object().field("scope", string()).field("tenant", string()));
+        let claims = json!({
+            "exp": NOW + 60, "iss": "https://auth.example.com", "aud": "api",
+            "scope": "read", "tenant": "acme", "email": "a@example.com",
+        });
+        assert_eq!(schema.validate(&claims).unwrap(), claims);
+
+        let err = schema.validate(&json!({"exp": NOW + 60, "iss": "https://auth.example.com", "aud": "api", "scope": "read"})).unwrap_err();
+        assert_eq!(err.context.path, "tenant");
+    }
+}
@@ -0,0 +1,122 @@
+use super::{Presence, Schema, SchemaType, UnionSchema, UnionStrategy};
+
+/// Whether every value valid under `b` is valid under `a`, see
+/// [`Schema::accepts`]; `false` when it cannot be shown
+pub(crate) fn accepts(a: &SchemaType, b: &SchemaType) -> bool {
+    // Wrappers that only change errors or reporting accept what they wrap
+    match a {
+        SchemaType::Sensitive(s) => return accepts(&s.schema, b),
+        SchemaType::ErrorMapped(e) => return accepts(&e.schema, b),
+        SchemaType::Cached(c) => return accepts(&c.schema, b),
+        SchemaType::Deprecated(d) => return accepts(&d.schema, b),
+        _ => {}
+    }
+    match b {
+        SchemaType::Never(_) => true,
+        SchemaType::Sensitive(s) => accepts(a, &s.schema),
+        SchemaType::ErrorMapped(e) => accepts(a, &e.schema),
+        SchemaType::Cached(c) => accepts(a, &c.schema),
+        SchemaType::Deprecated(d) => accepts(a, &d.schema),
+        // Refinements and later steps only narrow what `b` accepts
+        SchemaType::Refined(r) => accepts(a, &r.schema),
+        SchemaType::Pipe(p) => accepts(a, &p.schema),
+        SchemaType::Literal(l) => a.validate(l.value()).is_ok(),
+        SchemaType::Union(u) => accepts_union(a, u),
+        _ => accepts_type(a, b),
+    }
+}
+
+/// `a` accepts a union if it accepts every member of an alternative or
+/// any member of an intersection
+fn accepts_union(a: &SchemaType, b: &UnionSchema) -> bool {
+    match b.strategy {
+        UnionStrategy::All => b.schemas.iter().any(|s| accepts(a, s)),
+        UnionStrategy::First | UnionStrategy::Best { .. } | UnionStrategy::ExactlyOne => {
+            b.schemas.iter().all(|s| accepts(a, s))
+        }
+    }
+}
+
+fn accepts_type(a: &SchemaType, b: &SchemaType) -> bool {
+    match (a, b) {
+        (SchemaType::Any(any), b) => covers_null(any.get_presence(), b.get_presence()),
+        (SchemaType::Union(u), b) => match u.strategy {
+            UnionStrategy::All => u.schemas.iter().all(|s| accepts(s, b)),
+            UnionStrategy::First | UnionStrategy::Best { .. } => u.schemas.iter().any(|s| accepts(s, b)),
+            // `b` may match another member too, known only for a single one
+            UnionStrategy::ExactlyOne => matches!(u.schemas.as_slice(), [only] if accepts(only, b)),
+        },
+        (SchemaType::String(a), SchemaType::String(b)) => a.covers(b),
+        (SchemaType::Number(a), SchemaType::Number(b)) => a.covers(b),
+        (SchemaType::Boolean(a), SchemaType::Boolean(b)) => a.covers(b),
+        (SchemaType::Bytes(a), SchemaType::Bytes(b)) => a.covers(b),
+        (SchemaType::Array(a), SchemaType::Array(b)) => a.covers(b),
+        (SchemaType::Object(a), SchemaType::Object(b)) => a.covers(b),
+        _ => false,
+    }
+}
+
+/// Whether `a` allows null wherever `b` does
+pub(crate) fn covers_null(a: Presence, b: Presence) -> bool {
+    a.allows_null() || !b.allows_null()
+}
+
+/// Whether the lower bound `a` is met by everything above the lower bound `b`
+pub(crate) fn covers_min<T: PartialOrd>(a: Option<T>, b: Option<T>) -> bool {
+    match (a, b) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(a), Some(b)) => a <= b,
+    }
+}
+
+/// Whether the upper bound `a` is met by everything below the upper bound `b`
+pub(crate) fn covers_max<T: PartialOrd>(a: Option<T>, b: Option<T>) -> bool {
+    match (a, b) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(a), Some(b)) => a >= b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{any, array, boolean, eq, never, number, object, string, union};
+    use crate::schemas::{Schema, StringSchema, UnionStrategy};
+
+    #[test]
+    fn test_accepts() {
+        let consumer = object()
+            .field("id", number().integer().min(1.0))
+            .field("email", string().email())
+            .optional_field("tags", array(string()).max_items(10))
+            .passthrough();
+        let producer = object()
+            .field("id", number().integer().min(100.0).max(1e6))
+            .field("email", string().email().max_length(200))
+            .field("tags", array(string().min_length(1)).max_items(3))
+            .field("extra", boolean());
+        assert!(consumer.accepts(&producer));
+        assert!(!producer.accepts(&consumer));
+
+        // An optional field the consumer requires, or an unknown key it rejects
+        let strict = object().field("id", number()).field("email", string());
+        assert!(!strict.accepts(&object().field("id", number()).optional_field("email", string())));
+        assert!(!strict.accepts(&producer));
+        assert!(strict.accepts(&object().field("id", number().integer()).field("email", string().email())));
+
+        assert!(number().min(0.0).accepts(&number().gt(0.0)));
+        assert!(!number().gt(0.0).accepts(&number().min(0.0)));
+        assert!(!string().accepts(&string().optional()));
+        assert!(string().optional().accepts(&string()));
+        assert!(string().min_length(2).accepts(&union((eq("ab"), eq("abc")))));
+        assert!(!string().min_length(3).accepts(&union((eq("ab"), eq("abc")))));
+        assert!(union((string(), number())).accepts(&number().integer()));
+        assert!(string().accepts(&union((string(), number())).strategy(UnionStrategy::All)));
+        assert!(any().accepts(&array(string())));
+        assert!(string().accepts(&never()));
+        assert!(!string().accepts(&number()));
+        // Custom checks cannot be compared
+        assert!(!string().custom(|_| Ok::<_, String>(())).accepts(&string()));
+    }
+}
@@ -0,0 +1,261 @@
+use std::sync::Arc;
+use serde_json::Value;
+
+use crate::error::{resolved::ResolvedMessages, ValidationError};
+use super::{Schema, Presence, SchemaType, InvalidFields, PartialOutput, LintFinding, Explanation, UnionSchema, UnionStrategy, validate_schema_type};
+
+/// A frozen, immutable schema optimized for repeated validation
+///
+/// Created with [`Schema::compile`]. Cloning only bumps a reference count, so
+/// one compiled schema can be handed to every request handler or worker.
+/// Compiling flattens nested unions, compiles every regex the schema would
+/// otherwise compile on first use, and resolves the default message of each
+/// error code up front, so validating does neither.
+#[derive(Clone)]
+pub struct CompiledSchema {
+    root: Arc<SchemaType>,
+    messages: Arc<ResolvedMessages>,
+}
+
+impl CompiledSchema {
+    pub fn new(schema: impl Schema) -> Self {
+        Self {
+            root: Arc::new(optimize(schema.into_schema_type())),
+            messages: Arc::new(ResolvedMessages::new()),
+        }
+    }
+
+    /// The optimized schema tree
+    pub fn schema(&self) -> &SchemaType {
+        &self.root
+    }
+}
+
+impl Schema for CompiledSchema {
+    fn is_optional(&self) -> bool {
+        self.root.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.root.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.root.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.root.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        self.root.explain()
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        ResolvedMessages::with(&self.messages, || validate_schema_type(&self.root, value))
+    }
+
+    fn validate_owned(&self, value: Value) -> Result<Value, ValidationError> {
+        ResolvedMessages::with(&self.messages, || self.root.validate_owned(value))
+    }
+
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        ResolvedMessages::with(&self.messages, || self.root.validate_ref(value))
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.root.preserves_input()
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        ResolvedMessages::with(&self.messages, || self.root.validate_partial_with(value, invalid))
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        self.root.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.root.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        Arc::try_unwrap(self.root).unwrap_or_else(|root| (*root).clone())
+    }
+}
+
+/// Rewrite a schema tree for faster validation without changing its
+/// results: nested unions are flattened and lazily built regexes compiled
+fn optimize(schema: SchemaType) -> SchemaType {
+    match schema {
+        SchemaType::String(s) => {
+            s.precompile();
+            SchemaType::String(s)
+        }
+        SchemaType::Array(mut a) => {
+            a.map_items(optimize);
+            SchemaType::Array(a)
+        }
+        SchemaType::Object(mut o) => {
+            o.map_fields(optimize);
+            SchemaType::Object(o)
+        }
+        SchemaType::Union(u) => SchemaType::Union(Box::new(flatten_union(*u))),
+        SchemaType::Transform(mut t) => {
+            t.schema = optimize(t.schema);
+            SchemaType::Transform(t)
+        }
+        SchemaType::Sensitive(mut s) => {
+            s.schema = optimize(s.schema);
+            SchemaType::Sensitive(s)
+        }
+        SchemaType::ErrorMapped(mut e) => {
+            e.schema = optimize(e.schema);
+            SchemaType::ErrorMapped(e)
+        }
+        SchemaType::Deprecated(mut d) => {
+            d.schema = optimize(d.schema);
+            SchemaType::Deprecated(d)
+        }
+        SchemaType::Catch(mut c) => {
+            c.schema = optimize(c.schema);
+            SchemaType::Catch(c)
+        }
+        SchemaType::Pipe(mut p) => {
+            p.schema = optimize(p.schema);
+            p.next = optimize(p.next);
+            SchemaType::Pipe(p)
+        }
+        SchemaType::MapOutput(mut m) => {
+            m.schema = optimize(m.schema);
+            SchemaType::MapOutput(m)
+        }
+        SchemaType::Refined(mut r) => {
+            r.schema = optimize(r.schema);
+            SchemaType::Refined(r)
+        }
+        SchemaType::Cached(mut c) => {
+            c.schema = optimize(c.schema);
+            SchemaType::Cached(c)
+        }
+        SchemaType::Not(mut n) => {
+            n.map_schema(optimize);
+            SchemaType::Not(n)
+        }
+        SchemaType::Switch(mut s) => {
+            s.map_cases(optimize);
+            SchemaType::Switch(s)
+        }
+        SchemaType::Conditional(mut c) => {
+            c.map_schemas(optimize);
+            SchemaType::Conditional(c)
+        }
+        other => other,
+    }
+}
+
+/// Splice nested unions of the same first-match or all-match strategy into
+/// their parent, so validation walks one flat list of branches
+fn flatten_union(union: UnionSchema) -> UnionSchema {
+    let UnionSchema { schemas, strategy, error_messages } = union;
+    let mut flat = Vec::with_capacity(schemas.len());
+    for schema in schemas.into_iter().map(optimize) {
+        match schema {
+            SchemaType::Union(inner) if inner.error_messages.is_empty() && same_strategy(&strategy, &inner.strategy) => {
+                flat.extend(inner.schemas);
+            }
+            other => flat.push(other),
+        }
+    }
+    UnionSchema { schemas: flat, strategy, error_messages }
+}
+
+fn same_strategy(a: &UnionStrategy, b: &UnionStrategy) -> bool {
+    matches!((a, b), (UnionStrategy::First, UnionStrategy::First) | (UnionStrategy::All, UnionStrategy::All))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{string, number, boolean, object, array, union, StringSchema};
+
+    #[test]
+    fn test_compiled_matches_builder() {
+        let schema = object()
+            .field("name", string().trim().min_length(2))
+            .field("tags", array(string().email()).max_items(2));
+        let compiled = schema.clone().compile();
+
+        for value in [
+            json!({"name": "  Al  ", "tags": ["a@b.co"]}),
+            json!({"name": "A", "tags": []}),
+            json!({"name": "Ann", "tags": ["nope"]}),
+        ] {
+            assert_eq!(
+                compiled.validate(&value).map_err(|e| e.context.code.clone()),
+                schema.validate(&value).map_err(|e| e.context.code.clone()),
+            );
+        }
+    }
+
+    #[test]
+    fn test_compiled_is_cheap_to_share() {
+        let compiled = string().min_length(1).compile();
+        let copy = compiled.clone();
+        assert!(std::ptr::eq(compiled.schema(), copy.schema()));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let compiled = compiled.clone();
+                std::thread::spawn(move || compiled.validate(&json!("ok")).is_ok())
+            })
+            .collect();
+        assert!(handles.into_iter().all(|h| h.join().unwrap()));
+    }
+
+    #[test]
+    fn test_compiled_resolves_ahead_of_validation() {
+        use crate::error::TEMPLATE_LOOKUPS;
+        use crate::schemas::string::REGEX_COMPILES;
+
+        let count = || (REGEX_COMPILES.with(|n| n.get()), TEMPLATE_LOOKUPS.with(|n| n.get()));
+        let compiled = object()
+            .field("email", string().email())
+            .field("code", string().pattern(r"^\d{5}$"))
+            .field("age", number().min(0.0))
+            .compile();
+
+        let before = count();
+        for value in [
+            json!({"email": "nope", "code": "12345", "age": 1}),
+            json!({"email": "a@b.co", "code": "1", "age": 1}),
+            json!({"email": "a@b.co", "code": "12345", "age": -1}),
+        ] {
+            let err = compiled.validate(&value).unwrap_err();
+            assert!(!err.to_string().is_empty());
+        }
+        assert_eq!(count(), before);
+
+        // Uncompiled errors still resolve their message as they are raised
+        let _ = number().min(0.0).validate(&json!(-1));
+        assert_eq!(count().1, before.1 + 1);
+    }
+
+    #[test]
+    fn test_compile_flattens_unions() {
+        let nested = union![
+            string().into_schema_type(),
+            union![number().into_schema_type(), boolean().into_schema_type()].into_schema_type(),
+        ];
+        let compiled = nested.compile();
+
+        match compiled.schema() {
+            SchemaType::Union(u) => assert_eq!(u.schemas.len(), 3),
+            _ => panic!("expected a union"),
+        }
+        assert!(compiled.validate(&json!(true)).is_ok());
+        assert!(compiled.validate(&json!([])).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{ValidationError, MessageIssue, ErrorCode, canonical_code};
+use crate::error::message_check::{check_templates, debug_check_template};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, explain, InvalidFields, PartialOutput, LintFinding, branch, no_messages, validate_schema_type};
+
+/// Schema accepting exactly one JSON value, e.g. a discriminator such as
+/// `"card"` tested by [`ConditionalSchema`] or [`ObjectSchema::when`](super::ObjectSchema::when)
+#[derive(Clone)]
+pub struct LiteralSchema {
+    value: Value,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+    /// Compare numbers by value, so that `1` also matches `1.0`
+    numeric: bool,
+}
+
+impl LiteralSchema {
+    pub fn new(value: impl Into<Value>) -> Self {
+        Self {
+            value: value.into(),
+            presence: Presence::Required,
+            error_messages: HashMap::new(),
+            numeric: false,
+        }
+    }
+
+    pub(crate) fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Match numbers, also inside arrays and objects, by their value as
+    /// JSON Schema's `enum` and `const` do
+    pub(crate) fn numeric(mut self) -> Self {
+        self.numeric = true;
+        self
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        match self.numeric {
+            true => same_json(value, &self.value),
+            false => *value == self.value,
+        }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+}
+
+impl HasErrorMessages for LiteralSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        check_templates("", &self.error_messages)
+    }
+}
+
+impl Schema for LiteralSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn preserves_input(&self) -> bool {
+        true
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        vec![Explanation::typed(format!("exactly {}", self.value), self.presence)]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        if self.matches(value) || (value.is_null() && self.presence.allows_null()) {
+            return Ok(value.clone());
+        }
+        Err(ValidationError::new(ErrorCode::LiteralMismatch)
+            .with_details(|d| d.expected = Some(self.value.to_string()))
+            .message_or_default(self.error_messages.get("literal.mismatch")))
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Literal(self)
+    }
+}
+
+/// JSON equality with numbers compared by value, integers exactly
+fn same_json(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => match (x.as_i64(), y.as_i64(), x.as_u64(), y.as_u64()) {
+            (Some(x), Some(y), ..) => x == y,
+            (.., Some(x), Some(y)) => x == y,
+            _ => x.as_f64() == y.as_f64(),
+        },
+        (Value::Array(x), Value::Array(y)) => x.len() == y.len() && x.iter().zip(y).all(|(x, y)| same_json(x, y)),
+        (Value::Object(x), Value::Object(y)) => {
+            x.len() == y.len() && x.iter().all(|(k, v)| y.get(k).is_some_and(|w| same_json(v, w)))
+        }
+        _ => a == b,
+    }
+}
+
+/// Schema validating with one of two schemas depending on whether the value
+/// passes a predicate schema, like JSON Schema's `if`/`then`/`else`
+///
+/// The predicate only steers validation: its errors, warnings and
+/// refinements are never reported.
+#[derive(Clone)]
+pub struct ConditionalSchema {
+    predicate: Box<SchemaType>,
+    then: Box<SchemaType>,
+    otherwise: Box<SchemaType>,
+    presence: Presence,
+}
+
+impl ConditionalSchema {
+    pub fn new(predicate: impl Schema, then: impl Schema, otherwise: impl Schema) -> Self {
+        Self {
+            predicate: Box::new(predicate.into_schema_type()),
+            then: Box::new(then.into_schema_type()),
+            otherwise: Box::new(otherwise.into_schema_type()),
+            presence: Presence::Required,
+        }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Rewrite the predicate and both branches in place
+    pub(crate) fn map_schemas(&mut self, f: impl Fn(SchemaType) -> SchemaType) {
+        for schema in [&mut self.predicate, &mut self.then, &mut self.otherwise] {
+            let taken = std::mem::replace(schema.as_mut(), SchemaType::Never(Default::default()));
+            **schema = f(taken);
+        }
+    }
+
+    fn select(&self, value: &Value) -> &SchemaType {
+        if passes(&self.predicate, value) { &self.then } else { &self.otherwise }
+    }
+}
+
+/// Whether `value` passes `predicate`, dropping whatever warnings and
+/// refinements the predicate raised along the way
+pub(crate) fn passes(predicate: &SchemaType, value: &Value) -> bool {
+    // `branch` discards on failure only, so always report one
+    branch(|| Err::<(), bool>(validate_schema_type(predicate, value).is_ok())).unwrap_err()
+}
+
+impl HasErrorMessages for ConditionalSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        no_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = self.then.check_messages();
+        issues.extend(self.otherwise.check_messages());
+        issues
+    }
+}
+
+impl Schema for ConditionalSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("conditional", self.presence);
+        explanation.rule(format!("if {}", explain::root_summary(&self.predicate.explain())));
+        explanation.rule(format!("then {}", explain::root_summary(&self.then.explain())));
+        explanation.rule(format!("else {}", explain::root_summary(&self.otherwise.explain())));
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        if value.is_null() && self.presence.allows_null() {
+            return Ok(value.clone());
+        }
+        validate_schema_type(self.select(value), value)
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        if value.is_null() && self.presence.allows_null() {
+            return PartialOutput::from_result(Ok(value.clone()), value, invalid);
+        }
+        self.select(value).validate_partial_with(value, invalid)
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.select(value).redact(value)
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.select(&output).canonical(output)
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        let mut findings = self.predicate.lint();
+        findings.extend(self.then.lint());
+        findings.extend(self.otherwise.lint());
+        findings
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Conditional(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{any, conditional, eq, number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[test]
+    fn test_literal() {
+        let schema = eq("card");
+        assert!(schema.validate(&json!("card")).is_ok());
+        let err = schema.validate(&json!("cash")).unwrap_err();
+        assert_eq!(err.context.code, "literal.mismatch");
+        assert_eq!(err.to_string(), "Must be \"card\"");
+        assert!(eq(json!({"a": 1})).validate(&json!({"a": 1})).is_ok());
+    }
+
+    #[test]
+    fn test_conditional_branches() {
+        // Business accounts need a VAT number, personal ones a birth date
+        let schema = conditional(
+            object().field("kind", eq("business")).passthrough(),
+            object().field("kind", string()).field("vat", string().min_length(4)),
+            object().field("kind", string()).field("born", string()),
+        );
+        assert!(schema.validate(&json!({"kind": "business", "vat": "DE123"})).is_ok());
+        assert!(schema.validate(&json!({"kind": "personal", "born": "1990-01-01"})).is_ok());
+
+        let err = schema.validate(&json!({"kind": "business", "born": "1990-01-01"})).unwrap_err();
+        assert_eq!(err.context.path, "vat");
+        // The predicate's own failure is never what gets reported
+        let err = schema.validate(&json!({"kind": "personal"})).unwrap_err();
+        assert_eq!(err.context.path, "born");
+    }
+
+    #[test]
+    fn test_conditional_on_scalars() {
+        let schema = conditional(number().min(0.0), any(), number().max(-10.0))
+            .optional();
+        assert!(schema.validate(&json!(5)).is_ok());
+        assert!(schema.validate(&json!(-20)).is_ok());
+        assert!(schema.validate(&json!(-5)).is_err());
+        assert!(schema.validate(&json!(null)).is_ok());
+    }
+}
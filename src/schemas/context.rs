@@ -0,0 +1,92 @@
+use std::{cell::RefCell, collections::{HashMap, HashSet}, sync::Arc};
+use serde_json::Value;
+
+use crate::error::{path_format::SharedFormatter, PathFormatter};
+
+/// Per-call settings visible to context-dependent schemas such as
+/// [`SwitchSchema`](super::SwitchSchema): feature flags, tenant, A/B bucket,
+/// and how errors raised during the call render their paths
+///
+/// Passed with [`Schema::validate_with_context`](super::Schema::validate_with_context);
+/// plain `validate` sees an empty context.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationContext {
+    flags: HashSet<String>,
+    values: HashMap<String, Value>,
+    path_formatter: Option<SharedFormatter>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Vec<ValidationContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the innermost context, also when validation panics
+struct ScopeGuard;
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        CURRENT.with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+impl ValidationContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turn on the feature flag `name`
+    pub fn flag(mut self, name: impl Into<String>) -> Self {
+        self.flags.insert(name.into());
+        self
+    }
+
+    /// Attach an arbitrary value, e.g. `("tenant", "acme")`
+    pub fn value(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.values.insert(key.into(), value.into());
+        self
+    }
+
+    /// Render the paths of errors and warnings raised during validation
+    /// with `formatter`, see [`ValidationError::with_path_formatter`](crate::ValidationError::with_path_formatter)
+    pub fn path_formatter(mut self, formatter: impl PathFormatter + 'static) -> Self {
+        self.path_formatter = Some(SharedFormatter(Arc::new(formatter)));
+        self
+    }
+
+    pub fn is_enabled(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    /// Run `f` with this context as the current one on this thread
+    pub(crate) fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        CURRENT.with(|stack| stack.borrow_mut().push(self.clone()));
+        let _guard = ScopeGuard;
+        f()
+    }
+
+    /// Copy of the current context, to carry it to another thread
+    pub(crate) fn current() -> ValidationContext {
+        Self::with_current(Clone::clone)
+    }
+
+    /// Path formatter of the current context, attached to each new error
+    pub(crate) fn current_path_formatter() -> Option<SharedFormatter> {
+        CURRENT.with(|stack| stack.borrow().last()?.path_formatter.clone())
+    }
+
+    /// Read the current context; `f` must not start a nested validation
+    pub(crate) fn with_current<R>(f: impl FnOnce(&ValidationContext) -> R) -> R {
+        CURRENT.with(|stack| match stack.borrow().last() {
+            Some(ctx) => f(ctx),
+            None => f(&ValidationContext::default()),
+        })
+    }
+}
@@ -0,0 +1,198 @@
+use std::io::Read;
+use serde_json::{Map, Value};
+
+use crate::error::{ErrorCode, ValidationError, ValidationErrors};
+use super::{ObjectSchema, Schema, string_values};
+
+/// Validates CSV rows against an object schema, e.g. for bulk imports
+///
+/// Header columns are bound to the fields of the same name, ignoring case.
+/// Cells are strings; they become numbers or booleans where the field
+/// expects one, and array fields split them on commas. Empty cells count
+/// as missing, so optional fields may be left blank. Errors are reported
+/// at `<row>.<column>`, rows counted from 0 after the header. A header with
+/// two columns for the same field, such as `email` and `Email`, is
+/// reported as a single `parse.invalid_csv` error instead of any rows.
+///
+/// ```
+/// use rusty_zod::prelude::*;
+/// use rusty_zod::CsvValidator;
+///
+/// let validator = CsvValidator::new(object().field("email", string().email()).field("age", number()));
+/// let rows: Vec<_> = validator.validate_reader("email,age\nada@example.com,36\nbob,x\n".as_bytes()).collect();
+/// assert!(rows[0].is_ok());
+/// assert_eq!(rows[1].as_ref().unwrap_err().len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct CsvValidator {
+    schema: ObjectSchema,
+}
+
+impl CsvValidator {
+    pub fn new(schema: ObjectSchema) -> Self {
+        Self { schema }
+    }
+
+    /// Validate every row of `reader`, which starts with a header row
+    pub fn validate_reader<R: Read>(&self, reader: R) -> CsvRows<'_, R> {
+        let mut reader = ::csv::ReaderBuilder::new().trim(::csv::Trim::All).from_reader(reader);
+        let (columns, error) = match reader.headers() {
+            Ok(headers) => {
+                let columns: Vec<String> = headers.iter().map(|h| self.column(h)).collect();
+                let error = duplicate_column(headers, &columns);
+                (columns, error)
+            }
+            Err(e) => (Vec::new(), Some(invalid_csv(e))),
+        };
+        CsvRows { schema: &self.schema, reader, columns, error, row: 0, done: false }
+    }
+
+    /// Field a header is bound to, or the header itself
+    fn column(&self, header: &str) -> String {
+        match self.schema.field_ignoring_case(header) {
+            Some((field, _)) => field.to_string(),
+            None => header.to_string(),
+        }
+    }
+}
+
+/// Rows of [`CsvValidator::validate_reader`], each validated output or
+/// every error found in it
+pub struct CsvRows<'a, R> {
+    schema: &'a ObjectSchema,
+    reader: ::csv::Reader<R>,
+    columns: Vec<String>,
+    error: Option<ValidationError>,
+    row: usize,
+    done: bool,
+}
+
+impl<R: Read> CsvRows<'_, R> {
+    fn validate_record(&self, record: &::csv::StringRecord) -> Result<Value, ValidationErrors> {
+        let mut obj = Map::new();
+        for (column, cell) in self.columns.iter().zip(record.iter()) {
+            if cell.is_empty() {
+                continue;
+            }
+            let value = match self.schema.field_ignoring_case(column) {
+                Some((_, schema)) => string_values::from_strings(schema, &[cell.to_string()]),
+                None => Value::String(cell.to_string()),
+            };
+            obj.insert(column.clone(), value);
+        }
+        let output = self.schema.validate_partial(&Value::Object(obj));
+        match output.value {
+            Some(value) if output.errors.is_empty() => Ok(value),
+            _ => Err(ValidationErrors::new(
+                output.errors.into_iter().map(|err| err.with_path_prefix(self.row)).collect(),
+            )),
+        }
+    }
+}
+
+impl<R: Read> Iterator for CsvRows<'_, R> {
+    type Item = Result<Value, ValidationErrors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            self.done = true;
+            return Some(Err(e.into()));
+        }
+        if self.done {
+            return None;
+        }
+        let mut record = ::csv::StringRecord::new();
+        let result = match self.reader.read_record(&mut record) {
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Ok(true) => self.validate_record(&record),
+            Err(e) => {
+                // Only malformed rows can be skipped; I/O errors end the stream
+                self.done = !matches!(e.kind(), ::csv::ErrorKind::UnequalLengths { .. } | ::csv::ErrorKind::Utf8 { .. });
+                Err(invalid_csv(e).with_path_prefix(self.row).into())
+            }
+        };
+        self.row += 1;
+        Some(result)
+    }
+}
+
+/// Error for the first header column bound to the same field as an
+/// earlier one, whose cells would overwrite each other
+fn duplicate_column(headers: &::csv::StringRecord, columns: &[String]) -> Option<ValidationError> {
+    let (i, j) = (1..columns.len()).find_map(|j| columns[..j].iter().position(|c| *c == columns[j]).map(|i| (i, j)))?;
+    let message = match (&headers[i], &headers[j]) {
+        (a, b) if a == b => format!("Invalid CSV: duplicate column '{}'", a),
+        (a, b) => format!("Invalid CSV: columns '{}' and '{}' both name field '{}'", a, b, columns[j]),
+    };
+    Some(ValidationError::new(ErrorCode::InvalidCsv).message(message))
+}
+
+fn invalid_csv(e: ::csv::Error) -> ValidationError {
+    ValidationError::new(ErrorCode::InvalidCsv).message(format!("Invalid CSV: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{array, boolean, number, object, string};
+    use crate::schemas::StringSchema;
+
+    fn validator() -> CsvValidator {
+        CsvValidator::new(
+            object()
+                .field("email", string().email())
+                .field("age", number().integer())
+                .optional_field("admin", boolean())
+                .optional_field("tags", array(string())),
+        )
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let csv = "Email, Age ,admin,tags\nada@example.com,36,true,\"math,engines\"\nbob,x,,\ncy@example.com,,false,\n";
+        let rows: Vec<_> = validator().validate_reader(csv.as_bytes()).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].as_ref().unwrap(), &json!({
+            "email": "ada@example.com", "age": 36, "admin": true, "tags": ["math", "engines"],
+        }));
+
+        let errors = rows[1].as_ref().unwrap_err();
+        let paths: Vec<_> = errors.iter().map(|e| e.context.path.to_string()).collect();
+        assert_eq!(paths, ["1.email", "1.age"]);
+        let errors = rows[2].as_ref().unwrap_err();
+        assert_eq!(errors.issues()[0].context.code, "object.required");
+        assert_eq!(errors.issues()[0].context.path, "2.age");
+    }
+
+    #[test]
+    fn test_csv_malformed_rows() {
+        let csv = "email,age,nickname\nada@example.com,36\nbob@example.com,40,bobby\n";
+        let rows: Vec<_> = validator().validate_reader(csv.as_bytes()).collect();
+        assert_eq!(rows.len(), 2);
+        let err = &rows[0].as_ref().unwrap_err().issues()[0];
+        assert_eq!(err.context.code, "parse.invalid_csv");
+        assert_eq!(err.context.path, "0");
+        // Columns without a field are unknown to a strict schema
+        assert_eq!(rows[1].as_ref().unwrap_err().issues()[0].context.path, "1.nickname");
+    }
+
+    #[test]
+    fn test_csv_duplicate_columns() {
+        let rows: Vec<_> = validator().validate_reader("email,age,email
+a@b.co,1,c@d.co
+".as_bytes()).collect();
+        assert_eq!(rows.len(), 1);
+        let err = &rows[0].as_ref().unwrap_err().issues()[0];
+        assert_eq!(err.context.code, "parse.invalid_csv");
+        assert_eq!(err.to_string(), "Invalid CSV: duplicate column 'email'");
+
+        let rows: Vec<_> = validator().validate_reader("Email,age,email
+a@b.co,1,c@d.co
+".as_bytes()).collect();
+        assert_eq!(rows[0].as_ref().unwrap_err().to_string(), "Invalid CSV: columns 'Email' and 'email' both name field 'email'");
+    }
+}
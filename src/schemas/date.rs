@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde_json::Value;
+
+use crate::error::{ValidationError, ErrorCode, canonical_code};
+use crate::error::message_check::debug_check_template;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, Unsatisfiable, satisfiable, get_type_name};
+
+/// Calendar date in `YYYY-MM-DD` (ISO 8601) form
+#[derive(Clone, Default)]
+pub struct DateSchema {
+    min: Option<NaiveDate>,
+    max: Option<NaiveDate>,
+    past: bool,
+    future: bool,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+/// Date and time in RFC 3339 form, e.g. `2024-02-29T12:00:00+02:00`
+#[derive(Clone, Default)]
+pub struct DateTimeSchema {
+    min: Option<DateTime<Utc>>,
+    max: Option<DateTime<Utc>>,
+    past: bool,
+    future: bool,
+    to_utc: bool,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl DateSchema {
+    pub fn min(mut self, date: NaiveDate) -> Self {
+        self.min = Some(date);
+        self
+    }
+
+    pub fn max(mut self, date: NaiveDate) -> Self {
+        self.max = Some(date);
+        self
+    }
+
+    /// Date must be strictly before today (UTC)
+    pub fn past(mut self) -> Self {
+        self.past = true;
+        self
+    }
+
+    /// Date must be strictly after today (UTC)
+    pub fn future(mut self) -> Self {
+        self.future = true;
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::DateRequired, message)
+    }
+}
+
+impl DateTimeSchema {
+    pub fn min(mut self, datetime: DateTime<Utc>) -> Self {
+        self.min = Some(datetime);
+        self
+    }
+
+    pub fn max(mut self, datetime: DateTime<Utc>) -> Self {
+        self.max = Some(datetime);
+        self
+    }
+
+    /// Instant must be before the time of validation
+    pub fn past(mut self) -> Self {
+        self.past = true;
+        self
+    }
+
+    /// Instant must be after the time of validation
+    pub fn future(mut self) -> Self {
+        self.future = true;
+        self
+    }
+
+    /// Output the value normalized to UTC (`2024-01-01T10:00:00Z`)
+    pub fn to_utc(mut self) -> Self {
+        self.to_utc = true;
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::DateTimeRequired, message)
+    }
+}
+
+impl HasErrorMessages for DateSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+}
+
+impl HasErrorMessages for DateTimeSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+}
+
+fn date_error(error_messages: &HashMap<String, String>, code: ErrorCode) -> ValidationError {
+    let custom = error_messages.get(code.code());
+    ValidationError::new(code).message_or_default(custom)
+}
+
+fn bound_error(error_messages: &HashMap<String, String>, code: ErrorCode, bound: String) -> ValidationError {
+    date_error(error_messages, code).with_details(|d| d.expected = Some(bound))
+}
+
+fn non_string_error(
+    error_messages: &HashMap<String, String>,
+    expected: &str,
+    value: &Value,
+) -> ValidationError {
+    let code = ErrorCode::from_code(&format!("{}.invalid_type", expected));
+    date_error(error_messages, code).with_type_info(expected, get_type_name(value))
+}
+
+impl Schema for DateSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("date.required").cloned()
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        satisfiable::check_bounds("date", self.min, self.max).into_iter().collect()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("date (YYYY-MM-DD)", self.presence);
+        if let Some(min) = self.min {
+            explanation.rule(format!("on or after {}", min));
+        }
+        if let Some(max) = self.max {
+            explanation.rule(format!("on or before {}", max));
+        }
+        if self.past {
+            explanation.rule("in the past");
+        }
+        if self.future {
+            explanation.rule("in the future");
+        }
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        let s = match value {
+            Value::Null if self.presence.allows_null() => return Ok(value.clone()),
+            Value::Null => {
+                return Err(date_error(&self.error_messages, ErrorCode::DateRequired))
+            }
+            Value::String(s) => s,
+            _ => return Err(non_string_error(&self.error_messages, "date", value)),
+        };
+
+        let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| {
+            date_error(&self.error_messages, ErrorCode::InvalidDate).with_details(|d| d.received = Some(Value::String(s.clone())))
+        })?;
+
+        if let Some(min) = self.min {
+            if date < min {
+                return Err(bound_error(&self.error_messages, ErrorCode::DateTooEarly, min.to_string()));
+            }
+        }
+
+        if let Some(max) = self.max {
+            if date > max {
+                return Err(bound_error(&self.error_messages, ErrorCode::DateTooLate, max.to_string()));
+            }
+        }
+
+        let today = Utc::now().date_naive();
+        if self.past && date >= today {
+            return Err(date_error(&self.error_messages, ErrorCode::DateNotPast));
+        }
+        if self.future && date <= today {
+            return Err(date_error(&self.error_messages, ErrorCode::DateNotFuture));
+        }
+
+        Ok(Value::String(date.format("%Y-%m-%d").to_string()))
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Date(self)
+    }
+}
+
+impl Schema for DateTimeSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("datetime.required").cloned()
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        satisfiable::check_bounds("date-time", self.min, self.max).into_iter().collect()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("date-time (RFC 3339)", self.presence);
+        if let Some(min) = self.min {
+            explanation.rule(format!("at or after {}", min.to_rfc3339_opts(SecondsFormat::AutoSi, true)));
+        }
+        if let Some(max) = self.max {
+            explanation.rule(format!("at or before {}", max.to_rfc3339_opts(SecondsFormat::AutoSi, true)));
+        }
+        if self.past {
+            explanation.rule("in the past");
+        }
+        if self.future {
+            explanation.rule("in the future");
+        }
+        if self.to_utc {
+            explanation.rule("converted to UTC");
+        }
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        let s = match value {
+            Value::Null if self.presence.allows_null() => return Ok(value.clone()),
+            Value::Null => {
+                return Err(date_error(&self.error_messages, ErrorCode::DateTimeRequired))
+            }
+            Value::String(s) => s,
+            _ => return Err(non_string_error(&self.error_messages, "datetime", value)),
+        };
+
+        let parsed = DateTime::parse_from_rfc3339(s).map_err(|_| {
+            date_error(&self.error_messages, ErrorCode::InvalidDateTime).with_details(|d| d.received = Some(Value::String(s.clone())))
+        })?;
+        let utc = parsed.with_timezone(&Utc);
+
+        if let Some(min) = self.min {
+            if utc < min {
+                return Err(bound_error(&self.error_messages, ErrorCode::DateTimeTooEarly, min.to_rfc3339()));
+            }
+        }
+
+        if let Some(max) = self.max {
+            if utc > max {
+                return Err(bound_error(&self.error_messages, ErrorCode::DateTimeTooLate, max.to_rfc3339()));
+            }
+        }
+
+        let now = Utc::now();
+        if self.past && utc >= now {
+            return Err(date_error(&self.error_messages, ErrorCode::DateTimeNotPast));
+        }
+        if self.future && utc <= now {
+            return Err(date_error(&self.error_messages, ErrorCode::DateTimeNotFuture));
+        }
+
+        if self.to_utc {
+            Ok(Value::String(utc.to_rfc3339_opts(SecondsFormat::AutoSi, true)))
+        } else {
+            Ok(value.clone())
+        }
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::DateTime(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_date_calendar_validation() {
+        let schema = DateSchema::default();
+
+        assert!(schema.validate(&json!("2024-02-29")).is_ok());
+        let err = schema.validate(&json!("2023-02-29")).unwrap_err();
+        assert_eq!(err.context.code, "date.invalid");
+        assert!(schema.validate(&json!("2024-13-01")).is_err());
+        assert_eq!(schema.validate(&json!(20240101)).unwrap_err().context.code, "date.invalid_type");
+    }
+
+    #[test]
+    fn test_date_range() {
+        let schema = DateSchema::default()
+            .min(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap())
+            .max(NaiveDate::from_ymd_opt(2020, 12, 31).unwrap());
+
+        assert!(schema.validate(&json!("2020-06-15")).is_ok());
+        assert_eq!(schema.validate(&json!("2019-12-31")).unwrap_err().context.code, "date.too_early");
+        assert_eq!(schema.validate(&json!("2021-01-01")).unwrap_err().context.code, "date.too_late");
+    }
+
+    #[test]
+    fn test_date_past_future() {
+        assert!(DateSchema::default().past().validate(&json!("2000-01-01")).is_ok());
+        assert!(DateSchema::default().future().validate(&json!("2000-01-01")).is_err());
+        assert!(DateSchema::default().future().validate(&json!("9999-01-01")).is_ok());
+    }
+
+    #[test]
+    fn test_datetime_offsets_and_normalization() {
+        let schema = DateTimeSchema::default().to_utc();
+
+        assert_eq!(
+            schema.validate(&json!("2024-01-01T12:00:00+02:00")).unwrap(),
+            json!("2024-01-01T10:00:00Z")
+        );
+        assert!(schema.validate(&json!("2024-01-01T12:00:00+25:00")).is_err());
+        assert!(schema.validate(&json!("2024-01-01 12:00")).is_err());
+
+        let raw = DateTimeSchema::default();
+        assert_eq!(
+            raw.validate(&json!("2024-01-01T12:00:00+02:00")).unwrap(),
+            json!("2024-01-01T12:00:00+02:00")
+        );
+    }
+
+    #[test]
+    fn test_datetime_range() {
+        let min = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let schema = DateTimeSchema::default().min(min).past();
+
+        assert!(schema.validate(&json!("2024-06-01T00:00:00Z")).is_ok());
+        assert_eq!(
+            schema.validate(&json!("2023-12-31T23:59:59Z")).unwrap_err().context.code,
+            "datetime.too_early"
+        );
+        assert_eq!(
+            schema.validate(&json!("9999-01-01T00:00:00Z")).unwrap_err().context.code,
+            "datetime.not_past"
+        );
+    }
+}
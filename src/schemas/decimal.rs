@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::error::{ValidationError, ErrorCode, canonical_code};
+use crate::error::message_check::debug_check_template;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, Unsatisfiable, satisfiable, get_type_name};
+
+/// Arbitrary-precision decimal carried as a numeric string, e.g. `"19.9900"`
+///
+/// The string is returned as given, so surrounding whitespace is rejected
+/// rather than carried into the output.
+#[derive(Clone, Default)]
+pub struct DecimalSchema {
+    max_precision: Option<u32>,
+    max_scale: Option<u32>,
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+    coerce: bool,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl DecimalSchema {
+    /// Maximum number of significant digits (integer and fractional part)
+    pub fn max_precision(mut self, digits: u32) -> Self {
+        self.max_precision = Some(digits);
+        self
+    }
+
+    /// Maximum number of digits after the decimal point
+    pub fn max_scale(mut self, digits: u32) -> Self {
+        self.max_scale = Some(digits);
+        self
+    }
+
+    pub fn min(mut self, value: Decimal) -> Self {
+        self.min = Some(value);
+        self
+    }
+
+    pub fn max(mut self, value: Decimal) -> Self {
+        self.max = Some(value);
+        self
+    }
+
+    /// Also accept JSON numbers, using their textual representation
+    pub fn coerce(mut self) -> Self {
+        self.coerce = true;
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::DecimalRequired, message)
+    }
+
+    fn error(&self, code: ErrorCode) -> ValidationError {
+        let custom = self.error_messages.get(code.code());
+        ValidationError::new(code).message_or_default(custom)
+    }
+
+    fn out_of_bounds(&self, code: ErrorCode, expected: impl ToString) -> ValidationError {
+        self.error(code).with_details(|d| d.expected = Some(expected.to_string()))
+    }
+
+    fn check(&self, text: &str) -> Result<(), ValidationError> {
+        let decimal = Decimal::from_str_exact(text)
+            .map_err(|_| self.error(ErrorCode::InvalidDecimal).with_details(|d| d.received = Some(Value::String(text.to_string()))))?;
+
+        if let Some(max_scale) = self.max_scale {
+            if decimal.scale() > max_scale {
+                return Err(self.out_of_bounds(ErrorCode::DecimalScale, max_scale));
+            }
+        }
+
+        if let Some(max_precision) = self.max_precision {
+            let digits = decimal.mantissa().unsigned_abs().to_string().len() as u32;
+            if digits > max_precision {
+                return Err(self.out_of_bounds(ErrorCode::DecimalPrecision, max_precision));
+            }
+        }
+
+        if let Some(min) = self.min {
+            if decimal < min {
+                return Err(self.out_of_bounds(ErrorCode::DecimalTooSmall, min));
+            }
+        }
+
+        if let Some(max) = self.max {
+            if decimal > max {
+                return Err(self.out_of_bounds(ErrorCode::DecimalTooLarge, max));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl HasErrorMessages for DecimalSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+}
+
+impl Schema for DecimalSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("decimal.required").cloned()
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        satisfiable::check_bounds("value", self.min, self.max).into_iter().collect()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("decimal string", self.presence);
+        if self.coerce {
+            explanation.rule("numbers accepted");
+        }
+        if let Some(digits) = self.max_precision {
+            explanation.rule(format!("at most {} digits", digits));
+        }
+        if let Some(digits) = self.max_scale {
+            explanation.rule(format!("at most {} decimal places", digits));
+        }
+        if let Some(min) = self.min {
+            explanation.rule(format!("at least {}", min));
+        }
+        if let Some(max) = self.max {
+            explanation.rule(format!("at most {}", max));
+        }
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
+            Value::Null => Err(self.error(ErrorCode::DecimalRequired)),
+            Value::String(s) => self.check(s).map(|_| value.clone()),
+            Value::Number(n) if self.coerce => {
+                let text = n.to_string();
+                self.check(&text).map(|_| Value::String(text))
+            }
+            _ => Err(self.error(ErrorCode::DecimalInvalidType).with_type_info("decimal", get_type_name(value))),
+        }
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Decimal(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decimal_precision_and_scale() {
+        let schema = DecimalSchema::default().max_precision(6).max_scale(4);
+
+        assert_eq!(schema.validate(&json!("19.9900")).unwrap(), json!("19.9900"));
+        assert_eq!(schema.validate(&json!("19.99001")).unwrap_err().context.code, "decimal.scale");
+        assert_eq!(schema.validate(&json!("1234.567")).unwrap_err().context.code, "decimal.precision");
+        assert_eq!(schema.validate(&json!("12,5")).unwrap_err().context.code, "decimal.invalid");
+        assert_eq!(schema.validate(&json!(" 1.50 ")).unwrap_err().context.code, "decimal.invalid");
+    }
+
+    #[test]
+    fn test_decimal_range() {
+        let schema = DecimalSchema::default()
+            .min(Decimal::new(1, 2))
+            .max(Decimal::new(100, 0));
+
+        assert!(schema.validate(&json!("0.01")).is_ok());
+        assert_eq!(schema.validate(&json!("0.009")).unwrap_err().context.code, "decimal.too_small");
+        assert_eq!(schema.validate(&json!("100.0001")).unwrap_err().context.code, "decimal.too_large");
+    }
+
+    #[test]
+    fn test_decimal_type() {
+        let schema = DecimalSchema::default();
+        assert_eq!(schema.validate(&json!(1.5)).unwrap_err().context.code, "decimal.invalid_type");
+        assert_eq!(schema.validate(&json!(null)).unwrap_err().context.code, "decimal.required");
+
+        let schema = DecimalSchema::default().coerce();
+        assert_eq!(schema.validate(&json!(12)).unwrap(), json!("12"));
+    }
+}
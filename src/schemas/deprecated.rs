@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{ErrorCode, MessageIssue, ValidationError};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput, warnings};
+
+/// A schema whose values are still accepted but reported as deprecated
+///
+/// Created with [`Schema::deprecated`]. A present, non-null value raises a
+/// `deprecated` warning in [`Schema::validate_with_report`]; plain
+/// validation is unaffected.
+#[derive(Clone)]
+pub struct Deprecated<S> {
+    pub schema: S,
+    pub message: String,
+}
+
+impl<S> Deprecated<S> {
+    pub fn new(schema: S, message: impl Into<String>) -> Self {
+        Self { schema, message: message.into() }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+
+    fn warn(&self, value: &Value) {
+        if !value.is_null() {
+            warnings::warn(ValidationError::new(ErrorCode::Deprecated).custom_message(self.message.clone()));
+        }
+    }
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for Deprecated<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for Deprecated<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<super::Explanation> {
+        super::explain::with_rules(self.schema.explain(), [format!("deprecated: {}", self.message)])
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        let output = self.schema.validate(value)?;
+        self.warn(value);
+        Ok(output)
+    }
+
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        self.schema.validate_ref(value)?;
+        self.warn(value);
+        Ok(())
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.schema.preserves_input()
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let output = self.schema.validate_partial_with(value, invalid);
+        self.warn(value);
+        output
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<super::LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Deprecated(Box::new(Deprecated::new(self.schema.into_schema_type(), self.message)))
+    }
+}
@@ -0,0 +1,138 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::error::{ParseError, ValidationError};
+use super::{ObjectSchema, Schema, SchemaType, string_values};
+
+impl ObjectSchema {
+    /// Validate the process environment, e.g. `APP_DB__PORT=5432` as
+    /// `{"db": {"port": 5432}}` with the prefix `APP_`
+    ///
+    /// Variables are bound to fields by the name after `prefix`, ignoring
+    /// case, with `__` separating nested object fields. Values become
+    /// numbers or booleans where the field expects one; array fields split
+    /// them on commas. Variables without a field are ignored.
+    pub fn validate_env_with_prefix(&self, prefix: &str) -> Result<Value, ValidationError> {
+        self.validate_env_vars(prefix, process_env())
+    }
+
+    /// [`ObjectSchema::validate_env_with_prefix`] over `vars` rather than
+    /// the process environment, e.g. a `.env` file's entries
+    pub fn validate_env_vars<K, V>(&self, prefix: &str, vars: impl IntoIterator<Item = (K, V)>) -> Result<Value, ValidationError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.validate(&self.env_value(prefix, vars))
+    }
+
+    /// Validate the process environment as [`ObjectSchema::validate_env_with_prefix`]
+    /// does and deserialize the output into a config struct
+    pub fn parse_env_with_prefix<T>(&self, prefix: &str) -> Result<T, ParseError>
+    where
+        T: DeserializeOwned,
+    {
+        let output = self.validate_env_with_prefix(prefix)?;
+        serde_json::from_value(output).map_err(|e| ParseError::Parse(format!("Failed to parse environment: {}", e)))
+    }
+
+    fn env_value<K, V>(&self, prefix: &str, vars: impl IntoIterator<Item = (K, V)>) -> Value
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut obj = Map::new();
+        for (name, value) in vars {
+            if let Some(name) = name.as_ref().strip_prefix(prefix) {
+                let path: Vec<&str> = name.split("__").collect();
+                insert_env(self, &mut obj, &path, value.as_ref());
+            }
+        }
+        Value::Object(obj)
+    }
+}
+
+/// Variables of the process environment, skipping any that are not UTF-8
+fn process_env() -> impl Iterator<Item = (String, String)> {
+    std::env::vars_os().filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+}
+
+fn insert_env(schema: &ObjectSchema, obj: &mut Map<String, Value>, path: &[&str], raw: &str) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    let Some((field, field_schema)) = schema.field_ignoring_case(first) else {
+        return;
+    };
+    if rest.is_empty() {
+        obj.insert(field.to_string(), string_values::from_strings(field_schema, &[raw.to_string()]));
+        return;
+    }
+    let SchemaType::Object(nested) = string_values::value_schema(field_schema) else {
+        return;
+    };
+    if let Value::Object(child) = obj.entry(field).or_insert_with(|| Value::Object(Map::new())) {
+        insert_env(nested, child, rest, raw);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+    use crate::{array, boolean, number, object, string};
+    use crate::schemas::StringSchema;
+
+    fn config() -> crate::ObjectSchema {
+        object()
+            .field("name", string().min_length(1))
+            .optional_field("debug", boolean())
+            .optional_field("hosts", array(string()))
+            .field("db", object().field("host", string()).field("port", number().integer()))
+    }
+
+    #[test]
+    fn test_validate_env_vars() {
+        let vars = [
+            ("APP_NAME", "api"),
+            ("APP_DEBUG", "true"),
+            ("APP_HOSTS", "a.example.com, b.example.com"),
+            ("APP_DB__HOST", "localhost"),
+            ("APP_DB__PORT", "5432"),
+            ("APP_UNKNOWN", "x"),
+            ("HOME", "/root"),
+        ];
+        let output = config().validate_env_vars("APP_", vars).unwrap();
+        assert_eq!(output, json!({
+            "name": "api",
+            "debug": true,
+            "hosts": ["a.example.com", "b.example.com"],
+            "db": {"host": "localhost", "port": 5432},
+        }));
+
+        let err = config().validate_env_vars("APP_", [("APP_NAME", "api"), ("APP_DB__HOST", "h"), ("APP_DB__PORT", "x")]).unwrap_err();
+        assert_eq!(err.context.path, "db.port");
+        let err = config().validate_env_vars("APP_", [("APP_NAME", "api")]).unwrap_err();
+        assert_eq!(err.context.code, "object.required");
+        assert_eq!(err.context.path, "db");
+    }
+
+    #[test]
+    fn test_env_vars_into_config() {
+        #[derive(Deserialize)]
+        struct Db {
+            host: String,
+            port: u16,
+        }
+        #[derive(Deserialize)]
+        struct Config {
+            name: String,
+            db: Db,
+        }
+
+        let vars = [("APP_NAME", "api"), ("APP_DB__HOST", "localhost"), ("APP_DB__PORT", "5432")];
+        let output = config().validate_env_vars("APP_", vars).unwrap();
+        let config: Config = serde_json::from_value(output).unwrap();
+        assert_eq!((config.name.as_str(), config.db.host.as_str(), config.db.port), ("api", "localhost", 5432));
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use crate::error::{CustomIssue, ErrorCode};
+use super::{Schema, SchemaType, NumberSchema, ObjectSchema, LiteralSchema, claims::unix_now, string::{StringSchema, StringSchemaImpl}};
+
+/// Options for [`envelope_with`](crate::envelope_with)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvelopePolicy {
+    /// Unix time to check `timestamp` against; the system clock at
+    /// validation when `None`
+    pub now: Option<i64>,
+    /// Seconds `timestamp` may lie before or after `now`, five minutes by
+    /// default
+    pub max_skew: i64,
+}
+
+impl Default for EnvelopePolicy {
+    fn default() -> Self {
+        Self { now: None, max_skew: 300 }
+    }
+}
+
+impl EnvelopePolicy {
+    /// Object schema for `{ "id": ..., "timestamp": ..., "type": ..., "data": ... }`
+    /// whose `data` is validated by the schema `payloads` lists for its `type`
+    pub(crate) fn schema<K, S>(self, payloads: impl IntoIterator<Item = (K, S)>) -> ObjectSchema
+    where
+        K: Into<String>,
+        S: Schema,
+    {
+        let payloads: Vec<(String, SchemaType)> = payloads
+            .into_iter()
+            .map(|(kind, schema)| (kind.into(), schema.into_schema_type()))
+            .collect();
+        let kinds: Arc<Vec<String>> = Arc::new(payloads.iter().map(|(kind, _)| kind.clone()).collect());
+        let kind = StringSchemaImpl::default().custom(move |kind| {
+            if !kinds.iter().any(|k| k == kind) {
+                return Err(CustomIssue::from(ErrorCode::WebhookType));
+            }
+            Ok(())
+        });
+        let timestamp = NumberSchema::default().integer64().custom(move |timestamp| {
+            let now = self.now.unwrap_or_else(unix_now) as f64;
+            if (timestamp - now).abs() > self.max_skew as f64 {
+                return Err(CustomIssue::from(ErrorCode::WebhookTimestamp));
+            }
+            Ok(())
+        });
+
+        let mut schema = ObjectSchema::default()
+            .field("id", StringSchemaImpl::default().min_length(1))
+            .field("timestamp", timestamp)
+            .field("type", kind);
+        for (kind, payload) in payloads {
+            schema = schema.when("type", LiteralSchema::new(kind), |s| s.field("data", payload));
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{envelope, envelope_with, number, object, string};
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn events() -> Vec<(&'static str, SchemaType)> {
+        vec![
+            ("user.created", object().field("email", string().email()).into_schema_type()),
+            ("invoice.paid", object().field("amount", number().min(0.0)).into_schema_type()),
+        ]
+    }
+
+    #[test]
+    fn test_envelope_selects_payload_by_type() {
+        let schema = envelope_with(events(), EnvelopePolicy { now: Some(NOW), ..EnvelopePolicy::default() });
+        let event = json!({"id": "evt_1", "timestamp": NOW - 60, "type": "invoice.paid", "data": {"amount": 12}});
+        assert_eq!(schema.validate(&event).unwrap(), event);
+        assert!(schema.validate(&json!({
+            "id": "evt_2", "timestamp": NOW, "type": "user.created", "data": {"email": "ada@example.com"},
+        })).is_ok());
+
+        let err = schema.validate(&json!({
+            "id": "evt_3", "timestamp": NOW, "type": "user.created", "data": {"amount": 12},
+        })).unwrap_err();
+        assert_eq!(err.context.path, "data.email");
+        let err = schema.validate(&json!({"id": "evt_4", "timestamp": NOW, "type": "user.deleted", "data": {}})).unwrap_err();
+        assert_eq!(err.context.code, "webhook.type");
+        assert_eq!(err.to_string(), "Unknown event type");
+        assert!(schema.validate(&json!({"id": "evt_5", "timestamp": NOW, "type": "invoice.paid"})).is_err());
+    }
+
+    #[test]
+    fn test_envelope_timestamp_freshness() {
+        let schema = envelope_with(events(), EnvelopePolicy { now: Some(NOW), max_skew: 60 });
+        let event = |timestamp: i64| json!({"id": "evt_1", "timestamp": timestamp, "type": "invoice.paid", "data": {"amount": 1}});
+        assert!(schema.validate(&event(NOW + 60)).is_ok());
+        let err = schema.validate(&event(NOW - 61)).unwrap_err();
+        assert_eq!(err.context.code, "webhook.timestamp");
+        assert_eq!(err.context.path, "timestamp");
+        assert!(schema.validate(&event(NOW + 61)).is_err());
+
+        // The preset checks against the system clock
+        assert!(envelope(events()).validate(&event(unix_now())).is_ok());
+        assert!(envelope(events()).validate(&event(NOW)).is_err());
+    }
+}
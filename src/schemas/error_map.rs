@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{ErrorMap, MessageIssue, ValidationError};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput};
+
+/// A schema whose built-in error messages, including those of everything
+/// nested inside it, are rewritten by an [`ErrorMap`]
+///
+/// Created with [`Schema::error_map`]. The map closest to where an error
+/// is raised wins, and the global map from
+/// [`set_error_map`](crate::error::set_error_map) only sees what no
+/// per-schema map replaced.
+#[derive(Clone)]
+pub struct ErrorMapped<S> {
+    pub schema: S,
+    pub map: ErrorMap,
+}
+
+impl<S> ErrorMapped<S> {
+    pub fn new(schema: S, map: ErrorMap) -> Self {
+        Self { schema, map }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for ErrorMapped<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for ErrorMapped<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<super::Explanation> {
+        self.schema.explain()
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        self.schema.validate(value).map_err(|mut err| {
+            self.map.apply(&mut err);
+            err
+        })
+    }
+
+    fn validate_owned(&self, value: Value) -> Result<Value, ValidationError> {
+        self.schema.validate_owned(value).map_err(|mut err| {
+            self.map.apply(&mut err);
+            err
+        })
+    }
+
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        self.schema.validate_ref(value).map_err(|mut err| {
+            self.map.apply(&mut err);
+            err
+        })
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.schema.preserves_input()
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let mut output = self.schema.validate_partial_with(value, invalid);
+        for err in &mut output.errors {
+            self.map.apply(err);
+        }
+        output
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<super::LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::ErrorMapped(Box::new(ErrorMapped::new(self.schema.into_schema_type(), self.map)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::error::{clear_error_map, set_error_map, ErrorCode};
+    use crate::{string, number, object, StringSchema};
+
+    #[test]
+    fn test_error_map_replaces_default_messages() {
+        let schema = object()
+            .field("name", string().min_length(3))
+            .field("age", number().min(0.0).error_message("number.too_small", "Age can't be negative"))
+            .error_map(|code, _| match code {
+                ErrorCode::StringTooShort => Some("Need {min_length}+ characters".to_string()),
+                ErrorCode::NumberTooSmall => Some("Too small".to_string()),
+                _ => None,
+            });
+
+        let err = schema.validate(&json!({"name": "al", "age": 1})).unwrap_err();
+        assert_eq!(err.to_string(), "Need 3+ characters");
+
+        let err = schema.validate(&json!({"name": "alice", "age": -1})).unwrap_err();
+        assert_eq!(err.to_string(), "Age can't be negative");
+
+        let err = schema.validate(&json!("nope")).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+    }
+
+    #[test]
+    fn test_innermost_error_map_wins() {
+        let schema = object()
+            .field("tag", string().max_length(2).error_map(|_, _| Some("inner".to_string())))
+            .field("code", string().max_length(2))
+            .error_map(|_, ctx| Some(format!("outer at {}", ctx.path)));
+
+        let errors = schema.safe_parse::<Value>(&json!({"tag": "abc", "code": "abc"})).issues().to_vec();
+        let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(messages, ["inner", "outer at code"]);
+    }
+
+    #[test]
+    fn test_global_error_map() {
+        set_error_map(|code, ctx| {
+            (*code == ErrorCode::StringRequired && ctx.path == "global_map_probe").then(|| "Please fill this in".to_string())
+        });
+        let schema = object().field("global_map_probe", string()).field("other", string().optional());
+        let err = schema.validate(&json!({"global_map_probe": null})).unwrap_err();
+        let mapped = err.to_string();
+
+        let local = object().field("global_map_probe", string().error_map(|_, _| Some("Local".to_string())));
+        let local_err = local.validate(&json!({"global_map_probe": null})).unwrap_err();
+        clear_error_map();
+
+        assert_eq!(mapped, "Please fill this in");
+        assert_eq!(local_err.to_string(), "Local");
+    }
+}
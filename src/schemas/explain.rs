@@ -0,0 +1,129 @@
+use std::fmt;
+
+use super::Presence;
+
+/// What a schema enforces on the values at one path, listed by
+/// [`Schema::explain`](super::Schema::explain)
+///
+/// Displays as `email: string, required, trimmed, must be a valid email`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// Path of the value (empty for the root, `*` for array items)
+    pub path: String,
+    /// Rules in plain words, the type first and the rest in the order
+    /// they are applied
+    pub rules: Vec<String>,
+}
+
+impl Explanation {
+    /// Rules of the root value, starting with its type and whether it may be null
+    pub(crate) fn typed(type_name: impl Into<String>, presence: Presence) -> Self {
+        let mut rules = vec![type_name.into()];
+        if presence.allows_null() {
+            rules.push("nullable".to_string());
+        }
+        Self { path: String::new(), rules }
+    }
+
+    pub(crate) fn rule(&mut self, rule: impl Into<String>) {
+        self.rules.push(rule.into());
+    }
+
+    /// The rules joined into one phrase, without the path
+    pub(crate) fn summary(&self) -> String {
+        self.rules.join(", ")
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "(root)" } else { &self.path };
+        write!(f, "{}: {}", path, self.summary())
+    }
+}
+
+/// Prefix the path of nested explanations with `segment`
+pub(crate) fn nest_explanations(segment: &str, explanations: Vec<Explanation>) -> Vec<Explanation> {
+    explanations
+        .into_iter()
+        .map(|mut explanation| {
+            explanation.path = if explanation.path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}.{}", segment, explanation.path)
+            };
+            explanation
+        })
+        .collect()
+}
+
+/// Insert `rules` into the root explanation right after its type, as
+/// wrappers such as transforms and object fields describe the value first
+pub(crate) fn after_type(mut explanations: Vec<Explanation>, rules: impl IntoIterator<Item = String>) -> Vec<Explanation> {
+    match explanations.iter_mut().find(|e| e.path.is_empty()) {
+        Some(root) => {
+            let at = root.rules.len().min(1);
+            root.rules.splice(at..at, rules);
+        }
+        None => explanations.insert(0, Explanation { path: String::new(), rules: rules.into_iter().collect() }),
+    }
+    explanations
+}
+
+/// Append `rules` to the root explanation
+pub(crate) fn with_rules(mut explanations: Vec<Explanation>, rules: impl IntoIterator<Item = String>) -> Vec<Explanation> {
+    match explanations.iter_mut().find(|e| e.path.is_empty()) {
+        Some(root) => root.rules.extend(rules),
+        None => explanations.insert(0, Explanation { path: String::new(), rules: rules.into_iter().collect() }),
+    }
+    explanations
+}
+
+/// One-phrase summary of the root explanation, e.g. for union branches
+pub(crate) fn root_summary(explanations: &[Explanation]) -> String {
+    explanations
+        .iter()
+        .find(|e| e.path.is_empty())
+        .map(Explanation::summary)
+        .unwrap_or_else(|| "any value".to_string())
+}
+
+/// Count bounds in words, e.g. `at least 2 characters` for the `unit` `character`
+pub(crate) fn bounds(min: Option<usize>, max: Option<usize>, unit: &str) -> Vec<String> {
+    let count = |n: usize| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => vec![format!("exactly {}", count(min))],
+        (min, max) => min
+            .map(|min| format!("at least {}", count(min)))
+            .into_iter()
+            .chain(max.map(|max| format!("at most {}", count(max))))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{array, boolean, eq, number, object, string};
+    use crate::schemas::{Schema, StringSchema};
+
+    #[test]
+    fn test_explain() {
+        let schema = object()
+            .field("email", string().email().to_lowercase())
+            .optional_field("tags", array(string().max_length(20)).min_items(1))
+            .default_field("age", number().integer().min(18.0), 18)
+            .example_for_field("email", "ada@example.com")
+            .field("business", boolean())
+            .when("business", eq(true), |s| s.field("company", string().min_length(1)));
+        let lines: Vec<_> = schema.explain().iter().map(ToString::to_string).collect();
+        assert_eq!(lines, [
+            "(root): object, no unknown fields",
+            "email: string, required, e.g. \"ada@example.com\", trimmed, lowercased, must be a valid email",
+            "tags: array, optional, at least 1 item",
+            "tags.*: string, at most 20 characters",
+            "age: integer, optional, defaults to 18, at least 18",
+            "business: boolean, required",
+            "company: string, required, when `business` is exactly true, at least 1 character",
+        ]);
+    }
+}
@@ -0,0 +1,48 @@
+use super::{Schema, SchemaType};
+
+/// A named bundle of field definitions shared by several object schemas,
+/// e.g. audit timestamps or pagination parameters
+///
+/// Mixed in with [`ObjectSchema::include`](super::ObjectSchema::include);
+/// the set is borrowed, so one definition can serve any number of schemas.
+#[derive(Clone)]
+pub struct FieldSet {
+    name: String,
+    fields: Vec<(String, SchemaType, bool)>,
+}
+
+impl FieldSet {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), fields: Vec::new() }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn field(mut self, name: &str, schema: impl Schema) -> Self {
+        self.fields.push((name.to_string(), schema.into_schema_type(), true));
+        self
+    }
+
+    pub fn optional_field(mut self, name: &str, schema: impl Schema) -> Self {
+        self.fields.push((name.to_string(), schema.into_schema_type(), false));
+        self
+    }
+
+    /// Add every field of `other`, e.g. to compose larger sets
+    pub fn include(mut self, other: &FieldSet) -> Self {
+        self.fields.extend(other.fields.iter().cloned());
+        self
+    }
+
+    /// Field names in declaration order
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|(name, _, _)| name.as_str())
+    }
+
+    /// Fields as `(name, schema, required)`
+    pub(crate) fn fields(&self) -> &[(String, SchemaType, bool)] {
+        &self.fields
+    }
+}
@@ -0,0 +1,80 @@
+use http::HeaderMap;
+use serde_json::{Map, Value};
+
+use crate::error::ValidationError;
+use super::{ObjectSchema, Schema, string_values};
+
+impl ObjectSchema {
+    /// Validate request headers, each bound to the field of the same name
+    /// ignoring case (`X-Request-Id` to `x-request-id`)
+    ///
+    /// Header values are strings; they become numbers or booleans where the
+    /// field expects one. Array fields take every comma-separated value of
+    /// every occurrence of their header, other fields the occurrences joined
+    /// by `", "`. Headers without a field are ignored.
+    pub fn validate_headers(&self, headers: &HeaderMap) -> Result<Value, ValidationError> {
+        self.validate(&self.headers_value(headers))
+    }
+
+    fn headers_value(&self, headers: &HeaderMap) -> Value {
+        let mut obj = Map::new();
+        for name in headers.keys() {
+            let Some((field, schema)) = self.field_ignoring_case(name.as_str()) else {
+                continue;
+            };
+            let values: Vec<String> = headers
+                .get_all(name)
+                .iter()
+                .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+                .collect();
+            obj.insert(field.to_string(), string_values::from_strings(schema, &values));
+        }
+        Value::Object(obj)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{HeaderMap, HeaderValue};
+    use serde_json::json;
+    use crate::{array, boolean, number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[test]
+    fn test_validate_headers() {
+        let schema = object()
+            .field("X-Request-Id", string().min_length(8))
+            .optional_field("x-retry-count", number().integer().max(5.0))
+            .optional_field("x-debug", boolean())
+            .optional_field("accept-language", array(string()))
+            .optional_field("cache-control", string());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("abcd1234"));
+        headers.insert("x-retry-count", HeaderValue::from_static("2"));
+        headers.insert("x-debug", HeaderValue::from_static("TRUE"));
+        headers.append("accept-language", HeaderValue::from_static("en-US, fr"));
+        headers.append("accept-language", HeaderValue::from_static("de"));
+        headers.append("cache-control", HeaderValue::from_static("no-cache"));
+        headers.append("cache-control", HeaderValue::from_static("no-store"));
+        headers.insert("user-agent", HeaderValue::from_static("curl/8.0"));
+
+        let output = schema.validate_headers(&headers).unwrap();
+        assert_eq!(output, json!({
+            "X-Request-Id": "abcd1234",
+            "x-retry-count": 2,
+            "x-debug": true,
+            "accept-language": ["en-US", "fr", "de"],
+            "cache-control": "no-cache, no-store",
+        }));
+
+        headers.insert("x-retry-count", HeaderValue::from_static("9"));
+        let err = schema.validate_headers(&headers).unwrap_err();
+        assert_eq!(err.context.path, "x-retry-count");
+
+        headers.remove("x-request-id");
+        let err = schema.validate_headers(&headers).unwrap_err();
+        assert_eq!(err.context.code, "object.required");
+        assert_eq!(err.context.path, "X-Request-Id");
+    }
+}
@@ -0,0 +1,106 @@
+use std::{cell::RefCell, sync::Arc};
+use serde_json::Value;
+
+use crate::error::{ErrorPath, PathSegment, ValidationError};
+
+/// Callback invoked with the path of a field and its validation outcome,
+/// registered with [`ObjectSchema::on_field`](super::ObjectSchema::on_field)
+pub type FieldHook = Arc<dyn Fn(&ErrorPath, &mut Result<Value, ValidationError>) + Send + Sync>;
+
+/// Hooks of the objects being validated on this thread, each with the
+/// depth of [`Active::path`] its object sits at
+#[derive(Clone, Default)]
+pub(crate) struct Active {
+    hooks: Vec<(usize, FieldHook)>,
+    path: Vec<PathSegment>,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Active> = RefCell::new(Active::default());
+}
+
+/// Restores the hooks or path that were active before, also when
+/// validation panics
+struct Restore {
+    hooks: usize,
+    path: usize,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        ACTIVE.with(|active| {
+            let mut active = active.borrow_mut();
+            active.hooks.truncate(self.hooks);
+            active.path.truncate(self.path);
+        });
+    }
+}
+
+fn restore_point() -> Restore {
+    ACTIVE.with(|active| {
+        let active = active.borrow();
+        Restore { hooks: active.hooks.len(), path: active.path.len() }
+    })
+}
+
+/// Whether an object being validated on this thread has hooks, so that
+/// objects below it must run them for their own fields
+pub(crate) fn is_active() -> bool {
+    ACTIVE.with(|active| !active.borrow().hooks.is_empty())
+}
+
+/// Run `f` with `hooks` applying to the fields of the object at the
+/// current path and of everything below it
+pub(crate) fn with<R>(hooks: &[FieldHook], f: impl FnOnce() -> R) -> R {
+    let _restore = restore_point();
+    ACTIVE.with(|active| {
+        let mut active = active.borrow_mut();
+        let depth = active.path.len();
+        active.hooks.extend(hooks.iter().map(|hook| (depth, Arc::clone(hook))));
+    });
+    f()
+}
+
+/// Validate a child value at `segment`, tracked only while hooks are active
+pub(crate) fn nested<R>(segment: impl Into<PathSegment>, f: impl FnOnce() -> R) -> R {
+    if !is_active() {
+        return f();
+    }
+    let _restore = restore_point();
+    ACTIVE.with(|active| active.borrow_mut().path.push(segment.into()));
+    f()
+}
+
+/// Pass the outcome of `field` to every active hook, each with the path of
+/// the field from the object that registered it
+pub(crate) fn run(field: &str, outcome: &mut Result<Value, ValidationError>) {
+    if !is_active() {
+        return;
+    }
+    let Active { hooks, path } = ACTIVE.with(|active| active.borrow().clone());
+    for (depth, hook) in hooks {
+        let mut field_path = ErrorPath::from(path[depth..].to_vec());
+        field_path.push(field);
+        hook(&field_path, outcome);
+    }
+}
+
+/// Hooks and path active on this thread, to install on worker threads
+pub(crate) fn current() -> Active {
+    ACTIVE.with(|active| active.borrow().clone())
+}
+
+/// Run `f` with `state` active in place of this thread's own, as on the
+/// thread it was taken from
+pub(crate) fn install<R>(state: &Active, f: impl FnOnce() -> R) -> R {
+    struct Swap(Active);
+
+    impl Drop for Swap {
+        fn drop(&mut self) {
+            ACTIVE.with(|active| *active.borrow_mut() = std::mem::take(&mut self.0));
+        }
+    }
+
+    let _swap = Swap(ACTIVE.with(|active| active.replace(state.clone())));
+    f()
+}
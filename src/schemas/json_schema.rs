@@ -0,0 +1,571 @@
+use std::fmt;
+use std::ops::Bound;
+use serde_json::{Map, Value};
+
+use crate::{any, array, boolean, conditional, eq, never, not, number, object, string};
+use super::{ObjectSchema, Presence, Schema, SchemaType, StringSchema, UnionSchema, UnionStrategy};
+
+/// Why a JSON Schema document could not be compiled, see
+/// [`SchemaType::from_json_schema`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonSchemaError {
+    /// A keyword holds a value of the wrong shape, e.g. a string `minimum`
+    Invalid { path: String, message: String },
+    /// A keyword this crate has no counterpart for, e.g. `patternProperties`
+    Unsupported { path: String, keyword: String },
+    /// A `$ref` that does not point into the document, or refers back to
+    /// a schema it is part of
+    Ref { path: String, reference: String },
+}
+
+impl fmt::Display for JsonSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonSchemaError::Invalid { path, message } => write!(f, "Invalid JSON Schema at '{}': {}", path, message),
+            JsonSchemaError::Unsupported { path, keyword } => {
+                write!(f, "Unsupported JSON Schema keyword '{}' at '{}'", keyword, path)
+            }
+            JsonSchemaError::Ref { path, reference } => write!(f, "Unresolvable $ref '{}' at '{}'", reference, path),
+        }
+    }
+}
+
+impl std::error::Error for JsonSchemaError {}
+
+/// Keywords that only describe a schema, skipped when compiling
+const ANNOTATIONS: &[&str] = &[
+    "$schema", "$id", "$comment", "$defs", "definitions", "title", "description", "default", "examples",
+    "deprecated", "readOnly", "writeOnly", "format",
+];
+
+/// Keywords compiled by [`Compiler::compile`] and [`Compiler::typed`]
+const KEYWORDS: &[&str] = &[
+    "$ref", "type", "const", "enum", "anyOf", "oneOf", "allOf", "not",
+    "minLength", "maxLength", "pattern",
+    "minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum", "multipleOf",
+    "items", "minItems", "maxItems", "uniqueItems",
+    "properties", "required", "additionalProperties",
+];
+
+/// Keywords that constrain only instances of one type, applied to that
+/// type alone when a node has no `type`
+const TYPE_KEYWORDS: &[(&str, &[&str])] = &[
+    ("string", &["minLength", "maxLength", "pattern"]),
+    ("number", &["minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum", "multipleOf"]),
+    ("array", &["items", "minItems", "maxItems", "uniqueItems"]),
+    ("object", &["properties", "required", "additionalProperties"]),
+];
+
+impl SchemaType {
+    /// Compile a JSON Schema document into the equivalent schema, so that
+    /// externally authored schemas can be enforced
+    ///
+    /// Supports `type` (a list including `"null"` makes the schema
+    /// nullable), `enum`, `const`, `anyOf`, `allOf`, `not`, `$ref` to
+    /// JSON Pointers within the document, and the bounds of strings,
+    /// numbers, arrays and objects. `oneOf` fails values matching more than
+    /// one of its schemas as `union.ambiguous`.
+    /// Formats `email`, `uri` (any absolute URI, such as `urn:` or
+    /// `mailto:`), `url` (web URLs only), `uuid` and `ipv4` are checked,
+    /// other formats are annotations. String lengths count characters. Properties left
+    /// out of `required` may be missing, and unlisted properties pass
+    /// through unless `additionalProperties` is `false`. Without a `type`,
+    /// keywords such as `minimum` or `properties` constrain only values of
+    /// their type, and keywords next to a `$ref` apply on top of it.
+    ///
+    /// Keywords that constrain values but have no counterpart here, such as
+    /// `patternProperties` or `if`, fail as [`JsonSchemaError::Unsupported`]
+    /// rather than being ignored, as are recursive `$ref`s.
+    ///
+    /// ```
+    /// use rusty_zod::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let schema = SchemaType::from_json_schema(&json!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "email": {"type": "string", "format": "email"},
+    ///         "age": {"type": "integer", "minimum": 0}
+    ///     },
+    ///     "required": ["email"]
+    /// }))
+    /// .unwrap();
+    /// assert!(schema.validate(&json!({"email": "ada@example.com"})).is_ok());
+    /// assert!(schema.validate(&json!({"email": "ada@example.com", "age": -1})).is_err());
+    /// ```
+    pub fn from_json_schema(document: &Value) -> Result<SchemaType, JsonSchemaError> {
+        Compiler { root: document, resolving: Vec::new() }.compile(document, "")
+    }
+}
+
+struct Compiler<'a> {
+    root: &'a Value,
+    /// `$ref`s being compiled, to reject cycles
+    resolving: Vec<String>,
+}
+
+impl Compiler<'_> {
+    fn compile(&mut self, node: &Value, path: &str) -> Result<SchemaType, JsonSchemaError> {
+        let obj = match node {
+            Value::Bool(true) => return Ok(any().into_schema_type()),
+            Value::Bool(false) => return Ok(never().into_schema_type()),
+            Value::Object(obj) => obj,
+            _ => return Err(invalid(path, "a schema must be an object or a boolean")),
+        };
+        if let Some(keyword) = obj.keys().find(|k| !KEYWORDS.contains(&k.as_str()) && !ANNOTATIONS.contains(&k.as_str())) {
+            return Err(JsonSchemaError::Unsupported { path: path.to_string(), keyword: keyword.clone() });
+        }
+
+        // Every part must hold, as an intersection
+        let mut parts = Vec::new();
+        if let Some(reference) = obj.get("$ref") {
+            parts.push(self.resolve(reference, path)?);
+        }
+        if let Some(value) = obj.get("const") {
+            parts.push(eq(value.clone()).numeric().into_schema_type());
+        }
+        if let Some(values) = obj.get("enum") {
+            let values = values.as_array().ok_or_else(|| invalid(&child(path, "enum"), "must be an array"))?;
+            let literals = values.iter().map(|v| eq(v.clone()).numeric().into_schema_type()).collect();
+            parts.push(one_of(literals));
+        }
+        parts.extend(self.typed(obj, path)?);
+        if let Some(schemas) = obj.get("anyOf") {
+            parts.push(one_of(self.compile_all(schemas, &child(path, "anyOf"))?));
+        }
+        if let Some(schemas) = obj.get("oneOf") {
+            let schemas = self.compile_all(schemas, &child(path, "oneOf"))?;
+            parts.push(UnionSchema::new(schemas).strategy(UnionStrategy::ExactlyOne).into_schema_type());
+        }
+        if let Some(schemas) = obj.get("allOf") {
+            parts.extend(self.compile_all(schemas, &child(path, "allOf"))?);
+        }
+        if let Some(schema) = obj.get("not") {
+            parts.push(not(self.compile(schema, &child(path, "not"))?).into_schema_type());
+        }
+
+        Ok(match parts.len() {
+            0 => any().into_schema_type(),
+            1 => parts.remove(0),
+            _ => UnionSchema::new(parts).strategy(UnionStrategy::All).into_schema_type(),
+        })
+    }
+
+    fn compile_all(&mut self, schemas: &Value, path: &str) -> Result<Vec<SchemaType>, JsonSchemaError> {
+        let schemas = schemas.as_array().ok_or_else(|| invalid(path, "must be an array of schemas"))?;
+        schemas.iter().enumerate().map(|(i, schema)| self.compile(schema, &child(path, &i.to_string()))).collect()
+    }
+
+    fn resolve(&mut self, reference: &Value, path: &str) -> Result<SchemaType, JsonSchemaError> {
+        let reference = reference.as_str().ok_or_else(|| invalid(&child(path, "$ref"), "must be a string"))?;
+        let unresolvable = || JsonSchemaError::Ref { path: path.to_string(), reference: reference.to_string() };
+        let pointer = reference.strip_prefix('#').ok_or_else(unresolvable)?;
+        let target = self.root.pointer(pointer).ok_or_else(unresolvable)?;
+        if self.resolving.iter().any(|r| r == reference) {
+            return Err(unresolvable());
+        }
+        self.resolving.push(reference.to_string());
+        let result = self.compile(target, pointer);
+        self.resolving.pop();
+        result
+    }
+
+    /// Schemas for the `type` keyword and the bounds that go with it, or
+    /// without a type, for the bounds of each type the node constrains
+    fn typed(&mut self, obj: &Map<String, Value>, path: &str) -> Result<Vec<SchemaType>, JsonSchemaError> {
+        let types: Vec<&str> = match obj.get("type") {
+            Some(Value::String(t)) => vec![t],
+            Some(Value::Array(ts)) => ts
+                .iter()
+                .map(|t| t.as_str().ok_or_else(|| invalid(&child(path, "type"), "must list type names")))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid(&child(path, "type"), "must be a type name or a list of them")),
+            None => return self.untyped(obj, path),
+        };
+        let nullable = types.contains(&"null");
+        let presence = if nullable { Presence::Nullable } else { Presence::Required };
+        let mut schemas = types
+            .iter()
+            .filter(|t| **t != "null")
+            .map(|t| self.of_type(t, obj, path, presence))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(vec![match schemas.len() {
+            0 => eq(Value::Null).into_schema_type(),
+            1 => schemas.remove(0),
+            _ => {
+                if nullable {
+                    schemas.push(eq(Value::Null).into_schema_type());
+                }
+                one_of(schemas)
+            }
+        }])
+    }
+
+    /// Bounds of a node without a `type`, each checked only on values of
+    /// its own type, so that `{"minimum": 0}` still accepts `"x"`
+    fn untyped(&mut self, obj: &Map<String, Value>, path: &str) -> Result<Vec<SchemaType>, JsonSchemaError> {
+        let mut checks = Vec::new();
+        for (t, keywords) in TYPE_KEYWORDS {
+            let constrained = keywords.iter().any(|k| obj.contains_key(*k)) || (*t == "string" && checked_format(obj).is_some());
+            if !constrained {
+                continue;
+            }
+            let typed = self.of_type(t, obj, path, Presence::Required)?;
+            let check = match *t {
+                "string" => conditional(string(), typed, any()),
+                "number" => conditional(number(), typed, any()),
+                "array" => conditional(array(any()), typed, any()),
+                _ => conditional(object().passthrough(), typed, any()),
+            };
+            checks.push(check.into_schema_type());
+        }
+        Ok(checks)
+    }
+
+    fn of_type(&mut self, t: &str, obj: &Map<String, Value>, path: &str, presence: Presence) -> Result<SchemaType, JsonSchemaError> {
+        let schema = match t {
+            "string" => {
+                let mut s = string().count_chars();
+                if let Some(min) = count(obj, "minLength", path)? {
+                    s.set_min_length(min);
+                }
+                if let Some(max) = count(obj, "maxLength", path)? {
+                    s.set_max_length(max);
+                }
+                if let Some(pattern) = obj.get("pattern") {
+                    let pattern = pattern.as_str().ok_or_else(|| invalid(&child(path, "pattern"), "must be a string"))?;
+                    regex::Regex::new(pattern).map_err(|e| invalid(&child(path, "pattern"), &e.to_string()))?;
+                    s.set_pattern(pattern);
+                }
+                s = match checked_format(obj) {
+                    Some("email") => s.email(),
+                    Some("uri") => s.uri(),
+                    Some("url") => s.url(),
+                    Some("uuid") => s.uuid(),
+                    Some("ipv4") => s.ip(),
+                    _ => s,
+                };
+                s.set_presence(presence);
+                s.into_schema_type()
+            }
+            "number" | "integer" => {
+                let mut n = number().presence(presence);
+                if t == "integer" {
+                    n = n.integer();
+                }
+                let lower = tightest(bound(obj, "minimum", path)?, bound(obj, "exclusiveMinimum", path)?, true);
+                let upper = tightest(bound(obj, "maximum", path)?, bound(obj, "exclusiveMaximum", path)?, false);
+                match lower {
+                    Bound::Included(min) => n = n.min(min),
+                    Bound::Excluded(min) => n = n.gt(min),
+                    Bound::Unbounded => {}
+                }
+                match upper {
+                    Bound::Included(max) => n = n.max(max),
+                    Bound::Excluded(max) => n = n.lt(max),
+                    Bound::Unbounded => {}
+                }
+                if let Some(step) = bound(obj, "multipleOf", path)? {
+                    n = n.multiple_of(step);
+                }
+                n.into_schema_type()
+            }
+            "boolean" => boolean().presence(presence).into_schema_type(),
+            "array" => {
+                let items = match obj.get("items") {
+                    Some(Value::Array(_)) => {
+                        return Err(JsonSchemaError::Unsupported { path: child(path, "items"), keyword: "items".to_string() })
+                    }
+                    Some(items) => self.compile(items, &child(path, "items"))?,
+                    None => any().into_schema_type(),
+                };
+                let mut a = array(items).presence(presence);
+                if let Some(min) = count(obj, "minItems", path)? {
+                    a = a.min_items(min);
+                }
+                if let Some(max) = count(obj, "maxItems", path)? {
+                    a = a.max_items(max);
+                }
+                if obj.get("uniqueItems") == Some(&Value::Bool(true)) {
+                    a = a.unique();
+                }
+                a.into_schema_type()
+            }
+            "object" => self.object(obj, path)?.presence(presence).into_schema_type(),
+            other => return Err(invalid(&child(path, "type"), &format!("unknown type '{}'", other))),
+        };
+        Ok(schema)
+    }
+
+    fn object(&mut self, obj: &Map<String, Value>, path: &str) -> Result<ObjectSchema, JsonSchemaError> {
+        let required: Vec<&str> = match obj.get("required") {
+            Some(Value::Array(names)) => names
+                .iter()
+                .map(|n| n.as_str().ok_or_else(|| invalid(&child(path, "required"), "must list property names")))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid(&child(path, "required"), "must be an array")),
+            None => Vec::new(),
+        };
+        let mut schema = match obj.get("additionalProperties") {
+            None | Some(Value::Bool(true)) => object().passthrough(),
+            Some(Value::Bool(false)) => object(),
+            Some(_) => {
+                return Err(JsonSchemaError::Unsupported {
+                    path: child(path, "additionalProperties"),
+                    keyword: "additionalProperties".to_string(),
+                })
+            }
+        };
+        let properties_path = child(path, "properties");
+        if let Some(properties) = obj.get("properties") {
+            let properties = properties.as_object().ok_or_else(|| invalid(&properties_path, "must be an object"))?;
+            for (name, property) in properties {
+                let compiled = self.compile(property, &child(&properties_path, name))?;
+                schema = match required.contains(&name.as_str()) {
+                    true => schema.field(name, compiled),
+                    false => schema.optional_field(name, compiled),
+                };
+            }
+        }
+        // Required properties without a schema may hold anything
+        for name in required {
+            if obj.get("properties").is_none_or(|p| p.get(name).is_none()) {
+                schema = schema.field(name, any());
+            }
+        }
+        Ok(schema)
+    }
+}
+
+/// Union accepting a value any of `schemas` accepts
+fn one_of(mut schemas: Vec<SchemaType>) -> SchemaType {
+    match schemas.len() {
+        1 => schemas.remove(0),
+        _ => UnionSchema::new(schemas).into_schema_type(),
+    }
+}
+
+/// Value of `format` if it is one of the formats that are checked
+fn checked_format(obj: &Map<String, Value>) -> Option<&str> {
+    obj.get("format").and_then(Value::as_str).filter(|f| matches!(*f, "email" | "uri" | "url" | "uuid" | "ipv4"))
+}
+
+fn child(path: &str, segment: &str) -> String {
+    format!("{}/{}", path, segment.replace('~', "~0").replace('/', "~1"))
+}
+
+fn invalid(path: &str, message: &str) -> JsonSchemaError {
+    JsonSchemaError::Invalid { path: path.to_string(), message: message.to_string() }
+}
+
+/// Non-negative integer value of `keyword`, e.g. `minLength`
+fn count(obj: &Map<String, Value>, keyword: &str, path: &str) -> Result<Option<usize>, JsonSchemaError> {
+    obj.get(keyword)
+        .map(|v| v.as_u64().map(|n| n as usize).ok_or_else(|| invalid(&child(path, keyword), "must be a non-negative integer")))
+        .transpose()
+}
+
+/// The stricter of an inclusive and an exclusive bound on one side, the
+/// exclusive one winning a tie; `lower` keeps the larger value
+fn tightest(inclusive: Option<f64>, exclusive: Option<f64>, lower: bool) -> Bound<f64> {
+    match (inclusive, exclusive) {
+        (Some(i), Some(e)) if (lower && i > e) || (!lower && i < e) => Bound::Included(i),
+        (_, Some(e)) => Bound::Excluded(e),
+        (Some(i), None) => Bound::Included(i),
+        (None, None) => Bound::Unbounded,
+    }
+}
+
+/// Numeric value of `keyword`, e.g. `minimum`
+fn bound(obj: &Map<String, Value>, keyword: &str, path: &str) -> Result<Option<f64>, JsonSchemaError> {
+    obj.get(keyword)
+        .map(|v| v.as_f64().ok_or_else(|| invalid(&child(path, keyword), "must be a number")))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn compile(document: Value) -> SchemaType {
+        SchemaType::from_json_schema(&document).unwrap()
+    }
+
+    #[test]
+    fn test_from_json_schema() {
+        let schema = compile(json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "id": {"type": "string", "format": "uuid"},
+                "name": {"type": "string", "minLength": 1, "maxLength": 20},
+                "nickname": {"type": ["string", "null"]},
+                "age": {"type": "integer", "minimum": 0, "exclusiveMaximum": 150},
+                "role": {"enum": ["admin", "member"]},
+                "tags": {"type": "array", "items": {"type": "string"}, "uniqueItems": true, "maxItems": 3},
+                "address": {"$ref": "#/$defs/address"}
+            },
+            "required": ["id", "name", "role"],
+            "$defs": {
+                "address": {
+                    "type": "object",
+                    "properties": {"zip": {"type": "string", "pattern": "^[0-9]{5}$"}},
+                    "required": ["zip"],
+                    "additionalProperties": false
+                }
+            }
+        }));
+        let valid = json!({
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "name": "Ada",
+            "nickname": null,
+            "role": "admin",
+            "tags": ["a", "b"],
+            "address": {"zip": "12345"},
+            "extra": true
+        });
+        assert_eq!(schema.validate(&valid).unwrap(), valid);
+
+        let fails = |patch: Value| {
+            let mut value = valid.clone();
+            value.as_object_mut().unwrap().extend(patch.as_object().unwrap().clone());
+            schema.validate(&value).unwrap_err().context.path.to_string()
+        };
+        assert_eq!(fails(json!({"id": "nope"})), "id");
+        assert_eq!(fails(json!({"name": ""})), "name");
+        assert_eq!(fails(json!({"age": 150})), "age");
+        assert_eq!(fails(json!({"age": 1.5})), "age");
+        assert_eq!(fails(json!({"role": "owner"})), "role");
+        assert_eq!(fails(json!({"tags": ["a", "a"]})), "tags.1");
+        assert_eq!(fails(json!({"address": {"zip": "1234"}})), "address.zip");
+        assert_eq!(fails(json!({"address": {"zip": "12345", "city": "x"}})), "address.city");
+        assert!(schema.validate(&json!({"name": "Ada", "role": "admin"})).is_err());
+    }
+
+    #[test]
+    fn test_from_json_schema_combinators() {
+        let schema = compile(json!({"anyOf": [{"type": "string"}, {"type": "number", "multipleOf": 5}]}));
+        assert!(schema.validate(&json!("x")).is_ok());
+        assert!(schema.validate(&json!(10)).is_ok());
+        assert!(schema.validate(&json!(7)).is_err());
+
+        let schema = compile(json!({"oneOf": [{"type": "integer"}, {"type": "number", "multipleOf": 2.5}]}));
+        assert!(schema.validate(&json!(3)).is_ok());
+        assert!(schema.validate(&json!(7.5)).is_ok());
+        assert_eq!(schema.validate(&json!(10)).unwrap_err().context.code, "union.ambiguous");
+        assert!(schema.validate(&json!("x")).is_err());
+
+        let schema = compile(json!({"allOf": [{"type": "integer", "minimum": 1}, {"not": {"const": 13}}]}));
+        assert!(schema.validate(&json!(12)).is_ok());
+        assert!(schema.validate(&json!(13)).is_err());
+        assert!(schema.validate(&json!(0)).is_err());
+
+        let schema = compile(json!({"type": ["string", "integer", "null"]}));
+        assert!(schema.validate(&json!(null)).is_ok());
+        assert!(schema.validate(&json!(true)).is_err());
+        assert!(compile(json!(true)).validate(&json!([1])).is_ok());
+        assert!(compile(json!(false)).validate(&json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_from_json_schema_untyped() {
+        // Bounds without a type apply to values of their own type only
+        let schema = compile(json!({"minimum": 0}));
+        assert!(schema.validate(&json!(-5)).is_err());
+        assert!(schema.validate(&json!("x")).is_ok());
+        let schema = compile(json!({"minLength": 3}));
+        assert!(schema.validate(&json!("ab")).is_err());
+        assert!(schema.validate(&json!(1)).is_ok());
+        let schema = compile(json!({"required": ["a"]}));
+        assert!(schema.validate(&json!({})).is_err());
+        assert!(schema.validate(&json!(null)).is_ok());
+        let schema = compile(json!({"properties": {"a": {"type": "string"}}}));
+        assert!(schema.validate(&json!("x")).is_ok());
+        assert_eq!(schema.validate(&json!({"a": 1})).unwrap_err().context.path.to_string(), "a");
+        let schema = compile(json!({"allOf": [{"type": "integer"}, {"minimum": 1}]}));
+        assert!(schema.validate(&json!(0)).is_err());
+
+        // Lengths count characters, not bytes
+        let schema = compile(json!({"type": "string", "maxLength": 1}));
+        assert!(schema.validate(&json!("é")).is_ok());
+        assert!(schema.validate(&json!("ab")).is_err());
+
+        // Keywords next to a `$ref` apply on top of it
+        let schema = compile(json!({"$defs": {"n": {"type": "number"}}, "$ref": "#/$defs/n", "maximum": 5}));
+        assert!(schema.validate(&json!(5)).is_ok());
+        assert!(schema.validate(&json!(6)).is_err());
+        assert!(schema.validate(&json!("x")).is_err());
+    }
+
+    #[test]
+    fn test_from_json_schema_formats() {
+        let schema = compile(json!({"type": "string", "format": "uri"}));
+        for uri in ["urn:isbn:0451450523", "mailto:a@b.co", "https://example.com/a?b", "file:///tmp/x"] {
+            assert!(schema.validate(&json!(uri)).is_ok(), "{}", uri);
+        }
+        for not_uri in ["example.com", "/relative/path", "1ab:x", "a b:c"] {
+            assert_eq!(schema.validate(&json!(not_uri)).unwrap_err().context.code, "string.url", "{}", not_uri);
+        }
+        let schema = compile(json!({"type": "string", "format": "url"}));
+        assert!(schema.validate(&json!("https://example.com")).is_ok());
+        assert!(schema.validate(&json!("mailto:a@b.co")).is_err());
+    }
+
+    #[test]
+    fn test_from_json_schema_enum_numbers() {
+        // Numbers equal by value match, as JSON Schema compares them
+        let schema = compile(json!({"enum": [1, 2, "x"]}));
+        assert_eq!(schema.validate(&json!(1.0)).unwrap(), json!(1.0));
+        assert!(schema.validate(&json!(2)).is_ok());
+        assert!(schema.validate(&json!(1.5)).is_err());
+        assert!(schema.validate(&json!("1")).is_err());
+        let schema = compile(json!({"const": {"at": [0, 2.5]}}));
+        assert!(schema.validate(&json!({"at": [0.0, 2.5]})).is_ok());
+        assert!(schema.validate(&json!({"at": [0, 2.5, 1]})).is_err());
+        let schema = compile(json!({"const": u64::MAX}));
+        assert!(schema.validate(&json!(u64::MAX)).is_ok());
+        assert!(schema.validate(&json!(u64::MAX - 1)).is_err());
+    }
+
+    #[test]
+    fn test_from_json_schema_bounds() {
+        // The stricter of `minimum` and `exclusiveMinimum` applies, whichever it is
+        let schema = compile(json!({"type": "number", "minimum": 10, "exclusiveMinimum": 0}));
+        assert!(schema.validate(&json!(5)).is_err());
+        assert!(schema.validate(&json!(10)).is_ok());
+        let schema = compile(json!({"type": "number", "minimum": 0, "exclusiveMinimum": 10}));
+        assert!(schema.validate(&json!(10)).is_err());
+        assert!(schema.validate(&json!(10.5)).is_ok());
+        let schema = compile(json!({"type": "number", "minimum": 3, "exclusiveMinimum": 3}));
+        assert!(schema.validate(&json!(3)).is_err());
+
+        let schema = compile(json!({"type": "number", "maximum": 10, "exclusiveMaximum": 100}));
+        assert!(schema.validate(&json!(50)).is_err());
+        assert!(schema.validate(&json!(10)).is_ok());
+        let schema = compile(json!({"type": "number", "maximum": 100, "exclusiveMaximum": 10}));
+        assert!(schema.validate(&json!(10)).is_err());
+        assert!(schema.validate(&json!(9.5)).is_ok());
+        let schema = compile(json!({"maximum": 3, "exclusiveMaximum": 3}));
+        assert!(schema.validate(&json!(3)).is_err());
+    }
+
+    #[test]
+    fn test_from_json_schema_errors() {
+        let error = |document: Value| SchemaType::from_json_schema(&document).err().unwrap();
+        assert_eq!(
+            error(json!({"type": "object", "patternProperties": {"^x": {}}})),
+            JsonSchemaError::Unsupported { path: "".to_string(), keyword: "patternProperties".to_string() }
+        );
+        assert_eq!(
+            error(json!({"properties": {"a": {"minLength": "3", "type": "string"}}})),
+            JsonSchemaError::Invalid { path: "/properties/a/minLength".to_string(), message: "must be a non-negative integer".to_string() }
+        );
+        assert!(matches!(error(json!({"$ref": "#/$defs/missing"})), JsonSchemaError::Ref { .. }));
+        assert!(matches!(
+            error(json!({"$defs": {"node": {"items": {"$ref": "#/$defs/node"}}}, "$ref": "#/$defs/node"})),
+            JsonSchemaError::Ref { .. }
+        ));
+        assert!(matches!(error(json!({"type": "string", "pattern": "(?=x)"})), JsonSchemaError::Invalid { .. }));
+    }
+}
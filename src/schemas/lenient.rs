@@ -0,0 +1,189 @@
+use serde_json::Value;
+
+use crate::error::{SourceSpan, ValidationError};
+
+/// Output of [`Schema::validate_lenient_str`](super::Schema::validate_lenient_str):
+/// the validated value and the repairs its input needed
+#[derive(Debug, Clone, PartialEq)]
+pub struct LenientOutput {
+    pub value: Value,
+    pub repairs: Vec<Repair>,
+}
+
+/// A defect of hand-written JSON repaired before validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    pub kind: RepairKind,
+    /// Where the defect sits in the input as written
+    pub span: SourceSpan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairKind {
+    /// A comma before a closing `}` or `]`, which was dropped
+    TrailingComma,
+    /// A string in single quotes, which now uses double quotes
+    SingleQuotes,
+    /// An object key without quotes, which was quoted
+    UnquotedKey,
+}
+
+/// Strict JSON text of a relaxed input, with the repairs made and, for
+/// every byte of the repaired text, the offset it came from in the input
+pub(crate) struct Repaired {
+    pub text: String,
+    pub repairs: Vec<Repair>,
+    offsets: Vec<usize>,
+}
+
+impl Repaired {
+    /// `err` with its span moved from the repaired text into `original`
+    pub fn remap(&self, err: ValidationError, original: &str) -> ValidationError {
+        match err.context.details.span {
+            Some(span) => {
+                let (start, end) = (self.offset(span.start), self.offset(span.end));
+                err.with_details(|d| d.span = Some(SourceSpan::from_offsets(original, start, end.max(start))))
+            }
+            None => err,
+        }
+    }
+
+    fn offset(&self, repaired: usize) -> usize {
+        self.offsets.get(repaired).or(self.offsets.last()).copied().unwrap_or(0)
+    }
+
+    fn push(&mut self, c: char, from: usize) {
+        self.text.push(c);
+        self.offsets.extend(std::iter::repeat_n(from, c.len_utf8()));
+    }
+}
+
+/// Rewrite `input` as strict JSON, dropping trailing commas, turning
+/// single-quoted strings into double-quoted ones and quoting bare keys
+pub(crate) fn repair(input: &str) -> Repaired {
+    let mut out = Repaired { text: String::with_capacity(input.len()), repairs: Vec::new(), offsets: Vec::new() };
+    let mut chars = input.char_indices().peekable();
+    let record = |out: &mut Repaired, kind, start, end| {
+        out.repairs.push(Repair { kind, span: SourceSpan::from_offsets(input, start, end) });
+    };
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c, i);
+                while let Some((j, c)) = chars.next() {
+                    out.push(c, j);
+                    match c {
+                        '\\' => {
+                            if let Some((k, escaped)) = chars.next() {
+                                out.push(escaped, k);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '\'' => {
+                out.push('"', i);
+                let mut end = input.len();
+                while let Some((j, c)) = chars.next() {
+                    match c {
+                        '\\' => match chars.next() {
+                            Some((k, '\'')) => out.push('\'', k),
+                            Some((k, escaped)) => {
+                                out.push('\\', j);
+                                out.push(escaped, k);
+                            }
+                            None => out.push('\\', j),
+                        },
+                        '"' => {
+                            out.push('\\', j);
+                            out.push('"', j);
+                        }
+                        '\'' => {
+                            out.push('"', j);
+                            end = j + 1;
+                            break;
+                        }
+                        _ => out.push(c, j),
+                    }
+                }
+                record(&mut out, RepairKind::SingleQuotes, i, end);
+            }
+            ',' => {
+                let next = input[i + 1..].trim_start().chars().next();
+                if matches!(next, Some('}' | ']')) {
+                    record(&mut out, RepairKind::TrailingComma, i, i + 1);
+                } else {
+                    out.push(c, i);
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    end = j + c.len_utf8();
+                    chars.next();
+                }
+                let word = &input[i..end];
+                let is_key = input[end..].trim_start().starts_with(':');
+                if is_key {
+                    out.push('"', i);
+                }
+                for (j, c) in word.char_indices() {
+                    out.push(c, i + j);
+                }
+                if is_key {
+                    out.push('"', end);
+                    record(&mut out, RepairKind::UnquotedKey, i, end);
+                }
+            }
+            _ => out.push(c, i),
+        }
+    }
+    out.offsets.push(input.len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{array, number, object, string};
+    use crate::schemas::Schema;
+
+    #[test]
+    fn test_repair() {
+        let repaired = repair("{name: 'Ada \"L\"', 'tags': ['x', \"it's\",],}");
+        assert_eq!(repaired.text, r#"{"name": "Ada \"L\"", "tags": ["x", "it's"]}"#);
+        let kinds: Vec<_> = repaired.repairs.iter().map(|r| r.kind).collect();
+        assert_eq!(kinds, [
+            RepairKind::UnquotedKey,
+            RepairKind::SingleQuotes,
+            RepairKind::SingleQuotes,
+            RepairKind::SingleQuotes,
+            RepairKind::TrailingComma,
+            RepairKind::TrailingComma,
+        ]);
+        assert_eq!(repair(r#"{"a": [1, 2], "b": true}"#).repairs, []);
+    }
+
+    #[test]
+    fn test_validate_lenient_str() {
+        let schema = object().field("name", string()).field("port", number()).optional_field("tags", array(string()));
+        let output = schema.validate_lenient_str("{\n  name: 'api',\n  port: 80,\n}").unwrap();
+        assert_eq!(output.value, json!({"name": "api", "port": 80}));
+        assert_eq!(output.repairs.len(), 4);
+        assert_eq!((output.repairs[3].span.line, output.repairs[3].span.column), (3, 11));
+
+        // Spans point into the input as written
+        let err = schema.validate_lenient_str("{name: 'api', port: 'eighty'}").unwrap_err();
+        assert_eq!(err.context.path, "port");
+        let span = err.context.details.span.unwrap();
+        assert_eq!(span.start, 20);
+        assert!(schema.validate_lenient_str("{name: api}").is_err());
+    }
+}
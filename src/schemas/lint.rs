@@ -0,0 +1,221 @@
+use regex::Regex;
+use regex_syntax::ast::{parse::Parser, AssertionKind, Ast, RepetitionKind, RepetitionRange};
+use regex_syntax::hir::Look;
+
+/// Counted repeats above this compile into very large automata
+pub const LARGE_REPEAT: u32 = 1000;
+
+/// A risky setting found by [`Schema::lint`](super::Schema::lint)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    /// Path of the schema (empty for the root, `*` for array items)
+    pub path: String,
+    pub kind: LintKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintKind {
+    /// An unbounded repeat inside another, e.g. `(a+)+`, which backtracking
+    /// engines (`pattern_fancy`, or clients reusing the pattern) can take
+    /// exponential time on
+    NestedQuantifier,
+    /// A pattern without `^` and `$` around all of it, so it matches substrings
+    Unanchored,
+    /// A counted repeat above [`LARGE_REPEAT`]
+    LargeRepeat,
+    /// A string accepted at any length
+    UnboundedLength,
+    /// A pattern that needs the backtracking engine of `pattern_fancy`
+    Backtracking,
+}
+
+impl LintFinding {
+    pub(crate) fn new(kind: LintKind, message: impl Into<String>) -> Self {
+        Self { path: String::new(), kind, message: message.into() }
+    }
+}
+
+/// Prefix the path of nested findings with `segment`
+pub(crate) fn nest_findings(segment: &str, findings: Vec<LintFinding>) -> Vec<LintFinding> {
+    findings
+        .into_iter()
+        .map(|mut finding| {
+            finding.path = if finding.path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}.{}", segment, finding.path)
+            };
+            finding
+        })
+        .collect()
+}
+
+/// Findings for a `regex`-syntax pattern
+pub(crate) fn lint_pattern(pattern: &str) -> Vec<LintFinding> {
+    let Ok(ast) = Parser::new().parse(pattern) else {
+        return Vec::new();
+    };
+    let mut findings = Vec::new();
+    if !is_anchored(&ast) {
+        findings.push(LintFinding::new(
+            LintKind::Unanchored,
+            format!("Pattern `{}` matches anywhere in the string; use pattern_full_match or add ^ and $", pattern),
+        ));
+    }
+    walk(&ast, pattern, &mut findings);
+    findings
+}
+
+/// Whether an anchored `pattern` only matches strings up to some length,
+/// as with `^\d{5}$`, so that it needs no `max_length` of its own
+pub(crate) fn bounds_length(pattern: &str) -> bool {
+    let Ok(hir) = regex_syntax::parse(pattern) else {
+        return false;
+    };
+    let props = hir.properties();
+    props.look_set_prefix().contains(Look::Start)
+        && props.look_set_suffix().contains(Look::End)
+        && props.maximum_len().is_some()
+}
+
+fn is_anchored(ast: &Ast) -> bool {
+    let is = |ast: Option<&Ast>, kinds: [AssertionKind; 2]| {
+        matches!(ast, Some(Ast::Assertion(a)) if kinds.contains(&a.kind))
+    };
+    match ast {
+        Ast::Concat(concat) => {
+            is(concat.asts.first(), [AssertionKind::StartText, AssertionKind::StartLine])
+                && is(concat.asts.last(), [AssertionKind::EndText, AssertionKind::EndLine])
+        }
+        _ => false,
+    }
+}
+
+fn walk(ast: &Ast, pattern: &str, findings: &mut Vec<LintFinding>) {
+    match ast {
+        Ast::Repetition(rep) => {
+            if let RepetitionKind::Range(range) = &rep.op.kind {
+                let count = match *range {
+                    RepetitionRange::Exactly(n) | RepetitionRange::AtLeast(n) => n,
+                    RepetitionRange::Bounded(_, m) => m,
+                };
+                if count > LARGE_REPEAT {
+                    findings.push(LintFinding::new(
+                        LintKind::LargeRepeat,
+                        format!("Repeat of {} in `{}` is above {}; prefer max_length", count, pattern, LARGE_REPEAT),
+                    ));
+                }
+            }
+            if is_unbounded(&rep.op.kind) {
+                let mut inner = Vec::new();
+                unbounded_repeats(&rep.ast, &mut inner);
+                if !inner.is_empty() && !delimited(&rep.ast, &inner, pattern) {
+                    findings.push(LintFinding::new(
+                        LintKind::NestedQuantifier,
+                        format!("`{}` nests unbounded repeats, which backtracking engines can take exponential time on", source(ast, pattern)),
+                    ));
+                }
+            }
+            walk(&rep.ast, pattern, findings);
+        }
+        Ast::Group(group) => walk(&group.ast, pattern, findings),
+        Ast::Alternation(alt) => alt.asts.iter().for_each(|ast| walk(ast, pattern, findings)),
+        Ast::Concat(concat) => concat.asts.iter().for_each(|ast| walk(ast, pattern, findings)),
+        _ => {}
+    }
+}
+
+fn is_unbounded(kind: &RepetitionKind) -> bool {
+    matches!(kind, RepetitionKind::ZeroOrMore | RepetitionKind::OneOrMore | RepetitionKind::Range(RepetitionRange::AtLeast(_)))
+}
+
+fn unbounded_repeats<'a>(ast: &'a Ast, out: &mut Vec<&'a Ast>) {
+    match ast {
+        Ast::Repetition(rep) if is_unbounded(&rep.op.kind) => out.push(&rep.ast),
+        Ast::Repetition(rep) => unbounded_repeats(&rep.ast, out),
+        Ast::Group(group) => unbounded_repeats(&group.ast, out),
+        Ast::Alternation(alt) => alt.asts.iter().for_each(|ast| unbounded_repeats(ast, out)),
+        Ast::Concat(concat) => concat.asts.iter().for_each(|ast| unbounded_repeats(ast, out)),
+        _ => {}
+    }
+}
+
+/// Whether each iteration of `body` starts or ends with a literal none of the
+/// `inner` repeats can match, as the `.` in `(\.\w+)+`; such iterations
+/// cannot overlap, so backtracking stays linear
+fn delimited(body: &Ast, inner: &[&Ast], pattern: &str) -> bool {
+    let body = match body {
+        Ast::Group(group) => &group.ast,
+        other => other,
+    };
+    let Ast::Concat(concat) = body else {
+        return false;
+    };
+    let separator = |ast: Option<&Ast>| match ast {
+        Some(Ast::Literal(lit)) => {
+            let c = lit.c.to_string();
+            inner.iter().all(|repeated| {
+                Regex::new(&format!("^(?:{})$", source(repeated, pattern)))
+                    .map(|re| !re.is_match(&c))
+                    .unwrap_or(false)
+            })
+        }
+        _ => false,
+    };
+    separator(concat.asts.first()) || separator(concat.asts.last())
+}
+
+fn source<'a>(ast: &Ast, pattern: &'a str) -> &'a str {
+    let span = ast.span();
+    &pattern[span.start.offset..span.end.offset]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{array, object, string};
+    use crate::schemas::{Schema, StringSchema};
+
+    fn kinds(findings: &[LintFinding]) -> Vec<LintKind> {
+        findings.iter().map(|f| f.kind).collect()
+    }
+
+    #[test]
+    fn test_lint_pattern() {
+        assert!(lint_pattern(r"^\d{5}$").is_empty());
+        assert_eq!(kinds(&lint_pattern(r"\d{5}")), [LintKind::Unanchored]);
+        // An alternation escapes the anchors
+        assert_eq!(kinds(&lint_pattern(r"^cat|dog$")), [LintKind::Unanchored]);
+        assert_eq!(kinds(&lint_pattern(r"^(a+)+$")), [LintKind::NestedQuantifier]);
+        assert_eq!(kinds(&lint_pattern(r"^(\w+\s?)*$")), [LintKind::NestedQuantifier]);
+        assert_eq!(kinds(&lint_pattern(r"^a{1,5000}$")), [LintKind::LargeRepeat]);
+        // Iterations separated by a literal cannot overlap
+        assert!(lint_pattern(r"^\w+(\.\w+)+$").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_patterns_are_clean() {
+        let findings = object()
+            .field("site", string().max_length(200).url())
+            .field("id", string().uuid())
+            .field("ip", string().ip())
+            .lint();
+        assert_eq!(findings, []);
+    }
+
+    #[test]
+    fn test_schema_lint_paths() {
+        let schema = object()
+            .field("zip", string().max_length(5).pattern(r"\d{5}"))
+            .field("tags", array(string().max_length(20)))
+            .field("bio", string());
+        let findings: Vec<_> = schema.lint().into_iter().map(|f| (f.path, f.kind)).collect();
+        assert_eq!(findings, [
+            ("zip".to_string(), LintKind::Unanchored),
+            ("bio".to_string(), LintKind::UnboundedLength),
+        ]);
+        assert!(schema.validate(&json!({"zip": "12345", "tags": [], "bio": ""})).is_ok());
+    }
+}
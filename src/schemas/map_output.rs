@@ -0,0 +1,166 @@
+use std::{collections::HashMap, sync::Arc};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::error::{ErrorCode, MessageIssue, ValidationError};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput, LintFinding, Explanation, explain, canonical};
+
+type OutputMap = Arc<dyn Fn(Value) -> Result<Value, ValidationError> + Send + Sync>;
+
+/// A schema whose validated output is reshaped by a function, e.g. to
+/// collapse `{first, last}` into a single `name`
+///
+/// Created with [`Schema::map_output`] or [`Schema::map_into`]. Unlike
+/// [`Transform`](super::Transform)s, which prepare the input, the mapping
+/// only sees values that passed validation. A null the schema accepts is
+/// passed through unmapped.
+#[derive(Clone)]
+pub struct MapOutput<S> {
+    pub schema: S,
+    pub map: OutputMap,
+}
+
+impl<S> MapOutput<S> {
+    pub fn new<F>(schema: S, map: F) -> Self
+    where
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        Self { schema, map: Arc::new(move |value| Ok(map(value))) }
+    }
+
+    /// Reshape the output through `T`'s `Deserialize` and `Serialize`
+    /// impls, applying its renames and defaults; output `T` cannot
+    /// deserialize from fails as `parse.deserialize`
+    pub fn into_type<T>(schema: S) -> Self
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        let map = |value| {
+            serde_json::from_value::<T>(value)
+                .and_then(|typed| serde_json::to_value(typed))
+                .map_err(|e| ValidationError::new(ErrorCode::DeserializeFailed)
+                    .message(format!("Failed to deserialize: {}", e)))
+        };
+        Self { schema, map: Arc::new(map) }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+}
+
+impl<S: Schema> MapOutput<S> {
+    fn apply(&self, output: Value) -> Result<Value, ValidationError> {
+        if output.is_null() && self.schema.get_presence().allows_null() {
+            return Ok(output);
+        }
+        (self.map)(output)
+    }
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for MapOutput<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for MapOutput<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        explain::with_rules(self.schema.explain(), ["output mapped".to_string()])
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        self.apply(self.schema.validate(value)?)
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let output = self.schema.validate_partial_with(value, invalid);
+        match output.value {
+            Some(v) if output.errors.is_empty() => PartialOutput::from_result(self.apply(v), value, invalid),
+            _ => output,
+        }
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        // The schema no longer describes the reshaped output
+        canonical::normalize(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::MapOutput(Box::new(MapOutput { schema: self.schema.into_schema_type(), map: self.map }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+    use crate::{number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[test]
+    fn test_map_output() {
+        let person = object()
+            .field("first", string().min_length(1))
+            .field("last", string().min_length(1))
+            .map_output(|v| json!({"name": format!("{} {}", v["first"].as_str().unwrap(), v["last"].as_str().unwrap())}));
+        let schema = object().field("person", person).field("age", number());
+
+        let output = schema.validate(&json!({"person": {"first": "Ada", "last": "Lovelace"}, "age": 36})).unwrap();
+        assert_eq!(output, json!({"person": {"name": "Ada Lovelace"}, "age": 36}));
+        // The mapping never sees invalid values
+        let err = schema.validate(&json!({"person": {"first": "", "last": "Lovelace"}, "age": 36})).unwrap_err();
+        assert_eq!(err.context.path, "person.first");
+
+        let output = schema.validate_partial(&json!({"person": {"first": "Ada", "last": "Lovelace"}, "age": "x"}));
+        assert_eq!(output.value.unwrap()["person"], json!({"name": "Ada Lovelace"}));
+    }
+
+    #[test]
+    fn test_map_into() {
+        #[derive(Serialize, Deserialize)]
+        struct Point {
+            x: f64,
+            #[serde(default)]
+            y: f64,
+        }
+
+        let schema = object()
+            .field("x", number())
+            .optional_field("y", number().optional())
+            .map_into::<Point>();
+        assert_eq!(schema.validate(&json!({"x": 1})).unwrap(), json!({"x": 1.0, "y": 0.0}));
+
+        let err = schema.validate(&json!({"x": 1, "y": null})).unwrap_err();
+        assert_eq!(err.context.code, "parse.deserialize");
+    }
+}
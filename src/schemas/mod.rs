@@ -1,50 +1,533 @@
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, sync::{Arc, OnceLock}};
 
-use crate::error::ValidationError;
+use crate::error::{ValidationError, MessageIssue, ErrorCode, ErrorMap, ValidationErrorContext, canonical_code};
+use crate::error::message_check::{check_templates, debug_check_template};
 
 pub mod string;
+pub mod password;
+pub mod money;
+pub mod claims;
+pub mod envelope;
+pub(crate) mod pagination;
+mod path;
 pub mod number;
 pub mod array;
 pub mod object;
+mod hooks;
+pub mod field_set;
+pub mod overlay;
+pub mod json_schema;
 pub mod boolean;
+pub mod bytes;
 pub mod transform;
+pub mod negation;
+pub mod any;
+pub mod sensitive;
+pub mod error_map;
+pub mod cached;
+pub mod batch;
+mod parallel;
+pub mod deprecated;
+pub mod catch;
+pub mod pipe;
+pub mod map_output;
+pub mod refine;
+pub mod lint;
+pub mod explain;
+pub mod satisfiable;
+mod compat;
+mod canonical;
+pub mod warnings;
+pub mod partial;
+pub mod safe_parse;
+pub mod session;
+pub mod ndjson;
+pub mod lenient;
+pub mod streaming;
+pub mod body;
+pub mod typed;
+mod strict_json;
+#[cfg(any(feature = "yaml", feature = "toml"))]
+mod source_formats;
+pub(crate) mod spans;
+pub mod compiled;
+pub mod context;
+pub mod switch;
+pub mod conditional;
+#[cfg(feature = "http")]
+mod headers;
+mod env;
+#[cfg(feature = "csv")]
+pub mod csv;
+mod string_values;
+#[cfg(feature = "chrono")]
+pub mod date;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal;
 
 pub use string::StringSchema;
+pub use password::PasswordPolicy;
+pub use money::MoneyPolicy;
+pub use session::ValidationSession;
+pub use ndjson::NdjsonLines;
+pub use lenient::{LenientOutput, Repair, RepairKind};
+pub use streaming::StreamingArrayValidator;
+pub use claims::ClaimsPolicy;
+pub use envelope::EnvelopePolicy;
 pub use number::NumberSchema;
 pub use array::ArraySchema;
 pub use object::ObjectSchema;
+pub use field_set::FieldSet;
+pub use overlay::{OverlayError, OverlaySpec};
+pub use json_schema::JsonSchemaError;
 pub use boolean::BooleanSchema;
+pub use bytes::{BytesEncoding, BytesSchema};
 pub use transform::{Transform, Transformable, WithTransform};
+pub use negation::{NeverSchema, NotSchema};
+pub use any::AnySchema;
+pub use sensitive::{Sensitive, REDACTED};
+pub use error_map::ErrorMapped;
+pub use cached::Cached;
+pub use batch::{BatchOptions, BatchReport};
+pub use deprecated::Deprecated;
+pub use catch::Catch;
+pub use pipe::Pipe;
+pub use map_output::MapOutput;
+pub use refine::{Refined, Refinement, RefinementContext};
+pub use lint::{LintFinding, LintKind};
+pub use explain::Explanation;
+pub use satisfiable::{Unsatisfiable, UnsatisfiableKind};
+pub use warnings::ValidationReport;
+pub use partial::{InvalidFields, PartialOutput};
+pub use safe_parse::SafeParseResult;
+pub use compiled::CompiledSchema;
+pub use context::ValidationContext;
+pub use switch::SwitchSchema;
+pub use conditional::{ConditionalSchema, LiteralSchema};
+#[cfg(feature = "csv")]
+pub use self::csv::{CsvRows, CsvValidator};
+#[cfg(feature = "chrono")]
+pub use date::{DateSchema, DateTimeSchema};
+#[cfg(feature = "rust_decimal")]
+pub use decimal::DecimalSchema;
 
 #[derive(Clone)]
 pub enum SchemaType {
     String(string::StringSchemaImpl),
     Number(NumberSchema),
     Boolean(BooleanSchema),
+    Bytes(BytesSchema),
     Array(Box<ArraySchema>),
     Object(Box<ObjectSchema>),
     Union(Box<UnionSchema>),
+    Transform(Box<WithTransform<SchemaType>>),
+    Never(NeverSchema),
+    Not(Box<NotSchema>),
+    Any(AnySchema),
+    Sensitive(Box<Sensitive<SchemaType>>),
+    ErrorMapped(Box<ErrorMapped<SchemaType>>),
+    Cached(Box<Cached<SchemaType>>),
+    Deprecated(Box<Deprecated<SchemaType>>),
+    Catch(Box<Catch<SchemaType>>),
+    Pipe(Box<Pipe<SchemaType, SchemaType>>),
+    MapOutput(Box<MapOutput<SchemaType>>),
+    Refined(Box<Refined<SchemaType>>),
+    Switch(Box<SwitchSchema>),
+    Literal(LiteralSchema),
+    Conditional(Box<ConditionalSchema>),
+    /// A user-defined schema, so third-party types nest inside objects,
+    /// arrays and unions like the built-ins
+    Custom(Arc<dyn Schema + Send + Sync>),
+    #[cfg(feature = "chrono")]
+    Date(DateSchema),
+    #[cfg(feature = "chrono")]
+    DateTime(DateTimeSchema),
+    #[cfg(feature = "rust_decimal")]
+    Decimal(DecimalSchema),
 }
 
-pub trait Schema {
+/// Whether a value may be missing from its enclosing object and/or be null
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Presence {
+    /// Must be present and non-null
+    #[default]
+    Required,
+    /// May be missing, but not null when present
+    Optional,
+    /// Must be present, but may be null
+    Nullable,
+    /// May be missing or null (what `.optional()` sets)
+    OptionalNullable,
+}
+
+impl Presence {
+    pub fn allows_missing(self) -> bool {
+        matches!(self, Presence::Optional | Presence::OptionalNullable)
+    }
+
+    pub fn allows_null(self) -> bool {
+        matches!(self, Presence::Nullable | Presence::OptionalNullable)
+    }
+}
+
+/// A validation schema.
+///
+/// Every schema is `Send + Sync`, so built schemas can be stored in statics,
+/// `Arc`s or shared across async tasks.
+pub trait Schema: Send + Sync {
     fn validate(&self, value: &Value) -> Result<Value, ValidationError>;
     fn into_schema_type(self) -> SchemaType where Self: Sized;
     fn is_optional(&self) -> bool { false }
+    /// Missing/null configuration, derived from `is_optional` unless overridden
+    fn get_presence(&self) -> Presence {
+        if self.is_optional() { Presence::OptionalNullable } else { Presence::Required }
+    }
+    /// Custom message set via `required_message`, used when the value is
+    /// null or the enclosing object is missing the field
+    fn missing_message(&self) -> Option<String> { None }
+
+    /// Copy of `value` with everything under a [`Sensitive`] schema replaced
+    /// by `"[REDACTED]"`, safe to write to logs or audit reports
+    fn redact(&self, value: &Value) -> Value {
+        value.clone()
+    }
+
+    /// Settings that are risky on untrusted input, such as patterns prone
+    /// to catastrophic backtracking or strings without a `max_length`;
+    /// meant for reviewing schemas loaded from external sources
+    fn lint(&self) -> Vec<LintFinding> {
+        Vec::new()
+    }
+
+    /// Constraints no value can meet, such as a `min_length` above the
+    /// `max_length` or a required field of `never()`, so that broken
+    /// schemas fail a test rather than every request
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        Vec::new()
+    }
+
+    /// What this schema enforces, one [`Explanation`] per path in plain
+    /// words (`email: string, required, trimmed, must be a valid email`),
+    /// for support teams and API consumers who do not read Rust
+    fn explain(&self) -> Vec<Explanation> {
+        vec![Explanation::typed("custom", self.get_presence())]
+    }
+
+    /// Whether every value valid under `other` is also valid under this
+    /// schema, so a contract test can check that a producer's output schema
+    /// fits a consumer's input schema
+    ///
+    /// Constraints are compared rather than values sampled, and the answer
+    /// is conservative: `false` means compatibility could not be shown, as
+    /// for custom validators, refinements on this schema or patterns with a
+    /// different source. Literals of `other` are checked by validation.
+    fn accepts<S: Schema + Clone>(&self, other: &S) -> bool
+    where
+        Self: Clone + Sized,
+    {
+        compat::accepts(&self.clone().into_schema_type(), &other.clone().into_schema_type())
+    }
+
+    /// Validate `value` and bring the output into a canonical form for
+    /// hashing, signing or deduplication: object keys sorted, integral
+    /// floats such as `1.0` written as integers, and optional fields that
+    /// are null left out
+    fn canonicalize(&self, value: &Value) -> Result<Value, ValidationError> {
+        self.validate(value).map(|output| self.canonical(output))
+    }
+
+    /// Canonical form of output this schema produced, see [`Schema::canonicalize`]
+    fn canonical(&self, output: Value) -> Value {
+        canonical::normalize(output)
+    }
+
+    /// [`Schema::validate`] without building the output, failing with the
+    /// same error; read-only checks of large documents then allocate only
+    /// where a transform runs
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        self.validate(value).map(drop)
+    }
+
+    /// Whether valid input is output unchanged, so [`Schema::validate_cow`]
+    /// may hand it back rather than copy it
+    fn preserves_input(&self) -> bool {
+        false
+    }
+
+    /// [`Schema::validate`] of a value the caller owns, such as one just
+    /// parsed, so that transforms apply to it in place rather than to a copy
+    ///
+    /// Objects whose fields validate apart, arrays within schema trees and
+    /// transforms move their values through; other schemas check `value`
+    /// by reference and return it, or validate it as usual.
+    fn validate_owned(&self, value: Value) -> Result<Value, ValidationError> {
+        validate_owned_by_ref(self, value)
+    }
+
+    /// This schema as an [`ObjectSchema`], so typed parsing can validate
+    /// its fields one by one; `None` for every other schema
+    fn as_object(&self) -> Option<&ObjectSchema> {
+        None
+    }
+
+    /// [`Schema::validate`] borrowing `value` as the output when the schema
+    /// leaves valid input unchanged, and copying only when it transforms
+    fn validate_cow<'v>(&self, value: &'v Value) -> Result<Cow<'v, Value>, ValidationError> {
+        if self.preserves_input() {
+            self.validate_ref(value).map(|()| Cow::Borrowed(value))
+        } else {
+            self.validate(value).map(Cow::Owned)
+        }
+    }
+
+    /// Validate as much as possible, returning the transformed output with
+    /// invalid fields left out, alongside every error found
+    fn validate_partial(&self, value: &Value) -> PartialOutput {
+        self.validate_partial_with(value, InvalidFields::Omit)
+    }
+
+    /// [`Schema::validate_partial`] with a choice of what to do with invalid
+    /// fields. Schemas without fields validate as a whole.
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        PartialOutput::from_result(self.validate(value), value, invalid)
+    }
+
+    /// Revalidate `new`, an edit of `old` that validated as `previous`,
+    /// re-running only the checks the edit can affect, so a large document
+    /// edited interactively is not validated in full on every keystroke
+    ///
+    /// Objects keep the outcome of fields whose value is unchanged and
+    /// recurse into nested objects; an object with rules, conditions or
+    /// refinements relating its fields is validated in full. Other schemas
+    /// validate `new` in full unless it equals `old`.
+    fn revalidate(&self, old: &Value, new: &Value, previous: &PartialOutput) -> PartialOutput {
+        if old == new {
+            previous.clone()
+        } else {
+            self.validate_partial(new)
+        }
+    }
+
+    /// Validate without panicking and deserialize the output into `T`,
+    /// reporting every issue found rather than only the first
+    fn safe_parse<T>(&self, value: &Value) -> SafeParseResult<T>
+    where
+        Self: Sized,
+        T: serde::de::DeserializeOwned,
+    {
+        SafeParseResult::from_partial(self.validate_partial(value))
+    }
+
+    /// Validate with `ctx` as the current [`ValidationContext`], which
+    /// context-dependent schemas such as [`SwitchSchema`] select on
+    fn validate_with_context(&self, value: &Value, ctx: &ValidationContext) -> Result<Value, ValidationError> {
+        ctx.scope(|| self.validate(value))
+    }
+
+    /// Validate and translate the error message into `locale` using the
+    /// loaded [`MessageCatalog`](crate::error::MessageCatalog)s
+    fn validate_with_locale(&self, value: &Value, locale: &str) -> Result<Value, ValidationError> {
+        self.validate(value).map_err(|err| err.localized(locale))
+    }
+
+    /// Validate, also collecting non-fatal warnings such as deprecated
+    /// fields and soft limits. Hard errors still fail.
+    fn validate_with_report(&self, value: &Value) -> Result<ValidationReport, ValidationError> {
+        let (result, warnings) = warnings::collect(|| self.validate(value));
+        result.map(|value| ValidationReport { value, warnings })
+    }
+
+    /// Validate every value in `values`, collecting per-index results and
+    /// pass/fail statistics
+    fn validate_batch(&self, values: &[Value]) -> BatchReport {
+        self.validate_batch_with(values, BatchOptions::default())
+    }
+
+    /// [`Schema::validate_batch`] with a thread count and an error cap
+    fn validate_batch_with(&self, values: &[Value], options: BatchOptions) -> BatchReport {
+        batch::validate_batch(self, values, options)
+    }
+
+    /// Parse JSON text and validate it, recording in each error's
+    /// `details.span` where the offending value sits in the text
+    fn validate_str(&self, json: &str) -> Result<Value, ValidationError> {
+        let value = serde_json::from_str(json).map_err(|e| spans::invalid_json(e, json))?;
+        self.validate_owned(value).map_err(|err| spans::attach_span(err, json))
+    }
+
+    /// [`Schema::validate_str`] for JSON written by hand: trailing commas,
+    /// single-quoted strings and unquoted keys are repaired before parsing
+    /// and listed in the output. Error spans point into `json` as written.
+    fn validate_lenient_str(&self, json: &str) -> Result<LenientOutput, ValidationError> {
+        let repaired = lenient::repair(json);
+        let value = self.validate_str(&repaired.text).map_err(|err| repaired.remap(err, json))?;
+        Ok(LenientOutput { value, repairs: repaired.repairs })
+    }
+
+    /// Validate newline-delimited JSON (JSON Lines) from `reader` line by
+    /// line, without holding the input in memory; see [`NdjsonLines`]
+    fn validate_ndjson<R: std::io::Read>(&self, reader: R) -> NdjsonLines<'_, Self, R>
+    where
+        Self: Sized,
+    {
+        NdjsonLines::new(self, reader)
+    }
+
+    /// [`Schema::validate_str`] rejecting objects that repeat a key with
+    /// `object.duplicate_key` rather than keeping the last value
+    fn validate_str_strict(&self, json: &str) -> Result<Value, ValidationError> {
+        let value = strict_json::from_str_strict(json).map_err(|err| spans::attach_span(err, json))?;
+        self.validate(&value).map_err(|err| spans::attach_span(err, json))
+    }
+
+    /// Parse YAML text and validate it; each error's `details.source_path`
+    /// is its path in YAML notation, e.g. `servers[0].port`
+    ///
+    /// Output keys are in `serde_json::Map` order, which is document order
+    /// only if the application enables serde_json's `preserve_order`.
+    #[cfg(feature = "yaml")]
+    fn validate_yaml(&self, yaml: &str) -> Result<Value, ValidationError> {
+        source_formats::from_yaml(yaml)
+            .and_then(|value| self.validate(&value))
+            .map_err(|err| {
+                let path = err.context.path.to_bracket_notation();
+                err.with_details(|d| d.source_path = Some(path))
+            })
+    }
+
+    /// Parse TOML text and validate it, passing date-times as strings;
+    /// each error's `details.source_path`
+    /// is its path as a TOML dotted key, e.g. `servers[0]."host name"`
+    ///
+    /// Output keys are in `serde_json::Map` order, as for YAML.
+    #[cfg(feature = "toml")]
+    fn validate_toml(&self, toml: &str) -> Result<Value, ValidationError> {
+        source_formats::from_toml(toml)
+            .and_then(|value| self.validate(&value))
+            .map_err(|err| {
+                let path = err.context.path.to_toml_key();
+                err.with_details(|d| d.source_path = Some(path))
+            })
+    }
+
+    /// Mark this schema's values as sensitive, see [`Sensitive`]
+    fn sensitive(self) -> Sensitive<Self>
+    where
+        Self: Sized,
+    {
+        Sensitive::new(self)
+    }
+
+    /// Rewrite the built-in messages of this schema and everything nested
+    /// in it, see [`ErrorMap`]
+    fn error_map<F>(self, map: F) -> ErrorMapped<Self>
+    where
+        Self: Sized,
+        F: Fn(&ErrorCode, &ValidationErrorContext) -> Option<String> + Send + Sync + 'static,
+    {
+        ErrorMapped::new(self, ErrorMap::new(map))
+    }
+
+    /// Keep accepting values but report them as deprecated, see [`Deprecated`]
+    fn deprecated(self, message: impl Into<String>) -> Deprecated<Self>
+    where
+        Self: Sized,
+    {
+        Deprecated::new(self, message)
+    }
+
+    /// Replace invalid values with `fallback` instead of failing, see [`Catch`]
+    fn catch(self, fallback: impl Into<Value>) -> Catch<Self>
+    where
+        Self: Sized,
+    {
+        let fallback = fallback.into();
+        Catch::new(self, move |_| fallback.clone())
+    }
+
+    /// Replace invalid values with one computed from the error, see [`Catch`]
+    fn catch_with<F>(self, fallback: F) -> Catch<Self>
+    where
+        Self: Sized,
+        F: Fn(&ValidationError) -> Value + Send + Sync + 'static,
+    {
+        Catch::new(self, fallback)
+    }
+
+    /// Validate this schema's output with `next`, e.g. to parse a trimmed
+    /// string as a number; see [`Pipe`]
+    fn pipe<S: Schema>(self, next: S) -> Pipe<Self, S>
+    where
+        Self: Sized,
+    {
+        Pipe::new(self, next)
+    }
+
+    /// Reshape the validated output, see [`MapOutput`]
+    fn map_output<F>(self, f: F) -> MapOutput<Self>
+    where
+        Self: Sized,
+        F: Fn(Value) -> Value + Send + Sync + 'static,
+    {
+        MapOutput::new(self, f)
+    }
+
+    /// Reshape the validated output through a serde type `T`, e.g. to apply
+    /// its field renames and defaults; see [`MapOutput::into_type`]
+    fn map_into<T>(self) -> MapOutput<Self>
+    where
+        Self: Sized,
+        T: serde::de::DeserializeOwned + serde::Serialize,
+    {
+        MapOutput::into_type::<T>(self)
+    }
+
+    /// Apply a runtime [`OverlaySpec`], e.g. one tenant's stricter limits
+    /// and messages, to a copy of this schema
+    fn overlay(self, spec: &OverlaySpec) -> Result<SchemaType, OverlayError>
+    where
+        Self: Sized,
+    {
+        overlay::apply(self.into_schema_type(), spec, "")
+    }
+
+    /// Check the validated value further, with access to its path and the
+    /// whole document, e.g. for rules across sibling fields; see [`Refined`]
+    fn refine<F>(self, refinement: F) -> Refined<Self>
+    where
+        Self: Sized,
+        F: Fn(&Value, &mut RefinementContext) + Send + Sync + 'static,
+    {
+        Refined::new(self, refinement)
+    }
+
+    /// Memoize results for up to `capacity` small inputs, see [`Cached`]
+    fn cached(self, capacity: usize) -> Cached<Self>
+    where
+        Self: Sized,
+    {
+        Cached::new(self, capacity)
+    }
+
+    /// Freeze the schema into a cheaply cloneable [`CompiledSchema`]
+    fn compile(self) -> CompiledSchema
+    where
+        Self: Sized,
+    {
+        CompiledSchema::new(self)
+    }
 }
 
 pub trait ValueTransform {
     fn transform(&self, value: Value) -> Value;
 }
 
-pub trait Refinement {
-    fn refine(&self, value: &Value) -> Result<(), String>;
-}
-
 #[derive(Clone)]
 pub enum UnionStrategy {
     First,  // Use first schema that validates
-    All,    // All schemas must validate (intersection)
+    All,    // All schemas must validate (intersection), outputs merged by `merge_outputs`
+    ExactlyOne,  // Exactly one schema must validate, as JSON Schema's `oneOf`
     Best {  // Use schema with least errors
         error_score: Arc<dyn Fn(&ValidationError) -> u32 + Send + Sync>,
     },
@@ -72,15 +555,71 @@ impl UnionSchema {
     }
 
     pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
-        self.error_messages.insert(code.into(), message.into());
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
         self
     }
+
+    /// Message used when an enclosing object is missing this field
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message("union.required", message)
+    }
+}
+
+/// A list of schemas for a union, possibly of different concrete types
+///
+/// Implemented for `Vec`s and arrays of one schema type, and for tuples of up
+/// to eight schemas of any types, e.g. `union((string(), number()))`.
+pub trait IntoSchemaList {
+    fn into_schema_list(self) -> Vec<SchemaType>;
+}
+
+impl<S: Schema> IntoSchemaList for Vec<S> {
+    fn into_schema_list(self) -> Vec<SchemaType> {
+        self.into_iter().map(Schema::into_schema_type).collect()
+    }
+}
+
+impl<S: Schema, const N: usize> IntoSchemaList for [S; N] {
+    fn into_schema_list(self) -> Vec<SchemaType> {
+        self.into_iter().map(Schema::into_schema_type).collect()
+    }
+}
+
+macro_rules! impl_into_schema_list_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Schema),+> IntoSchemaList for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_schema_list(self) -> Vec<SchemaType> {
+                let ($($name,)+) = self;
+                vec![$($name.into_schema_type()),+]
+            }
+        }
+    };
 }
 
+impl_into_schema_list_for_tuple!(A);
+impl_into_schema_list_for_tuple!(A, B);
+impl_into_schema_list_for_tuple!(A, B, C);
+impl_into_schema_list_for_tuple!(A, B, C, D);
+impl_into_schema_list_for_tuple!(A, B, C, D, E);
+impl_into_schema_list_for_tuple!(A, B, C, D, E, F);
+impl_into_schema_list_for_tuple!(A, B, C, D, E, F, G);
+impl_into_schema_list_for_tuple!(A, B, C, D, E, F, G, H);
+
 impl HasErrorMessages for UnionSchema {
     fn error_messages(&self) -> &HashMap<String, String> {
         &self.error_messages
     }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = check_templates("", &self.error_messages);
+        for schema in &self.schemas {
+            issues.extend(schema.check_messages());
+        }
+        issues
+    }
 }
 
 impl Schema for UnionSchema {
@@ -89,26 +628,42 @@ impl Schema for UnionSchema {
             UnionStrategy::First => {
                 let mut last_error = None;
                 for schema in &self.schemas {
-                    match validate_schema_type(schema, value) {
+                    match branch(|| validate_schema_type(schema, value)) {
                         Ok(v) => return Ok(v),
                         Err(e) => last_error = Some(e),
                     }
                 }
-                Err(last_error.unwrap_or_else(|| ValidationError::new("union.no_match")
-                    .message("Value did not match any schema")))
+                Err(last_error.unwrap_or_else(|| ValidationError::new(ErrorCode::UnionNoMatch)))
             }
             UnionStrategy::All => {
+                let mut merged: Option<Value> = None;
                 for schema in &self.schemas {
-                    validate_schema_type(schema, value)?;
+                    let output = validate_schema_type(schema, value)?;
+                    merged = Some(match merged {
+                        Some(acc) => merge_outputs(value, acc, output),
+                        None => output,
+                    });
                 }
-                Ok(value.clone())
+                Ok(merged.unwrap_or_else(|| value.clone()))
+            }
+            UnionStrategy::ExactlyOne => {
+                let mut matched = None;
+                let mut last_error = None;
+                for schema in &self.schemas {
+                    match branch(|| validate_schema_type(schema, value)) {
+                        Ok(_) if matched.is_some() => return Err(ValidationError::new(ErrorCode::UnionAmbiguous)),
+                        Ok(v) => matched = Some(v),
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+                matched.ok_or_else(|| last_error.unwrap_or_else(|| ValidationError::new(ErrorCode::UnionNoMatch)))
             }
             UnionStrategy::Best { error_score } => {
                 let mut best_result = None;
                 let mut best_score = u32::MAX;
 
                 for schema in &self.schemas {
-                    match validate_schema_type(schema, value) {
+                    match branch(|| validate_schema_type(schema, value)) {
                         Ok(v) => return Ok(v),
                         Err(e) => {
                             let score = error_score(&e);
@@ -122,18 +677,86 @@ impl Schema for UnionSchema {
 
                 match best_result {
                     Some((_, e)) => Err(e),
-                    None => Err(ValidationError::new("union.no_match")
-                        .message("Value did not match any schema")),
+                    None => Err(ValidationError::new(ErrorCode::UnionNoMatch)),
                 }
             }
         }
     }
 
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("union.required").cloned()
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        self.schemas.iter().flat_map(Schema::lint).collect()
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.schemas.iter().all(Schema::preserves_input)
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        let branches: Vec<_> = self.schemas.iter().map(Schema::check_satisfiable).collect();
+        let mut issues: Vec<_> = satisfiable::check_literals(self).into_iter().collect();
+        // An intersection fails with any of its branches, a union only with all of them
+        if matches!(self.strategy, UnionStrategy::All) || branches.iter().all(|issues| !issues.is_empty()) {
+            issues.extend(branches.into_iter().flatten());
+        }
+        issues
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let branches: Vec<_> = self
+            .schemas
+            .iter()
+            .map(|schema| match explain::root_summary(&schema.explain()) {
+                summary if summary.contains(", ") => format!("({})", summary),
+                summary => summary,
+            })
+            .collect();
+        let rule = match self.strategy {
+            UnionStrategy::All => format!("all of: {}", branches.join(" & ")),
+            UnionStrategy::ExactlyOne => format!("exactly one of: {}", branches.join(" | ")),
+            _ => format!("one of: {}", branches.join(" | ")),
+        };
+        vec![Explanation { path: String::new(), rules: vec![rule] }]
+    }
+
     fn into_schema_type(self) -> SchemaType {
         SchemaType::Union(Box::new(self))
     }
 }
 
+/// Merge the outputs of two intersection branches validated against `input`
+///
+/// Objects are merged key by key, recursing into nested objects. When both
+/// branches produce a key, a value the branch changed (transformed) wins over
+/// one passed through unchanged from the input; if both changed it, the later
+/// branch wins. Keys a branch stripped stay stripped only if every branch
+/// dropped them.
+fn merge_outputs(input: &Value, acc: Value, next: Value) -> Value {
+    match (acc, next) {
+        (Value::Object(mut acc), Value::Object(next)) => {
+            for (key, next_value) in next {
+                let input_value = input.get(&key).unwrap_or(&Value::Null);
+                let merged = match acc.remove(&key) {
+                    Some(acc_value) => merge_outputs(input_value, acc_value, next_value),
+                    None => next_value,
+                };
+                acc.insert(key, merged);
+            }
+            Value::Object(acc)
+        }
+        (acc, next) => {
+            if next == *input && acc != *input {
+                acc
+            } else {
+                next
+            }
+        }
+    }
+}
+
 pub trait ErrorMessage {
     fn get_error_message(&self, code: &str) -> Option<String>;
 }
@@ -149,19 +772,505 @@ where
 
 pub trait HasErrorMessages {
     fn error_messages(&self) -> &HashMap<String, String>;
+
+    /// Report custom message templates that reference placeholders their
+    /// error code never fills, e.g. `{min_len}` instead of `{min_length}`
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        check_templates("", self.error_messages())
+    }
+}
+
+/// Validate a child value of an object or array: warnings and refinements
+/// raised meanwhile get `segment` prepended to their paths, and field
+/// hooks see it in the paths of the fields below
+pub(crate) fn nested<T, R>(segment: T, f: impl FnOnce() -> R) -> R
+where
+    T: Into<crate::error::PathSegment> + Copy,
+{
+    warnings::nested(segment, || refine::nested(segment, || hooks::nested(segment, f)))
+}
+
+/// [`Schema::validate_owned`] of a schema that does not move values through
+pub(crate) fn validate_owned_by_ref<S: Schema + ?Sized>(schema: &S, value: Value) -> Result<Value, ValidationError> {
+    if schema.preserves_input() {
+        schema.validate_ref(&value).map(|()| value)
+    } else {
+        schema.validate(&value)
+    }
+}
+
+/// Validate a union branch, dropping its warnings and refinements if it fails
+pub(crate) fn branch<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    warnings::branch(|| refine::branch(f))
+}
+
+/// Prefix the path of nested template issues with `segment`
+pub(crate) fn nest_issues(segment: &str, issues: Vec<MessageIssue>) -> Vec<MessageIssue> {
+    issues
+        .into_iter()
+        .map(|mut issue| {
+            issue.path = if issue.path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}.{}", segment, issue.path)
+            };
+            issue
+        })
+        .collect()
+}
+
+/// Message map for schemas that carry none of their own
+fn no_messages() -> &'static HashMap<String, String> {
+    static EMPTY: OnceLock<HashMap<String, String>> = OnceLock::new();
+    EMPTY.get_or_init(HashMap::new)
+}
+
+impl SchemaType {
+    /// Wrap a user-defined schema so it can be nested inside built-in ones
+    pub fn custom(schema: impl Schema + 'static) -> Self {
+        SchemaType::Custom(Arc::new(schema))
+    }
+}
+
+impl HasErrorMessages for SchemaType {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        match self {
+            SchemaType::String(s) => s.error_messages(),
+            SchemaType::Number(n) => n.error_messages(),
+            SchemaType::Boolean(b) => b.error_messages(),
+            SchemaType::Bytes(b) => b.error_messages(),
+            SchemaType::Array(a) => a.error_messages(),
+            SchemaType::Object(o) => o.error_messages(),
+            SchemaType::Union(u) => u.error_messages(),
+            SchemaType::Transform(t) => t.error_messages(),
+            SchemaType::Never(n) => n.error_messages(),
+            SchemaType::Not(n) => n.error_messages(),
+            SchemaType::Any(a) => a.error_messages(),
+            SchemaType::Sensitive(s) => s.error_messages(),
+            SchemaType::ErrorMapped(e) => e.error_messages(),
+            SchemaType::Cached(c) => c.error_messages(),
+            SchemaType::Deprecated(d) => d.error_messages(),
+            SchemaType::Catch(c) => c.error_messages(),
+            SchemaType::Pipe(p) => p.error_messages(),
+            SchemaType::MapOutput(m) => m.error_messages(),
+            SchemaType::Refined(r) => r.error_messages(),
+            SchemaType::Switch(s) => s.error_messages(),
+            SchemaType::Literal(l) => l.error_messages(),
+            SchemaType::Conditional(c) => c.error_messages(),
+            SchemaType::Custom(_) => no_messages(),
+            #[cfg(feature = "chrono")]
+            SchemaType::Date(d) => d.error_messages(),
+            #[cfg(feature = "chrono")]
+            SchemaType::DateTime(d) => d.error_messages(),
+            #[cfg(feature = "rust_decimal")]
+            SchemaType::Decimal(d) => d.error_messages(),
+        }
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        match self {
+            SchemaType::String(s) => s.check_messages(),
+            SchemaType::Number(n) => n.check_messages(),
+            SchemaType::Boolean(b) => b.check_messages(),
+            SchemaType::Bytes(b) => b.check_messages(),
+            SchemaType::Array(a) => a.check_messages(),
+            SchemaType::Object(o) => o.check_messages(),
+            SchemaType::Union(u) => u.check_messages(),
+            SchemaType::Transform(t) => t.check_messages(),
+            SchemaType::Never(n) => n.check_messages(),
+            SchemaType::Not(n) => n.check_messages(),
+            SchemaType::Any(a) => a.check_messages(),
+            SchemaType::Sensitive(s) => s.check_messages(),
+            SchemaType::ErrorMapped(e) => e.check_messages(),
+            SchemaType::Cached(c) => c.check_messages(),
+            SchemaType::Deprecated(d) => d.check_messages(),
+            SchemaType::Catch(c) => c.check_messages(),
+            SchemaType::Pipe(p) => p.check_messages(),
+            SchemaType::MapOutput(m) => m.check_messages(),
+            SchemaType::Refined(r) => r.check_messages(),
+            SchemaType::Switch(s) => s.check_messages(),
+            SchemaType::Literal(l) => l.check_messages(),
+            SchemaType::Conditional(c) => c.check_messages(),
+            SchemaType::Custom(_) => Vec::new(),
+            #[cfg(feature = "chrono")]
+            SchemaType::Date(d) => d.check_messages(),
+            #[cfg(feature = "chrono")]
+            SchemaType::DateTime(d) => d.check_messages(),
+            #[cfg(feature = "rust_decimal")]
+            SchemaType::Decimal(d) => d.check_messages(),
+        }
+    }
 }
 
 pub fn validate_schema_type(schema: &SchemaType, value: &Value) -> Result<Value, ValidationError> {
+    dispatch_validate(schema, value).map_err(|err| err.with_received(value))
+}
+
+fn dispatch_validate(schema: &SchemaType, value: &Value) -> Result<Value, ValidationError> {
     match schema {
         SchemaType::String(s) => s.validate(value),
         SchemaType::Number(n) => n.validate(value),
         SchemaType::Boolean(b) => b.validate(value),
+        SchemaType::Bytes(b) => b.validate(value),
         SchemaType::Array(a) => a.as_ref().validate(value),
         SchemaType::Object(o) => o.as_ref().validate(value),
         SchemaType::Union(u) => u.as_ref().validate(value),
+        SchemaType::Transform(t) => t.as_ref().validate(value),
+        SchemaType::Never(n) => n.validate(value),
+        SchemaType::Not(n) => n.validate(value),
+        SchemaType::Any(a) => a.validate(value),
+        SchemaType::Sensitive(s) => s.validate(value),
+        SchemaType::ErrorMapped(e) => e.validate(value),
+        SchemaType::Cached(c) => c.validate(value),
+        SchemaType::Deprecated(d) => d.validate(value),
+        SchemaType::Catch(c) => c.validate(value),
+        SchemaType::Pipe(p) => p.validate(value),
+        SchemaType::MapOutput(m) => m.validate(value),
+        SchemaType::Refined(r) => r.validate(value),
+        SchemaType::Switch(s) => s.validate(value),
+        SchemaType::Literal(l) => l.validate(value),
+        SchemaType::Conditional(c) => c.validate(value),
+        SchemaType::Custom(c) => c.validate(value),
+        #[cfg(feature = "chrono")]
+        SchemaType::Date(d) => d.validate(value),
+        #[cfg(feature = "chrono")]
+        SchemaType::DateTime(d) => d.validate(value),
+        #[cfg(feature = "rust_decimal")]
+        SchemaType::Decimal(d) => d.validate(value),
     }
 }
 
+impl Schema for SchemaType {
+    fn is_optional(&self) -> bool {
+        self.get_presence().allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        match self {
+            SchemaType::String(s) => s.get_presence(),
+            SchemaType::Number(n) => n.get_presence(),
+            SchemaType::Boolean(b) => b.get_presence(),
+            SchemaType::Bytes(b) => b.get_presence(),
+            SchemaType::Array(a) => a.get_presence(),
+            SchemaType::Object(o) => o.get_presence(),
+            SchemaType::Union(u) => u.get_presence(),
+            SchemaType::Transform(t) => t.get_presence(),
+            SchemaType::Never(n) => n.get_presence(),
+            SchemaType::Not(n) => n.get_presence(),
+            SchemaType::Any(a) => a.get_presence(),
+            SchemaType::Sensitive(s) => s.get_presence(),
+            SchemaType::ErrorMapped(e) => e.get_presence(),
+            SchemaType::Cached(c) => c.get_presence(),
+            SchemaType::Deprecated(d) => d.get_presence(),
+            SchemaType::Catch(c) => c.get_presence(),
+            SchemaType::Pipe(p) => p.get_presence(),
+            SchemaType::MapOutput(m) => m.get_presence(),
+            SchemaType::Refined(r) => r.get_presence(),
+            SchemaType::Switch(s) => s.get_presence(),
+            SchemaType::Literal(l) => l.get_presence(),
+            SchemaType::Conditional(c) => c.get_presence(),
+            SchemaType::Custom(c) => c.get_presence(),
+            #[cfg(feature = "chrono")]
+            SchemaType::Date(d) => d.get_presence(),
+            #[cfg(feature = "chrono")]
+            SchemaType::DateTime(d) => d.get_presence(),
+            #[cfg(feature = "rust_decimal")]
+            SchemaType::Decimal(d) => d.get_presence(),
+        }
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        validate_schema_type(self, value)
+    }
+
+    fn validate_owned(&self, value: Value) -> Result<Value, ValidationError> {
+        match (self, value) {
+            (SchemaType::Object(o), Value::Object(obj)) if o.validates_fields_apart() => o.validate_fields_owned(obj),
+            (SchemaType::Array(a), Value::Array(items)) if a.moves_items(&items) => a.validate_items_owned(items),
+            (SchemaType::Transform(t), value) => t.validate_owned(value),
+            (SchemaType::ErrorMapped(e), value) => e.validate_owned(value),
+            (_, value) => validate_owned_by_ref(self, value),
+        }
+    }
+
+    fn as_object(&self) -> Option<&ObjectSchema> {
+        match self {
+            SchemaType::Object(o) => Some(o),
+            _ => None,
+        }
+    }
+
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        let result = match self {
+            SchemaType::String(s) => s.validate_ref(value),
+            SchemaType::Bytes(b) => b.validate_ref(value),
+            SchemaType::Array(a) => a.as_ref().validate_ref(value),
+            SchemaType::Object(o) => o.as_ref().validate_ref(value),
+            SchemaType::Any(a) => a.validate_ref(value),
+            SchemaType::ErrorMapped(e) => e.validate_ref(value),
+            SchemaType::Deprecated(d) => d.validate_ref(value),
+            _ => dispatch_validate(self, value).map(drop),
+        };
+        result.map_err(|err| err.with_received(value))
+    }
+
+    fn preserves_input(&self) -> bool {
+        match self {
+            SchemaType::String(s) => s.preserves_input(),
+            SchemaType::Number(n) => n.preserves_input(),
+            SchemaType::Boolean(b) => b.preserves_input(),
+            SchemaType::Bytes(b) => b.preserves_input(),
+            SchemaType::Array(a) => a.preserves_input(),
+            SchemaType::Object(o) => o.preserves_input(),
+            SchemaType::Union(u) => u.preserves_input(),
+            SchemaType::Never(n) => n.preserves_input(),
+            SchemaType::Any(a) => a.preserves_input(),
+            SchemaType::Literal(l) => l.preserves_input(),
+            SchemaType::Sensitive(s) => s.preserves_input(),
+            SchemaType::ErrorMapped(e) => e.preserves_input(),
+            SchemaType::Cached(c) => c.preserves_input(),
+            SchemaType::Deprecated(d) => d.preserves_input(),
+            SchemaType::Refined(r) => r.preserves_input(),
+            _ => false,
+        }
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let mut output = match self {
+            SchemaType::String(s) => s.validate_partial_with(value, invalid),
+            SchemaType::Array(a) => a.validate_partial_with(value, invalid),
+            SchemaType::Object(o) => o.validate_partial_with(value, invalid),
+            SchemaType::Transform(t) => t.validate_partial_with(value, invalid),
+            SchemaType::Custom(c) => c.validate_partial_with(value, invalid),
+            SchemaType::Switch(s) => s.validate_partial_with(value, invalid),
+            SchemaType::Conditional(c) => c.validate_partial_with(value, invalid),
+            SchemaType::ErrorMapped(e) => e.validate_partial_with(value, invalid),
+            SchemaType::Cached(c) => c.validate_partial_with(value, invalid),
+            SchemaType::Deprecated(d) => d.validate_partial_with(value, invalid),
+            SchemaType::Catch(c) => c.validate_partial_with(value, invalid),
+            SchemaType::Pipe(p) => p.validate_partial_with(value, invalid),
+            SchemaType::MapOutput(m) => m.validate_partial_with(value, invalid),
+            SchemaType::Refined(r) => r.validate_partial_with(value, invalid),
+            _ => PartialOutput::from_result(self.validate(value), value, invalid),
+        };
+        output.errors = output.errors.into_iter().map(|err| err.with_received(value)).collect();
+        output
+    }
+
+    fn revalidate(&self, old: &Value, new: &Value, previous: &PartialOutput) -> PartialOutput {
+        match self {
+            SchemaType::Object(o) if old != new => {
+                let mut output = o.revalidate(old, new, previous);
+                output.errors = output.errors.into_iter().map(|err| err.with_received(new)).collect();
+                output
+            }
+            _ if old == new => previous.clone(),
+            _ => self.validate_partial(new),
+        }
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        match self {
+            SchemaType::Array(a) => a.redact(value),
+            SchemaType::Object(o) => o.redact(value),
+            SchemaType::Transform(t) => t.schema.redact(value),
+            SchemaType::Sensitive(s) => s.redact(value),
+            SchemaType::ErrorMapped(e) => e.redact(value),
+            SchemaType::Cached(c) => c.redact(value),
+            SchemaType::Deprecated(d) => d.redact(value),
+            SchemaType::Catch(c) => c.redact(value),
+            SchemaType::Pipe(p) => p.redact(value),
+            SchemaType::MapOutput(m) => m.redact(value),
+            SchemaType::Refined(r) => r.redact(value),
+            SchemaType::Switch(s) => s.redact(value),
+            SchemaType::Conditional(c) => c.redact(value),
+            SchemaType::Custom(c) => c.redact(value),
+            _ => value.clone(),
+        }
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        match self {
+            SchemaType::Array(a) => a.canonical(output),
+            SchemaType::Object(o) => o.canonical(output),
+            SchemaType::Transform(t) => t.canonical(output),
+            SchemaType::Sensitive(s) => s.canonical(output),
+            SchemaType::ErrorMapped(e) => e.canonical(output),
+            SchemaType::Cached(c) => c.canonical(output),
+            SchemaType::Deprecated(d) => d.canonical(output),
+            SchemaType::Catch(c) => c.canonical(output),
+            SchemaType::Pipe(p) => p.canonical(output),
+            SchemaType::MapOutput(m) => m.canonical(output),
+            SchemaType::Refined(r) => r.canonical(output),
+            SchemaType::Switch(s) => s.canonical(output),
+            SchemaType::Conditional(c) => c.canonical(output),
+            SchemaType::Custom(c) => c.canonical(output),
+            _ => canonical::normalize(output),
+        }
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        match self {
+            SchemaType::String(s) => s.check_satisfiable(),
+            SchemaType::Number(n) => n.check_satisfiable(),
+            SchemaType::Bytes(b) => b.check_satisfiable(),
+            SchemaType::Array(a) => a.check_satisfiable(),
+            SchemaType::Object(o) => o.check_satisfiable(),
+            SchemaType::Union(u) => u.check_satisfiable(),
+            SchemaType::Transform(t) => t.check_satisfiable(),
+            SchemaType::Sensitive(s) => s.check_satisfiable(),
+            SchemaType::ErrorMapped(e) => e.check_satisfiable(),
+            SchemaType::Cached(c) => c.check_satisfiable(),
+            SchemaType::Deprecated(d) => d.check_satisfiable(),
+            SchemaType::Pipe(p) => p.check_satisfiable(),
+            SchemaType::MapOutput(m) => m.check_satisfiable(),
+            SchemaType::Refined(r) => r.check_satisfiable(),
+            SchemaType::Custom(c) => c.check_satisfiable(),
+            #[cfg(feature = "chrono")]
+            SchemaType::Date(d) => d.check_satisfiable(),
+            #[cfg(feature = "chrono")]
+            SchemaType::DateTime(d) => d.check_satisfiable(),
+            #[cfg(feature = "rust_decimal")]
+            SchemaType::Decimal(d) => d.check_satisfiable(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        match self {
+            SchemaType::String(s) => s.explain(),
+            SchemaType::Number(n) => n.explain(),
+            SchemaType::Boolean(b) => b.explain(),
+            SchemaType::Bytes(b) => b.explain(),
+            SchemaType::Array(a) => a.explain(),
+            SchemaType::Object(o) => o.explain(),
+            SchemaType::Union(u) => u.explain(),
+            SchemaType::Transform(t) => t.explain(),
+            SchemaType::Never(n) => n.explain(),
+            SchemaType::Not(n) => n.explain(),
+            SchemaType::Any(a) => a.explain(),
+            SchemaType::Sensitive(s) => s.explain(),
+            SchemaType::ErrorMapped(e) => e.explain(),
+            SchemaType::Cached(c) => c.explain(),
+            SchemaType::Deprecated(d) => d.explain(),
+            SchemaType::Catch(c) => c.explain(),
+            SchemaType::Pipe(p) => p.explain(),
+            SchemaType::MapOutput(m) => m.explain(),
+            SchemaType::Refined(r) => r.explain(),
+            SchemaType::Switch(s) => s.explain(),
+            SchemaType::Literal(l) => l.explain(),
+            SchemaType::Conditional(c) => c.explain(),
+            SchemaType::Custom(c) => c.explain(),
+            #[cfg(feature = "chrono")]
+            SchemaType::Date(d) => d.explain(),
+            #[cfg(feature = "chrono")]
+            SchemaType::DateTime(d) => d.explain(),
+            #[cfg(feature = "rust_decimal")]
+            SchemaType::Decimal(d) => d.explain(),
+        }
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        match self {
+            SchemaType::String(s) => s.lint(),
+            SchemaType::Array(a) => a.lint(),
+            SchemaType::Object(o) => o.lint(),
+            SchemaType::Union(u) => u.lint(),
+            SchemaType::Transform(t) => t.lint(),
+            SchemaType::Not(n) => n.lint(),
+            SchemaType::Sensitive(s) => s.lint(),
+            SchemaType::ErrorMapped(e) => e.lint(),
+            SchemaType::Cached(c) => c.lint(),
+            SchemaType::Deprecated(d) => d.lint(),
+            SchemaType::Catch(c) => c.lint(),
+            SchemaType::Pipe(p) => p.lint(),
+            SchemaType::MapOutput(m) => m.lint(),
+            SchemaType::Refined(r) => r.lint(),
+            SchemaType::Switch(s) => s.lint(),
+            SchemaType::Conditional(c) => c.lint(),
+            SchemaType::Custom(c) => c.lint(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        match self {
+            SchemaType::String(s) => s.missing_message(),
+            SchemaType::Number(n) => n.missing_message(),
+            SchemaType::Boolean(b) => b.missing_message(),
+            SchemaType::Bytes(b) => b.missing_message(),
+            SchemaType::Array(a) => a.missing_message(),
+            SchemaType::Object(o) => o.missing_message(),
+            SchemaType::Union(u) => u.missing_message(),
+            SchemaType::Transform(t) => t.missing_message(),
+            SchemaType::Never(n) => n.missing_message(),
+            SchemaType::Not(n) => n.missing_message(),
+            SchemaType::Any(a) => a.missing_message(),
+            SchemaType::Sensitive(s) => s.missing_message(),
+            SchemaType::ErrorMapped(e) => e.missing_message(),
+            SchemaType::Cached(c) => c.missing_message(),
+            SchemaType::Deprecated(d) => d.missing_message(),
+            SchemaType::Catch(c) => c.missing_message(),
+            SchemaType::Pipe(p) => p.missing_message(),
+            SchemaType::MapOutput(m) => m.missing_message(),
+            SchemaType::Refined(r) => r.missing_message(),
+            SchemaType::Switch(s) => s.missing_message(),
+            SchemaType::Literal(l) => l.missing_message(),
+            SchemaType::Conditional(c) => c.missing_message(),
+            SchemaType::Custom(c) => c.missing_message(),
+            #[cfg(feature = "chrono")]
+            SchemaType::Date(d) => d.missing_message(),
+            #[cfg(feature = "chrono")]
+            SchemaType::DateTime(d) => d.missing_message(),
+            #[cfg(feature = "rust_decimal")]
+            SchemaType::Decimal(d) => d.missing_message(),
+        }
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        self
+    }
+}
+
+const fn assert_send_sync<T: Send + Sync>() {}
+
+const _: () = {
+    assert_send_sync::<SchemaType>();
+    assert_send_sync::<string::StringSchemaImpl>();
+    assert_send_sync::<NumberSchema>();
+    assert_send_sync::<BooleanSchema>();
+    assert_send_sync::<BytesSchema>();
+    assert_send_sync::<ArraySchema>();
+    assert_send_sync::<ObjectSchema>();
+    assert_send_sync::<UnionSchema>();
+    assert_send_sync::<UnionStrategy>();
+    assert_send_sync::<Transform>();
+    assert_send_sync::<NeverSchema>();
+    assert_send_sync::<NotSchema>();
+    assert_send_sync::<AnySchema>();
+    assert_send_sync::<Sensitive<SchemaType>>();
+    assert_send_sync::<ErrorMapped<SchemaType>>();
+    assert_send_sync::<Cached<SchemaType>>();
+    assert_send_sync::<Deprecated<SchemaType>>();
+    assert_send_sync::<Catch<SchemaType>>();
+    assert_send_sync::<Pipe<SchemaType, SchemaType>>();
+    assert_send_sync::<MapOutput<SchemaType>>();
+    assert_send_sync::<Refined<SchemaType>>();
+    assert_send_sync::<CompiledSchema>();
+    assert_send_sync::<SwitchSchema>();
+    assert_send_sync::<LiteralSchema>();
+    assert_send_sync::<ConditionalSchema>();
+    assert_send_sync::<ValidationContext>();
+    assert_send_sync::<WithTransform<SchemaType>>();
+    assert_send_sync::<ValidationError>();
+    #[cfg(feature = "chrono")]
+    assert_send_sync::<DateSchema>();
+    #[cfg(feature = "chrono")]
+    assert_send_sync::<DateTimeSchema>();
+    #[cfg(feature = "rust_decimal")]
+    assert_send_sync::<DecimalSchema>();
+};
+
 pub fn get_type_name(value: &Value) -> &'static str {
     match value {
         Value::Null => "null",
@@ -179,6 +1288,17 @@ mod tests {
     use serde_json::json;
     use crate::{string, number};
 
+    #[test]
+    fn test_schema_type_keeps_transforms() {
+        let trimmed = string().trim().min_length(2).into_schema_type();
+        assert!(matches!(trimmed, SchemaType::Transform(_)));
+        assert_eq!(trimmed.validate(&json!("  ok  ")).unwrap(), json!("ok"));
+
+        let union = UnionSchema::new(vec![trimmed, number().into_schema_type()]);
+        assert_eq!(union.validate(&json!(" hi ")).unwrap(), json!("hi"));
+        assert!(matches!(string().into_schema_type(), SchemaType::String(_)));
+    }
+
     #[test]
     fn test_type_name() {
         assert_eq!(get_type_name(&json!(null)), "null");
@@ -213,6 +1333,23 @@ mod tests {
         assert!(schema.validate(&json!("hello world")).is_err());  // too long
     }
 
+    #[test]
+    fn test_union_all_merges_outputs() {
+        let schema = UnionSchema::new(vec![
+            ObjectSchema::default().field("name", string().trim()).passthrough().into_schema_type(),
+            ObjectSchema::default().field("email", string().to_lowercase()).passthrough().into_schema_type(),
+        ]).strategy(UnionStrategy::All);
+
+        let output = schema.validate(&json!({"name": "  Ada ", "email": "ADA@X.IO", "extra": 1})).unwrap();
+        assert_eq!(output, json!({"name": "Ada", "email": "ada@x.io", "extra": 1}));
+
+        let both = UnionSchema::new(vec![
+            string().to_lowercase().into_schema_type(),
+            string().to_uppercase().into_schema_type(),
+        ]).strategy(UnionStrategy::All);
+        assert_eq!(both.validate(&json!("aB")).unwrap(), json!("AB"));
+    }
+
     #[test]
     fn test_union_best_match() {
         let schema = UnionSchema::new(vec![
@@ -239,4 +1376,82 @@ mod tests {
         let err = schema.validate(&json!("1234")).unwrap_err();
         assert_eq!(err.context.code, "string.too_short");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_schema_shared_across_threads() {
+        let schema = Arc::new(UnionSchema::new(vec![
+            string().min_length(2).into_schema_type(),
+            number().min(0.0).into_schema_type(),
+        ]));
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let schema = Arc::clone(&schema);
+                std::thread::spawn(move || schema.validate(&json!(i)).is_ok())
+            })
+            .collect();
+
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
+        assert_eq!(schema.validate(&json!("ok")).unwrap(), json!("ok"));
+    }
+
+    struct Even;
+
+    impl Schema for Even {
+        fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+            match value.as_i64() {
+                Some(n) if n % 2 == 0 => Ok(value.clone()),
+                _ => Err(ValidationError::new("even.invalid").message("Must be even")),
+            }
+        }
+
+        fn into_schema_type(self) -> SchemaType {
+            SchemaType::custom(self)
+        }
+    }
+
+    #[test]
+    fn test_custom_schema_nests_in_builtins() {
+        let schema = ObjectSchema::default()
+            .field("id", Even)
+            .field("pairs", ArraySchema::new(Even));
+
+        assert!(schema.validate(&json!({"id": 2, "pairs": [0, 4]})).is_ok());
+
+        let err = schema.validate(&json!({"id": 2, "pairs": [0, 3]})).unwrap_err();
+        assert_eq!(err.context.code, "even.invalid");
+        assert_eq!(err.context.path, "pairs.1");
+
+        let union = UnionSchema::new(vec![Even.into_schema_type(), string().into_schema_type()]);
+        assert!(union.validate(&json!("x")).is_ok());
+        assert!(union.validate(&json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_error_received_value() {
+        let schema = crate::object()
+            .field("age", number().min(0.0))
+            .field("bio", string().max_length(3))
+            .field("tags", crate::array(string()).max_items(1));
+
+        let err = schema.validate(&json!({"age": -4, "bio": "ok", "tags": []})).unwrap_err();
+        assert_eq!(err.context.details.received, Some(json!(-4)));
+        assert_eq!(err.to_json()["context"]["details"]["received"], json!(-4));
+
+        let long = "x".repeat(100);
+        let err = schema.validate(&json!({"age": 1, "bio": long})).unwrap_err();
+        assert_eq!(err.context.details.received, Some(json!(format!("{}…", "x".repeat(64)))));
+
+        let err = schema.validate(&json!({"age": 1, "bio": "ok", "tags": ["a", "b"]})).unwrap_err();
+        assert_eq!(err.context.details.received, Some(json!("<array of 2 items>")));
+
+        let missing = schema.validate(&json!({"bio": "ok"})).unwrap_err();
+        assert_eq!(missing.context.details.received, None);
+
+        let partial = schema.into_schema_type().validate_partial(&json!({"age": "x", "bio": "long"}));
+        assert_eq!(partial.errors[0].context.details.received, Some(json!("x")));
+        assert_eq!(partial.errors[1].context.details.received, Some(json!("long")));
+    }
+}
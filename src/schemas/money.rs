@@ -0,0 +1,185 @@
+use std::{collections::HashMap, sync::Arc};
+use serde_json::{Number, Value};
+
+use crate::error::{CustomIssue, ErrorCode};
+use super::{NumberSchema, ObjectSchema, Transformable, string::StringSchemaImpl};
+
+/// Active ISO 4217 currency codes, sorted
+const ISO_4217: &[&str] = &[
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT", "BGN",
+    "BHD", "BIF", "BMD", "BND", "BOB", "BOV", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD", "CDF",
+    "CHE", "CHF", "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUC", "CUP", "CVE", "CZK", "DJF",
+    "DKK", "DOP", "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD",
+    "GNF", "GTQ", "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD",
+    "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR",
+    "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRU", "MUR", "MVR", "MWK",
+    "MXN", "MXV", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB", "PEN", "PGK",
+    "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF", "SAR", "SBD", "SCR", "SDG", "SEK",
+    "SGD", "SHP", "SLE", "SLL", "SOS", "SRD", "SSP", "STN", "SVC", "SYP", "SZL", "THB", "TJS", "TMT",
+    "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD", "USN", "UYI", "UYU", "UYW", "UZS",
+    "VED", "VES", "VND", "VUV", "WST", "XAF", "XCD", "XCG", "XOF", "XPF", "YER", "ZAR", "ZMW", "ZWG",
+    "ZWL",
+];
+
+/// ISO 4217 currencies whose minor unit is not the usual two decimal places
+const MINOR_UNIT_EXCEPTIONS: &[(&str, u32)] = &[
+    ("BHD", 3), ("BIF", 0), ("CLF", 4), ("CLP", 0), ("DJF", 0), ("GNF", 0), ("IQD", 3), ("ISK", 0),
+    ("JOD", 3), ("JPY", 0), ("KMF", 0), ("KRW", 0), ("KWD", 3), ("LYD", 3), ("OMR", 3), ("PYG", 0),
+    ("RWF", 0), ("TND", 3), ("UGX", 0), ("UYI", 0), ("UYW", 4), ("VND", 0), ("VUV", 0), ("XAF", 0),
+    ("XOF", 0), ("XPF", 0),
+];
+
+/// Options for [`money_with`](crate::money_with)
+///
+/// An amount may have at most as many decimal places as its currency's
+/// minor unit: two for `USD`, none for `JPY`, three for `KWD`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MoneyPolicy {
+    /// Currencies accepted; every ISO 4217 code when empty
+    pub currencies: Vec<String>,
+    /// Decimal places per currency, overriding ISO 4217 or adding codes it
+    /// lacks, e.g. `("BTC", 8)`
+    pub minor_units: HashMap<String, u32>,
+    pub allow_negative: bool,
+}
+
+impl MoneyPolicy {
+    /// Decimal places `currency` allows, or `None` if it is not accepted
+    pub fn minor_units(&self, currency: &str) -> Option<u32> {
+        if !self.currencies.is_empty() && !self.currencies.iter().any(|c| c == currency) {
+            return None;
+        }
+        if let Some(units) = self.minor_units.get(currency) {
+            return Some(*units);
+        }
+        ISO_4217.binary_search(&currency).ok()?;
+        let exception = MINOR_UNIT_EXCEPTIONS.iter().find(|(code, _)| *code == currency);
+        Some(exception.map_or(2, |(_, units)| *units))
+    }
+
+    /// Object schema for `{ "amount": ..., "currency": ... }`
+    pub(crate) fn schema(self) -> ObjectSchema {
+        let policy = Arc::new(self);
+        let mut amount = NumberSchema::default();
+        if !policy.allow_negative {
+            amount = amount.min(0.0);
+        }
+        let currencies = policy.clone();
+        let currency = StringSchemaImpl::default()
+            .to_uppercase()
+            .custom(move |code| match currencies.minor_units(code) {
+                Some(_) => Ok(()),
+                None => Err(CustomIssue::from(ErrorCode::MoneyCurrency)),
+            });
+        ObjectSchema::default()
+            .field("amount", amount.transform(normalize_amount))
+            .field("currency", currency)
+            .rule(["currency", "amount"], move |obj| {
+                let currency = obj["currency"].as_str().unwrap_or_default();
+                let (Some(units), Value::Number(amount)) = (policy.minor_units(currency), &obj["amount"]) else {
+                    return Ok(());
+                };
+                if decimal_places(amount) <= units {
+                    return Ok(());
+                }
+                Err(CustomIssue::from(ErrorCode::MoneyPrecision)
+                    .with_details(|d| d.expected = Some(units.to_string())))
+            })
+    }
+}
+
+/// String amounts such as `" 1,234.50 "` as JSON numbers, integers kept
+/// integral; anything unparsable is left for the number schema to reject
+fn normalize_amount(value: Value) -> Value {
+    let Value::String(s) = &value else {
+        return value;
+    };
+    let s = s.trim();
+    let s = s.strip_prefix('+').unwrap_or(s);
+    let digits = if is_grouped(s) { s.replace(',', "") } else { s.to_string() };
+    if let Ok(int) = digits.parse::<i64>() {
+        return Value::Number(int.into());
+    }
+    let valid = !digits.is_empty() && digits.trim_start_matches('-').chars().all(|c| c.is_ascii_digit() || c == '.');
+    match digits.parse::<f64>().ok().and_then(Number::from_f64) {
+        Some(n) if valid => Value::Number(n),
+        _ => value,
+    }
+}
+
+/// Whether `s` groups its integer digits by thousands, e.g. `-12,345.6`
+fn is_grouped(s: &str) -> bool {
+    let int = s.trim_start_matches('-').split('.').next().unwrap_or_default();
+    let mut groups = int.split(',');
+    let first = groups.next().unwrap_or_default();
+    int.contains(',')
+        && (1..=3).contains(&first.len())
+        && groups.all(|g| g.len() == 3)
+        && int.chars().all(|c| c.is_ascii_digit() || c == ',')
+}
+
+/// Decimal places in the shortest representation of `n`, e.g. `2` for `0.25`
+fn decimal_places(n: &Number) -> u32 {
+    if !n.is_f64() {
+        return 0;
+    }
+    let repr = n.to_string();
+    let (mantissa, exponent) = match repr.split_once(['e', 'E']) {
+        Some((m, e)) => (m, e.parse::<i64>().unwrap_or_default()),
+        None => (repr.as_str(), 0),
+    };
+    let fraction = mantissa.split_once('.').map_or(0, |(_, f)| f.trim_end_matches('0').len() as i64);
+    (fraction - exponent).max(0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{money, money_with};
+    use crate::schemas::Schema;
+
+    #[test]
+    fn test_money() {
+        let schema = money();
+        let output = schema.validate(&json!({"amount": " 1,234.50 ", "currency": "usd"})).unwrap();
+        assert_eq!(output, json!({"amount": 1234.5, "currency": "USD"}));
+        assert_eq!(schema.validate(&json!({"amount": "500", "currency": "JPY"})).unwrap()["amount"], 500);
+
+        let err = schema.validate(&json!({"amount": 10.5, "currency": "JPY"})).unwrap_err();
+        assert_eq!(err.context.code, "money.precision");
+        assert_eq!(err.context.path, "amount");
+        assert_eq!(err.to_string(), "Amounts in this currency allow at most 0 decimal places");
+        assert!(schema.validate(&json!({"amount": "1.125", "currency": "KWD"})).is_ok());
+        assert!(schema.validate(&json!({"amount": 0.1 + 0.2, "currency": "EUR"})).is_err());
+
+        let err = schema.validate(&json!({"amount": 1, "currency": "XYZ"})).unwrap_err();
+        assert_eq!(err.context.code, "money.currency");
+        assert_eq!(err.context.path, "currency");
+        assert_eq!(schema.validate(&json!({"amount": -1, "currency": "EUR"})).unwrap_err().context.code, "number.too_small");
+        assert!(schema.validate(&json!({"amount": "1,23", "currency": "EUR"})).is_err());
+        assert!(schema.validate(&json!({"amount": "1e3", "currency": "EUR"})).is_err());
+    }
+
+    #[test]
+    fn test_money_policy() {
+        let schema = money_with(MoneyPolicy {
+            currencies: vec!["EUR".into(), "BTC".into()],
+            minor_units: HashMap::from([("BTC".to_string(), 8)]),
+            allow_negative: true,
+        });
+        assert!(schema.validate(&json!({"amount": "-0.00000001", "currency": "BTC"})).is_ok());
+        assert!(schema.validate(&json!({"amount": 0.000000001, "currency": "BTC"})).is_err());
+        assert!(schema.validate(&json!({"amount": 1, "currency": "USD"})).is_err());
+    }
+
+    #[test]
+    fn test_decimal_places() {
+        let places = |v: Value| decimal_places(v.as_number().unwrap());
+        assert_eq!(places(json!(12)), 0);
+        assert_eq!(places(json!(12.0)), 0);
+        assert_eq!(places(json!(0.25)), 2);
+        assert_eq!(places(json!(1e-7)), 7);
+        assert_eq!(places(json!(1.5e21)), 0);
+    }
+}
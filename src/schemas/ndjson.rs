@@ -0,0 +1,108 @@
+use std::io::{BufRead, BufReader, Read};
+use serde_json::Value;
+
+use crate::error::{ErrorCode, ValidationError};
+use super::Schema;
+
+/// Lines of [`Schema::validate_ndjson`], each validated output or the
+/// 1-based line number with the error found on it
+///
+/// Lines are read one at a time, so memory use does not grow with the
+/// input. Blank lines are skipped. Errors carry source spans relative to
+/// their line, as with [`Schema::validate_str`]. A line that is not UTF-8
+/// fails as `parse.invalid_json` and reading goes on; an I/O error ends
+/// the iteration after being reported.
+///
+/// ```
+/// use rusty_zod::prelude::*;
+///
+/// let schema = object().field("id", number());
+/// let lines: Vec<_> = schema.validate_ndjson("{\"id\": 1}\n\n{\"id\": \"x\"}\n".as_bytes()).collect();
+/// assert!(lines[0].is_ok());
+/// assert_eq!(lines[1].as_ref().unwrap_err().0, 3);
+/// ```
+pub struct NdjsonLines<'a, S, R> {
+    schema: &'a S,
+    reader: BufReader<R>,
+    buf: Vec<u8>,
+    line: usize,
+    done: bool,
+}
+
+impl<'a, S: Schema, R: Read> NdjsonLines<'a, S, R> {
+    pub(crate) fn new(schema: &'a S, reader: R) -> Self {
+        Self { schema, reader: BufReader::new(reader), buf: Vec::new(), line: 0, done: false }
+    }
+}
+
+impl<S: Schema, R: Read> Iterator for NdjsonLines<'_, S, R> {
+    type Item = Result<Value, (usize, ValidationError)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buf.clear();
+            let read = self.reader.read_until(b'\n', &mut self.buf);
+            self.line += 1;
+            let result = match read {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(_) => match std::str::from_utf8(&self.buf) {
+                    Ok(text) if text.trim().is_empty() => continue,
+                    Ok(text) => self.schema.validate_str(text.trim()),
+                    Err(e) => Err(invalid_json(e)),
+                },
+                Err(e) => {
+                    self.done = true;
+                    Err(invalid_json(e))
+                }
+            };
+            return Some(result.map_err(|err| (self.line, err)));
+        }
+        None
+    }
+}
+
+fn invalid_json(e: impl std::fmt::Display) -> ValidationError {
+    ValidationError::new(ErrorCode::InvalidJson).message(format!("Invalid JSON: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read};
+    use crate::{number, object, string};
+    use crate::schemas::Schema;
+
+    /// Reader yielding its bytes and then failing
+    struct Failing<'a>(&'a [u8]);
+
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::ConnectionReset, "connection reset"));
+            }
+            self.0.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_validate_ndjson() {
+        let schema = object().field("id", number()).field("name", string());
+        let input = b"{\"id\": 1, \"name\": \"a\"}\r\n\n{\"id\": \"x\", \"name\": \"b\"}\n{\"id\": \xff}\n{\"id\": 3,\n{\"id\": 4, \"name\": \"d\"}";
+        let lines: Vec<_> = schema.validate_ndjson(&input[..]).collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0].as_ref().unwrap()["name"], "a");
+        let (line, err) = lines[1].as_ref().unwrap_err();
+        assert_eq!((*line, err.context.path.to_string()), (3, "id".to_string()));
+        assert!(err.context.details.span.is_some());
+        assert_eq!(lines[2].as_ref().unwrap_err().0, 4);
+        let (line, err) = lines[3].as_ref().unwrap_err();
+        assert_eq!((*line, err.context.code.as_str()), (5, "parse.invalid_json"));
+        assert_eq!(lines[4].as_ref().unwrap()["id"], 4);
+
+        let lines: Vec<_> = schema.validate_ndjson(Failing(b"{\"id\": 1, \"name\": \"a\"}\n")).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].as_ref().unwrap_err().0, 2);
+    }
+}
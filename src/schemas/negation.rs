@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{ValidationError, MessageIssue, ErrorCode, canonical_code};
+use crate::error::message_check::{check_templates, debug_check_template};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, explain, LintFinding, validate_schema_type};
+
+/// Schema that rejects every value
+///
+/// As an optional object field it forbids the key from being present.
+#[derive(Clone, Default)]
+pub struct NeverSchema {
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl NeverSchema {
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+}
+
+impl HasErrorMessages for NeverSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+}
+
+impl Schema for NeverSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn preserves_input(&self) -> bool {
+        true
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        vec![Explanation::typed("never", self.presence)]
+    }
+
+    fn validate(&self, _value: &Value) -> Result<Value, ValidationError> {
+        let mut err = ValidationError::new(ErrorCode::NeverValid);
+        if let Some(msg) = self.error_messages.get("never.invalid") {
+            err = err.custom_message(msg.clone());
+        }
+        Err(err)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Never(self)
+    }
+}
+
+/// Schema that succeeds only when the inner schema fails
+#[derive(Clone)]
+pub struct NotSchema {
+    schema: Box<SchemaType>,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl NotSchema {
+    pub fn new(schema: impl Schema) -> Self {
+        Self {
+            schema: Box::new(schema.into_schema_type()),
+            presence: Presence::Required,
+            error_messages: HashMap::new(),
+        }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+}
+
+impl NotSchema {
+    /// Rewrite the negated schema in place
+    pub(crate) fn map_schema(&mut self, f: impl FnOnce(SchemaType) -> SchemaType) {
+        let taken = std::mem::replace(self.schema.as_mut(), SchemaType::Never(NeverSchema::default()));
+        *self.schema = f(taken);
+    }
+}
+
+impl HasErrorMessages for NotSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = check_templates("", &self.error_messages);
+        issues.extend(self.schema.check_messages());
+        issues
+    }
+}
+
+impl Schema for NotSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("any value", self.presence);
+        explanation.rule(format!("must not be {}", explain::root_summary(&self.schema.explain())));
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        if value.is_null() && self.presence.allows_null() {
+            return Ok(value.clone());
+        }
+        match validate_schema_type(&self.schema, value) {
+            Err(_) => Ok(value.clone()),
+            Ok(_) => {
+                let mut err = ValidationError::new(ErrorCode::NotMatched);
+                if let Some(msg) = self.error_messages.get("not.matched") {
+                    err = err.custom_message(msg.clone());
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        self.schema.lint()
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Not(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::schemas::{string::{StringSchema, StringSchemaImpl}, NumberSchema, ObjectSchema, UnionSchema};
+
+    #[test]
+    fn test_never() {
+        let schema = NeverSchema::default();
+
+        assert_eq!(schema.validate(&json!(1)).unwrap_err().context.code, "never.invalid");
+        assert!(schema.validate(&json!(null)).is_err());
+    }
+
+    #[test]
+    fn test_never_forbids_key() {
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default())
+            .optional_field("legacy_id", NeverSchema::default());
+
+        assert!(schema.validate(&json!({"name": "x"})).is_ok());
+        let err = schema.validate(&json!({"name": "x", "legacy_id": 1})).unwrap_err();
+        assert_eq!(err.context.code, "never.invalid");
+        assert_eq!(err.context.path, "legacy_id");
+    }
+
+    #[test]
+    fn test_not() {
+        let schema = NotSchema::new(StringSchemaImpl::default().email());
+
+        assert_eq!(schema.validate(&json!("plain")).unwrap(), json!("plain"));
+        assert_eq!(schema.validate(&json!(42)).unwrap(), json!(42));
+        assert_eq!(schema.validate(&json!("a@b.co")).unwrap_err().context.code, "not.matched");
+    }
+
+    #[test]
+    fn test_not_closes_union_branch() {
+        let schema = UnionSchema::new(vec![
+            NotSchema::new(NumberSchema::default().max(0.0)).into_schema_type(),
+        ]).strategy(crate::UnionStrategy::All);
+
+        assert!(schema.validate(&json!(5)).is_ok());
+        assert!(schema.validate(&json!(-5)).is_err());
+    }
+}
@@ -1,45 +1,179 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::{Bound, RangeBounds}, sync::Arc};
 use serde_json::Value;
 
-use crate::error::ValidationError;
-use super::{Schema, SchemaType, HasErrorMessages, get_type_name, transform::{Transformable, Transform, WithTransform}};
+use crate::error::{ValidationError, ErrorCode, CustomIssue, canonical_code};
+use crate::error::message_check::debug_check_template;
+use crate::error::PathSegment;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, Unsatisfiable, UnsatisfiableKind, satisfiable, compat, get_type_name, transform::{Transformable, Transform, WithTransform}};
+use super::refine::{self, Refinement, RefinementContext};
 
-#[derive(Clone)]
+type CustomValidator = Arc<dyn Fn(f64) -> Result<(), CustomIssue> + Send + Sync>;
+type Bounds = (Bound<f64>, Bound<f64>);
+
+/// Bounds picked by a sibling field's value, see [`NumberSchema::scaled_by_field`]
+struct FieldRanges {
+    field: String,
+    ranges: Vec<(Value, Bounds)>,
+}
+
+#[derive(Clone, Default)]
 pub struct NumberSchema {
     min: Option<f64>,
     max: Option<f64>,
+    min_exclusive: bool,
+    max_exclusive: bool,
     integer: bool,
+    integer64: bool,
+    int_min: Option<i128>,
+    int_max: Option<i128>,
     coerce: bool,
-    optional: bool,
+    multiple_of: Option<f64>,
+    allowed: Option<Vec<f64>>,
+    epsilon: f64,
+    presence: Presence,
     error_messages: HashMap<String, String>,
-}
-
-impl Default for NumberSchema {
-    fn default() -> Self {
-        Self {
-            min: None,
-            max: None,
-            integer: false,
-            coerce: false,
-            optional: false,
-            error_messages: HashMap::new(),
-        }
-    }
+    custom_validators: Vec<CustomValidator>,
+    scaled_by: Option<Arc<FieldRanges>>,
 }
 
 impl NumberSchema {
     pub fn min(mut self, value: f64) -> Self {
+        self.set_min(value);
+        self
+    }
+
+    /// In-place form of [`NumberSchema::min`]
+    pub fn set_min(&mut self, value: f64) -> &mut Self {
         self.min = Some(value);
+        self.min_exclusive = false;
         self
     }
 
     pub fn max(mut self, value: f64) -> Self {
+        self.set_max(value);
+        self
+    }
+
+    /// In-place form of [`NumberSchema::max`]
+    pub fn set_max(&mut self, value: f64) -> &mut Self {
         self.max = Some(value);
+        self.max_exclusive = false;
+        self
+    }
+
+    /// Configured `(min, max)`, whether inclusive or exclusive
+    pub(crate) fn bounds(&self) -> (Option<f64>, Option<f64>) {
+        (self.min, self.max)
+    }
+
+    /// Value must be strictly greater than `value`
+    pub fn gt(mut self, value: f64) -> Self {
+        self.min = Some(value);
+        self.min_exclusive = true;
+        self
+    }
+
+    /// Alias for [`NumberSchema::min`]
+    pub fn gte(self, value: f64) -> Self {
+        self.min(value)
+    }
+
+    /// Value must be strictly less than `value`
+    pub fn lt(mut self, value: f64) -> Self {
+        self.max = Some(value);
+        self.max_exclusive = true;
+        self
+    }
+
+    /// Alias for [`NumberSchema::max`]
+    pub fn lte(self, value: f64) -> Self {
+        self.max(value)
+    }
+
+    /// Set both bounds from a Rust range: `0.0..100.0` excludes 100,
+    /// `0.0..=100.0` includes it.
+    pub fn range<R: RangeBounds<f64>>(mut self, range: R) -> Self {
+        match range.start_bound() {
+            Bound::Included(v) => self = self.gte(*v),
+            Bound::Excluded(v) => self = self.gt(*v),
+            Bound::Unbounded => self.min = None,
+        }
+        match range.end_bound() {
+            Bound::Included(v) => self = self.lte(*v),
+            Bound::Excluded(v) => self = self.lt(*v),
+            Bound::Unbounded => self.max = None,
+        }
+        self
+    }
+
+    /// Further bound the number by a range chosen by the value of a sibling
+    /// field, e.g. a weight limited by its `"unit"`:
+    ///
+    /// `number().scaled_by_field("unit", [("kg", 0.0..=500.0), ("lb", 0.0..=1100.0)])`
+    ///
+    /// Checked once the enclosing document passes its other checks, like a
+    /// [`refine`](Schema::refine); the number is not further bounded when the
+    /// sibling is missing or has no range, nor when validated on its own.
+    pub fn scaled_by_field<K, R>(mut self, field: impl Into<String>, ranges: impl IntoIterator<Item = (K, R)>) -> Self
+    where
+        K: Into<Value>,
+        R: RangeBounds<f64>,
+    {
+        let ranges = ranges
+            .into_iter()
+            .map(|(key, range)| (key.into(), (range.start_bound().cloned(), range.end_bound().cloned())))
+            .collect();
+        self.scaled_by = Some(Arc::new(FieldRanges { field: field.into(), ranges }));
         self
     }
 
     pub fn integer(mut self) -> Self {
-        self.integer = true;
+        self.set_integer(true);
+        self
+    }
+
+    /// In-place form of [`NumberSchema::integer`], which can also lift the requirement
+    pub fn set_integer(&mut self, integer: bool) -> &mut Self {
+        self.integer = integer;
+        self
+    }
+
+    /// Validate integers exactly as `i64`/`u64` without converting through
+    /// `f64`, so IDs above 2^53 round-trip unchanged
+    pub fn integer64(mut self) -> Self {
+        self.integer64 = true;
+        self
+    }
+
+    /// Inclusive lower bound checked in integer space (see [`NumberSchema::integer64`])
+    pub fn min_int(mut self, value: impl Into<i128>) -> Self {
+        self.int_min = Some(value.into());
+        self
+    }
+
+    /// Inclusive upper bound checked in integer space (see [`NumberSchema::integer64`])
+    pub fn max_int(mut self, value: impl Into<i128>) -> Self {
+        self.int_max = Some(value.into());
+        self
+    }
+
+    /// Value must be a whole multiple of `step`
+    pub fn multiple_of(mut self, step: f64) -> Self {
+        self.multiple_of = Some(step);
+        self
+    }
+
+    /// Value must equal one of `values`
+    pub fn one_of(mut self, values: impl IntoIterator<Item = f64>) -> Self {
+        self.allowed = Some(values.into_iter().collect());
+        self
+    }
+
+    /// Absolute tolerance for the equality comparisons made by
+    /// [`NumberSchema::multiple_of`] and [`NumberSchema::one_of`], so
+    /// `0.1 + 0.2` counts as `0.3`. Comparisons are exact by default.
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon.abs();
         self
     }
 
@@ -49,14 +183,50 @@ impl NumberSchema {
     }
 
     pub fn optional(mut self) -> Self {
-        self.optional = true;
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.set_presence(presence);
+        self
+    }
+
+    /// In-place form of [`NumberSchema::presence`]
+    pub fn set_presence(&mut self, presence: Presence) -> &mut Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Custom check run after the built-in ones, reported as `number.custom`
+    /// unless it returns a [`CustomIssue`] with its own code
+    pub fn custom<F, E>(mut self, validator: F) -> Self
+    where
+        F: Fn(f64) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
+    {
+        self.custom_validators.push(Arc::new(move |n| validator(n).map_err(Into::into)));
         self
     }
 
     pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
-        self.error_messages.insert(code.into(), message.into());
+        self.set_error_message(code, message);
         self
     }
+
+    /// In-place form of [`NumberSchema::error_message`]
+    pub fn set_error_message(&mut self, code: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::NumberRequired, message)
+    }
 }
 
 impl HasErrorMessages for NumberSchema {
@@ -72,40 +242,139 @@ impl Transformable for NumberSchema {
 }
 
 impl Schema for NumberSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn preserves_input(&self) -> bool {
+        !self.coerce
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("number.required").cloned()
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        let mut issues = Vec::new();
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max || (min == max && (self.min_exclusive || self.max_exclusive)) {
+                issues.push(Unsatisfiable::new(
+                    UnsatisfiableKind::ContradictoryBounds,
+                    format!("No number lies between the minimum {} and the maximum {}", min, max),
+                ));
+            }
+        }
+        issues.extend(satisfiable::check_bounds("integer", self.int_min, self.int_max));
+        issues
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let type_name = if self.integer || self.integer64 { "integer" } else { "number" };
+        let mut explanation = Explanation::typed(type_name, self.presence);
+        if self.coerce {
+            explanation.rule("numeric strings accepted");
+        }
+        match (self.min, self.min_exclusive) {
+            (Some(min), false) => explanation.rule(format!("at least {}", min)),
+            (Some(min), true) => explanation.rule(format!("greater than {}", min)),
+            (None, _) => {}
+        }
+        match (self.max, self.max_exclusive) {
+            (Some(max), false) => explanation.rule(format!("at most {}", max)),
+            (Some(max), true) => explanation.rule(format!("less than {}", max)),
+            (None, _) => {}
+        }
+        if let Some(min) = self.int_min {
+            explanation.rule(format!("at least {}", min));
+        }
+        if let Some(max) = self.int_max {
+            explanation.rule(format!("at most {}", max));
+        }
+        if let Some(step) = self.multiple_of {
+            explanation.rule(format!("multiple of {}", step));
+        }
+        if let Some(allowed) = &self.allowed {
+            let allowed: Vec<_> = allowed.iter().map(f64::to_string).collect();
+            explanation.rule(format!("one of {}", allowed.join(", ")));
+        }
+        if let Some(ranges) = &self.scaled_by {
+            explanation.rule(format!("bounded by the value of `{}`", ranges.field));
+        }
+        for _ in &self.custom_validators {
+            explanation.rule("custom check");
+        }
+        vec![explanation]
+    }
+
     fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
-        match value {
-            Value::Null if self.optional => Ok(value.clone()),
+        let output = match value {
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
+            Value::Number(n) if self.integer64 => {
+                let int = n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from));
+                match int {
+                    Some(int) => self.check_integer64(int).map(|_| value.clone()),
+                    None => Err(self.not_integer_error()),
+                }
+            }
             Value::Number(n) => {
                 let num = n.as_f64().unwrap();
-                self.validate_number(num)
+                // Keep the original representation so integers stay integers
+                self.check_number(num).map(|_| value.clone())
+            }
+            Value::String(s) if self.coerce && self.integer64 => {
+                match s.trim().parse::<i128>().ok().and_then(|i| int_to_value(i).map(|v| (i, v))) {
+                    Some((int, value)) => self.check_integer64(int).map(|_| value),
+                    None => Err(ValidationError::new(ErrorCode::InvalidNumber)
+                        .with_details(|d| {
+                            d.expected_type = Some("integer".to_string());
+                            d.actual_type = Some("string (not a valid integer)".to_string());
+                        }))
+                }
             }
+            Value::String(s) if self.coerce && is_unsafe_integer(s) => Err(self.error(ErrorCode::UnsafePrecision)),
             Value::String(s) if self.coerce => {
                 match s.parse::<f64>() {
-                    Ok(num) => self.validate_number(num),
-                    Err(_) => Err(ValidationError::new("number.invalid_type")
-                        .message("Could not parse string as number")
+                    // "NaN", "inf" and out-of-range literals have no JSON number
+                    Ok(num) if !num.is_finite() => Err(ValidationError::new(ErrorCode::InvalidNumber)
+                        .with_details(|d| {
+                            d.expected_type = Some("number".to_string());
+                            d.actual_type = Some("string (not a finite number)".to_string());
+                        })),
+                    Ok(num) => self.check_number(num)
+                        .map(|_| Value::Number(serde_json::Number::from_f64(num).unwrap())),
+                    Err(_) => Err(ValidationError::new(ErrorCode::InvalidNumber)
                         .with_details(|d| {
                             d.expected_type = Some("number".to_string());
                             d.actual_type = Some("string (not a valid number)".to_string());
                         }))
                 }
             }
-            Value::Null => Err(ValidationError::new("number.required")
-                .message("This field is required")),
+            Value::Null => Err(ValidationError::new(ErrorCode::NumberRequired)
+                .message_or_default(self.error_messages.get("number.required"))),
             _ => {
-                let mut err = ValidationError::new("number.invalid_type")
+                let mut err = ValidationError::new(ErrorCode::InvalidNumber)
                     .with_details(|d| {
                         d.expected_type = Some("number".to_string());
                         d.actual_type = Some(get_type_name(value).to_string());
                     });
-                if let Some(msg) = self.error_messages.get("number.invalid_type") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message(format!("Expected number, got {}", get_type_name(value)));
+                if let Some(msg) = self.error_messages.get("number.invalid") {
+                    err = err.custom_message(msg.clone());
                 }
                 Err(err)
             },
+        }?;
+        if let (Some(ranges), true) = (&self.scaled_by, output.is_number()) {
+            let check: Arc<dyn Refinement> = Arc::new(ScaledCheck {
+                ranges: ranges.clone(),
+                error_messages: self.error_messages.clone(),
+            });
+            refine::defer(&check, &output);
         }
+        Ok(output)
     }
 
     fn into_schema_type(self) -> SchemaType {
@@ -113,49 +382,196 @@ impl Schema for NumberSchema {
     }
 }
 
+/// Deferred check of [`NumberSchema::scaled_by_field`], worded by the
+/// schema's own `messages`
+struct ScaledCheck {
+    ranges: Arc<FieldRanges>,
+    error_messages: HashMap<String, String>,
+}
+
+impl Refinement for ScaledCheck {
+    fn refine(&self, value: &Value, ctx: &mut RefinementContext) {
+        let Some((_, parent)) = ctx.path().segments().split_last() else {
+            return;
+        };
+        let sibling = parent
+            .iter()
+            .try_fold(ctx.root(), |value, segment| match segment {
+                PathSegment::Key(key) => value.get(key),
+                PathSegment::Index(index) => value.get(index),
+            })
+            .and_then(|object| object.get(&self.ranges.field));
+        let Some(sibling) = sibling else {
+            return;
+        };
+        let Some((_, range)) = self.ranges.ranges.iter().find(|(key, _)| key == sibling) else {
+            return;
+        };
+        let schema = NumberSchema { error_messages: self.error_messages.clone(), ..Default::default() }.range(*range);
+        if let Err(err) = schema.check_number(value.as_f64().unwrap_or_default()) {
+            ctx.report(err);
+        }
+    }
+}
+
+/// Largest integer `n` such that `n` and `n + 1` are both exact in `f64`
+const MAX_SAFE_INTEGER: u128 = (1 << 53) - 1;
+
+/// Whether `s` is an integer literal that would be rounded when parsed as `f64`
+fn is_unsafe_integer(s: &str) -> bool {
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let digits = digits.trim_start_matches('0');
+    match digits.parse::<u128>() {
+        Ok(n) => n > MAX_SAFE_INTEGER,
+        // Only overflow can fail here, zero trims to an empty string
+        Err(_) => !digits.is_empty(),
+    }
+}
+
+fn int_to_value(int: i128) -> Option<Value> {
+    if int >= 0 {
+        u64::try_from(int).ok().map(|i| Value::Number(i.into()))
+    } else {
+        i64::try_from(int).ok().map(|i| Value::Number(i.into()))
+    }
+}
+
 impl NumberSchema {
-    fn validate_number(&self, num: f64) -> Result<Value, ValidationError> {
-        if self.integer && num.fract() != 0.0 {
-            let mut err = ValidationError::new("number.integer");
-            if let Some(msg) = self.error_messages.get("number.integer") {
-                err = err.message(msg.clone());
-            } else {
-                err = err.message("Must be an integer");
+    /// Whether bounded by a sibling field, see [`NumberSchema::scaled_by_field`]
+    pub(crate) fn is_scaled(&self) -> bool {
+        self.scaled_by.is_some()
+    }
+
+    /// Whether every value `other` accepts meets this schema's checks, see
+    /// [`Schema::accepts`]
+    pub(crate) fn covers(&self, other: &Self) -> bool {
+        let covers_min = match (self.min, other.min) {
+            (Some(a), Some(b)) => a < b || (a == b && (!self.min_exclusive || other.min_exclusive)),
+            (a, b) => compat::covers_min(a, b),
+        };
+        let covers_max = match (self.max, other.max) {
+            (Some(a), Some(b)) => a > b || (a == b && (!self.max_exclusive || other.max_exclusive)),
+            (a, b) => compat::covers_max(a, b),
+        };
+        let covers_multiple = self.multiple_of.is_none_or(|m| other.multiple_of.is_some_and(|n| (n / m).fract() == 0.0));
+        let covers_allowed =
+            self.allowed.as_ref().is_none_or(|a| other.allowed.as_ref().is_some_and(|b| b.iter().all(|v| a.contains(v))));
+        compat::covers_null(self.presence, other.presence)
+            && covers_min
+            && covers_max
+            && (!self.integer || other.integer || other.integer64)
+            && (!self.integer64 || other.integer64)
+            && compat::covers_min(self.int_min, other.int_min)
+            && compat::covers_max(self.int_max, other.int_max)
+            && covers_multiple
+            && covers_allowed
+            && (self.coerce || !other.coerce)
+            && self.custom_validators.is_empty()
+            && !self.is_scaled()
+            && !other.is_scaled()
+    }
+
+    fn not_integer_error(&self) -> ValidationError {
+        self.error(ErrorCode::NotInteger)
+    }
+
+    fn check_integer64(&self, int: i128) -> Result<(), ValidationError> {
+        if let Some(min) = self.int_min {
+            if int < min {
+                let mut err = ValidationError::new(ErrorCode::NumberTooSmall)
+                    .with_details(|d| {
+                        d.min_value = Some(min as f64);
+                    });
+                if let Some(msg) = self.error_messages.get("number.too_small") {
+                    err = err.custom_message(msg.clone());
+                }
+                return Err(err);
+            }
+        }
+
+        if let Some(max) = self.int_max {
+            if int > max {
+                let mut err = ValidationError::new(ErrorCode::NumberTooLarge)
+                    .with_details(|d| {
+                        d.max_value = Some(max as f64);
+                    });
+                if let Some(msg) = self.error_messages.get("number.too_large") {
+                    err = err.custom_message(msg.clone());
+                }
+                return Err(err);
             }
-            return Err(err);
+        }
+
+        // Float bounds still apply, compared at f64 precision
+        self.check_number(int as f64)
+    }
+
+    fn check_number(&self, num: f64) -> Result<(), ValidationError> {
+        if (self.integer || self.integer64) && num.fract() != 0.0 {
+            return Err(self.not_integer_error());
         }
 
         if let Some(min) = self.min {
-            if num < min {
-                let mut err = ValidationError::new("number.min")
+            if num < min || (self.min_exclusive && num == min) {
+                let mut err = ValidationError::new(ErrorCode::NumberTooSmall)
                     .with_details(|d| {
                         d.min_value = Some(min);
                     });
-                if let Some(msg) = self.error_messages.get("number.min") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message(format!("Must be at least {}", min));
+                if let Some(msg) = self.error_messages.get("number.too_small") {
+                    err = err.custom_message(msg.clone());
+                } else if self.min_exclusive {
+                    err = err.message("Number must be greater than {min_value}");
                 }
                 return Err(err);
             }
         }
 
         if let Some(max) = self.max {
-            if num > max {
-                let mut err = ValidationError::new("number.max")
+            if num > max || (self.max_exclusive && num == max) {
+                let mut err = ValidationError::new(ErrorCode::NumberTooLarge)
                     .with_details(|d| {
                         d.max_value = Some(max);
                     });
-                if let Some(msg) = self.error_messages.get("number.max") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message(format!("Must be at most {}", max));
+                if let Some(msg) = self.error_messages.get("number.too_large") {
+                    err = err.custom_message(msg.clone());
+                } else if self.max_exclusive {
+                    err = err.message("Number must be less than {max_value}");
                 }
                 return Err(err);
             }
         }
 
-        Ok(Value::Number(serde_json::Number::from_f64(num).unwrap()))
+        if let Some(step) = self.multiple_of {
+            let remainder = num - step * (num / step).round();
+            if !self.approx_eq(remainder, 0.0) {
+                return Err(self.error(ErrorCode::NotMultipleOf).with_details(|d| d.expected = Some(step.to_string())));
+            }
+        }
+
+        if let Some(allowed) = &self.allowed {
+            if !allowed.iter().any(|v| self.approx_eq(num, *v)) {
+                let list = allowed.iter().map(f64::to_string).collect::<Vec<_>>().join(", ");
+                return Err(self.error(ErrorCode::NumberNotAllowed).with_details(|d| d.expected = Some(list)));
+            }
+        }
+
+        for validator in &self.custom_validators {
+            validator(num).map_err(|issue| issue.into_error(ErrorCode::NumberCustom.to_string(), &self.error_messages))?;
+        }
+
+        Ok(())
+    }
+
+    fn approx_eq(&self, a: f64, b: f64) -> bool {
+        a == b || (a - b).abs() <= self.epsilon
+    }
+
+    fn error(&self, code: ErrorCode) -> ValidationError {
+        let custom = self.error_messages.get(code.code());
+        ValidationError::new(code).message_or_default(custom)
     }
 }
 
@@ -164,6 +580,15 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_number_keeps_integer_representation() {
+        let schema = NumberSchema::default().min(0.0);
+        let output = schema.validate(&json!(30)).unwrap();
+        assert!(output.is_i64());
+        assert_eq!(output, json!(30));
+        assert_eq!(schema.validate(&json!(2.5)).unwrap(), json!(2.5));
+    }
+
     #[test]
     fn test_number_range_validation() {
         let schema = NumberSchema::default()
@@ -175,16 +600,46 @@ mod tests {
         assert!(schema.validate(&json!(50)).is_ok());
         
         let err = schema.validate(&json!(-1)).unwrap_err();
-        assert_eq!(err.context.code, "number.min");
+        assert_eq!(err.context.code, "number.too_small");
         assert_eq!(err.context.details.min_value, Some(0.0));
         assert!(err.to_string().contains("Must be at least 0"));
 
         let err = schema.validate(&json!(101)).unwrap_err();
-        assert_eq!(err.context.code, "number.max");
+        assert_eq!(err.context.code, "number.too_large");
         assert_eq!(err.context.details.max_value, Some(100.0));
         assert!(err.to_string().contains("Must be at most 100"));
     }
 
+    #[test]
+    fn test_number_exclusive_bounds() {
+        let schema = NumberSchema::default().gt(0.0).lt(10.0);
+
+        assert!(schema.validate(&json!(5)).is_ok());
+        let err = schema.validate(&json!(0)).unwrap_err();
+        assert_eq!(err.context.code, "number.too_small");
+        assert_eq!(err.to_string(), "Number must be greater than 0");
+        assert_eq!(schema.validate(&json!(10)).unwrap_err().to_string(), "Number must be less than 10");
+
+        let schema = NumberSchema::default().gte(0.0).lte(10.0);
+        assert!(schema.validate(&json!(0)).is_ok());
+        assert!(schema.validate(&json!(10)).is_ok());
+    }
+
+    #[test]
+    fn test_number_range() {
+        let schema = NumberSchema::default().range(0.0..100.0);
+        assert!(schema.validate(&json!(0)).is_ok());
+        assert!(schema.validate(&json!(99.9)).is_ok());
+        assert!(schema.validate(&json!(100)).is_err());
+
+        let schema = NumberSchema::default().range(0.0..=100.0);
+        assert!(schema.validate(&json!(100)).is_ok());
+
+        let schema = NumberSchema::default().range(..=5.0);
+        assert!(schema.validate(&json!(-1000)).is_ok());
+        assert!(schema.validate(&json!(6)).is_err());
+    }
+
     #[test]
     fn test_number_integer_validation() {
         let schema = NumberSchema::default()
@@ -198,6 +653,36 @@ mod tests {
         assert!(err.to_string().contains("Must be an integer"));
     }
 
+    #[test]
+    fn test_number_integer64_precision() {
+        let schema = NumberSchema::default()
+            .integer64()
+            .min_int(9_007_199_254_740_993u64);
+
+        let big = json!(18_446_744_073_709_551_615u64);
+        assert_eq!(schema.validate(&big).unwrap(), big);
+
+        // 2^53 + 1 is not representable as f64 but is checked exactly
+        assert!(schema.validate(&json!(9_007_199_254_740_993u64)).is_ok());
+        let err = schema.validate(&json!(9_007_199_254_740_992u64)).unwrap_err();
+        assert_eq!(err.context.code, "number.too_small");
+
+        assert_eq!(schema.validate(&json!(1.5)).unwrap_err().context.code, "number.integer");
+    }
+
+    #[test]
+    fn test_number_integer64_coercion() {
+        let schema = NumberSchema::default().integer64().coerce().max_int(i64::MAX);
+
+        assert_eq!(
+            schema.validate(&json!("9007199254740993")).unwrap(),
+            json!(9_007_199_254_740_993i64)
+        );
+        assert_eq!(schema.validate(&json!("-5")).unwrap(), json!(-5));
+        assert!(schema.validate(&json!("1.5")).is_err());
+        assert!(schema.validate(&json!("18446744073709551615")).is_err());
+    }
+
     #[test]
     fn test_number_coercion() {
         let schema = NumberSchema::default()
@@ -230,7 +715,108 @@ mod tests {
         assert!(schema.validate(&json!(42)).is_ok());
         
         let err = schema.validate(&json!("not a number")).unwrap_err();
-        assert_eq!(err.context.code, "number.invalid_type");
+        assert_eq!(err.context.code, "number.invalid");
         assert!(err.to_string().contains("Must be a number"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_number_multiple_of_epsilon() {
+        let exact = NumberSchema::default().multiple_of(0.1);
+        assert!(exact.validate(&json!(0.5)).is_ok());
+        assert_eq!(exact.validate(&json!(0.1 + 0.2 + 0.05)).unwrap_err().context.code, "number.multiple_of");
+        assert!(exact.validate(&json!(0.7)).is_err());
+
+        let tolerant = NumberSchema::default().multiple_of(0.1).epsilon(1e-9);
+        assert!(tolerant.validate(&json!(0.7)).is_ok());
+        assert!(tolerant.validate(&json!(0.35)).is_err());
+    }
+
+    #[test]
+    fn test_number_one_of_epsilon() {
+        let schema = NumberSchema::default().one_of([0.3, 1.5]);
+        let err = schema.validate(&json!(0.1 + 0.2)).unwrap_err();
+        assert_eq!(err.context.code, "number.one_of");
+        assert_eq!(err.to_string(), "Must be one of: 0.3, 1.5");
+
+        let schema = schema.epsilon(1e-9);
+        assert!(schema.validate(&json!(0.1 + 0.2)).is_ok());
+        assert!(schema.validate(&json!(1.5)).is_ok());
+        assert!(schema.validate(&json!(2)).is_err());
+    }
+
+    #[test]
+    fn test_number_coercion_unsafe_precision() {
+        let schema = NumberSchema::default().coerce();
+
+        assert_eq!(schema.validate(&json!("9007199254740991")).unwrap(), json!(9_007_199_254_740_991.0));
+        assert_eq!(schema.validate(&json!("-0")).unwrap(), json!(-0.0));
+        let err = schema.validate(&json!("9007199254740993")).unwrap_err();
+        assert_eq!(err.context.code, "number.unsafe_precision");
+        assert!(schema.validate(&json!("-9007199254740993")).is_err());
+        assert!(schema.validate(&json!("1".repeat(50))).is_err());
+
+        let exact = NumberSchema::default().coerce().integer64();
+        assert_eq!(exact.validate(&json!("9007199254740993")).unwrap(), json!(9_007_199_254_740_993u64));
+    }
+
+    #[test]
+    fn test_number_coercion_non_finite() {
+        let schema = NumberSchema::default().coerce();
+        for input in ["NaN", "inf", "-infinity", "1e400"] {
+            let err = schema.validate(&json!(input)).unwrap_err();
+            assert_eq!(err.context.code, "number.invalid", "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_number_custom() {
+        let even = NumberSchema::default()
+            .integer()
+            .custom(|n| if n % 2.0 == 0.0 { Ok(()) } else { Err("Must be even") });
+        assert!(even.validate(&json!(4)).is_ok());
+
+        let err = even.validate(&json!(3)).unwrap_err();
+        assert_eq!(err.context.code, "number.custom");
+        assert_eq!(err.to_string(), "Must be even");
+
+        // Built-in checks run first
+        assert_eq!(even.validate(&json!(2.5)).unwrap_err().context.code, "number.integer");
+    }
+
+    #[test]
+    fn test_number_scaled_by_field() {
+        let weight = NumberSchema::default()
+            .min(0.0)
+            .scaled_by_field("unit", [("kg", 0.0..=500.0), ("lb", 0.0..=1100.0)]);
+        let parcel = crate::object()
+            .field("unit", crate::string())
+            .field("weight", weight.clone());
+        let parcels = crate::array(parcel.clone());
+
+        assert!(parcel.validate(&json!({"unit": "lb", "weight": 900})).is_ok());
+        let err = parcel.validate(&json!({"unit": "kg", "weight": 900})).unwrap_err();
+        assert_eq!(err.context.code, "number.too_large");
+        assert_eq!(err.context.path, "weight");
+
+        let err = parcels.validate(&json!([{"unit": "lb", "weight": 1}, {"unit": "kg", "weight": 501}])).unwrap_err();
+        assert_eq!(err.context.path, "1.weight");
+        // Units without a range, and numbers validated alone, keep the base bounds
+        assert!(parcel.validate(&json!({"unit": "t", "weight": 900})).is_ok());
+        assert!(weight.validate(&json!(900)).is_ok());
+        assert_eq!(parcel.validate(&json!({"unit": "kg", "weight": -1})).unwrap_err().context.code, "number.too_small");
+    }
+
+    #[test]
+    fn test_number_scaled_by_field_cached() {
+        let parcel = crate::object()
+            .field("unit", crate::string())
+            .field("weight", NumberSchema::default()
+                .scaled_by_field("unit", [("kg", 0.0..=500.0), ("lb", 0.0..=1100.0)])
+                .cached(8));
+        assert!(parcel.validate(&json!({"unit": "lb", "weight": 800})).is_ok());
+        // The cached number is checked against the new unit all the same
+        let err = parcel.validate(&json!({"unit": "kg", "weight": 800})).unwrap_err();
+        assert_eq!(err.context.code, "number.too_large");
+        assert_eq!(err.context.path, "weight");
+    }
+}
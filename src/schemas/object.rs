@@ -1,169 +1,1117 @@
-use std::collections::{HashMap, HashSet};
+use std::{borrow::Cow, collections::{HashMap, HashSet}, sync::Arc};
 use serde::{de::DeserializeOwned};
 use serde_json::Value;
 
-use crate::error::{ValidationError, ParseError};
-use super::{Schema, SchemaType, HasErrorMessages, get_type_name, validate_schema_type};
+use crate::error::{ValidationError, ParseError, MessageIssue, ErrorPath, PathSegment, ErrorCode, CustomIssue, canonical_code};
+use crate::error::message_check::{check_templates, debug_check_template};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, get_type_name, nest_issues, validate_schema_type, InvalidFields, PartialOutput, Cached, FieldSet, LintFinding, lint::nest_findings, Explanation, explain::{self, nest_explanations}, Unsatisfiable, UnsatisfiableKind, satisfiable::nest_unsatisfiable, conditional::passes, canonical, nested, validate_owned_by_ref, refine, compat, hooks, parallel};
+
+pub use super::hooks::FieldHook;
+
+type CustomValidator = Arc<dyn Fn(&serde_json::Map<String, Value>) -> Result<(), CustomIssue> + Send + Sync>;
+
+/// Fields added by [`ObjectSchema::when`] while its condition holds
+#[derive(Clone)]
+struct When {
+    field: String,
+    predicate: Box<SchemaType>,
+    then: Box<ObjectSchema>,
+}
+
+/// Cross-field check registered with [`ObjectSchema::rule`]
+#[derive(Clone)]
+struct Rule {
+    fields: Vec<String>,
+    check: CustomValidator,
+}
 
 #[derive(Clone)]
 pub struct ObjectSchema {
     fields: HashMap<String, Box<SchemaType>>,
+    field_order: Vec<String>,
     required: HashSet<String>,
-    optional: bool,
+    presence: Presence,
     error_messages: HashMap<String, String>,
+    field_hooks: Vec<FieldHook>,
+    custom_validators: Vec<CustomValidator>,
+    rules: Vec<Rule>,
+    conditions: Vec<When>,
+    defaults: HashMap<String, Value>,
+    examples: HashMap<String, Value>,
+    case_insensitive: bool,
+    parallel: Option<usize>,
 }
 
 impl Default for ObjectSchema {
     fn default() -> Self {
         Self {
             fields: HashMap::new(),
+            field_order: Vec::new(),
             required: HashSet::new(),
-            optional: false,
+            presence: Presence::Required,
             error_messages: HashMap::from([
                 ("object.unknown_field".to_string(), "Unknown field: {field}".to_string())
             ]),
+            field_hooks: Vec::new(),
+            custom_validators: Vec::new(),
+            rules: Vec::new(),
+            conditions: Vec::new(),
+            defaults: HashMap::new(),
+            examples: HashMap::new(),
+            case_insensitive: false,
+            parallel: None,
         }
     }
 }
 
 impl ObjectSchema {
     pub fn field(mut self, name: &str, schema: impl Schema) -> Self {
+        self.set_field(name, schema);
+        self
+    }
+
+    /// In-place form of [`ObjectSchema::field`]
+    pub fn set_field(&mut self, name: &str, schema: impl Schema) -> &mut Self {
         let schema_type = schema.into_schema_type();
         let name = name.to_string();
-        self.fields.insert(name.clone(), Box::new(schema_type));
+        self.insert_field(name.clone(), schema_type);
+        self.defaults.remove(&name);
         self.required.insert(name.clone());
-        self.error_messages.insert(format!("field.{}.required", name), format!("Field '{}' is required", name));
         self
     }
 
     pub fn optional_field(mut self, name: &str, schema: impl Schema) -> Self {
+        self.set_optional_field(name, schema);
+        self
+    }
+
+    /// In-place form of [`ObjectSchema::optional_field`]
+    pub fn set_optional_field(&mut self, name: &str, schema: impl Schema) -> &mut Self {
         let schema_type = schema.into_schema_type();
         let name = name.to_string();
-        self.fields.insert(name.clone(), Box::new(schema_type));
+        self.insert_field(name.clone(), schema_type);
+        self.defaults.remove(&name);
         self.required.remove(&name);
         self.error_messages.insert(format!("field.{}.optional", name), "This field is optional".to_string());
         self
     }
 
+    /// Add every field of `set`; a field declared again later replaces the
+    /// included one
+    pub fn include(mut self, set: &FieldSet) -> Self {
+        self.set_include(set);
+        self
+    }
+
+    /// Optional field whose output is `default` when the input lacks it; the
+    /// default is inserted as is, without validation
+    pub fn default_field(mut self, name: &str, schema: impl Schema, default: impl Into<Value>) -> Self {
+        self.set_optional_field(name, schema);
+        self.defaults.insert(name.to_string(), default.into());
+        self
+    }
+
+    /// Document a realistic value of field `name` for generated docs, such
+    /// as [`Schema::explain`]; the example is kept as is, without validation
+    pub fn example_for_field(mut self, name: &str, example: impl Into<Value>) -> Self {
+        self.examples.insert(name.to_string(), example.into());
+        self
+    }
+
+    /// Example set for field `name` with [`ObjectSchema::example_for_field`]
+    pub fn field_example(&self, name: &str) -> Option<&Value> {
+        self.examples.get(name)
+    }
+
+    /// In-place form of [`ObjectSchema::include`]
+    pub fn set_include(&mut self, set: &FieldSet) -> &mut Self {
+        for (name, schema, required) in set.fields() {
+            if *required {
+                self.set_field(name, schema.clone());
+            } else {
+                self.set_optional_field(name, schema.clone());
+            }
+        }
+        self
+    }
+
+    /// Add the fields, checks and conditions of `other`, its fields replacing
+    /// any of the same name (Zod's `merge`); unknown keys are still handled
+    /// as this schema handles them
+    pub fn merge(mut self, mut other: ObjectSchema) -> Self {
+        for name in std::mem::take(&mut other.field_order) {
+            let Some(schema) = other.fields.remove(&name) else { continue };
+            if other.required.contains(&name) {
+                self.set_field(&name, *schema);
+            } else {
+                self.set_optional_field(&name, *schema);
+            }
+        }
+        other.error_messages.remove("object.unknown_field");
+        self.error_messages.extend(other.error_messages);
+        self.defaults.extend(other.defaults);
+        self.examples.extend(other.examples);
+        self.field_hooks.extend(other.field_hooks);
+        self.custom_validators.extend(other.custom_validators);
+        self.rules.extend(other.rules);
+        self.conditions.extend(other.conditions);
+        self
+    }
+
+    /// Drop a declared field, e.g. one disabled by configuration
+    pub fn remove_field(&mut self, name: &str) -> &mut Self {
+        if self.fields.remove(name).is_some() {
+            self.field_order.retain(|f| f != name);
+            self.required.remove(name);
+            self.defaults.remove(name);
+            self.examples.remove(name);
+            self.error_messages.remove(&format!("field.{}.required", name));
+            self.error_messages.remove(&format!("field.{}.optional", name));
+        }
+        self
+    }
+
+    /// Rewrite every field schema in place, including those of [`ObjectSchema::when`]
+    pub(crate) fn map_fields(&mut self, f: impl Fn(SchemaType) -> SchemaType) {
+        self.map_fields_dyn(&f);
+    }
+
+    fn map_fields_dyn(&mut self, f: &dyn Fn(SchemaType) -> SchemaType) {
+        for schema in self.fields.values_mut() {
+            let taken = std::mem::replace(schema.as_mut(), SchemaType::Never(Default::default()));
+            **schema = f(taken);
+        }
+        for condition in &mut self.conditions {
+            condition.then.map_fields_dyn(f);
+        }
+    }
+
+    /// Rewrite one declared field in place; `false` if there is no such field
+    pub(crate) fn map_field(&mut self, name: &str, f: impl FnOnce(SchemaType) -> SchemaType) -> bool {
+        match self.fields.get_mut(name) {
+            Some(schema) => {
+                let taken = std::mem::replace(schema.as_mut(), SchemaType::Never(Default::default()));
+                **schema = f(taken);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Make a declared field required; `false` if there is no such field
+    pub(crate) fn require(&mut self, name: &str) -> bool {
+        if !self.fields.contains_key(name) {
+            return false;
+        }
+        if self.required.insert(name.to_string()) {
+            self.error_messages.remove(&format!("field.{}.optional", name));
+        }
+        true
+    }
+
+    /// Required field whose nested objects accept unknown keys, so a
+    /// free-form subtree can pass through while the rest stays strict
+    pub fn field_lenient(self, name: &str, schema: impl Schema) -> Self {
+        self.field(name, lenient(schema.into_schema_type()))
+    }
+
+    /// Optional counterpart of [`ObjectSchema::field_lenient`]
+    pub fn optional_field_lenient(self, name: &str, schema: impl Schema) -> Self {
+        self.optional_field(name, lenient(schema.into_schema_type()))
+    }
+
+    fn insert_field(&mut self, name: String, schema: SchemaType) {
+        if self.fields.insert(name.clone(), Box::new(schema)).is_none() {
+            self.field_order.push(name);
+        }
+    }
+
     pub fn optional(mut self) -> Self {
-        self.optional = true;
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.set_presence(presence);
+        self
+    }
+
+    /// In-place form of [`ObjectSchema::presence`]
+    pub fn set_presence(&mut self, presence: Presence) -> &mut Self {
+        self.presence = presence;
         self
     }
 
     pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
-        self.error_messages.insert(code.into(), message.into());
+        self.set_error_message(code, message);
+        self
+    }
+
+    /// In-place form of [`ObjectSchema::error_message`]
+    pub fn set_error_message(&mut self, code: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Message used when the value is null or the enclosing object is missing it
+    pub fn required_message(self, message: impl Into<String>) -> Self {
+        self.error_message(ErrorCode::RequiredField, message)
+    }
+
+    /// Custom check over the validated object, e.g. a rule across several
+    /// fields, reported as `object.custom` unless it returns a
+    /// [`CustomIssue`] with its own code
+    pub fn custom<F, E>(mut self, validator: F) -> Self
+    where
+        F: Fn(&serde_json::Map<String, Value>) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
+    {
+        self.custom_validators.push(Arc::new(move |obj| validator(obj).map_err(Into::into)));
+        self
+    }
+
+    /// Check across `fields` once they have been validated, e.g. that
+    /// `["password", "password_confirm"]` match. A failure is reported as
+    /// `object.rule` on the last field listed, or on the object itself when
+    /// the list is empty; with partial validation the rule is skipped while
+    /// any of its fields is invalid.
+    pub fn rule<F, E>(mut self, fields: impl IntoIterator<Item = impl Into<String>>, check: F) -> Self
+    where
+        F: Fn(&serde_json::Map<String, Value>) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
+    {
+        self.rules.push(Rule {
+            fields: fields.into_iter().map(Into::into).collect(),
+            check: Arc::new(move |obj| check(obj).map_err(Into::into)),
+        });
+        self
+    }
+
+    /// Fields that apply only while `field` is present and passes
+    /// `predicate`, e.g. `.when("type", eq("card"), |s| s.field("card_number", string()))`
+    ///
+    /// `declare` receives an empty object schema. Its fields are validated
+    /// alongside this schema's own, replacing any of the same name, and are
+    /// known fields only while the condition holds. With `any()` as the
+    /// predicate, fields depend on `field` merely being present, like JSON
+    /// Schema's `dependentRequired`.
+    pub fn when(mut self, field: impl Into<String>, predicate: impl Schema, declare: impl FnOnce(ObjectSchema) -> ObjectSchema) -> Self {
+        self.conditions.push(When {
+            field: field.into(),
+            predicate: Box::new(predicate.into_schema_type()),
+            then: Box::new(declare(ObjectSchema::default()).passthrough()),
+        });
+        self
+    }
+
+    /// Schemas of the [`ObjectSchema::when`] conditions `obj` meets
+    fn met_conditions(&self, obj: &serde_json::Map<String, Value>) -> Vec<&ObjectSchema> {
+        self.conditions
+            .iter()
+            .filter(|c| obj.get(&c.field).is_some_and(|v| passes(&c.predicate, v)))
+            .map(|c| c.then.as_ref())
+            .collect()
+    }
+
+    fn check_rule(&self, rule: &Rule, obj: &serde_json::Map<String, Value>) -> Result<(), ValidationError> {
+        (rule.check)(obj).map_err(|issue| {
+            let err = issue.into_error(ErrorCode::ObjectRule.to_string(), &self.error_messages);
+            match rule.fields.last() {
+                Some(field) => err.with_path_prefix(field),
+                None => err,
+            }
+        })
+    }
+
+    fn check_custom(&self, obj: &serde_json::Map<String, Value>) -> Result<(), ValidationError> {
+        for validator in &self.custom_validators {
+            validator(obj).map_err(|issue| issue.into_error(ErrorCode::ObjectCustom.to_string(), &self.error_messages))?;
+        }
+        Ok(())
+    }
+
+    /// Register a hook that runs for every present or required field after it
+    /// has been validated. Hooks may inspect the outcome (auditing, metrics) or
+    /// rewrite it, e.g. to mask sensitive values before errors are logged.
+    ///
+    /// Hooks also run for the fields of objects nested in this one, also
+    /// inside arrays, and get the path of each field from this object, e.g.
+    /// `users.0.password`.
+    pub fn on_field<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ErrorPath, &mut Result<Value, ValidationError>) + Send + Sync + 'static,
+    {
+        self.field_hooks.push(Arc::new(hook));
         self
     }
 
+    /// Validate the fields on several threads once the object holds at
+    /// least `min_fields` of the declared ones
+    ///
+    /// Worth it for wide objects of costly fields. The output, the error
+    /// reported and the order hooks run in are those of validating in order.
+    #[cfg(feature = "rayon")]
+    pub fn parallel(mut self, min_fields: usize) -> Self {
+        self.parallel = Some(min_fields);
+        self
+    }
+
+    /// Validate the present declared fields with `validate` side by side,
+    /// if enough of them for [`ObjectSchema::parallel`]; empty otherwise
+    fn validate_present<'a, R: Send>(
+        &'a self,
+        obj: &serde_json::Map<String, Value>,
+        met: &[&ObjectSchema],
+        validate: impl Fn(&String, &Value) -> R + Sync,
+    ) -> HashMap<&'a String, R> {
+        let Some(min) = self.parallel else { return HashMap::new() };
+        let present: Vec<_> = self.field_order
+            .iter()
+            .filter(|field| !declares(met, field))
+            .filter_map(|field| obj.get(field).map(|value| (field, value)))
+            .collect();
+        if present.len() < min {
+            return HashMap::new();
+        }
+        let outputs = parallel::map_indexed(&present, parallel::available_threads(), |_, (field, value)| validate(field, value));
+        present.into_iter().map(|(field, _)| field).zip(outputs).collect()
+    }
+
     pub fn strict(self) -> Self {
-        self.error_message("object.unknown_field", "Unknown field: {field}")
+        self.error_message(ErrorCode::UnknownField, "Unknown field: {field}")
+    }
+
+    /// Match input keys to declared fields ignoring ASCII case, so `Email`
+    /// and `EMAIL` bind to `email`. Output uses the declared spelling.
+    pub fn case_insensitive_keys(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Keep unknown keys in the output instead of rejecting them
+    pub fn passthrough(mut self) -> Self {
+        self.error_messages.remove("object.unknown_field");
+        self
     }
 
+    /// Validate `value` and deserialize the output into `T`
+    ///
+    /// `T` sees the output, with defaults filled in and transforms applied.
+    /// Missing fields stay missing and null ones null, so an `Option<T>`
+    /// field is `None` for either; declare it `Option<Option<T>>` with
+    /// [`double_option`](crate::double_option) to tell them apart.
     pub fn parse<T>(&self, value: &Value) -> Result<T, ParseError>
     where
         T: DeserializeOwned,
     {
         // First validate the value
-        self.validate(value).map_err(ParseError::from)?;
-        
-        // Then try to deserialize into the target type
-        serde_json::from_value(value.clone())
+        let output = self.validate(value).map_err(ParseError::from)?;
+
+        // Then try to deserialize the output into the target type
+        serde_json::from_value(output)
             .map_err(|e| ParseError::Parse(format!("Failed to parse object: {}", e)))
     }
 }
 
+impl ObjectSchema {
+    fn is_required(&self, field: &str, schema: &SchemaType) -> bool {
+        self.required.contains(field) && !schema.get_presence().allows_missing()
+    }
+
+    /// Whether every value `other` accepts meets this schema's checks, see
+    /// [`Schema::accepts`]
+    pub(crate) fn covers(&self, other: &Self) -> bool {
+        let relates_fields = |o: &Self| !o.rules.is_empty() || !o.conditions.is_empty() || !o.field_hooks.is_empty() || o.case_insensitive;
+        if relates_fields(self) || relates_fields(other) || !self.custom_validators.is_empty() {
+            return false;
+        }
+        if !compat::covers_null(self.presence, other.presence) {
+            return false;
+        }
+        if self.is_strict() && !(other.is_strict() && other.fields.keys().all(|f| self.fields.contains_key(f))) {
+            return false;
+        }
+        self.field_order.iter().all(|field| {
+            let schema = &self.fields[field];
+            let allows_missing = !self.is_required(field, schema);
+            match other.fields.get(field) {
+                Some(theirs) => (allows_missing || other.is_required(field, theirs)) && compat::accepts(schema, theirs),
+                // Keys `other` passes through may hold anything
+                None => allows_missing && other.is_strict(),
+            }
+        })
+    }
+
+    fn missing_field_error(&self, field: &str, schema: &SchemaType) -> ValidationError {
+        ValidationError::new(ErrorCode::RequiredField)
+            .at(PathSegment::from(field))
+            .with_details(|d| {
+                d.field_name = Some(field.to_string());
+            })
+            .message_or_default(
+                schema.missing_message()
+                    .or_else(|| self.error_messages.get(&format!("field.{}.required", field)).cloned()),
+            )
+    }
+
+    /// Rename input keys to the declared field names when matching is
+    /// case-insensitive; an exact match takes precedence over other spellings
+    fn normalize_keys<'a>(&self, obj: &'a serde_json::Map<String, Value>) -> Cow<'a, serde_json::Map<String, Value>> {
+        if !self.case_insensitive {
+            return Cow::Borrowed(obj);
+        }
+        let mut normalized = serde_json::Map::new();
+        for (key, value) in obj {
+            let declared = if self.fields.contains_key(key) {
+                key
+            } else {
+                match self.field_order.iter().find(|f| f.eq_ignore_ascii_case(key)) {
+                    Some(declared) if !obj.contains_key(declared) => declared,
+                    _ => key,
+                }
+            };
+            normalized.entry(declared.clone()).or_insert_with(|| value.clone());
+        }
+        Cow::Owned(normalized)
+    }
+
+    /// Declared field matching `key` ignoring ASCII case, with its declared name
+    pub(crate) fn field_ignoring_case(&self, key: &str) -> Option<(&str, &SchemaType)> {
+        self.field_order
+            .iter()
+            .find(|f| f.eq_ignore_ascii_case(key))
+            .map(|f| (f.as_str(), self.fields[f].as_ref()))
+    }
+
+    /// Schema bound to an input key, honouring case-insensitive matching
+    fn field_schema(&self, key: &str) -> Option<&SchemaType> {
+        match self.fields.get(key) {
+            Some(schema) => Some(schema),
+            None if self.case_insensitive => self.field_ignoring_case(key).map(|(_, schema)| schema),
+            None => None,
+        }
+    }
+
+    /// Schema bound to an input key, preferring the met [`ObjectSchema::when`]
+    /// conditions declared last
+    fn schema_for<'a>(&'a self, met: &[&'a ObjectSchema], key: &str) -> Option<&'a SchemaType> {
+        met.iter()
+            .rev()
+            .find_map(|then| then.fields.get(key).map(Box::as_ref))
+            .or_else(|| self.field_schema(key))
+    }
+
+    fn is_strict(&self) -> bool {
+        self.error_messages.contains_key("object.unknown_field")
+    }
+
+    /// Explanations of `field` and the values nested in it, with whether it
+    /// is required and any condition declaring it
+    fn explain_field(&self, field: &str, condition: Option<&str>) -> Vec<Explanation> {
+        let schema = &self.fields[field];
+        let mut rules = vec![if self.is_required(field, schema) { "required" } else { "optional" }.to_string()];
+        if let Some(default) = self.defaults.get(field) {
+            rules.push(format!("defaults to {}", default));
+        }
+        if let Some(example) = self.examples.get(field) {
+            rules.push(format!("e.g. {}", example));
+        }
+        rules.extend(condition.map(str::to_string));
+        nest_explanations(field, explain::after_type(schema.explain(), rules))
+    }
+
+    /// Schemas of the declared fields and of those [`ObjectSchema::when`]
+    /// conditions declare
+    pub(crate) fn nested_schemas(&self) -> impl Iterator<Item = &SchemaType> {
+        self.fields
+            .values()
+            .map(Box::as_ref)
+            .chain(self.conditions.iter().flat_map(|c| c.then.nested_schemas().collect::<Vec<_>>()))
+    }
+
+    /// Whether each field validates on its own: no rules, conditions, hooks
+    /// or checks deferred to the document tie it to its siblings
+    pub(crate) fn validates_fields_apart(&self) -> bool {
+        self.rules.is_empty()
+            && self.conditions.is_empty()
+            && self.field_hooks.is_empty()
+            && !hooks::is_active()
+            && self.parallel.is_none()
+            && self.custom_validators.is_empty()
+            && !self.case_insensitive
+            && !self.fields.values().any(|schema| refine::defers(schema))
+    }
+
+    /// [`Schema::revalidate`] of an object whose fields validate apart,
+    /// keeping the outcome of every field whose value is unchanged
+    fn revalidate_fields(
+        &self,
+        old: &serde_json::Map<String, Value>,
+        new: &serde_json::Map<String, Value>,
+        previous: &PartialOutput,
+    ) -> PartialOutput {
+        let mut result = serde_json::Map::new();
+        let mut errors = Vec::new();
+
+        for field in &self.field_order {
+            let schema = &self.fields[field];
+            let output = match (old.get(field), new.get(field)) {
+                (old, Some(new)) if old == Some(new) => previous.field(field).nested(field),
+                (Some(old), Some(new)) => {
+                    let previous = previous.field(field);
+                    nested(field, || schema.revalidate(old, new, &previous)).nested(field)
+                }
+                (None, Some(new)) => nested(field, || schema.validate_partial(new)).nested(field),
+                (_, None) if self.is_required(field, schema) => PartialOutput {
+                    value: None,
+                    errors: vec![self.missing_field_error(field, schema)],
+                },
+                (_, None) => match self.defaults.get(field) {
+                    Some(default) => PartialOutput { value: Some(default.clone()), errors: Vec::new() },
+                    None => continue,
+                },
+            };
+            if let Some(v) = output.value {
+                result.insert(field.clone(), v);
+            }
+            errors.extend(output.errors);
+        }
+
+        for (field, value) in new {
+            if self.fields.contains_key(field) {
+                continue;
+            }
+            if self.is_strict() {
+                errors.push(unknown_field_error(field));
+            } else {
+                result.insert(field.clone(), value.clone());
+            }
+        }
+
+        PartialOutput { value: Some(Value::Object(result)), errors }
+    }
+}
+
+/// A field of an object whose fields validate apart, see
+/// [`ObjectSchema::field_entries`]
+pub(crate) enum FieldEntry<'s> {
+    /// Declared field with its schema and the value still to validate
+    Declared(&'s String, &'s SchemaType, Value),
+    /// Default or unknown field, output as it is
+    Kept(String, Value),
+}
+
+/// Whether a met [`ObjectSchema::when`] condition declares `field`
+fn declares(met: &[&ObjectSchema], field: &str) -> bool {
+    met.iter().any(|then| then.fields.contains_key(field))
+}
+
+fn unknown_field_error(field: &str) -> ValidationError {
+    ValidationError::new(ErrorCode::UnknownField)
+        .at(PathSegment::from(field))
+        .with_details(|d| {
+            d.field_name = Some(field.to_string());
+        })
+}
+
+/// Turn off unknown-key checks on every object nested in `schema`
+fn lenient(schema: SchemaType) -> SchemaType {
+    match schema {
+        SchemaType::Object(o) => {
+            let mut o = o.passthrough();
+            o.map_fields(lenient);
+            SchemaType::Object(Box::new(o))
+        }
+        SchemaType::Array(mut a) => {
+            a.map_items(lenient);
+            SchemaType::Array(a)
+        }
+        SchemaType::Union(mut u) => {
+            u.schemas = std::mem::take(&mut u.schemas).into_iter().map(lenient).collect();
+            SchemaType::Union(u)
+        }
+        SchemaType::Transform(mut t) => {
+            t.schema = lenient(t.schema);
+            SchemaType::Transform(t)
+        }
+        SchemaType::Sensitive(mut s) => {
+            s.schema = lenient(s.schema);
+            SchemaType::Sensitive(s)
+        }
+        SchemaType::ErrorMapped(mut e) => {
+            e.schema = lenient(e.schema);
+            SchemaType::ErrorMapped(e)
+        }
+        SchemaType::Deprecated(mut d) => {
+            d.schema = lenient(d.schema);
+            SchemaType::Deprecated(d)
+        }
+        SchemaType::Catch(mut c) => {
+            c.schema = lenient(c.schema);
+            SchemaType::Catch(c)
+        }
+        SchemaType::Pipe(mut p) => {
+            p.schema = lenient(p.schema);
+            p.next = lenient(p.next);
+            SchemaType::Pipe(p)
+        }
+        SchemaType::MapOutput(mut m) => {
+            m.schema = lenient(m.schema);
+            SchemaType::MapOutput(m)
+        }
+        SchemaType::Refined(mut r) => {
+            r.schema = lenient(r.schema);
+            SchemaType::Refined(r)
+        }
+        SchemaType::Conditional(mut c) => {
+            c.map_schemas(lenient);
+            SchemaType::Conditional(c)
+        }
+        SchemaType::Cached(c) => {
+            // Lenient results differ, so they must not share the strict cache
+            let capacity = c.capacity();
+            SchemaType::Cached(Box::new(Cached::new(lenient(c.into_inner()), capacity)))
+        }
+        other => other,
+    }
+}
+
 impl HasErrorMessages for ObjectSchema {
     fn error_messages(&self) -> &HashMap<String, String> {
         &self.error_messages
     }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = check_templates("", &self.error_messages);
+        for field in &self.field_order {
+            issues.extend(nest_issues(field, self.fields[field].check_messages()));
+        }
+        for condition in &self.conditions {
+            issues.extend(condition.then.check_messages());
+        }
+        issues
+    }
 }
 
 impl Schema for ObjectSchema {
-    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("object.required").cloned()
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("object", self.presence);
+        explanation.rule(if self.is_strict() { "no unknown fields" } else { "unknown fields kept" });
+        if self.case_insensitive {
+            explanation.rule("keys matched ignoring case");
+        }
+        for rule in &self.rules {
+            let fields: Vec<_> = rule.fields.iter().map(|f| format!("`{}`", f)).collect();
+            explanation.rule(format!("rule on {}", fields.join(", ")));
+        }
+        for _ in &self.custom_validators {
+            explanation.rule("custom check");
+        }
+        let mut explanations = vec![explanation];
+        for field in &self.field_order {
+            explanations.extend(self.explain_field(field, None));
+        }
+        for c in &self.conditions {
+            let condition = format!("when `{}` is {}", c.field, explain::root_summary(&c.predicate.explain()));
+            for field in &c.then.field_order {
+                explanations.extend(c.then.explain_field(field, Some(&condition)));
+            }
+        }
+        explanations
+    }
+
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        let mut issues = Vec::new();
+        for field in &self.field_order {
+            let schema = &self.fields[field];
+            if self.is_required(field, schema) && matches!(schema.as_ref(), SchemaType::Never(_)) {
+                issues.push(Unsatisfiable {
+                    path: field.clone(),
+                    kind: UnsatisfiableKind::RequiredNever,
+                    message: format!("Field '{}' is required but accepts no value", field),
+                });
+            }
+            issues.extend(nest_unsatisfiable(field, schema.check_satisfiable()));
+        }
+        issues.extend(self.conditions.iter().flat_map(|c| c.then.check_satisfiable()));
+        issues
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        self.field_order
+            .iter()
+            .flat_map(|name| nest_findings(name, self.fields[name].lint()))
+            .chain(self.conditions.iter().flat_map(|c| c.then.lint()))
+            .collect()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        let Value::Object(obj) = output else {
+            return output;
+        };
+        let met = self.met_conditions(&obj);
+        let schemas: Vec<_> = obj.keys().map(|key| self.schema_for(&met, key)).collect();
+        canonical::sorted(obj.into_iter().zip(schemas).filter_map(|((key, value), schema)| match schema {
+            Some(schema) if value.is_null() && schema.get_presence().allows_missing() => None,
+            Some(schema) => Some((key, schema.canonical(value))),
+            None => Some((key, canonical::normalize(value))),
+        }))
+    }
+
+    fn redact(&self, value: &Value) -> Value {
         match value {
             Value::Object(obj) => {
-                let mut result = serde_json::Map::new();
-
-                // Check required fields and validate each field
-                for (field, schema) in &self.fields {
-                    match obj.get(field) {
-                        Some(value) => {
-                            match validate_schema_type(schema.as_ref(), value) {
-                                Ok(validated) => {
-                                    result.insert(field.clone(), validated);
-                                }
-                                Err(e) => {
-                                    return Err(e.with_path_prefix(field));
-                                }
-                            }
-                        }
-                        None => {
-                            if self.required.contains(field) {
-                                let mut err = ValidationError::new("object.required")
-                                    .at(field)
-                                    .with_details(|d| {
-                                        d.field_name = Some(field.clone());
-                                    });
-                                err = err.message(format!("Field '{}' is required", field));
-                                return Err(err);
-                            }
-                        }
-                    }
-                }
+                let met = self.met_conditions(obj);
+                Value::Object(obj.iter()
+                    .map(|(key, v)| {
+                        let v = match self.schema_for(&met, key) {
+                            Some(schema) => schema.redact(v),
+                            None => v.clone(),
+                        };
+                        (key.clone(), v)
+                    })
+                    .collect())
+            }
+            _ => value.clone(),
+        }
+    }
 
-                // Check unknown fields if strict mode is enabled
-                if self.error_messages.contains_key("object.unknown_field") {
-                    for field in obj.keys() {
-                        if !self.fields.contains_key(field) {
-                            let mut err = ValidationError::new("object.unknown_field")
-                                .at(field)
-                                .with_details(|d| {
-                                    d.field_name = Some(field.clone());
-                                });
-                            err = err.message(format!("Unknown field: {}", field));
-                            return Err(err);
-                        }
-                    }
-                } else {
-                    // Copy over any additional fields in non-strict mode
-                    for (field, value) in obj {
-                        if !self.fields.contains_key(field) {
-                            result.insert(field.clone(), value.clone());
-                        }
-                    }
-                }
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let Value::Object(obj) = value else {
+            return PartialOutput::from_result(self.validate(value), value, invalid);
+        };
+        refine::root_partial(value, || self.validate_fields_partial(obj, invalid))
+    }
 
-                Ok(Value::Object(result))
+    fn revalidate(&self, old: &Value, new: &Value, previous: &PartialOutput) -> PartialOutput {
+        match (old, new) {
+            _ if old == new => previous.clone(),
+            (Value::Object(old), Value::Object(new)) if self.validates_fields_apart() => {
+                self.revalidate_fields(old, new, previous)
             }
-            Value::Null if self.optional => Ok(value.clone()),
+            _ => self.validate_partial(new),
+        }
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::Object(obj) => refine::root(value, || self.validate_fields(obj)),
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
             Value::Null => {
-                let err = ValidationError::new("object.required")
-                    .message("This field is required");
+                let err = ValidationError::new(ErrorCode::RequiredField)
+                    .message_or(self.error_messages.get("object.required"), "This field is required");
                 Err(err)
             }
             _ => {
-                let err = ValidationError::new("object.invalid_type")
+                let err = ValidationError::new(ErrorCode::InvalidType)
                     .with_details(|d| {
                         d.expected_type = Some("object".to_string());
                         d.actual_type = Some(get_type_name(value).to_string());
                     })
-                    .message("Must be an object");
+                    .message_or_default(self.error_messages.get("object.invalid_type"));
                 Err(err)
             }
         }
     }
 
+    fn validate_owned(&self, value: Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::Object(obj) if self.validates_fields_apart() => self.validate_fields_owned(obj),
+            value => validate_owned_by_ref(self, value),
+        }
+    }
+
+    fn as_object(&self) -> Option<&ObjectSchema> {
+        Some(self)
+    }
+
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        match value {
+            Value::Object(obj) if self.validates_fields_apart() => self.check_fields(obj),
+            _ => self.validate(value).map(drop),
+        }
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.defaults.is_empty()
+            && self.conditions.is_empty()
+            && self.field_hooks.is_empty()
+            && !hooks::is_active()
+            && !self.case_insensitive
+            && self.fields.values().all(|schema| schema.preserves_input())
+    }
+
     fn into_schema_type(self) -> SchemaType {
         SchemaType::Object(Box::new(self))
     }
 }
 
+impl ObjectSchema {
+    /// Run `f` with this object's hooks active for its fields and those
+    /// of the objects below it
+    fn with_hooks<R>(&self, f: impl FnOnce() -> R) -> R {
+        match self.field_hooks.is_empty() {
+            true => f(),
+            false => hooks::with(&self.field_hooks, f),
+        }
+    }
+
+    fn validate_fields_partial(&self, obj: &serde_json::Map<String, Value>, invalid: InvalidFields) -> PartialOutput {
+        self.with_hooks(|| self.run_fields_partial(obj, invalid))
+    }
+
+    fn run_fields_partial(&self, obj: &serde_json::Map<String, Value>, invalid: InvalidFields) -> PartialOutput {
+        let obj = self.normalize_keys(obj);
+        let met = self.met_conditions(&obj);
+        let mut result = serde_json::Map::new();
+        let mut errors = Vec::new();
+        let validate = |field: &String, value: &Value| {
+            nested(field, || self.fields[field].validate_partial_with(value, invalid)).nested(field)
+        };
+        let mut validated = self.validate_present(&obj, &met, validate);
+
+        for field in &self.field_order {
+            if declares(&met, field) {
+                continue;
+            }
+            let schema = &self.fields[field];
+            let mut output = match obj.get(field) {
+                Some(value) => validated.remove(field).unwrap_or_else(|| validate(field, value)),
+                None if self.is_required(field, schema) => PartialOutput {
+                    value: None,
+                    errors: vec![self.missing_field_error(field, schema)],
+                },
+                None => match self.defaults.get(field) {
+                    Some(default) => PartialOutput { value: Some(default.clone()), errors: Vec::new() },
+                    None => continue,
+                },
+            };
+
+            if hooks::is_active() {
+                let mut outcome = match output.errors.first() {
+                    Some(err) => Err(err.clone()),
+                    None => Ok(output.value.clone().unwrap_or(Value::Null)),
+                };
+                hooks::run(field, &mut outcome);
+                match outcome {
+                    Ok(v) if output.errors.is_empty() => output.value = Some(v),
+                    Ok(_) => {}
+                    Err(err) if output.errors.is_empty() => output.errors.push(err),
+                    Err(err) => output.errors[0] = err,
+                }
+            }
+
+            if let Some(v) = output.value {
+                result.insert(field.clone(), v);
+            }
+            errors.extend(output.errors);
+        }
+
+        for then in &met {
+            let output = then.validate_fields_partial(&obj, invalid);
+            if let Some(Value::Object(mut validated)) = output.value {
+                for field in &then.field_order {
+                    if let Some(v) = validated.remove(field) {
+                        result.insert(field.clone(), v);
+                    }
+                }
+            }
+            errors.extend(output.errors);
+        }
+
+        for (field, value) in obj.iter() {
+            if self.fields.contains_key(field) || declares(&met, field) {
+                continue;
+            }
+            if !self.is_strict() {
+                result.insert(field.clone(), value.clone());
+            } else {
+                errors.push(unknown_field_error(field));
+                if invalid == InvalidFields::KeepRaw {
+                    result.insert(field.clone(), value.clone());
+                }
+            }
+        }
+
+        for rule in &self.rules {
+            let field_failed = |field: &String| errors.iter().any(|e: &ValidationError| {
+                e.context.path.segments().first() == Some(&PathSegment::Key(field.clone()))
+            });
+            if rule.fields.iter().any(field_failed) {
+                continue;
+            }
+            if let Err(err) = self.check_rule(rule, &result) {
+                errors.push(err);
+            }
+        }
+
+        if errors.is_empty() {
+            if let Err(err) = self.check_custom(&result) {
+                errors.push(err);
+            }
+        }
+
+        PartialOutput { value: Some(Value::Object(result)), errors }
+    }
+
+    fn validate_fields(&self, obj: &serde_json::Map<String, Value>) -> Result<Value, ValidationError> {
+        self.with_hooks(|| self.run_fields(obj))
+    }
+
+    fn run_fields(&self, obj: &serde_json::Map<String, Value>) -> Result<Value, ValidationError> {
+        let obj = self.normalize_keys(obj);
+        let met = self.met_conditions(&obj);
+        let mut result = serde_json::Map::new();
+        let validate = |field: &String, value: &Value| {
+            nested(field, || validate_schema_type(&self.fields[field], value)).map_err(|e| e.with_path_prefix(field))
+        };
+        let mut validated = self.validate_present(&obj, &met, validate);
+
+        // Check required fields and validate each field in declaration order
+        for field in &self.field_order {
+            if declares(&met, field) {
+                continue;
+            }
+            let schema = &self.fields[field];
+            let mut outcome = match obj.get(field) {
+                Some(value) => validated.remove(field).unwrap_or_else(|| validate(field, value)),
+                None if self.is_required(field, schema) => Err(self.missing_field_error(field, schema)),
+                None => match self.defaults.get(field) {
+                    Some(default) => Ok(default.clone()),
+                    None => continue,
+                },
+            };
+
+            hooks::run(field, &mut outcome);
+
+            result.insert(field.clone(), outcome?);
+        }
+
+        for then in &met {
+            if let Value::Object(mut validated) = then.validate_fields(&obj)? {
+                for field in &then.field_order {
+                    if let Some(v) = validated.remove(field) {
+                        result.insert(field.clone(), v);
+                    }
+                }
+            }
+        }
+
+        // Check unknown fields if strict mode is enabled
+        if self.is_strict() {
+            for field in obj.keys() {
+                if !self.fields.contains_key(field) && !declares(&met, field) {
+                    return Err(unknown_field_error(field));
+                }
+            }
+        } else {
+            // Copy over any additional fields in non-strict mode
+            for (field, value) in obj.iter() {
+                if !self.fields.contains_key(field) && !declares(&met, field) {
+                    result.insert(field.clone(), value.clone());
+                }
+            }
+        }
+
+        for rule in &self.rules {
+            self.check_rule(rule, &result)?;
+        }
+        self.check_custom(&result)?;
+        Ok(Value::Object(result))
+    }
+
+    /// [`ObjectSchema::validate_fields`] of an object whose fields validate
+    /// apart, moving each value through its field schema
+    pub(crate) fn validate_fields_owned(&self, obj: serde_json::Map<String, Value>) -> Result<Value, ValidationError> {
+        let mut result = serde_json::Map::new();
+        for entry in self.field_entries(obj) {
+            match entry? {
+                FieldEntry::Declared(field, schema, value) => {
+                    let output = nested(field, || schema.validate_owned(value)).map_err(|e| e.with_path_prefix(field))?;
+                    result.insert(field.clone(), output);
+                }
+                FieldEntry::Kept(field, value) => {
+                    result.insert(field, value);
+                }
+            }
+        }
+        Ok(Value::Object(result))
+    }
+
+    /// Fields of `obj` in output order, for an object whose fields validate
+    /// apart: declared fields, then unknown ones. A missing required field,
+    /// or an unknown one in strict mode, is an error in its place.
+    pub(crate) fn field_entries(
+        &self,
+        obj: serde_json::Map<String, Value>,
+    ) -> impl Iterator<Item = Result<FieldEntry<'_>, ValidationError>> + '_ {
+        let mut declared = HashMap::with_capacity(self.fields.len());
+        let mut unknown = Vec::new();
+        for (field, value) in obj {
+            if self.fields.contains_key(&field) {
+                declared.insert(field, value);
+            } else {
+                unknown.push((field, value));
+            }
+        }
+        let fields = self.field_order.iter().filter_map(move |field| {
+            let schema = &self.fields[field];
+            match declared.remove(field) {
+                Some(value) => Some(Ok(FieldEntry::Declared(field, schema, value))),
+                None if self.is_required(field, schema) => Some(Err(self.missing_field_error(field, schema))),
+                None => self.defaults.get(field).map(|default| Ok(FieldEntry::Kept(field.clone(), default.clone()))),
+            }
+        });
+        let strict = self.is_strict();
+        let unknown = unknown.into_iter().map(move |(field, value)| match strict {
+            true => Err(unknown_field_error(&field)),
+            false => Ok(FieldEntry::Kept(field, value)),
+        });
+        fields.chain(unknown)
+    }
+
+    /// [`ObjectSchema::validate_fields`] of an object whose fields validate
+    /// apart, without building the output
+    fn check_fields(&self, obj: &serde_json::Map<String, Value>) -> Result<(), ValidationError> {
+        for field in &self.field_order {
+            let schema = &self.fields[field];
+            match obj.get(field) {
+                Some(value) => nested(field, || schema.validate_ref(value)).map_err(|e| e.with_path_prefix(field))?,
+                None if self.is_required(field, schema) => return Err(self.missing_field_error(field, schema)),
+                None => {}
+            }
+        }
+        if self.is_strict() {
+            if let Some(field) = obj.keys().find(|field| !self.fields.contains_key(*field)) {
+                return Err(unknown_field_error(field));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde::{Deserialize, Serialize};
     use serde_json::json;
-    use crate::schemas::{string::StringSchemaImpl, NumberSchema};
+    use crate::error::ErrorPath;
+    use crate::schemas::{string::{StringSchema, StringSchemaImpl}, NumberSchema, ArraySchema};
 
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
     struct User {
@@ -292,6 +1240,158 @@ mod tests {
         assert!(err.to_string().contains("Must be an object"));
     }
 
+    #[test]
+    fn test_object_required_message() {
+        let schema = ObjectSchema::default()
+            .field("email", StringSchemaImpl::default().required_message("Please provide your email"))
+            .field("age", NumberSchema::default().required_message("Age is missing"))
+            .field("name", StringSchemaImpl::default())
+            .error_message("field.name.required", "Who are you?");
+
+        let err = schema.validate(&json!({})).unwrap_err();
+        assert_eq!(err.context.code, "object.required");
+        assert_eq!(err.context.path, "email");
+        assert_eq!(err.to_string(), "Please provide your email");
+
+        let err = schema.validate(&json!({"email": "a@b.co"})).unwrap_err();
+        assert_eq!(err.to_string(), "Age is missing");
+
+        let err = schema.validate(&json!({"email": "a@b.co", "age": null})).unwrap_err();
+        assert_eq!(err.context.code, "number.required");
+        assert_eq!(err.to_string(), "Age is missing");
+
+        let err = schema.validate(&json!({"email": "a@b.co", "age": 1})).unwrap_err();
+        assert_eq!(err.to_string(), "Who are you?");
+    }
+
+    #[test]
+    fn test_object_check_messages() {
+        let valid = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default().min_length(2)
+                .error_message("string.too_short", "At least {min_length} characters"));
+        assert!(valid.check_messages().is_empty());
+
+        // Builders reject typos in debug builds, so plant one directly
+        let mut inner = ObjectSchema::default();
+        inner.error_messages.insert("object.unknown_field".to_string(), "Unexpected {fieldname}".to_string());
+        let schema = ObjectSchema::default().field("address", inner);
+
+        let issues = schema.check_messages();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "address");
+        assert_eq!(issues[0].code, "object.unknown_field");
+        assert_eq!(issues[0].placeholder, "fieldname");
+    }
+
+    #[test]
+    #[should_panic(expected = "never filled")]
+    fn test_error_message_debug_assert() {
+        let _ = ObjectSchema::default().error_message("object.required", "{min_len}");
+    }
+
+    #[test]
+    fn test_object_field_hooks() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default())
+            .field("password", StringSchemaImpl::default().min_length(8))
+            .optional_field("age", NumberSchema::default())
+            .on_field(move |path, result| {
+                log.lock().unwrap().push((path.to_string(), result.is_ok()));
+            })
+            .on_field(|path, result| {
+                if path.to_string().ends_with("password") {
+                    if let Err(e) = result {
+                        e.context.message = Some("[REDACTED]".to_string());
+                    }
+                }
+            });
+
+        let err = schema.validate(&json!({
+            "name": "John",
+            "password": "hunter2"
+        })).unwrap_err();
+        assert_eq!(err.context.path, "password");
+        assert_eq!(err.to_string(), "[REDACTED]");
+
+        let seen = seen.lock().unwrap();
+        assert!(seen.contains(&("password".to_string(), false)));
+        assert!(!seen.iter().any(|(path, _)| path == "age"));
+    }
+
+    #[test]
+    fn test_object_field_hooks_nested() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let user = || ObjectSchema::default()
+            .field("name", StringSchemaImpl::default())
+            .field("password", StringSchemaImpl::default());
+        let users = ArraySchema::new(user());
+        #[cfg(feature = "rayon")]
+        let users = users.parallel(2);
+        let schema = ObjectSchema::default()
+            .field("owner", user())
+            .field("users", users)
+            .on_field(move |path, _| log.lock().unwrap().push(path.to_string()))
+            .on_field(|path, result| {
+                if path.segments().last() == Some(&PathSegment::Key("password".to_string())) {
+                    if let Ok(value) = result {
+                        *value = json!("***");
+                    }
+                }
+            });
+
+        let output = schema.validate(&json!({
+            "owner": {"name": "Ada", "password": "a"},
+            "users": [{"name": "Bob", "password": "b"}, {"name": "Eve", "password": "e"}]
+        })).unwrap();
+        assert_eq!(output, json!({
+            "owner": {"name": "Ada", "password": "***"},
+            "users": [{"name": "Bob", "password": "***"}, {"name": "Eve", "password": "***"}]
+        }));
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen, [
+            "owner", "owner.name", "owner.password", "users",
+            "users.0.name", "users.0.password", "users.1.name", "users.1.password",
+        ]);
+
+        // Hooks of a nested object see paths from that object
+        let inner = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = Arc::clone(&inner);
+        let schema = ObjectSchema::default()
+            .field("owner", user().on_field(move |path, _| log.lock().unwrap().push(path.to_string())));
+        schema.validate(&json!({"owner": {"name": "Ada", "password": "a"}})).unwrap();
+        assert_eq!(*inner.lock().unwrap(), ["name", "password"]);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_object_parallel() {
+        let names: Vec<_> = (0..50).map(|i| format!("f{}", i)).collect();
+        let wide = |parallel: bool| {
+            let schema = names.iter().fold(ObjectSchema::default(), |schema, name| {
+                schema.field(name, ArraySchema::new(StringSchemaImpl::default().min_length(2)).parallel(4))
+            });
+            if parallel { schema.parallel(8) } else { schema }
+        };
+        let (sequential, parallel) = (wide(false), wide(true));
+        let mut value: serde_json::Map<_, _> = names.iter().map(|name| (name.clone(), json!(vec!["ab"; 10]))).collect();
+        assert_eq!(parallel.validate(&Value::Object(value.clone())).unwrap(), sequential.validate(&Value::Object(value.clone())).unwrap());
+
+        value.insert("f30".to_string(), json!(["ab", "x"]));
+        value.insert("f7".to_string(), json!(1));
+        value.remove("f12");
+        let value = Value::Object(value);
+        let err = parallel.validate(&value).unwrap_err();
+        assert_eq!(err.context.path, "f7");
+        let paths = |output: PartialOutput| output.errors.iter().map(|e| e.context.path.to_string()).collect::<Vec<_>>();
+        assert_eq!(paths(parallel.validate_partial(&value)), ["f7", "f12", "f30.1"]);
+        assert_eq!(paths(parallel.validate_partial(&value)), paths(sequential.validate_partial(&value)));
+    }
+
     #[test]
     fn test_object_parse_simple() {
         let schema = ObjectSchema::default()
@@ -412,4 +1512,367 @@ mod tests {
             ParseError::Parse(_) => panic!("Expected ValidationError"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_object_field_lenient() {
+        let schema = ObjectSchema::default()
+            .field("id", NumberSchema::default())
+            .field_lenient("settings", ObjectSchema::default()
+                .field("theme", StringSchemaImpl::default())
+                .field("extra", ObjectSchema::default()))
+            .field_lenient("metadata", crate::schemas::AnySchema::default());
+
+        let value = json!({
+            "id": 1,
+            "settings": {"theme": "dark", "font": "mono", "extra": {"x": 1}},
+            "metadata": {"anything": [1, 2]}
+        });
+        assert_eq!(schema.validate(&value).unwrap(), value);
+
+        let err = schema.validate(&json!({"id": 1, "settings": {}, "metadata": 0})).unwrap_err();
+        assert_eq!(err.context.path, "settings.theme");
+
+        let err = schema.validate(&json!({"id": 1, "settings": {"theme": "x", "extra": {}}, "metadata": 0, "stray": 1})).unwrap_err();
+        assert_eq!(err.context.code, "object.unknown_field");
+        assert_eq!(err.context.path, "stray");
+    }
+
+    #[test]
+    fn test_object_passthrough() {
+        let schema = ObjectSchema::default().field("a", NumberSchema::default()).passthrough();
+        assert_eq!(schema.validate(&json!({"a": 1, "b": 2})).unwrap(), json!({"a": 1, "b": 2}));
+    }
+
+    #[test]
+    fn test_object_presence_matrix() {
+        use crate::schemas::Presence;
+
+        let schema = ObjectSchema::default()
+            .field("required", StringSchemaImpl::default())
+            .field("optional", StringSchemaImpl::default().presence(Presence::Optional))
+            .field("nullable", StringSchemaImpl::default().presence(Presence::Nullable))
+            .field("both", StringSchemaImpl::default().presence(Presence::OptionalNullable));
+
+        assert!(schema.validate(&json!({"required": "a", "nullable": null})).is_ok());
+        assert!(schema.validate(&json!({"required": "a", "optional": "b", "nullable": "c", "both": null})).is_ok());
+
+        let err = schema.validate(&json!({"required": "a", "optional": null, "nullable": null})).unwrap_err();
+        assert_eq!(err.context.path, "optional");
+
+        let err = schema.validate(&json!({"required": "a"})).unwrap_err();
+        assert_eq!(err.context.code, "object.required");
+        assert_eq!(err.context.path, "nullable");
+
+        assert!(schema.validate(&json!({"required": null, "nullable": null})).is_err());
+    }
+
+    #[test]
+    fn test_object_validate_partial() {
+        use crate::schemas::{ArraySchema, InvalidFields};
+
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default().trim())
+            .field("age", NumberSchema::default().min(0.0))
+            .field("email", StringSchemaImpl::default().email())
+            .field("tags", ArraySchema::new(StringSchemaImpl::default().min_length(2)));
+        let input = json!({"name": " Ada ", "age": -1, "tags": ["ok", "x", "fine"]});
+
+        let output = schema.validate_partial(&input);
+        assert_eq!(output.value, Some(json!({"name": "Ada", "tags": ["ok", "fine"]})));
+        let paths: Vec<_> = output.errors.iter().map(|e| e.context.path.to_string()).collect();
+        assert_eq!(paths, vec!["age", "email", "tags.1"]);
+
+        let output = schema.validate_partial_with(&input, InvalidFields::KeepRaw);
+        assert_eq!(output.value, Some(json!({"name": "Ada", "age": -1, "tags": ["ok", "x", "fine"]})));
+        assert_eq!(output.errors.len(), 3);
+
+        let valid = json!({"name": "Ada", "age": 1, "email": "a@b.co", "tags": []});
+        assert!(schema.validate_partial(&valid).is_valid());
+        assert_eq!(schema.validate_partial(&valid).into_result().unwrap(), valid);
+    }
+
+    #[test]
+    fn test_object_case_insensitive_keys() {
+        let schema = ObjectSchema::default()
+            .field("email", StringSchemaImpl::default().email())
+            .field("userId", NumberSchema::default())
+            .case_insensitive_keys();
+
+        let output = schema.validate(&json!({"EMAIL": "a@b.co", "userid": 7})).unwrap();
+        assert_eq!(output, json!({"email": "a@b.co", "userId": 7}));
+
+        let output = schema.validate(&json!({"Email": "x", "email": "a@b.co", "UserId": 1})).unwrap_err();
+        assert_eq!(output.context.code, "object.unknown_field");
+        assert_eq!(output.context.path, "Email");
+
+        let strict = ObjectSchema::default().field("email", StringSchemaImpl::default());
+        assert!(strict.validate(&json!({"Email": "a@b.co"})).is_err());
+    }
+
+    #[test]
+    fn test_object_validate_str_strict() {
+        let schema = ObjectSchema::default()
+            .field("role", StringSchemaImpl::default())
+            .field("profile", ObjectSchema::default().field("id", NumberSchema::default()));
+
+        let output = schema.validate_str_strict(r#"{"role": "user", "profile": {"id": 1}}"#).unwrap();
+        assert_eq!(output, json!({"role": "user", "profile": {"id": 1}}));
+
+        let err = schema.validate_str_strict(r#"{"role": "user", "profile": {"id": 1, "id": 2}}"#).unwrap_err();
+        assert_eq!(err.context.code, "object.duplicate_key");
+        assert_eq!(err.context.path, "profile");
+    }
+
+    #[test]
+    fn test_object_in_place_builders() {
+        let mut schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default())
+            .field("legacy_id", NumberSchema::default());
+
+        schema.remove_field("legacy_id").set_optional_field("tenant", StringSchemaImpl::default());
+        assert_eq!(schema.validate(&json!({"name": "a"})).unwrap(), json!({"name": "a"}));
+
+        schema.set_field("tenant", StringSchemaImpl::default());
+        assert_eq!(schema.validate(&json!({"name": "a"})).unwrap_err().context.path, "tenant");
+    }
+
+    #[test]
+    fn test_include_field_set() {
+        use crate::{number, object, string};
+
+        let audit = FieldSet::new("audit")
+            .field("created_at", string())
+            .optional_field("updated_at", string());
+        let paging = FieldSet::new("paging").optional_field("page", number().integer()).include(&audit);
+        assert_eq!(paging.field_names().collect::<Vec<_>>(), ["page", "created_at", "updated_at"]);
+
+        let user = object().field("name", string()).include(&audit);
+        let post = object().include(&audit).field("created_at", number());
+
+        assert!(user.validate(&json!({"name": "ada", "created_at": "2024-01-01"})).is_ok());
+        let err = user.validate(&json!({"name": "ada"})).unwrap_err();
+        assert_eq!(err.context.path, "created_at");
+        assert!(post.validate(&json!({"created_at": 1, "updated_at": "x"})).is_ok());
+    }
+
+    #[test]
+    fn test_object_custom() {
+        let schema = ObjectSchema::default()
+            .field("password", StringSchemaImpl::default())
+            .field("confirm", StringSchemaImpl::default())
+            .custom(|obj| match obj["password"] == obj["confirm"] {
+                true => Ok(()),
+                false => Err(CustomIssue::from("Passwords do not match").with_details(|d| d.field_name = Some("confirm".to_string()))),
+            });
+        assert!(schema.validate(&json!({"password": "hunter2", "confirm": "hunter2"})).is_ok());
+
+        let err = schema.validate(&json!({"password": "hunter2", "confirm": "hunter3"})).unwrap_err();
+        assert_eq!(err.context.code, "object.custom");
+        assert_eq!(err.to_string(), "Passwords do not match");
+        assert_eq!(err.context.details.field_name.as_deref(), Some("confirm"));
+
+        // Field errors come first; the cross-field rule sees only valid objects
+        let err = schema.validate(&json!({"password": "hunter2"})).unwrap_err();
+        assert_eq!(err.context.path, "confirm");
+    }
+
+    #[test]
+    fn test_object_rule() {
+        let schema = ObjectSchema::default()
+            .field("password", StringSchemaImpl::default().min_length(8))
+            .field("password_confirm", StringSchemaImpl::default())
+            .rule(["password", "password_confirm"], |obj| match obj["password"] == obj["password_confirm"] {
+                true => Ok(()),
+                false => Err("Passwords do not match"),
+            })
+            .error_message("object.rule", "Please repeat the same password");
+        assert!(schema.validate(&json!({"password": "hunter22", "password_confirm": "hunter22"})).is_ok());
+
+        let err = schema.validate(&json!({"password": "hunter22", "password_confirm": "hunter23"})).unwrap_err();
+        assert_eq!(err.context.code, "object.rule");
+        assert_eq!(err.context.path, "password_confirm");
+        assert_eq!(err.to_string(), "Please repeat the same password");
+
+        // A rule over an invalid field is skipped rather than reported twice
+        let output = schema.validate_partial(&json!({"password": "short", "password_confirm": "other"}));
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.errors[0].context.path, "password");
+    }
+
+    #[test]
+    fn test_object_when() {
+        use crate::{any, eq};
+
+        let schema = ObjectSchema::default()
+            .field("type", StringSchemaImpl::default())
+            .optional_field("card_number", StringSchemaImpl::default())
+            .when("type", eq("card"), |s| s.field("card_number", StringSchemaImpl::default().min_length(12)))
+            .when("type", eq("bank"), |s| s.field("iban", StringSchemaImpl::default()))
+            .when("iban", any(), |s| s.field("bic", StringSchemaImpl::default()));
+
+        assert!(schema.validate(&json!({"type": "cash"})).is_ok());
+        assert!(schema.validate(&json!({"type": "card", "card_number": "4111111111111111"})).is_ok());
+
+        // The condition makes the optional field required and tightens it
+        let err = schema.validate(&json!({"type": "card"})).unwrap_err();
+        assert_eq!(err.context.code, "object.required");
+        assert_eq!(err.context.path, "card_number");
+        let err = schema.validate(&json!({"type": "card", "card_number": "4111"})).unwrap_err();
+        assert_eq!(err.context.code, "string.too_short");
+
+        // Conditional fields are unknown while their condition does not hold
+        let err = schema.validate(&json!({"type": "cash", "iban": "DE89", "bic": "DEUT"})).unwrap_err();
+        assert_eq!(err.context.code, "object.unknown_field");
+        assert_eq!(err.context.path, "iban");
+
+        // A present `iban` requires `bic`
+        let err = schema.validate(&json!({"type": "bank", "iban": "DE89"})).unwrap_err();
+        assert_eq!(err.context.path, "bic");
+        assert!(schema.validate(&json!({"type": "bank", "iban": "DE89", "bic": "DEUT"})).is_ok());
+
+        let output = schema.validate_partial(&json!({"type": "bank", "iban": 1}));
+        let paths: Vec<_> = output.errors.iter().map(|e| e.context.path.to_string()).collect();
+        assert_eq!(paths, ["iban", "bic"]);
+    }
+
+    #[test]
+    fn test_default_field() {
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default())
+            .default_field("role", StringSchemaImpl::default(), "member");
+        assert_eq!(schema.validate(&json!({"name": "Ada"})).unwrap(), json!({"name": "Ada", "role": "member"}));
+        assert_eq!(schema.validate(&json!({"name": "Ada", "role": "admin"})).unwrap()["role"], "admin");
+        assert!(schema.validate(&json!({"name": "Ada", "role": 1})).is_err());
+
+        let output = schema.validate_partial(&json!({"name": 1}));
+        assert_eq!(output.value.unwrap(), json!({"role": "member"}));
+
+        // Redeclaring the field drops its default
+        let schema = schema.optional_field("role", StringSchemaImpl::default());
+        assert_eq!(schema.validate(&json!({"name": "Ada"})).unwrap(), json!({"name": "Ada"}));
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = ObjectSchema::default()
+            .field("id", NumberSchema::default())
+            .optional_field("note", StringSchemaImpl::default())
+            .custom(|obj| match obj.get("tags").and_then(Value::as_array) {
+                Some(tags) if !tags.is_empty() && obj.contains_key("note") => Err("Either note or tags"),
+                _ => Ok(()),
+            });
+        let extra = ObjectSchema::default()
+            .field("note", StringSchemaImpl::default())
+            .default_field("tags", ArraySchema::new(StringSchemaImpl::default()), json!([]))
+            .passthrough();
+        let schema = base.merge(extra);
+
+        assert_eq!(schema.validate(&json!({"id": 1})).unwrap_err().context.code, "object.required");
+        assert_eq!(schema.validate(&json!({"id": 1, "note": "x"})).unwrap(), json!({"id": 1, "note": "x", "tags": []}));
+        // Checks of both schemas run
+        assert!(schema.validate(&json!({"id": 1, "note": "x", "tags": ["a"]})).is_err());
+        // `other` being passthrough does not loosen the merged schema
+        assert!(schema.validate(&json!({"id": 1, "note": "x", "extra": true})).is_err());
+    }
+
+    #[test]
+    fn test_revalidate() {
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default().min_length(2))
+            .field("address", ObjectSchema::default()
+                .field("zip", StringSchemaImpl::default().min_length(5))
+                .field("city", StringSchemaImpl::default()))
+            .optional_field("tags", ArraySchema::new(StringSchemaImpl::default()));
+        let old = json!({"name": "A", "address": {"zip": "123", "city": "Paris"}, "tags": ["x"]});
+        let new = json!({"name": "A", "address": {"zip": "75001", "city": "Paris"}, "tags": ["x"], "extra": 1});
+        let previous = schema.validate_partial(&old);
+        let output = schema.revalidate(&old, &new, &previous);
+        let full = schema.validate_partial(&new);
+        assert_eq!(output.value, full.value);
+        let paths = |output: &PartialOutput| output.errors.iter().map(|e| e.context.path.to_string()).collect::<Vec<_>>();
+        assert_eq!(paths(&output), ["name", "extra"]);
+        assert_eq!(paths(&output), paths(&full));
+
+        // Unchanged fields keep their previous outcome rather than being
+        // rerun; changed ones lose theirs
+        let stale = PartialOutput {
+            value: previous.value.clone(),
+            errors: vec![ValidationError::new(ErrorCode::RefineCustom).at(ErrorPath::parse("address.city"))],
+        };
+        assert_eq!(paths(&schema.revalidate(&old, &new, &stale)), ["address.city", "extra"]);
+        let edited = json!({"name": "A", "address": {"zip": "123", "city": "Lyon"}});
+        assert!(schema.revalidate(&old, &edited, &stale).is_valid());
+
+        // Rules relate fields, so an object with one is validated in full
+        let schema = schema.rule(["name"], |_| Err("Always fails"));
+        assert_eq!(paths(&schema.revalidate(&old, &new, &stale)), ["name", "extra"]);
+    }
+
+    #[test]
+    fn test_validate_ref() {
+        use std::borrow::Cow;
+
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default().min_length(2))
+            .field("age", NumberSchema::default().integer())
+            .optional_field("tags", ArraySchema::new(StringSchemaImpl::default()).unique())
+            .optional_field("meta", ObjectSchema::default().field("id", NumberSchema::default()).passthrough());
+        let valid = json!({"name": "Ada", "age": 36, "tags": ["a", "b"], "meta": {"id": 1, "x": true}});
+        assert!(schema.validate_ref(&valid).is_ok());
+        assert!(matches!(schema.validate_cow(&valid), Ok(Cow::Borrowed(v)) if *v == valid));
+
+        // The same error as the full validation
+        for invalid in [
+            json!({"name": "A", "age": 36}),
+            json!({"name": "Ada", "age": 3.5}),
+            json!({"name": "Ada"}),
+            json!({"name": "Ada", "age": 36, "tags": ["a", "a"]}),
+            json!({"name": "Ada", "age": 36, "meta": {"id": "1"}}),
+            json!({"name": "Ada", "age": 36, "extra": 1}),
+            json!([]),
+        ] {
+            let expected = schema.validate(&invalid).unwrap_err();
+            let err = schema.validate_ref(&invalid).unwrap_err();
+            assert_eq!((err.context.code, err.context.path), (expected.context.code, expected.context.path));
+        }
+
+        // Output that differs from the input is copied
+        let schema = schema.default_field("role", StringSchemaImpl::default(), "user");
+        assert!(!schema.preserves_input());
+        let Ok(Cow::Owned(output)) = schema.validate_cow(&valid) else {
+            panic!("expected an owned output");
+        };
+        assert_eq!(output["role"], "user");
+    }
+
+    #[test]
+    fn test_validate_owned() {
+        let item = ObjectSchema::default().field("sku", StringSchemaImpl::default().to_uppercase());
+        let schema = ObjectSchema::default()
+            .field("name", StringSchemaImpl::default().trim().min_length(2))
+            .field("items", ArraySchema::new(item).min_items(1))
+            .default_field("count", NumberSchema::default(), 0)
+            .field("meta", ObjectSchema::default().passthrough());
+        let inputs = [
+            json!({"name": " Ada ", "items": [{"sku": "ab"}, {"sku": "cd"}], "meta": {"x": 1}}),
+            json!({"name": " A ", "items": [{"sku": "ab"}], "meta": {}}),
+            json!({"name": "Ada", "items": [], "meta": {}}),
+            json!({"name": "Ada", "items": [{"sku": 1}], "meta": {}}),
+            json!({"name": "Ada", "items": [{"sku": "ab"}], "meta": {}, "extra": true}),
+            json!({"name": "Ada", "items": [{"sku": "ab"}]}),
+            json!("Ada"),
+        ];
+        let as_type = schema.clone().into_schema_type();
+        for input in inputs {
+            let expected = schema.validate(&input).map_err(|e| (e.context.code, e.context.path));
+            let owned = schema.validate_owned(input.clone()).map_err(|e| (e.context.code, e.context.path));
+            assert_eq!(owned, expected);
+            let context = |e: ValidationError| (e.context.code, e.context.path, e.context.details.received);
+            let expected = as_type.validate(&input).map_err(context);
+            assert_eq!(as_type.validate_owned(input).map_err(context), expected);
+        }
+
+        let output = schema.validate_owned(json!({"name": " Ada ", "items": [{"sku": "ab"}], "meta": {"x": 1}})).unwrap();
+        assert_eq!(output, json!({"name": "Ada", "items": [{"sku": "AB"}], "count": 0, "meta": {"x": 1}}));
+    }
+}
@@ -0,0 +1,313 @@
+use std::{collections::BTreeMap, fmt};
+use serde::Deserialize;
+
+use super::{SchemaType, Cached};
+
+/// Declarative adjustments layered onto a built schema at runtime, e.g. one
+/// tenant's stricter limits and wording loaded from its configuration
+///
+/// Overlays only tighten: a `max_length` above the schema's own limit leaves
+/// that limit in place, and `required` can make optional fields required but
+/// never the reverse. Nested fields and array items take their own overlay:
+///
+/// ```json
+/// {
+///   "required": ["phone"],
+///   "fields": { "name": { "max_length": 40, "messages": { "string.too_long": "Name too long" } } }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OverlaySpec {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    /// Object fields that become required
+    pub required: Vec<String>,
+    /// Messages by error code, replacing the schema's own
+    pub messages: BTreeMap<String, String>,
+    /// Overlays for the object's fields
+    pub fields: BTreeMap<String, OverlaySpec>,
+    /// Overlay for the array's item schema
+    pub items: Option<Box<OverlaySpec>>,
+}
+
+impl OverlaySpec {
+    /// Parse an overlay from JSON text
+    pub fn from_json(json: &str) -> Result<Self, OverlayError> {
+        serde_json::from_str(json).map_err(|e| OverlayError::Invalid(e.to_string()))
+    }
+
+    /// Parse an overlay from an already-decoded JSON value
+    pub fn from_value(value: serde_json::Value) -> Result<Self, OverlayError> {
+        serde_json::from_value(value).map_err(|e| OverlayError::Invalid(e.to_string()))
+    }
+
+    /// Names of the settings this overlay uses
+    fn settings(&self) -> Vec<&'static str> {
+        let mut settings = Vec::new();
+        let mut note = |set: bool, name| if set { settings.push(name) };
+        note(self.min_length.is_some(), "min_length");
+        note(self.max_length.is_some(), "max_length");
+        note(self.min.is_some(), "min");
+        note(self.max.is_some(), "max");
+        note(self.min_items.is_some(), "min_items");
+        note(self.max_items.is_some(), "max_items");
+        note(!self.required.is_empty(), "required");
+        note(!self.messages.is_empty(), "messages");
+        note(!self.fields.is_empty(), "fields");
+        note(self.items.is_some(), "items");
+        settings
+    }
+
+    /// Reject settings the schema at `path` has no use for
+    fn only(&self, path: &str, allowed: &[&str]) -> Result<(), OverlayError> {
+        match self.settings().into_iter().find(|s| !allowed.contains(s)) {
+            Some(setting) => Err(OverlayError::Unsupported { path: path.to_string(), setting }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Why an overlay could not be applied
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayError {
+    /// The overlay JSON is malformed or uses an unknown key
+    Invalid(String),
+    /// `required` or `fields` names a field the object does not declare
+    UnknownField { path: String },
+    /// The setting does not apply to the schema at this path
+    Unsupported { path: String, setting: &'static str },
+}
+
+impl fmt::Display for OverlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverlayError::Invalid(msg) => write!(f, "Invalid overlay: {}", msg),
+            OverlayError::UnknownField { path } => write!(f, "Overlay names unknown field '{}'", path),
+            OverlayError::Unsupported { path, setting } => {
+                write!(f, "Overlay setting '{}' does not apply to {}", setting, display_path(path))
+            }
+        }
+    }
+}
+
+impl std::error::Error for OverlayError {}
+
+fn display_path(path: &str) -> String {
+    if path.is_empty() { "the root schema".to_string() } else { format!("'{}'", path) }
+}
+
+fn child_path(path: &str, name: &str) -> String {
+    if path.is_empty() { name.to_string() } else { format!("{}.{}", path, name) }
+}
+
+/// Tighter of an existing lower bound and an overlay's
+fn raise<T: PartialOrd + Copy>(current: Option<T>, overlay: Option<T>) -> Option<T> {
+    match (current, overlay) {
+        (Some(c), Some(o)) if o <= c => None,
+        (_, o) => o,
+    }
+}
+
+/// Tighter of an existing upper bound and an overlay's
+fn lower<T: PartialOrd + Copy>(current: Option<T>, overlay: Option<T>) -> Option<T> {
+    match (current, overlay) {
+        (Some(c), Some(o)) if o >= c => None,
+        (_, o) => o,
+    }
+}
+
+pub(crate) fn apply(schema: SchemaType, spec: &OverlaySpec, path: &str) -> Result<SchemaType, OverlayError> {
+    match schema {
+        SchemaType::String(mut s) => {
+            spec.only(path, &["min_length", "max_length", "messages"])?;
+            let (min, max) = s.length_limits();
+            if let Some(min) = raise(min, spec.min_length) {
+                s.set_min_length(min);
+            }
+            if let Some(max) = lower(max, spec.max_length) {
+                s.set_max_length(max);
+            }
+            for (code, message) in &spec.messages {
+                s.set_error_message(code, message);
+            }
+            Ok(SchemaType::String(s))
+        }
+        SchemaType::Number(mut n) => {
+            spec.only(path, &["min", "max", "messages"])?;
+            let (min, max) = n.bounds();
+            if let Some(min) = raise(min, spec.min) {
+                n.set_min(min);
+            }
+            if let Some(max) = lower(max, spec.max) {
+                n.set_max(max);
+            }
+            for (code, message) in &spec.messages {
+                n.set_error_message(code, message);
+            }
+            Ok(SchemaType::Number(n))
+        }
+        SchemaType::Boolean(mut b) => {
+            spec.only(path, &["messages"])?;
+            for (code, message) in &spec.messages {
+                b.set_error_message(code, message);
+            }
+            Ok(SchemaType::Boolean(b))
+        }
+        SchemaType::Bytes(mut b) => {
+            spec.only(path, &["messages"])?;
+            for (code, message) in &spec.messages {
+                b.set_error_message(code, message);
+            }
+            Ok(SchemaType::Bytes(b))
+        }
+        SchemaType::Array(mut a) => {
+            spec.only(path, &["min_items", "max_items", "items", "messages"])?;
+            let (min, max) = a.item_limits();
+            if let Some(min) = raise(min, spec.min_items) {
+                a.set_min_items(min);
+            }
+            if let Some(max) = lower(max, spec.max_items) {
+                a.set_max_items(max);
+            }
+            for (code, message) in &spec.messages {
+                a.set_error_message(code, message);
+            }
+            if let Some(items) = &spec.items {
+                let mut result = Ok(());
+                a.map_items(|item| apply(item, items, &format!("{}[]", path)).unwrap_or_else(|e| {
+                    result = Err(e);
+                    SchemaType::Never(Default::default())
+                }));
+                result?;
+            }
+            Ok(SchemaType::Array(a))
+        }
+        SchemaType::Object(mut o) => {
+            spec.only(path, &["required", "fields", "messages"])?;
+            for name in &spec.required {
+                if !o.require(name) {
+                    return Err(OverlayError::UnknownField { path: child_path(path, name) });
+                }
+            }
+            for (code, message) in &spec.messages {
+                o.set_error_message(code, message);
+            }
+            for (name, field_spec) in &spec.fields {
+                let field_path = child_path(path, name);
+                let mut result = Ok(());
+                let found = o.map_field(name, |field| apply(field, field_spec, &field_path).unwrap_or_else(|e| {
+                    result = Err(e);
+                    SchemaType::Never(Default::default())
+                }));
+                if !found {
+                    return Err(OverlayError::UnknownField { path: field_path });
+                }
+                result?;
+            }
+            Ok(SchemaType::Object(o))
+        }
+        SchemaType::Transform(mut t) => {
+            t.schema = apply(t.schema, spec, path)?;
+            Ok(SchemaType::Transform(t))
+        }
+        SchemaType::Sensitive(mut s) => {
+            s.schema = apply(s.schema, spec, path)?;
+            Ok(SchemaType::Sensitive(s))
+        }
+        SchemaType::ErrorMapped(mut e) => {
+            e.schema = apply(e.schema, spec, path)?;
+            Ok(SchemaType::ErrorMapped(e))
+        }
+        SchemaType::Deprecated(mut d) => {
+            d.schema = apply(d.schema, spec, path)?;
+            Ok(SchemaType::Deprecated(d))
+        }
+        SchemaType::Catch(mut c) => {
+            c.schema = apply(c.schema, spec, path)?;
+            Ok(SchemaType::Catch(c))
+        }
+        SchemaType::Pipe(mut p) => {
+            // Paths address the input, which the first stage validates
+            p.schema = apply(p.schema, spec, path)?;
+            Ok(SchemaType::Pipe(p))
+        }
+        SchemaType::MapOutput(mut m) => {
+            m.schema = apply(m.schema, spec, path)?;
+            Ok(SchemaType::MapOutput(m))
+        }
+        SchemaType::Refined(mut r) => {
+            r.schema = apply(r.schema, spec, path)?;
+            Ok(SchemaType::Refined(r))
+        }
+        SchemaType::Cached(c) => {
+            // Results under the overlay differ, so they get a cache of their own
+            let capacity = c.capacity();
+            Ok(SchemaType::Cached(Box::new(Cached::new(apply(c.into_inner(), spec, path)?, capacity))))
+        }
+        other => {
+            spec.only(path, &[])?;
+            Ok(other)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::{Schema, StringSchema};
+    use crate::{number, object, string};
+    use serde_json::json;
+
+    fn base() -> SchemaType {
+        object()
+            .field("name", string().max_length(100))
+            .optional_field("phone", string())
+            .field("age", number().min(0.0).max(150.0))
+            .into_schema_type()
+    }
+
+    #[test]
+    fn test_overlay_tightens_and_requires() {
+        let spec = OverlaySpec::from_json(r#"{
+            "required": ["phone"],
+            "fields": {
+                "name": { "max_length": 5, "messages": { "string.too_long": "Keep it short" } },
+                "age": { "min": 18, "max": 200 }
+            }
+        }"#).unwrap();
+        let schema = base().overlay(&spec).unwrap();
+
+        let err = schema.validate(&json!({"name": "Alice", "age": 30})).unwrap_err();
+        assert_eq!(err.context.path, "phone");
+
+        let err = schema.validate(&json!({"name": "Alexander", "phone": "1", "age": 30})).unwrap_err();
+        assert_eq!(err.context.code, "string.too_long");
+        assert_eq!(err.to_string(), "Keep it short");
+
+        // min raised to 18, max left at the stricter 150
+        assert!(schema.validate(&json!({"name": "Al", "phone": "1", "age": 17})).is_err());
+        assert!(schema.validate(&json!({"name": "Al", "phone": "1", "age": 160})).is_err());
+        assert!(schema.validate(&json!({"name": "Al", "phone": "1", "age": 40})).is_ok());
+
+        // The base schema is untouched
+        assert!(base().validate(&json!({"name": "Alexander", "age": 30})).is_ok());
+    }
+
+    #[test]
+    fn test_overlay_errors() {
+        let spec = OverlaySpec::from_json(r#"{"fields": {"nickname": {}}}"#).unwrap();
+        assert_eq!(base().overlay(&spec).err().unwrap(), OverlayError::UnknownField { path: "nickname".to_string() });
+
+        let spec = OverlaySpec::from_json(r#"{"fields": {"age": {"max_length": 3}}}"#).unwrap();
+        let err = base().overlay(&spec).err().unwrap();
+        assert_eq!(err, OverlayError::Unsupported { path: "age".to_string(), setting: "max_length" });
+        assert_eq!(err.to_string(), "Overlay setting 'max_length' does not apply to 'age'");
+
+        assert!(matches!(OverlaySpec::from_json(r#"{"max_lenght": 3}"#), Err(OverlayError::Invalid(_))));
+    }
+}
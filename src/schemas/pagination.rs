@@ -0,0 +1,51 @@
+use super::{AnySchema, NeverSchema, NumberSchema, ObjectSchema, string::{StringSchema, StringSchemaImpl}};
+
+/// Page size used when the request names none
+const DEFAULT_PAGE_SIZE: u32 = 20;
+
+/// Object schema for `page`, `page_size` and `cursor` query parameters
+///
+/// Numbers may arrive as strings. `page` defaults to 1 and `page_size` to 20
+/// (or `max_page_size` if lower); with a `cursor`, `page` is rejected and
+/// gets no default.
+pub(crate) fn schema(max_page_size: u32) -> ObjectSchema {
+    let count = || NumberSchema::default().integer64().coerce().min_int(1);
+    ObjectSchema::default()
+        .default_field("page", count(), 1)
+        .default_field("page_size", count().max_int(max_page_size), DEFAULT_PAGE_SIZE.min(max_page_size))
+        .optional_field("cursor", StringSchemaImpl::default().min_length(1))
+        .when("cursor", AnySchema::default(), |s| {
+            s.optional_field(
+                "page",
+                NeverSchema::default().error_message("never.invalid", "Use either cursor or page, not both"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::pagination;
+    use crate::schemas::Schema;
+
+    #[test]
+    fn test_pagination() {
+        let schema = pagination(50);
+        assert_eq!(schema.validate(&json!({})).unwrap(), json!({"page": 1, "page_size": 20}));
+        assert_eq!(
+            schema.validate(&json!({"page": "3", "page_size": "50"})).unwrap(),
+            json!({"page": 3, "page_size": 50}),
+        );
+        assert_eq!(schema.validate(&json!({"cursor": "abc"})).unwrap(), json!({"cursor": "abc", "page_size": 20}));
+        assert_eq!(pagination(10).validate(&json!({})).unwrap()["page_size"], 10);
+
+        let err = schema.validate(&json!({"page_size": "51"})).unwrap_err();
+        assert_eq!(err.context.path, "page_size");
+        assert_eq!(schema.validate(&json!({"page": "0"})).unwrap_err().context.path, "page");
+        assert!(schema.validate(&json!({"page": "1.5"})).is_err());
+
+        let err = schema.validate(&json!({"cursor": "abc", "page": 2})).unwrap_err();
+        assert_eq!(err.context.path, "page");
+        assert_eq!(err.to_string(), "Use either cursor or page, not both");
+    }
+}
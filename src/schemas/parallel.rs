@@ -0,0 +1,78 @@
+#[cfg(not(feature = "rayon"))]
+use std::{num::NonZeroUsize, thread};
+
+use super::{context::ValidationContext, hooks, refine, warnings};
+
+/// Run `f` on each item with its index in up to `threads` chunks run side
+/// by side, returning the results in item order
+///
+/// Chunks run on rayon's global pool with the `rayon` feature, and on
+/// scoped threads otherwise. Workers validate as if on the calling thread:
+/// they see its current [`ValidationContext`] and field hooks, and the
+/// warnings and deferred refinements they leave are handed back to it in
+/// item order once all have finished.
+pub(crate) fn map_indexed<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(usize, &T) -> R + Sync,
+{
+    let threads = threads.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().enumerate().map(|(i, item)| f(i, item)).collect();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    let context = ValidationContext::current();
+    let in_document = refine::in_document();
+    let active_hooks = hooks::current();
+    let run_chunk = |n: usize, chunk: &[T]| {
+        context.scope(|| {
+            hooks::install(&active_hooks, || {
+                let work = || chunk.iter().enumerate().map(|(j, item)| f(n * chunk_size + j, item)).collect::<Vec<_>>();
+                // Items of a document defer their refinements to its root
+                warnings::collect(|| refine::isolate(|| if in_document { refine::detach(work) } else { (work(), Default::default()) }))
+            })
+        })
+    };
+    let chunks = run_chunks(items, chunk_size, &run_chunk);
+
+    let mut results = Vec::with_capacity(items.len());
+    for ((chunk, deferred), chunk_warnings) in chunks {
+        results.extend(chunk);
+        refine::adopt(deferred);
+        chunk_warnings.into_iter().for_each(warnings::warn);
+    }
+    results
+}
+
+#[cfg(feature = "rayon")]
+fn run_chunks<T: Sync, C: Send>(items: &[T], chunk_size: usize, run_chunk: &(impl Fn(usize, &[T]) -> C + Sync)) -> Vec<C> {
+    use rayon::prelude::*;
+    items.par_chunks(chunk_size).enumerate().map(|(n, chunk)| run_chunk(n, chunk)).collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn run_chunks<T: Sync, C: Send>(items: &[T], chunk_size: usize, run_chunk: &(impl Fn(usize, &[T]) -> C + Sync)) -> Vec<C> {
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(n, chunk)| scope.spawn(move || run_chunk(n, chunk)))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    })
+}
+
+/// Threads to validate on when none are configured
+#[cfg(feature = "rayon")]
+pub(crate) fn available_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
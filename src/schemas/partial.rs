@@ -0,0 +1,73 @@
+use serde_json::Value;
+
+use crate::error::{ValidationError, PathSegment};
+
+/// What `validate_partial` does with values that fail validation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InvalidFields {
+    /// Leave invalid fields and array items out of the output
+    #[default]
+    Omit,
+    /// Copy invalid fields and array items into the output unchanged
+    KeepRaw,
+}
+
+/// Best-effort output of `validate_partial` together with every error found
+#[derive(Debug, Clone)]
+pub struct PartialOutput {
+    /// Transformed output, or `None` if nothing could be kept
+    pub value: Option<Value>,
+    pub errors: Vec<ValidationError>,
+}
+
+impl PartialOutput {
+    /// Output of a validation that either fully passed or fully failed
+    pub fn from_result(result: Result<Value, ValidationError>, raw: &Value, invalid: InvalidFields) -> Self {
+        match result {
+            Ok(value) => Self { value: Some(value), errors: Vec::new() },
+            Err(err) => Self {
+                value: (invalid == InvalidFields::KeepRaw).then(|| raw.clone()),
+                errors: vec![err],
+            },
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Strict view of the outcome: the output, or the first error
+    pub fn into_result(mut self) -> Result<Value, ValidationError> {
+        if self.errors.is_empty() {
+            Ok(self.value.unwrap_or(Value::Null))
+        } else {
+            Err(self.errors.swap_remove(0))
+        }
+    }
+
+    /// Outcome of `key` in this outcome of an object, its errors at paths
+    /// relative to it
+    pub(crate) fn field(&self, key: &str) -> Self {
+        let segment = PathSegment::from(key);
+        Self {
+            value: self.value.as_ref().and_then(|v| v.get(key)).cloned(),
+            errors: self
+                .errors
+                .iter()
+                .filter_map(|err| {
+                    let path = err.context.path.strip_prefix(&segment)?;
+                    let mut err = err.clone();
+                    err.context.path = path;
+                    Some(err)
+                })
+                .collect(),
+        }
+    }
+
+    /// Prefix the path of every error with `segment`
+    pub(crate) fn nested(mut self, segment: impl Into<PathSegment>) -> Self {
+        let segment = segment.into();
+        self.errors = self.errors.into_iter().map(|e| e.with_path_prefix(segment.clone())).collect();
+        self
+    }
+}
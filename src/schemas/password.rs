@@ -0,0 +1,70 @@
+use crate::error::ErrorCode;
+
+/// Password strength rules for `StringSchemaImpl::password`
+///
+/// Each unmet rule has its own error code under `string.password.*`:
+/// `min_len`, `upper`, `lower`, `digit`, `symbol` and `repeats`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum length in characters
+    pub min_len: usize,
+    pub require_upper: bool,
+    pub require_lower: bool,
+    pub require_digit: bool,
+    /// Require a character that is neither alphanumeric nor whitespace
+    pub require_symbol: bool,
+    /// Longest allowed run of one repeated character, e.g. `2` rejects `"aaa"`
+    pub max_repeats: Option<usize>,
+}
+
+/// A rule of a [`PasswordPolicy`] that a password does not meet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PasswordViolation {
+    pub code: ErrorCode,
+    pub min_length: Option<usize>,
+}
+
+impl PasswordPolicy {
+    /// Every rule `password` breaks, in declaration order
+    pub(crate) fn violations(&self, password: &str) -> Vec<PasswordViolation> {
+        let mut violations = Vec::new();
+        if password.chars().count() < self.min_len {
+            violations.push(PasswordViolation {
+                code: ErrorCode::PasswordTooShort,
+                min_length: Some(self.min_len),
+            });
+        }
+
+        let mut push = |code| violations.push(PasswordViolation { code, min_length: None });
+        if self.require_upper && !password.chars().any(char::is_uppercase) {
+            push(ErrorCode::PasswordNoUpper);
+        }
+        if self.require_lower && !password.chars().any(char::is_lowercase) {
+            push(ErrorCode::PasswordNoLower);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            push(ErrorCode::PasswordNoDigit);
+        }
+        if self.require_symbol && !password.chars().any(|c| !c.is_alphanumeric() && !c.is_whitespace()) {
+            push(ErrorCode::PasswordNoSymbol);
+        }
+        if let Some(max) = self.max_repeats {
+            if longest_run(password) > max {
+                push(ErrorCode::PasswordRepeats);
+            }
+        }
+        violations
+    }
+}
+
+fn longest_run(s: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous = None;
+    for c in s.chars() {
+        current = if previous == Some(c) { current + 1 } else { 1 };
+        longest = longest.max(current);
+        previous = Some(c);
+    }
+    longest
+}
@@ -0,0 +1,78 @@
+use crate::error::ErrorCode;
+
+/// Rules for strings validated by `StringSchemaImpl::file_path`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct PathPolicy {
+    pub absolute: bool,
+    pub no_traversal: bool,
+}
+
+impl PathPolicy {
+    /// Error code and default message for the first rule `path` breaks
+    pub(crate) fn violation(&self, path: &str) -> Option<ErrorCode> {
+        if path.is_empty() || path.contains('\0') {
+            return Some(ErrorCode::InvalidFilePath);
+        }
+        if self.absolute && !is_absolute(path) {
+            return Some(ErrorCode::PathNotAbsolute);
+        }
+        if self.no_traversal && path.split(['/', '\\']).any(|part| part == "..") {
+            return Some(ErrorCode::PathTraversal);
+        }
+        None
+    }
+}
+
+/// Absolute in either Unix (`/etc`) or Windows (`C:\dir`, `\\server\share`) form,
+/// independent of the platform doing the validation
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || path.starts_with("\\\\")
+        || (bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'/' | b'\\'))
+}
+
+/// Whether `pattern` is a well-formed glob: balanced `[...]` classes and
+/// `{a,b}` alternations, no empty class and no dangling escape
+pub(crate) fn is_valid_glob(pattern: &str) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+    let mut chars = pattern.chars().peekable();
+    let mut braces = 0usize;
+    while let Some(c) = chars.next() {
+        match c {
+            // An escape consumes the following character and must not be last
+            '\\' if chars.next().is_none() => return false,
+            '\\' => {}
+            '[' => {
+                if chars.peek() == Some(&'!') || chars.peek() == Some(&'^') {
+                    chars.next();
+                }
+                // A leading `]` is a literal member of the class
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                } else if chars.peek().is_none() {
+                    return false;
+                }
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(_) => {}
+                        None => return false,
+                    }
+                }
+            }
+            ']' => return false,
+            '{' => braces += 1,
+            '}' => {
+                if braces == 0 {
+                    return false;
+                }
+                braces -= 1;
+            }
+            _ => {}
+        }
+    }
+    braces == 0
+}
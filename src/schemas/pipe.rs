@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{MessageIssue, ValidationError};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput, LintFinding, Explanation, explain};
+
+/// Two schemas in sequence: the output of the first is validated by the
+/// next (Zod's `pipe`)
+///
+/// Created with [`Schema::pipe`], e.g. `string().trim().pipe(number().coerce())`.
+/// Errors of either stage are reported at the value's own path. A null the
+/// first stage accepts is passed through without running the next, so
+/// optional fields stay optional.
+#[derive(Clone)]
+pub struct Pipe<A, B> {
+    pub schema: A,
+    pub next: B,
+}
+
+impl<A, B> Pipe<A, B> {
+    pub fn new(schema: A, next: B) -> Self {
+        Self { schema, next }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.schema, self.next)
+    }
+}
+
+impl<A: HasErrorMessages, B: HasErrorMessages> HasErrorMessages for Pipe<A, B> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = self.schema.check_messages();
+        issues.extend(self.next.check_messages());
+        issues
+    }
+}
+
+impl<A: Schema, B: Schema> Schema for Pipe<A, B> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        let mut issues = self.schema.check_satisfiable();
+        issues.extend(self.next.check_satisfiable());
+        issues
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let next = format!("then {}", explain::root_summary(&self.next.explain()));
+        explain::with_rules(self.schema.explain(), [next])
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        let output = self.schema.validate(value)?;
+        if output.is_null() && self.schema.get_presence().allows_null() {
+            return Ok(output);
+        }
+        self.next.validate(&output)
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let output = self.schema.validate_partial_with(value, invalid);
+        match &output.value {
+            Some(v) if output.errors.is_empty() && !(v.is_null() && self.schema.get_presence().allows_null()) => {
+                self.next.validate_partial_with(v, invalid)
+            }
+            _ => output,
+        }
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<LintFinding> {
+        let mut findings = self.schema.lint();
+        findings.extend(self.next.lint());
+        findings
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.next.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Pipe(Box::new(Pipe {
+            schema: self.schema.into_schema_type(),
+            next: self.next.into_schema_type(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[test]
+    fn test_pipe_feeds_output_to_next() {
+        let amount = string().trim().pipe(number().coerce().min(0.0));
+        assert_eq!(amount.validate(&json!(" 12.5 ")).unwrap(), json!(12.5));
+        assert_eq!(amount.validate(&json!("-1")).unwrap_err().context.code, "number.too_small");
+        assert_eq!(amount.validate(&json!(3)).unwrap_err().context.code, "string.invalid_type");
+
+        let schema = object()
+            .field("amount", amount.clone().into_schema_type())
+            .optional_field("tip", string().optional().pipe(number().coerce()));
+        assert_eq!(schema.validate(&json!({"amount": "5", "tip": null})).unwrap(), json!({"amount": 5.0, "tip": null}));
+        let err = schema.validate(&json!({"amount": "x"})).unwrap_err();
+        assert_eq!(err.context.path, "amount");
+        assert_eq!(err.context.code, "number.invalid");
+
+        let output = schema.validate_partial(&json!({"amount": "-3", "tip": "2"}));
+        assert_eq!(output.errors.len(), 1);
+        assert_eq!(output.value.unwrap(), json!({"tip": 2.0}));
+    }
+}
@@ -0,0 +1,439 @@
+use std::{cell::RefCell, collections::HashMap, sync::Arc};
+use serde_json::Value;
+
+use crate::error::{CustomIssue, ErrorCode, ErrorPath, MessageIssue, PathSegment, ValidationError};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput};
+
+/// A check with access to the whole document, attached with [`Schema::refine`]
+/// (Zod's `superRefine`)
+///
+/// Closures `Fn(&Value, &mut RefinementContext)` implement it; implement it
+/// on a type to reuse a refinement across schemas via [`Refined::new`].
+pub trait Refinement: Send + Sync {
+    fn refine(&self, value: &Value, ctx: &mut RefinementContext);
+}
+
+impl<F> Refinement for F
+where
+    F: Fn(&Value, &mut RefinementContext) + Send + Sync,
+{
+    fn refine(&self, value: &Value, ctx: &mut RefinementContext) {
+        self(value, ctx)
+    }
+}
+
+/// What a [`Refinement`] can see and report: where the refined value sits,
+/// the document it sits in, and issues at any path below it
+pub struct RefinementContext<'a> {
+    path: &'a ErrorPath,
+    root: &'a Value,
+    issues: Vec<ValidationError>,
+}
+
+impl RefinementContext<'_> {
+    /// Path of the refined value from the document root
+    pub fn path(&self) -> &ErrorPath {
+        self.path
+    }
+
+    /// The document being validated, as received
+    pub fn root(&self) -> &Value {
+        self.root
+    }
+
+    /// Report an issue on the refined value, as `refine.custom` unless the
+    /// [`CustomIssue`] names its own code
+    pub fn issue(&mut self, issue: impl Into<CustomIssue>) {
+        self.issue_at(ErrorPath::new(), issue);
+    }
+
+    /// Report an issue at `path` below the refined value, e.g. `"end_date"`
+    pub fn issue_at(&mut self, path: impl Into<ErrorPath>, issue: impl Into<CustomIssue>) {
+        let mut full = self.path.clone();
+        for segment in path.into().segments() {
+            full.push(segment.clone());
+        }
+        let err = issue.into().into_error(ErrorCode::RefineCustom.to_string(), &HashMap::new());
+        self.issues.push(err.at(full));
+    }
+
+    /// Report an error built by a schema on the refined value
+    pub(crate) fn report(&mut self, err: ValidationError) {
+        self.issues.push(err.at(self.path.clone()));
+    }
+
+    pub fn has_issues(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+/// A schema whose successful output is further checked by a [`Refinement`]
+///
+/// Created with [`Schema::refine`]. Inside an object or array the check runs
+/// once the whole document has passed its other checks, so that
+/// [`RefinementContext::root`] is complete; a [`Cached`](super::Cached)
+/// schema replays its output but runs the refinement again on every hit.
+#[derive(Clone)]
+pub struct Refined<S> {
+    pub schema: S,
+    pub refinement: Arc<dyn Refinement>,
+}
+
+impl<S> Refined<S> {
+    pub fn new(schema: S, refinement: impl Refinement + 'static) -> Self {
+        Self { schema, refinement: Arc::new(refinement) }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for Refined<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for Refined<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<super::Explanation> {
+        super::explain::with_rules(self.schema.explain(), ["custom refinement".to_string()])
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        root(value, || {
+            let output = self.schema.validate(value)?;
+            defer(&self.refinement, &output);
+            Ok(output)
+        })
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.schema.preserves_input()
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        root_partial(value, || {
+            let output = self.schema.validate_partial_with(value, invalid);
+            if let (Some(value), true) = (&output.value, output.errors.is_empty()) {
+                defer(&self.refinement, value);
+            }
+            output
+        })
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<super::LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Refined(Box::new(Refined {
+            schema: self.schema.into_schema_type(),
+            refinement: self.refinement,
+        }))
+    }
+}
+
+/// A refinement waiting for its document to finish validating
+#[derive(Clone)]
+struct Pending {
+    refinement: Arc<dyn Refinement>,
+    value: Value,
+    path: ErrorPath,
+}
+
+thread_local! {
+    static ROOTS: RefCell<Vec<Vec<Pending>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the innermost root, also when validation panics
+struct RootGuard;
+
+impl Drop for RootGuard {
+    fn drop(&mut self) {
+        ROOTS.with(|roots| roots.borrow_mut().pop());
+    }
+}
+
+pub(crate) fn in_document() -> bool {
+    ROOTS.with(|roots| !roots.borrow().is_empty())
+}
+
+/// Run `f`, collecting the refinements deferred meanwhile
+fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<Pending>) {
+    ROOTS.with(|roots| roots.borrow_mut().push(Vec::new()));
+    let guard = RootGuard;
+    let result = f();
+    let pending = ROOTS.with(|roots| roots.borrow_mut().last_mut().map(std::mem::take).unwrap_or_default());
+    drop(guard);
+    (result, pending)
+}
+
+/// Refinements deferred on another thread, see [`detach`], or recorded
+/// for a cached result, see [`replay`]
+#[derive(Clone, Default)]
+pub(crate) struct Deferred(Vec<Pending>);
+
+/// Run `f` on another thread as part of the document being validated on
+/// the calling one, returning the refinements it defers for [`adopt`]
+pub(crate) fn detach<R>(f: impl FnOnce() -> R) -> (R, Deferred) {
+    let (result, pending) = collect(f);
+    (result, Deferred(pending))
+}
+
+/// Run `f` outside of any document being validated on this thread, as a
+/// pool thread does when it picks up work while waiting for its own
+pub(crate) fn isolate<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(Vec<Vec<Pending>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ROOTS.with(|roots| *roots.borrow_mut() = std::mem::take(&mut self.0));
+        }
+    }
+
+    let _restore = Restore(ROOTS.with(|roots| std::mem::take(&mut *roots.borrow_mut())));
+    f()
+}
+
+/// Defer refinements returned by [`detach`] to the current document
+pub(crate) fn adopt(deferred: Deferred) {
+    ROOTS.with(|roots| {
+        if let Some(pending) = roots.borrow_mut().last_mut() {
+            pending.extend(deferred.0);
+        }
+    });
+}
+
+/// Defer refinements recorded by [`detach`] again for a successful
+/// `result`, or run them right away when `root` is a document of its own
+pub(crate) fn replay(result: Result<Value, ValidationError>, deferred: &Deferred, root: &Value) -> Result<Value, ValidationError> {
+    let output = result?;
+    if in_document() {
+        adopt(deferred.clone());
+        return Ok(output);
+    }
+    match run(deferred.0.clone(), root).into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(output),
+    }
+}
+
+fn run(pending: Vec<Pending>, root: &Value) -> Vec<ValidationError> {
+    pending
+        .into_iter()
+        .flat_map(|p| {
+            let mut ctx = RefinementContext { path: &p.path, root, issues: Vec::new() };
+            p.refinement.refine(&p.value, &mut ctx);
+            ctx.issues
+        })
+        .collect()
+}
+
+/// Validate `root` as a whole document unless one is already being
+/// validated; refinements deferred by `f` run once it succeeds
+pub(crate) fn root<T>(root: &Value, f: impl FnOnce() -> Result<T, ValidationError>) -> Result<T, ValidationError> {
+    if in_document() {
+        return f();
+    }
+    let (result, pending) = collect(f);
+    let output = result?;
+    match run(pending, root).into_iter().next() {
+        Some(err) => Err(err),
+        None => Ok(output),
+    }
+}
+
+/// Partial counterpart of [`root`], reporting every refinement issue
+pub(crate) fn root_partial(root: &Value, f: impl FnOnce() -> PartialOutput) -> PartialOutput {
+    if in_document() {
+        return f();
+    }
+    let (mut output, pending) = collect(f);
+    output.errors.extend(run(pending, root));
+    output
+}
+
+/// Whether validating with `schema` may defer checks to the document root,
+/// which then depend on values outside the one validated. Schemas it cannot
+/// see into count as deferring.
+pub(crate) fn defers(schema: &SchemaType) -> bool {
+    match schema {
+        SchemaType::Refined(_) => true,
+        SchemaType::Number(n) => n.is_scaled(),
+        SchemaType::String(_)
+        | SchemaType::Boolean(_)
+        | SchemaType::Bytes(_)
+        | SchemaType::Never(_)
+        | SchemaType::Any(_)
+        | SchemaType::Literal(_) => false,
+        #[cfg(feature = "chrono")]
+        SchemaType::Date(_) | SchemaType::DateTime(_) => false,
+        #[cfg(feature = "rust_decimal")]
+        SchemaType::Decimal(_) => false,
+        SchemaType::Array(a) => defers(a.item()),
+        SchemaType::Object(o) => o.nested_schemas().any(defers),
+        SchemaType::Union(u) => u.schemas.iter().any(defers),
+        SchemaType::Transform(t) => defers(&t.schema),
+        SchemaType::Sensitive(s) => defers(&s.schema),
+        SchemaType::ErrorMapped(e) => defers(&e.schema),
+        SchemaType::Cached(c) => defers(&c.schema),
+        SchemaType::Deprecated(d) => defers(&d.schema),
+        SchemaType::Catch(c) => defers(&c.schema),
+        SchemaType::MapOutput(m) => defers(&m.schema),
+        SchemaType::Pipe(p) => defers(&p.schema) || defers(&p.next),
+        _ => true,
+    }
+}
+
+pub(crate) fn defer(refinement: &Arc<dyn Refinement>, value: &Value) {
+    ROOTS.with(|roots| {
+        if let Some(pending) = roots.borrow_mut().last_mut() {
+            pending.push(Pending { refinement: refinement.clone(), value: value.clone(), path: ErrorPath::new() });
+        }
+    });
+}
+
+fn mark() -> usize {
+    ROOTS.with(|roots| roots.borrow().last().map_or(0, Vec::len))
+}
+
+/// Validate a child value, prefixing the paths of refinements it defers
+pub(crate) fn nested<R>(segment: impl Into<PathSegment>, f: impl FnOnce() -> R) -> R {
+    let start = mark();
+    let result = f();
+    ROOTS.with(|roots| {
+        if let Some(pending) = roots.borrow_mut().last_mut() {
+            if pending.len() > start {
+                let segment = segment.into();
+                for p in pending.iter_mut().skip(start) {
+                    p.path.prepend(segment.clone());
+                }
+            }
+        }
+    });
+    result
+}
+
+/// Validate a union branch, dropping its refinements if it fails
+pub(crate) fn branch<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let start = mark();
+    let result = f();
+    if result.is_err() {
+        ROOTS.with(|roots| {
+            if let Some(pending) = roots.borrow_mut().last_mut() {
+                pending.truncate(start);
+            }
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{array, number, object, string, union};
+
+    fn booking() -> impl Schema {
+        object()
+            .field("start_date", string())
+            .field("end_date", string())
+            .refine(|value, ctx| {
+                if value["end_date"].as_str() <= value["start_date"].as_str() {
+                    ctx.issue_at("end_date", "End date must be after start date");
+                }
+            })
+    }
+
+    #[test]
+    fn test_refine_cross_field() {
+        let schema = booking();
+        assert!(schema.validate(&json!({"start_date": "2024-01-01", "end_date": "2024-01-05"})).is_ok());
+
+        let err = schema.validate(&json!({"start_date": "2024-01-05", "end_date": "2024-01-01"})).unwrap_err();
+        assert_eq!(err.context.code, "refine.custom");
+        assert_eq!(err.context.path, "end_date");
+        assert_eq!(err.to_string(), "End date must be after start date");
+
+        // The refinement only sees values that passed the schema
+        let err = schema.validate(&json!({"start_date": "2024-01-05"})).unwrap_err();
+        assert_eq!(err.context.path, "end_date");
+        assert_ne!(err.context.code, "refine.custom");
+    }
+
+    #[test]
+    fn test_refine_sees_root_and_path() {
+        let line = object()
+            .field("currency", string())
+            .field("amount", number())
+            .refine(|value, ctx| {
+                if value["currency"] != ctx.root()["currency"] {
+                    let message = format!("{} must use the order currency", ctx.path());
+                    ctx.issue_at("currency", message);
+                }
+            });
+        let order = object()
+            .field("currency", string())
+            .field("lines", array(line));
+
+        let ok = json!({"currency": "EUR", "lines": [{"currency": "EUR", "amount": 1}]});
+        assert!(order.validate(&ok).is_ok());
+
+        let bad = json!({"currency": "EUR", "lines": [
+            {"currency": "EUR", "amount": 1},
+            {"currency": "USD", "amount": 2},
+        ]});
+        let err = order.validate(&bad).unwrap_err();
+        assert_eq!(err.context.path, "lines.1.currency");
+        assert_eq!(err.to_string(), "lines.1 must use the order currency");
+    }
+
+    #[test]
+    fn test_refine_partial_reports_all_issues() {
+        let schema = object()
+            .field("a", number().refine(|v, ctx| if v.as_f64() > Some(10.0) { ctx.issue("Too big") }))
+            .field("b", number().refine(|v, ctx| if v.as_f64() > Some(10.0) { ctx.issue("Too big") }));
+        let output = schema.validate_partial(&json!({"a": 11, "b": 12}));
+        let paths: Vec<_> = output.errors.iter().map(|e| e.context.path.to_string()).collect();
+        assert_eq!(paths, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_failed_union_branch_drops_refinements() {
+        let schema = object().field("v", union((
+            object().field("kind", string()).field("n", number()).refine(|_, ctx| ctx.issue("never")),
+            object().field("kind", string()),
+        )));
+        // The first branch fails on its own, so its refinement never runs
+        assert!(schema.validate(&json!({"v": {"kind": "x"}})).is_ok());
+    }
+}
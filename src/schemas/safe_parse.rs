@@ -0,0 +1,135 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::{ValidationError, ValidationErrors, ErrorCode};
+use super::PartialOutput;
+
+/// Outcome of [`Schema::safe_parse`](super::Schema::safe_parse): the typed
+/// output, or every issue found
+#[derive(Debug, Clone)]
+pub enum SafeParseResult<T> {
+    Success(T),
+    Failure(ValidationErrors),
+}
+
+impl<T: DeserializeOwned> SafeParseResult<T> {
+    /// Deserialize the output of a partial validation, or collect its errors
+    pub(crate) fn from_partial(output: PartialOutput) -> Self {
+        if !output.is_valid() {
+            return SafeParseResult::Failure(output.errors.into());
+        }
+        match serde_json::from_value(output.value.unwrap_or(Value::Null)) {
+            Ok(data) => SafeParseResult::Success(data),
+            Err(e) => SafeParseResult::Failure(
+                ValidationError::new(ErrorCode::DeserializeFailed)
+                    .message(format!("Failed to deserialize: {}", e))
+                    .into(),
+            ),
+        }
+    }
+}
+
+impl<T> SafeParseResult<T> {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, SafeParseResult::Success(_))
+    }
+
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+
+    pub fn data(&self) -> Option<&T> {
+        match self {
+            SafeParseResult::Success(data) => Some(data),
+            SafeParseResult::Failure(_) => None,
+        }
+    }
+
+    /// Issues found, empty on success
+    pub fn issues(&self) -> &[ValidationError] {
+        match self {
+            SafeParseResult::Success(_) => &[],
+            SafeParseResult::Failure(errors) => errors.issues(),
+        }
+    }
+
+    pub fn into_result(self) -> Result<T, ValidationErrors> {
+        match self {
+            SafeParseResult::Success(data) => Ok(data),
+            SafeParseResult::Failure(errors) => Err(errors),
+        }
+    }
+}
+
+impl<T: Serialize> SafeParseResult<T> {
+    /// `{"success": true, "data": ...}` or `{"success": false, "issues": [...]}`
+    pub fn to_json(&self) -> Value {
+        match self {
+            SafeParseResult::Success(data) => json!({
+                "success": true,
+                "data": serde_json::to_value(data).unwrap_or(Value::Null),
+            }),
+            SafeParseResult::Failure(errors) => json!({
+                "success": false,
+                "issues": errors.to_json(),
+            }),
+        }
+    }
+}
+
+impl<T> From<SafeParseResult<T>> for Result<T, ValidationErrors> {
+    fn from(result: SafeParseResult<T>) -> Self {
+        result.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use crate::schemas::{string::{StringSchema, StringSchemaImpl}, NumberSchema, ObjectSchema, Schema};
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct User {
+        name: String,
+        age: f64,
+    }
+
+    fn user_schema() -> ObjectSchema {
+        ObjectSchema::default()
+            .field("name", StringSchemaImpl::default().min_length(2))
+            .field("age", NumberSchema::default().min(0.0))
+    }
+
+    #[test]
+    fn test_safe_parse_success() {
+        let result = user_schema().safe_parse::<User>(&json!({"name": "Ann", "age": 30}));
+
+        assert!(result.is_ok());
+        assert!(result.issues().is_empty());
+        assert_eq!(result.data(), Some(&User { name: "Ann".into(), age: 30.0 }));
+        assert_eq!(result.to_json(), json!({"success": true, "data": {"name": "Ann", "age": 30.0}}));
+    }
+
+    #[test]
+    fn test_safe_parse_collects_all_issues() {
+        let result = user_schema().safe_parse::<User>(&json!({"name": "A", "age": -1}));
+
+        assert!(result.is_err());
+        let mut paths: Vec<_> = result.issues().iter().map(|e| e.context.path.to_string()).collect();
+        paths.sort();
+        assert_eq!(paths, ["age", "name"]);
+
+        let json = result.to_json();
+        assert_eq!(json["success"], json!(false));
+        assert_eq!(json["issues"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_safe_parse_deserialize_failure() {
+        let result = StringSchemaImpl::default().safe_parse::<u32>(&json!("abc"));
+
+        assert_eq!(result.issues()[0].context.code, "parse.deserialize");
+        assert!(result.into_result().is_err());
+    }
+}
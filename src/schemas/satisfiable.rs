@@ -0,0 +1,112 @@
+use std::fmt::Display;
+use serde_json::Value;
+
+use super::{SchemaType, UnionSchema, UnionStrategy};
+
+/// A constraint no value can meet, found by
+/// [`Schema::check_satisfiable`](super::Schema::check_satisfiable)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsatisfiable {
+    /// Path of the schema (empty for the root, `*` for array items)
+    pub path: String,
+    pub kind: UnsatisfiableKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnsatisfiableKind {
+    /// A lower bound above the upper one, e.g. `min_length(5).max_length(3)`
+    ContradictoryBounds,
+    /// An intersection of literals that no value equals all of
+    DisjointLiterals,
+    /// A required field whose schema accepts no value, such as `never()`
+    RequiredNever,
+}
+
+impl Unsatisfiable {
+    pub(crate) fn new(kind: UnsatisfiableKind, message: impl Into<String>) -> Self {
+        Self { path: String::new(), kind, message: message.into() }
+    }
+}
+
+/// Prefix the path of nested issues with `segment`
+pub(crate) fn nest_unsatisfiable(segment: &str, issues: Vec<Unsatisfiable>) -> Vec<Unsatisfiable> {
+    issues
+        .into_iter()
+        .map(|mut issue| {
+            issue.path = if issue.path.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}.{}", segment, issue.path)
+            };
+            issue
+        })
+        .collect()
+}
+
+/// An issue if the inclusive bounds `min` and `max` of `what` leave no room
+pub(crate) fn check_bounds<T: PartialOrd + Display>(what: &str, min: Option<T>, max: Option<T>) -> Option<Unsatisfiable> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Some(Unsatisfiable::new(
+            UnsatisfiableKind::ContradictoryBounds,
+            format!("Minimum {} {} is above the maximum {}", what, min, max),
+        )),
+        _ => None,
+    }
+}
+
+/// An issue if `union` intersects literals that share no value
+pub(crate) fn check_literals(union: &UnionSchema) -> Option<Unsatisfiable> {
+    if !matches!(union.strategy, UnionStrategy::All) {
+        return None;
+    }
+    let mut sets = union.schemas.iter().filter_map(literal_set);
+    let first = sets.next()?;
+    let common = sets.fold(first, |common, set| common.into_iter().filter(|v| set.contains(v)).collect());
+    common.is_empty().then(|| {
+        Unsatisfiable::new(UnsatisfiableKind::DisjointLiterals, "No value matches every literal of the intersection")
+    })
+}
+
+/// Values a schema accepts if it only accepts literals
+fn literal_set(schema: &SchemaType) -> Option<Vec<Value>> {
+    match schema {
+        SchemaType::Literal(l) => Some(vec![l.value().clone()]),
+        SchemaType::Union(u) if matches!(u.strategy, UnionStrategy::First | UnionStrategy::Best { .. }) => {
+            let sets: Option<Vec<_>> = u.schemas.iter().map(literal_set).collect();
+            sets.map(|sets| sets.into_iter().flatten().collect())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{array, eq, never, number, object, string, union};
+    use crate::schemas::{Schema, StringSchema};
+
+    #[test]
+    fn test_check_satisfiable() {
+        let schema = object()
+            .field("name", string().min_length(5).max_length(3))
+            .field("age", number().min(10.0).max(10.0))
+            .field("ratio", number().min(1.0).lt(1.0))
+            .optional_field("tags", array(string()).min_items(3).max_items(1))
+            .field("gone", never())
+            .optional_field("legacy", never())
+            .field("kind", union((eq("a"), union((eq("b"), eq("c"))))).strategy(UnionStrategy::All));
+        let issues: Vec<_> = schema.check_satisfiable().into_iter().map(|i| (i.path, i.kind)).collect();
+        assert_eq!(issues, [
+            ("name".to_string(), UnsatisfiableKind::ContradictoryBounds),
+            ("ratio".to_string(), UnsatisfiableKind::ContradictoryBounds),
+            ("tags".to_string(), UnsatisfiableKind::ContradictoryBounds),
+            ("gone".to_string(), UnsatisfiableKind::RequiredNever),
+            ("kind".to_string(), UnsatisfiableKind::DisjointLiterals),
+        ]);
+
+        let schema = array(object().field("kind", union((eq("a"), union((eq("a"), eq("b"))))).strategy(UnionStrategy::All)));
+        assert!(schema.check_satisfiable().is_empty());
+        assert_eq!(array(string().min_length(2).max_length(1)).check_satisfiable()[0].path, "*");
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use crate::error::{ValidationError, MessageIssue};
+use super::{Schema, Presence, SchemaType, HasErrorMessages};
+
+/// Placeholder substituted for sensitive values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Marks a schema's values as sensitive (passwords, tokens, secrets)
+///
+/// The received value recorded on errors is replaced by `"[REDACTED]"`, so
+/// messages that echo the input through `{value}` or `{received}` show the
+/// placeholder, and [`Schema::redact`] masks the whole value when producing
+/// log or audit copies of a document. Text a custom validator formats into
+/// its own message is left as written.
+#[derive(Clone)]
+pub struct Sensitive<S> {
+    pub schema: S,
+}
+
+impl<S> Sensitive<S> {
+    pub fn new(schema: S) -> Self {
+        Self { schema }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.schema
+    }
+}
+
+impl<S: HasErrorMessages> HasErrorMessages for Sensitive<S> {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        self.schema.check_messages()
+    }
+}
+
+impl<S: Schema> Schema for Sensitive<S> {
+    fn is_optional(&self) -> bool {
+        self.schema.is_optional()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<super::Explanation> {
+        super::explain::with_rules(self.schema.explain(), ["sensitive".to_string()])
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        self.schema.validate(value).map_err(|mut err| {
+            if err.context.path.is_empty() || err.context.details.received.is_some() {
+                err.context.details.received = Some(Value::String(REDACTED.to_string()));
+            }
+            err
+        })
+    }
+
+    fn preserves_input(&self) -> bool {
+        self.schema.preserves_input()
+    }
+
+    fn redact(&self, _value: &Value) -> Value {
+        Value::String(REDACTED.to_string())
+    }
+
+    fn lint(&self) -> Vec<super::LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Sensitive(Box::new(Sensitive::new(self.schema.into_schema_type())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{string, number, object, array, StringSchema};
+
+    #[test]
+    fn test_sensitive_redacts_echoed_value() {
+        let schema = string()
+            .custom(|s| if s.len() >= 8 { Ok(()) } else { Err("'{value}' is too weak".to_string()) })
+            .sensitive();
+
+        assert_eq!(schema.validate(&json!("longenough")).unwrap(), json!("longenough"));
+        let err = schema.validate(&json!("hunter2")).unwrap_err();
+        assert_eq!(err.to_string(), "'[REDACTED]' is too weak");
+    }
+
+    #[test]
+    fn test_sensitive_nested_in_object() {
+        let schema = object()
+            .field("user", string())
+            .field("password", string().custom(|_| Err("bad: {value}".to_string())).sensitive());
+
+        let err = schema.validate(&json!({"user": "ada", "password": "s3cret"})).unwrap_err();
+        assert_eq!(err.context.path, "password");
+        assert_eq!(err.to_string(), "bad: [REDACTED]");
+        assert_eq!(err.context.details.received, Some(json!("[REDACTED]")));
+    }
+
+    #[test]
+    fn test_sensitive_keeps_message_text() {
+        let schema = string().email().sensitive();
+        assert_eq!(schema.validate(&json!("a")).unwrap_err().to_string(), "Invalid email address");
+
+        let schema = number().min(10.0).sensitive();
+        let err = schema.validate(&json!(1)).unwrap_err();
+        assert_eq!(err.to_string(), "Number must be greater than or equal to 10");
+        assert_eq!(err.context.details.received, Some(json!("[REDACTED]")));
+    }
+
+    #[test]
+    fn test_redact_masks_sensitive_paths() {
+        let schema = object()
+            .field("user", string())
+            .field("password", string().sensitive())
+            .field("keys", array(object().field("token", string().sensitive()).field("name", string())));
+
+        let input = json!({
+            "user": "ada",
+            "password": "s3cret",
+            "keys": [{"token": "abc", "name": "ci"}]
+        });
+        assert_eq!(schema.redact(&input), json!({
+            "user": "ada",
+            "password": "[REDACTED]",
+            "keys": [{"token": "[REDACTED]", "name": "ci"}]
+        }));
+    }
+}
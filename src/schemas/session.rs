@@ -0,0 +1,182 @@
+use serde_json::{Map, Value};
+
+use crate::error::{PathSegment, ValidationError, ValidationErrors};
+use super::{ObjectSchema, PartialOutput, Schema};
+
+/// An object validated field by field as its values arrive, e.g. from the
+/// steps of a wizard or a form streamed to the server
+///
+/// Every [`ValidationSession::set`] revalidates the whole object, so
+/// conditions, rules and defaults see the other fields set so far. Fields
+/// not yet set are reported by [`ValidationSession::missing_fields`]
+/// rather than as errors.
+///
+/// ```
+/// use rusty_zod::prelude::*;
+/// use rusty_zod::ValidationSession;
+/// use serde_json::json;
+///
+/// let schema = object().field("email", string().email()).field("age", number().min(18.0));
+/// let mut session = ValidationSession::new(schema);
+/// assert!(!session.set("email", "not an email").is_empty());
+/// assert!(session.set("email", "ada@example.com").is_empty());
+/// assert_eq!(session.missing_fields(), ["age"]);
+///
+/// session.set("age", 36);
+/// assert_eq!(session.finish().unwrap(), json!({"email": "ada@example.com", "age": 36}));
+/// ```
+#[derive(Clone)]
+pub struct ValidationSession {
+    schema: ObjectSchema,
+    values: Map<String, Value>,
+    output: PartialOutput,
+}
+
+impl ValidationSession {
+    pub fn new(schema: ObjectSchema) -> Self {
+        let output = schema.validate_partial(&Value::Object(Map::new()));
+        Self { schema, values: Map::new(), output }
+    }
+
+    /// Set `field` and revalidate, returning the errors now reported for it
+    pub fn set(&mut self, field: &str, value: impl Into<Value>) -> Vec<&ValidationError> {
+        self.values.insert(field.to_string(), value.into());
+        self.revalidate();
+        self.field_errors(field)
+    }
+
+    /// Unset `field`, returning its value
+    pub fn remove(&mut self, field: &str) -> Option<Value> {
+        let value = self.values.remove(field);
+        self.revalidate();
+        value
+    }
+
+    /// Value `field` was set to, before validation
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.values.get(field)
+    }
+
+    /// Errors of `field`, including those of values nested in it
+    pub fn field_errors(&self, field: &str) -> Vec<&ValidationError> {
+        self.output.errors.iter().filter(|err| first_key(err) == Some(field)).collect()
+    }
+
+    /// Errors of the fields set so far and of the object as a whole
+    pub fn errors(&self) -> Vec<&ValidationError> {
+        self.output.errors.iter().filter(|err| !self.is_pending(err)).collect()
+    }
+
+    /// Required fields not yet set, in declaration order
+    pub fn missing_fields(&self) -> Vec<&str> {
+        let mut missing: Vec<&str> = Vec::new();
+        for field in self.output.errors.iter().filter(|err| self.is_pending(err)).filter_map(first_key) {
+            if !missing.contains(&field) {
+                missing.push(field);
+            }
+        }
+        missing
+    }
+
+    /// Whether every required field is set and the object validates
+    pub fn is_complete(&self) -> bool {
+        self.output.errors.is_empty()
+    }
+
+    /// Validated output so far, invalid fields left out
+    pub fn value(&self) -> Option<&Value> {
+        self.output.value.as_ref()
+    }
+
+    /// The validated object, or every error and missing field left
+    pub fn finish(&self) -> Result<Value, ValidationErrors> {
+        match (&self.output.value, self.is_complete()) {
+            (Some(value), true) => Ok(value.clone()),
+            _ => Err(ValidationErrors::new(self.output.errors.clone())),
+        }
+    }
+
+    fn revalidate(&mut self) {
+        self.output = self.schema.validate_partial(&Value::Object(self.values.clone()));
+    }
+
+    /// Whether `err` is about a field not set yet
+    fn is_pending(&self, err: &ValidationError) -> bool {
+        first_key(err).is_some_and(|field| !self.values.contains_key(field))
+    }
+}
+
+fn first_key(err: &ValidationError) -> Option<&str> {
+    match err.context.path.segments().first() {
+        Some(PathSegment::Key(key)) => Some(key),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{boolean, eq, number, object, string};
+    use crate::schemas::StringSchema;
+
+    fn signup() -> ObjectSchema {
+        object()
+            .field("email", string().email())
+            .field("password", string().min_length(8))
+            .field("confirm", string())
+            .field("business", boolean())
+            .optional_field("newsletter", boolean())
+            .when("business", eq(true), |s| s.field("company", string().min_length(1)))
+            .rule(["password", "confirm"], |obj| {
+                if obj["password"] != obj["confirm"] {
+                    return Err("Passwords do not match");
+                }
+                Ok(())
+            })
+    }
+
+    #[test]
+    fn test_session_reports_progress() {
+        let mut session = ValidationSession::new(signup());
+        assert!(session.errors().is_empty());
+        assert_eq!(session.missing_fields(), ["email", "password", "confirm", "business"]);
+
+        let errors = session.set("email", "ada");
+        assert_eq!(errors[0].context.code, "string.email");
+        assert!(session.set("email", "ada@example.com").is_empty());
+        assert!(session.set("password", "correct horse").is_empty());
+        // The rule waits for both of its fields
+        assert!(session.errors().is_empty());
+        assert_eq!(session.set("confirm", "wrong horse")[0].to_string(), "Passwords do not match");
+        session.set("confirm", "correct horse");
+
+        // A condition met adds its fields to the missing ones
+        session.set("business", true);
+        assert_eq!(session.missing_fields(), ["company"]);
+        assert!(!session.is_complete());
+        assert!(session.finish().is_err());
+
+        session.set("company", "Analytical Engines");
+        assert!(session.is_complete());
+        assert_eq!(session.finish().unwrap()["company"], "Analytical Engines");
+
+        session.remove("business");
+        assert_eq!(session.missing_fields(), ["business"]);
+        assert_eq!(session.get("email"), Some(&json!("ada@example.com")));
+        assert_eq!(session.value().unwrap()["password"], "correct horse");
+    }
+
+    #[test]
+    fn test_session_unknown_and_nested_fields() {
+        let schema = object().field("address", object().field("zip", string().min_length(5))).field("age", number());
+        let mut session = ValidationSession::new(schema);
+        assert_eq!(session.set("nickname", "ada")[0].context.code, "object.unknown_field");
+        session.remove("nickname");
+
+        let errors = session.set("address", json!({"zip": "123"}));
+        assert_eq!(errors[0].context.path, "address.zip");
+        assert_eq!(session.errors().len(), 1);
+        assert_eq!(session.finish().unwrap_err().len(), 2);
+    }
+}
@@ -0,0 +1,176 @@
+use serde_json::{Map, Number, Value};
+
+use crate::error::{ErrorCode, SourceSpan, ValidationError};
+
+/// JSON value of YAML `text`. Tags are dropped and scalar keys such as `1`
+/// or `true` become strings, failing when two keys become the same string.
+#[cfg(feature = "yaml")]
+pub(crate) fn from_yaml(text: &str) -> Result<Value, ValidationError> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| {
+        let err = ValidationError::new(ErrorCode::InvalidYaml).message(format!("Invalid YAML: {}", e));
+        match e.location() {
+            Some(at) => err.with_details(|d| d.span = Some(SourceSpan::from_offsets(text, at.index(), at.index()))),
+            None => err,
+        }
+    })?;
+    yaml_to_json(yaml)
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_to_json(yaml: serde_yaml::Value) -> Result<Value, ValidationError> {
+    use serde_yaml::Value as Yaml;
+
+    let value = match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+            (Some(i), _, _) => Value::from(i),
+            (_, Some(u), _) => Value::from(u),
+            (_, _, f) => float(f.unwrap_or(f64::NAN), ErrorCode::InvalidYaml, "YAML")?,
+        },
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(items) => Value::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| yaml_to_json(item).map_err(|err| err.with_path_prefix(i)))
+                .collect::<Result<_, _>>()?,
+        ),
+        Yaml::Mapping(mapping) => {
+            let mut obj = Map::new();
+            for (key, value) in mapping {
+                let key = match key {
+                    Yaml::String(s) => s,
+                    Yaml::Number(n) => n.to_string(),
+                    Yaml::Bool(b) => b.to_string(),
+                    Yaml::Null => "null".to_string(),
+                    _ => {
+                        return Err(ValidationError::new(ErrorCode::InvalidYaml)
+                            .message("Invalid YAML: mapping keys must be scalars"));
+                    }
+                };
+                if obj.contains_key(&key) {
+                    return Err(ValidationError::new(ErrorCode::InvalidYaml)
+                        .message(format!("Invalid YAML: duplicate key '{}'", key))
+                        .at(key.as_str()));
+                }
+                let value = yaml_to_json(value).map_err(|err| err.with_path_prefix(key.as_str()))?;
+                obj.insert(key, value);
+            }
+            Value::Object(obj)
+        }
+        Yaml::Tagged(tagged) => yaml_to_json(tagged.value)?,
+    };
+    Ok(value)
+}
+
+/// JSON value of TOML `text`, date-times as their RFC 3339 strings
+#[cfg(feature = "toml")]
+pub(crate) fn from_toml(text: &str) -> Result<Value, ValidationError> {
+    let table: toml::Table = toml::from_str(text).map_err(|e| {
+        let err = ValidationError::new(ErrorCode::InvalidToml).message(format!("Invalid TOML: {}", e.message()));
+        match e.span() {
+            Some(span) => err.with_details(|d| d.span = Some(SourceSpan::from_offsets(text, span.start, span.end))),
+            None => err,
+        }
+    })?;
+    toml_to_json(toml::Value::Table(table))
+}
+
+#[cfg(feature = "toml")]
+fn toml_to_json(toml: toml::Value) -> Result<Value, ValidationError> {
+    use toml::Value as Toml;
+
+    let value = match toml {
+        Toml::String(s) => Value::String(s),
+        Toml::Integer(i) => Value::from(i),
+        Toml::Float(f) => float(f, ErrorCode::InvalidToml, "TOML")?,
+        Toml::Boolean(b) => Value::Bool(b),
+        Toml::Datetime(dt) => Value::String(dt.to_string()),
+        Toml::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| toml_to_json(item).map_err(|err| err.with_path_prefix(i)))
+                .collect::<Result<_, _>>()?,
+        ),
+        Toml::Table(table) => {
+            let mut obj = Map::new();
+            for (key, value) in table {
+                let value = toml_to_json(value).map_err(|err| err.with_path_prefix(key.as_str()))?;
+                obj.insert(key, value);
+            }
+            Value::Object(obj)
+        }
+    };
+    Ok(value)
+}
+
+/// `f` as a JSON number; infinities and NaN, which JSON lacks, fail
+fn float(f: f64, code: ErrorCode, format: &str) -> Result<Value, ValidationError> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| ValidationError::new(code).message(format!("Invalid {}: {} has no JSON equivalent", format, f)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::{array, number, object, string};
+    use crate::schemas::Schema;
+
+    fn config() -> crate::ObjectSchema {
+        object()
+            .field("name", string())
+            .field("servers", array(object().field("host", string()).field("port", number().integer())))
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_validate_yaml() {
+        let yaml = "name: api\nservers:\n  - host: a\n    port: 80\n  - host: b\n    port: 81\n";
+        let output = config().validate_yaml(yaml).unwrap();
+        assert_eq!(output, json!({"name": "api", "servers": [{"host": "a", "port": 80}, {"host": "b", "port": 81}]}));
+        let output = config().passthrough().validate_yaml("z: 1\nname: api\na: 2\nservers: []\n").unwrap();
+        assert_eq!(output, json!({"name": "api", "servers": [], "z": 1, "a": 2}));
+
+        // `1` and "1" both become the key "1"
+        let err = object().passthrough().validate_yaml("1: a\n\"1\": b\n").unwrap_err();
+        assert_eq!(err.context.code, "parse.invalid_yaml");
+        assert_eq!(err.context.path, "1");
+        assert!(err.to_string().contains("duplicate key '1'"));
+        assert!(object().passthrough().validate_yaml("true: a\n\"True\": b\n").is_ok());
+
+        let err = config().validate_yaml("name: api\nservers:\n  - host: a\n    port: eighty\n").unwrap_err();
+        assert_eq!(err.context.path, "servers.0.port");
+        assert_eq!(err.context.details.source_path.as_deref(), Some("servers[0].port"));
+
+        let err = config().validate_yaml("name: [api\n").unwrap_err();
+        assert_eq!(err.context.code, "parse.invalid_yaml");
+        assert!(err.context.details.span.is_some());
+        let err = config().validate_yaml("name: api\nservers:\n  - host: a\n    port: .inf\n").unwrap_err();
+        assert_eq!(err.context.code, "parse.invalid_yaml");
+        assert_eq!(err.context.details.source_path.as_deref(), Some("servers[0].port"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_validate_toml() {
+        use crate::any;
+
+        let schema = config().field("released", string()).optional_field("labels", any());
+        let toml = "name = \"api\"\nreleased = 2024-05-01T10:00:00Z\n\n[[servers]]\nhost = \"a\"\nport = 80\n";
+        let output = schema.validate_toml(toml).unwrap();
+        assert_eq!(output, json!({"name": "api", "released": "2024-05-01T10:00:00Z", "servers": [{"host": "a", "port": 80}]}));
+        let output = schema.validate_toml(&format!("{}\n[labels]\nzone = \"eu\"\nowner = \"ops\"\n", toml)).unwrap();
+        assert_eq!(output["labels"], json!({"zone": "eu", "owner": "ops"}));
+
+        let err = schema.validate_toml("name = \"api\"\nreleased = \"x\"\n\n[[servers]]\nhost = 1\nport = 80\n").unwrap_err();
+        assert_eq!(err.context.path, "servers.0.host");
+        assert_eq!(err.context.details.source_path.as_deref(), Some("servers[0].host"));
+
+        let err = schema.validate_toml("name = ").unwrap_err();
+        assert_eq!(err.context.code, "parse.invalid_toml");
+        assert!(err.context.details.span.is_some());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::error::{ErrorCode, PathSegment, SourceSpan, ValidationError};
+
+/// Error for JSON `text` that failed to parse, located where parsing stopped
+pub(crate) fn invalid_json(e: serde_json::Error, text: &str) -> ValidationError {
+    ValidationError::new(ErrorCode::InvalidJson)
+        .message(format!("Invalid JSON: {}", e))
+        .with_details(|d| d.span = Some(SourceSpan::at_line_column(text, e.line(), e.column())))
+}
+
+/// Fill in the source span of `err` from the JSON `text` it was found in:
+/// the value at its path, or the closest enclosing value that exists
+pub(crate) fn attach_span(err: ValidationError, text: &str) -> ValidationError {
+    if err.context.details.span.is_some() {
+        return err;
+    }
+    let mut scanner = Scanner { bytes: text.as_bytes(), pos: 0 };
+    match scanner.locate(err.context.path.segments()) {
+        Some((start, end)) => err.with_details(|d| d.span = Some(SourceSpan::from_offsets(text, start, end))),
+        None => err,
+    }
+}
+
+/// Minimal JSON scanner over text `serde_json` has already accepted
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `byte` after optional whitespace
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_ws();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn skip_string(&mut self) {
+        self.pos += 1;
+        while let Some(b) = self.peek() {
+            self.pos += 1;
+            match b {
+                b'\\' => self.pos += 1,
+                b'"' => return,
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip the value at the cursor, returning its byte range
+    fn skip_value(&mut self) -> (usize, usize) {
+        self.skip_ws();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'"') => self.skip_string(),
+            Some(open @ (b'{' | b'[')) => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.pos += 1;
+                if !self.eat(close) {
+                    loop {
+                        if open == b'{' {
+                            self.skip_ws();
+                            self.skip_string();
+                            self.eat(b':');
+                        }
+                        self.skip_value();
+                        if !self.eat(b',') {
+                            self.eat(close);
+                            break;
+                        }
+                    }
+                }
+            }
+            _ => {
+                while matches!(self.peek(), Some(b) if !matches!(b, b',' | b']' | b'}' | b' ' | b'\t' | b'\n' | b'\r')) {
+                    self.pos += 1;
+                }
+            }
+        }
+        (start, self.pos)
+    }
+
+    /// Range of the value at `path` below the cursor, stopping at the deepest
+    /// segment that exists. Repeated keys resolve to the last one, as in
+    /// `serde_json`.
+    fn locate(&mut self, path: &[PathSegment]) -> Option<(usize, usize)> {
+        self.skip_ws();
+        let start = self.pos;
+        let Some((segment, rest)) = path.split_first() else {
+            return Some(self.skip_value());
+        };
+        let mut found = None;
+        match (segment, self.peek()) {
+            (PathSegment::Key(key), Some(b'{')) => {
+                self.pos += 1;
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_ws();
+                        let key_start = self.pos;
+                        self.skip_string();
+                        let name: Option<String> = serde_json::from_slice(&self.bytes[key_start..self.pos]).ok();
+                        self.eat(b':');
+                        if name.as_deref() == Some(key.as_str()) {
+                            found = self.locate(rest);
+                        } else {
+                            self.skip_value();
+                        }
+                        if !self.eat(b',') {
+                            self.eat(b'}');
+                            break;
+                        }
+                    }
+                }
+            }
+            (PathSegment::Index(index), Some(b'[')) => {
+                self.pos += 1;
+                if !self.eat(b']') {
+                    let mut i = 0;
+                    loop {
+                        if i == *index {
+                            found = self.locate(rest);
+                        } else {
+                            self.skip_value();
+                        }
+                        i += 1;
+                        if !self.eat(b',') {
+                            self.eat(b']');
+                            break;
+                        }
+                    }
+                }
+            }
+            _ => {
+                self.pos = start;
+                return Some(self.skip_value());
+            }
+        }
+        found.or(Some((start, self.pos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SourceSpan;
+    use crate::{array, number, object, string, Schema, StringSchema};
+
+    const TEXT: &str = "{\n  \"name\": \"Ada\",\n  \"tags\": [\"a\", \"bé\", 3],\n  \"age\": \"old\"\n}";
+
+    #[test]
+    fn test_span_of_nested_values() {
+        let schema = object()
+            .field("name", string())
+            .field("tags", array(string()))
+            .field("age", number());
+
+        let err = schema.validate_str(TEXT).unwrap_err();
+        let span = err.context.details.span.unwrap();
+        assert_eq!(&TEXT[span.start..span.end], "3");
+        assert_eq!((span.line, span.column), (3, 23));
+
+        let err = object().field("age", number()).validate_str(TEXT).unwrap_err();
+        let span = err.context.details.span.unwrap();
+        assert_eq!(&TEXT[span.start..span.end], "\"old\"");
+        assert_eq!((span.line, span.column), (4, 10));
+    }
+
+    #[test]
+    fn test_missing_field_points_at_enclosing_object() {
+        let err = object().field("email", string().email()).validate_str(TEXT).unwrap_err();
+        let span = err.context.details.span.unwrap();
+        assert_eq!((span.start, span.end), (0, TEXT.len()));
+    }
+
+    #[test]
+    fn test_invalid_json_span() {
+        let text = "{\n  \"a\": tru\n}";
+        let err = string().validate_str(text).unwrap_err();
+        assert_eq!(err.context.code, "parse.invalid_json");
+        let span = err.context.details.span.unwrap();
+        let resolved = SourceSpan::from_offsets(text, span.start, span.end);
+        assert_eq!((resolved.line, resolved.column), (span.line, span.column));
+        assert!(span.line > 1);
+    }
+}
@@ -0,0 +1,145 @@
+use std::{fmt, io::{BufReader, Read}};
+use serde::de::{Deserializer as _, SeqAccess, Visitor};
+use serde_json::Value;
+
+use crate::error::{ErrorCode, ValidationError};
+use super::Schema;
+
+/// Validates the items of a JSON array as they are parsed, for payloads
+/// too large to hold as one [`Value`]
+///
+/// Only one item is in memory at a time: each is parsed, validated with
+/// the item schema and handed to a callback before the next is read.
+/// Item errors are reported at `<index>`, like those of an array schema.
+///
+/// ```
+/// use rusty_zod::prelude::*;
+/// use rusty_zod::StreamingArrayValidator;
+///
+/// let validator = StreamingArrayValidator::new(object().field("id", number()));
+/// let mut invalid = Vec::new();
+/// let count = validator
+///     .validate_reader(r#"[{"id": 1}, {"id": "x"}, {"id": 3}]"#.as_bytes(), |index, result| {
+///         if result.is_err() {
+///             invalid.push(index);
+///         }
+///     })
+///     .unwrap();
+/// assert_eq!((count, invalid), (3, vec![1]));
+/// ```
+#[derive(Clone)]
+pub struct StreamingArrayValidator<S> {
+    item_schema: S,
+}
+
+impl<S: Schema> StreamingArrayValidator<S> {
+    pub fn new(item_schema: S) -> Self {
+        Self { item_schema }
+    }
+
+    /// Parse the JSON array in `reader`, calling `on_item` with the index
+    /// and outcome of each item in turn, and return the number of items
+    ///
+    /// Fails as `parse.invalid_json` if the input is not a JSON array;
+    /// items already handed to `on_item` stay handed. `reader` is buffered
+    /// here, so a plain `File` can be passed as is.
+    pub fn validate_reader<R, F>(&self, reader: R, on_item: F) -> Result<usize, ValidationError>
+    where
+        R: Read,
+        F: FnMut(usize, Result<Value, ValidationError>),
+    {
+        let mut de = serde_json::Deserializer::from_reader(BufReader::new(reader));
+        let count = de
+            .deserialize_seq(ItemVisitor { schema: &self.item_schema, on_item })
+            .and_then(|count| de.end().map(|_| count))
+            .map_err(|e| ValidationError::new(ErrorCode::InvalidJson).message(format!("Invalid JSON: {}", e)))?;
+        Ok(count)
+    }
+
+    /// [`StreamingArrayValidator::validate_reader`] collecting the items
+    /// that fail, as their errors, rather than calling back
+    pub fn invalid_items<R: Read>(&self, reader: R) -> Result<Vec<ValidationError>, ValidationError> {
+        let mut errors = Vec::new();
+        self.validate_reader(reader, |_, result| errors.extend(result.err()))?;
+        Ok(errors)
+    }
+}
+
+struct ItemVisitor<'a, S, F> {
+    schema: &'a S,
+    on_item: F,
+}
+
+impl<'de, S, F> Visitor<'de> for ItemVisitor<'_, S, F>
+where
+    S: Schema,
+    F: FnMut(usize, Result<Value, ValidationError>),
+{
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<usize, A::Error> {
+        let mut index = 0;
+        while let Some(item) = seq.next_element::<Value>()? {
+            let result = self.schema.validate(&item).map_err(|err| err.with_path_prefix(index));
+            (self.on_item)(index, result);
+            index += 1;
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[test]
+    fn test_streaming_array_validator() {
+        let validator = StreamingArrayValidator::new(object().field("id", number()).field("name", string().min_length(1)));
+        let input = r#"[{"id": 1, "name": "a"}, {"id": 2, "name": ""}, {"id": "x", "name": "c"}]"#;
+        let mut outputs = Vec::new();
+        let count = validator.validate_reader(input.as_bytes(), |index, result| outputs.push((index, result))).unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(outputs[0].1.as_ref().unwrap()["name"], "a");
+        assert_eq!(outputs[1].1.as_ref().unwrap_err().context.path, "1.name");
+
+        let errors = validator.invalid_items(input.as_bytes()).unwrap();
+        let paths: Vec<_> = errors.iter().map(|e| e.context.path.to_string()).collect();
+        assert_eq!(paths, ["1.name", "2.id"]);
+
+        assert_eq!(validator.validate_reader("[]".as_bytes(), |_, _| {}).unwrap(), 0);
+        let err = validator.invalid_items(r#"{"id": 1}"#.as_bytes()).unwrap_err();
+        assert_eq!(err.context.code, "parse.invalid_json");
+        // Items before a syntax error are still validated
+        let mut seen = 0;
+        assert!(validator.validate_reader(r#"[{"id": 1, "name": "a"}, {"id": "#.as_bytes(), |_, _| seen += 1).is_err());
+        assert_eq!(seen, 1);
+        assert!(validator.invalid_items("[] []".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_streaming_reads_in_blocks() {
+        struct CountingReader<'a> {
+            input: &'a [u8],
+            reads: usize,
+        }
+
+        impl Read for CountingReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.reads += 1;
+                self.input.read(buf)
+            }
+        }
+
+        let input = format!("[{}]", vec![r#"{"id": 1}"#; 1000].join(","));
+        let mut reader = CountingReader { input: input.as_bytes(), reads: 0 };
+        let validator = StreamingArrayValidator::new(object().field("id", number()));
+        assert_eq!(validator.validate_reader(&mut reader, |_, _| {}).unwrap(), 1000);
+        assert!(reader.reads < 10, "{} reads for {} bytes", reader.reads, input.len());
+    }
+}
@@ -0,0 +1,124 @@
+use std::{cell::RefCell, fmt};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+
+use crate::error::{ErrorPath, PathSegment, ValidationError, ErrorCode};
+use super::spans;
+
+/// Parse JSON text, failing on the first object with a repeated key instead
+/// of silently keeping the last value as `serde_json` does
+pub(crate) fn from_str_strict(json: &str) -> Result<Value, ValidationError> {
+    let duplicate = RefCell::new(None);
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let result = StrictValue { path: ErrorPath::new(), duplicate: &duplicate }
+        .deserialize(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|_| value));
+
+    match (result, duplicate.into_inner()) {
+        (_, Some((path, key))) => Err(ValidationError::new(ErrorCode::DuplicateKey)
+            .at(path)
+            .with_details(|d| d.field_name = Some(key))),
+        (Ok(value), None) => Ok(value),
+        (Err(e), None) => Err(spans::invalid_json(e, json)),
+    }
+}
+
+/// Seed deserializing one value at `path`, recording the first duplicate key
+struct StrictValue<'a> {
+    path: ErrorPath,
+    duplicate: &'a RefCell<Option<(ErrorPath, String)>>,
+}
+
+impl StrictValue<'_> {
+    fn child(&self, segment: impl Into<PathSegment>) -> Self {
+        StrictValue { path: self.path.child(segment), duplicate: self.duplicate }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for StrictValue<'_> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StrictValue<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(self.child(items.len()))? {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut object = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if object.contains_key(&key) {
+                *self.duplicate.borrow_mut() = Some((self.path.clone(), key));
+                return Err(de::Error::custom("duplicate key"));
+            }
+            let value = map.next_value_seed(self.child(&key))?;
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_strict_parse() {
+        assert_eq!(from_str_strict(r#"{"a": [1, {"b": null}]}"#).unwrap(), json!({"a": [1, {"b": null}]}));
+        assert_eq!(from_str_strict("{} x").unwrap_err().context.code, "parse.invalid_json");
+    }
+
+    #[test]
+    fn test_duplicate_key_path() {
+        let err = from_str_strict(r#"{"role": "user", "role": "admin"}"#).unwrap_err();
+        assert_eq!(err.context.code, "object.duplicate_key");
+        assert_eq!(err.context.path, "");
+        assert_eq!(err.context.details.field_name.as_deref(), Some("role"));
+
+        let err = from_str_strict(r#"{"items": [{"id": 1}, {"id": 2, "id": 3}]}"#).unwrap_err();
+        assert_eq!(err.context.path, "items.1");
+    }
+}
@@ -1,9 +1,10 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, ops::{Bound, RangeBounds}, sync::{Arc, OnceLock}};
 use regex::Regex;
 use serde_json::Value;
 
-use crate::error::{ValidationError, ErrorCode};
-use super::{Schema, SchemaType, HasErrorMessages, get_type_name, transform::{Transformable, Transform, WithTransform}};
+use crate::error::{ValidationError, ErrorCode, CustomIssue, canonical_code};
+use crate::error::message_check::debug_check_template;
+use super::{Schema, Presence, SchemaType, HasErrorMessages, InvalidFields, PartialOutput, LintFinding, LintKind, lint, Explanation, explain, Unsatisfiable, satisfiable, compat, get_type_name, warnings, password::{PasswordPolicy, PasswordViolation}, path::{PathPolicy, is_valid_glob}, transform::{Transformable, Transform, WithTransform}};
 
 pub trait StringSchema: Schema {
     fn min_length(self, length: usize) -> Self;
@@ -11,91 +12,476 @@ pub trait StringSchema: Schema {
     fn pattern(self, pattern: &str) -> Self;
     fn email(self) -> Self;
     fn optional(self) -> Self;
+    /// Whether the value may be missing and/or null
+    fn presence(self, presence: Presence) -> Self;
     fn error_message(self, code: impl Into<String>, message: impl Into<String>) -> Self;
-    fn custom<F>(self, validator: F) -> Self
+    /// Custom check returning a message, or a [`CustomIssue`] with its own
+    /// code and details
+    fn custom<F, E>(self, validator: F) -> Self
     where
-        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static;
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>;
+    /// Custom check reported under its own code, `string.custom.<label>`
+    fn custom_labeled<F, E>(self, label: impl Into<String>, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>;
+
+    /// Message used when the value is null or the enclosing object is missing it
+    fn required_message(self, message: impl Into<String>) -> Self
+    where
+        Self: Sized,
+    {
+        self.error_message(ErrorCode::StringRequired, message)
+    }
+
+    /// Like [`StringSchema::pattern`], but the whole string must match
+    /// rather than any substring, so `\d{5}` rejects `"abc12345def"`
+    fn pattern_full_match(self, pattern: &str) -> Self
+    where
+        Self: Sized,
+    {
+        self.pattern(&full_match(pattern))
+    }
+
+    /// Set both length bounds from a Rust range, e.g. `3..=20` or `1..64`
+    fn len_range<R: RangeBounds<usize>>(self, range: R) -> Self
+    where
+        Self: Sized,
+    {
+        let (min, max) = length_bounds(&range);
+        let schema = match min {
+            Some(min) => self.min_length(min),
+            None => self,
+        };
+        match max {
+            Some(max) => schema.max_length(max),
+            None => schema,
+        }
+    }
 }
 
-#[derive(Clone)]
+/// Resolve a length range into inclusive `(min, max)` bounds
+pub(crate) fn length_bounds<R: RangeBounds<usize>>(range: &R) -> (Option<usize>, Option<usize>) {
+    let min = match range.start_bound() {
+        Bound::Included(n) => Some(*n),
+        Bound::Excluded(n) => Some(n.saturating_add(1)),
+        Bound::Unbounded => None,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(n) => Some(*n),
+        Bound::Excluded(n) => Some(n.saturating_sub(1)),
+        Bound::Unbounded => None,
+    };
+    (min, max)
+}
+
+/// Anchor `pattern` at both ends; the group keeps alternations like `a|b`
+/// from escaping the anchors
+fn full_match(pattern: &str) -> String {
+    format!("^(?:{})$", pattern)
+}
+
+/// Shared email regex, compiled once on first use
+fn email_regex() -> &'static Regex {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    EMAIL.get_or_init(|| compile_regex(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$"))
+}
+
+/// Pattern of a built-in format, compiled once and shared by every schema using it
+fn shared_pattern(cell: &'static OnceLock<Arc<Regex>>, pattern: &str) -> Pattern {
+    Pattern::Standard(cell.get_or_init(|| Arc::new(compile_regex(pattern))).clone())
+}
+
+fn compile_regex(pattern: &str) -> Regex {
+    #[cfg(test)]
+    REGEX_COMPILES.with(|n| n.set(n.get() + 1));
+    Regex::new(pattern).unwrap()
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Regexes compiled on this thread
+    pub(crate) static REGEX_COMPILES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+type CustomValidator = Arc<dyn Fn(&str) -> Result<(), CustomIssue> + Send + Sync>;
+
+#[derive(Clone, Default)]
 pub struct StringSchemaImpl {
     min_length: Option<usize>,
     max_length: Option<usize>,
-    pattern: Option<Regex>,
+    pattern: Option<Pattern>,
+    /// Code reported when `pattern` is a built-in format, e.g. `string.url`
+    format: Option<ErrorCode>,
     email: bool,
-    optional: bool,
+    presence: Presence,
     error_messages: HashMap<String, String>,
-    custom_validators: Vec<Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>>,
+    custom_validators: Vec<(Option<String>, CustomValidator)>,
+    numeric: Option<NumericPolicy>,
+    as_number: bool,
+    no_control_chars: bool,
+    single_line: bool,
+    password: Option<PasswordPolicy>,
+    path: Option<PathPolicy>,
+    glob: bool,
+    soft_max_length: Option<usize>,
+    count_chars: bool,
 }
 
-impl Default for StringSchemaImpl {
-    fn default() -> Self {
-        Self {
-            min_length: None,
-            max_length: None,
-            pattern: None,
-            email: false,
-            optional: false,
-            error_messages: HashMap::new(),
-            custom_validators: Vec::new(),
+/// Compiled regex set by `pattern()` or `pattern_fancy()`, shared by
+/// clones of the schema
+#[derive(Clone)]
+enum Pattern {
+    Standard(Arc<Regex>),
+    #[cfg(feature = "fancy-regex")]
+    Fancy(Arc<fancy_regex::Regex>),
+}
+
+impl Pattern {
+    fn is_match(&self, s: &str) -> bool {
+        match self {
+            Pattern::Standard(re) => re.is_match(s),
+            #[cfg(feature = "fancy-regex")]
+            Pattern::Fancy(re) => re.is_match(s).unwrap_or(false),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Pattern::Standard(re) => re.as_str(),
+            #[cfg(feature = "fancy-regex")]
+            Pattern::Fancy(re) => re.as_str(),
         }
     }
 }
 
+/// Rules for digit-only strings set up by `numeric()`
+#[derive(Clone, Copy, Default)]
+struct NumericPolicy {
+    reject_leading_zeros: bool,
+}
+
 impl StringSchema for StringSchemaImpl {
     fn min_length(mut self, length: usize) -> Self {
-        self.min_length = Some(length);
+        self.set_min_length(length);
         self
     }
 
     fn max_length(mut self, length: usize) -> Self {
-        self.max_length = Some(length);
+        self.set_max_length(length);
         self
     }
 
     fn pattern(mut self, pattern: &str) -> Self {
-        self.pattern = Some(Regex::new(pattern).unwrap());
+        self.set_pattern(pattern);
         self
     }
 
     fn email(mut self) -> Self {
-        self.email = true;
+        self.set_email(true);
         self
     }
 
     fn optional(mut self) -> Self {
-        self.optional = true;
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    fn presence(mut self, presence: Presence) -> Self {
+        self.set_presence(presence);
         self
     }
 
     fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
-        self.error_messages.insert(code.into(), message.into());
+        self.set_error_message(code, message);
+        self
+    }
+
+    fn custom<F, E>(mut self, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
+    {
+        self.custom_validators.push((None, Arc::new(move |s| validator(s).map_err(Into::into))));
         self
     }
 
-    fn custom<F>(mut self, validator: F) -> Self
+    fn custom_labeled<F, E>(mut self, label: impl Into<String>, validator: F) -> Self
     where
-        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<CustomIssue>,
     {
-        self.custom_validators.push(Arc::new(validator));
+        self.custom_validators.push((Some(label.into()), Arc::new(move |s| validator(s).map_err(Into::into))));
         self
     }
 }
 
 impl StringSchemaImpl {
-    pub fn url(self) -> Self {
-        self.pattern(r"^https?://[\w\-]+(\.[\w\-]+)+[/#?]?.*$")
-            .error_message("string.url", "Invalid URL format")
+    /// In-place form of [`StringSchema::min_length`]
+    pub fn set_min_length(&mut self, length: usize) -> &mut Self {
+        self.min_length = Some(length);
+        self
+    }
+
+    /// In-place form of [`StringSchema::max_length`]
+    pub fn set_max_length(&mut self, length: usize) -> &mut Self {
+        self.max_length = Some(length);
+        self
+    }
+
+    /// Configured `(min_length, max_length)`
+    pub(crate) fn length_limits(&self) -> (Option<usize>, Option<usize>) {
+        (self.min_length, self.max_length)
+    }
+
+    /// In-place form of [`StringSchema::pattern`]
+    pub fn set_pattern(&mut self, pattern: &str) -> &mut Self {
+        self.pattern = Some(Pattern::Standard(Arc::new(compile_regex(pattern))));
+        self.format = None;
+        self
+    }
+
+    /// Like [`StringSchema::pattern`], but allowing lookaround and
+    /// backreferences, e.g. `^(?=.*[A-Z]).*$`, as used by many regexes copied
+    /// from other ecosystems
+    ///
+    /// Runs on `fancy-regex`, which backtracks for those constructs: matching
+    /// can take time exponential in the input length, so keep a `max_length`
+    /// on untrusted input. Input that hits the backtracking limit counts as a
+    /// mismatch. Patterns without such constructs still match in linear time.
+    #[cfg(feature = "fancy-regex")]
+    pub fn pattern_fancy(mut self, pattern: &str) -> Self {
+        self.set_pattern_fancy(pattern);
+        self
+    }
+
+    /// In-place form of [`StringSchemaImpl::pattern_fancy`]
+    #[cfg(feature = "fancy-regex")]
+    pub fn set_pattern_fancy(&mut self, pattern: &str) -> &mut Self {
+        self.pattern = Some(Pattern::Fancy(Arc::new(fancy_regex::Regex::new(pattern).unwrap())));
+        self.format = None;
+        self
+    }
+
+    /// In-place form of [`StringSchema::pattern_full_match`]
+    pub fn set_pattern_full_match(&mut self, pattern: &str) -> &mut Self {
+        self.set_pattern(&full_match(pattern))
+    }
+
+    /// In-place form of [`StringSchema::email`], which can also lift the requirement
+    pub fn set_email(&mut self, email: bool) -> &mut Self {
+        self.email = email;
+        self
+    }
+
+    /// In-place form of [`StringSchema::presence`]
+    pub fn set_presence(&mut self, presence: Presence) -> &mut Self {
+        self.presence = presence;
+        self
+    }
+
+    /// In-place form of [`StringSchema::error_message`]
+    pub fn set_error_message(&mut self, code: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    pub fn url(mut self) -> Self {
+        static URL: OnceLock<Arc<Regex>> = OnceLock::new();
+        self.pattern = Some(shared_pattern(&URL, r"^https?://[\w\-]+(\.[\w\-]+)+[/#?]?.*$"));
+        self.format = Some(ErrorCode::InvalidUrl);
+        self
     }
 
-    pub fn uuid(self) -> Self {
-        self.pattern(r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$")
-            .error_message("string.uuid", "Invalid UUID format")
+    /// Absolute URI of any scheme, e.g. `urn:isbn:0451450523` or
+    /// `mailto:a@b.co`, for JSON Schema's `uri` format
+    pub(crate) fn uri(mut self) -> Self {
+        static URI: OnceLock<Arc<Regex>> = OnceLock::new();
+        self.pattern = Some(shared_pattern(&URI, r"^[A-Za-z][A-Za-z0-9+.\-]*:[^\s]*$"));
+        self.format = Some(ErrorCode::InvalidUrl);
+        self
+    }
+
+    pub fn uuid(mut self) -> Self {
+        static UUID: OnceLock<Arc<Regex>> = OnceLock::new();
+        self.pattern = Some(shared_pattern(&UUID, r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$"));
+        self.format = Some(ErrorCode::InvalidUuid);
+        self
+    }
+
+    pub fn ip(mut self) -> Self {
+        static IP: OnceLock<Arc<Regex>> = OnceLock::new();
+        self.pattern = Some(shared_pattern(
+            &IP,
+            r"^(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\.){3}(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)$",
+        ));
+        self.format = Some(ErrorCode::InvalidIp);
+        self
+    }
+
+    /// Accept only ASCII digit strings such as numeric IDs; combine with
+    /// `min_length`/`max_length` for length bounds
+    pub fn numeric(mut self) -> Self {
+        self.numeric.get_or_insert_with(NumericPolicy::default);
+        self
+    }
+
+    /// Reject numeric strings with a leading zero, such as `"007"`
+    /// (`"0"` itself is allowed). Implies `numeric()`.
+    pub fn no_leading_zeros(mut self) -> Self {
+        self.numeric.get_or_insert_with(NumericPolicy::default).reject_leading_zeros = true;
+        self
+    }
+
+    /// Output the validated numeric string as a JSON number. Implies `numeric()`.
+    pub fn as_number(mut self) -> Self {
+        self.numeric.get_or_insert_with(NumericPolicy::default);
+        self.as_number = true;
+        self
+    }
+
+    fn check_numeric(&self, policy: NumericPolicy, s: &str) -> Result<(), ValidationError> {
+        let code = if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            ErrorCode::NotNumeric
+        } else if policy.reject_leading_zeros && s.len() > 1 && s.starts_with('0') {
+            ErrorCode::LeadingZero
+        } else {
+            return Ok(());
+        };
+        let custom = self.error_messages.get(code.code());
+        Err(ValidationError::new(code).message_or_default(custom))
+    }
+
+    fn numeric_output(&self, s: &str) -> Result<Value, ValidationError> {
+        match s.parse::<u64>() {
+            Ok(n) => Ok(Value::from(n)),
+            Err(_) => Err(ValidationError::new(ErrorCode::NumericOutOfRange)
+                .message_or_default(self.error_messages.get("string.numeric_range"))),
+        }
+    }
+
+    /// Reject control characters; tab, line feed and carriage return are
+    /// still allowed unless `single_line()` is also set
+    pub fn no_control_chars(mut self) -> Self {
+        self.no_control_chars = true;
+        self
+    }
+
+    /// Reject line breaks (`\n` and `\r`)
+    pub fn single_line(mut self) -> Self {
+        self.single_line = true;
+        self
+    }
+
+    pub fn collapse_whitespace(self) -> WithTransform<Self> {
+        WithTransform::new(self).with_transform(Transform::CollapseWhitespace)
+    }
+
+    /// Enforce a password strength policy. `validate` reports the first
+    /// unmet rule; `validate_partial` reports every one of them.
+    pub fn password(mut self, policy: PasswordPolicy) -> Self {
+        self.password = Some(policy);
+        self
+    }
+
+    /// Accept file paths: non-empty and without NUL bytes
+    pub fn file_path(mut self) -> Self {
+        self.path.get_or_insert_with(PathPolicy::default);
+        self
+    }
+
+    /// Require an absolute Unix or Windows path. Implies `file_path()`.
+    pub fn must_be_absolute(mut self) -> Self {
+        self.path.get_or_insert_with(PathPolicy::default).absolute = true;
+        self
+    }
+
+    /// Reject paths with `..` segments. Implies `file_path()`.
+    pub fn no_traversal(mut self) -> Self {
+        self.path.get_or_insert_with(PathPolicy::default).no_traversal = true;
+        self
+    }
+
+    /// Accept well-formed glob patterns such as `src/**/*.{rs,toml}`
+    pub fn glob(mut self) -> Self {
+        self.glob = true;
+        self
+    }
+
+    /// Warn, without failing, about strings longer than `length`; see
+    /// [`Schema::validate_with_report`]
+    pub fn soft_max_length(mut self, length: usize) -> Self {
+        self.soft_max_length = Some(length);
+        self
+    }
+
+    /// Measure length bounds in characters rather than UTF-8 bytes, so
+    /// that `max_length(1)` accepts `"é"`, as JSON Schema's `minLength`
+    /// and `maxLength` do
+    pub(crate) fn count_chars(mut self) -> Self {
+        self.count_chars = true;
+        self
+    }
+
+    /// Compile the regexes this schema would otherwise compile on first use
+    pub(crate) fn precompile(&self) {
+        if self.email {
+            email_regex();
+        }
+    }
+
+    /// Length of `s` in the unit the bounds are measured in
+    fn length(&self, s: &str) -> usize {
+        match self.count_chars {
+            true => s.chars().count(),
+            false => s.len(),
+        }
     }
 
-    pub fn ip(self) -> Self {
-        self.pattern(r"^(?:(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)\.){3}(?:25[0-5]|2[0-4][0-9]|[01]?[0-9][0-9]?)$")
-            .error_message("string.ip", "Invalid IP address format")
+    fn check_path(&self, s: &str) -> Result<(), ValidationError> {
+        let violation = match self.path {
+            Some(policy) => policy.violation(s),
+            None => None,
+        };
+        let violation = violation.or_else(|| {
+            (self.glob && !is_valid_glob(s)).then_some(ErrorCode::InvalidGlob)
+        });
+        let Some(code) = violation else { return Ok(()) };
+        let custom = self.error_messages.get(code.code());
+        Err(ValidationError::new(code).message_or_default(custom))
+    }
+
+    fn password_error(&self, violation: PasswordViolation) -> ValidationError {
+        let custom = self.error_messages.get(violation.code.code());
+        ValidationError::new(violation.code)
+            .with_details(|d| {
+                d.min_length = violation.min_length;
+            })
+            .message_or_default(custom)
+    }
+
+    /// Error for a failed custom validator, see [`CustomIssue::into_error`]
+    fn custom_error(&self, label: Option<&str>, issue: CustomIssue) -> ValidationError {
+        let code = match label {
+            Some(label) => format!("string.custom.{}", label),
+            None => ErrorCode::StringCustom.to_string(),
+        };
+        issue.into_error(code, &self.error_messages)
+    }
+
+    fn check_characters(&self, s: &str) -> Result<(), ValidationError> {
+        let code = if self.single_line && s.contains(['\n', '\r']) {
+            ErrorCode::NotSingleLine
+        } else if self.no_control_chars && s.chars().any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r')) {
+            ErrorCode::ControlChars
+        } else {
+            return Ok(());
+        };
+        let custom = self.error_messages.get(code.code());
+        Err(ValidationError::new(code).message_or_default(custom))
     }
 
     pub fn trim(self) -> WithTransform<Self> {
@@ -109,6 +495,113 @@ impl StringSchemaImpl {
     pub fn to_uppercase(self) -> WithTransform<Self> {
         WithTransform::new(self).with_transform(Transform::ToUpperCase)
     }
+
+    /// Whether every value `other` accepts meets this schema's checks, see
+    /// [`Schema::accepts`]
+    pub(crate) fn covers(&self, other: &Self) -> bool {
+        let same_pattern = match (&self.pattern, &other.pattern) {
+            (None, _) => true,
+            (Some(a), Some(b)) => a.as_str() == b.as_str(),
+            (Some(_), None) => false,
+        };
+        compat::covers_null(self.presence, other.presence)
+            && compat::covers_min(self.min_length, other.min_length)
+            && compat::covers_max(self.max_length, other.max_length)
+            && (self.count_chars == other.count_chars || (self.min_length, self.max_length) == (None, None))
+            && same_pattern
+            && (!self.email || other.email)
+            && (!self.no_control_chars || other.no_control_chars)
+            && (!self.single_line || other.single_line)
+            // Policies are not compared, so only their absence is known to accept
+            && self.numeric.is_none()
+            && self.password.is_none()
+            && self.path.is_none()
+            && !self.glob
+            && self.custom_validators.is_empty()
+    }
+
+    /// Every check of a string value, shared by [`Schema::validate`] and
+    /// [`Schema::validate_ref`]
+    fn check_str(&self, s: &str) -> Result<(), ValidationError> {
+        let length = self.length(s);
+        if let Some(soft_max) = self.soft_max_length.filter(|&max| length > max) {
+            let warning = ValidationError::new(ErrorCode::StringSoftMaxLength)
+                .with_details(|d| {
+                    d.max_length = Some(soft_max);
+                    d.actual_length = Some(length);
+                });
+            warnings::warn(warning);
+        }
+
+        if let Some(min_len) = self.min_length {
+            if length < min_len {
+                let mut err = ValidationError::new(ErrorCode::StringTooShort)
+                    .with_details(|d| {
+                        d.min_length = Some(min_len);
+                        d.actual_length = Some(length);
+                    });
+                if let Some(msg) = self.error_messages.get("string.too_short") {
+                    err = err.custom_message(msg.clone());
+                }
+                return Err(err);
+            }
+        }
+
+        if let Some(max_len) = self.max_length {
+            if length > max_len {
+                let mut err = ValidationError::new(ErrorCode::StringTooLong)
+                    .with_details(|d| {
+                        d.max_length = Some(max_len);
+                        d.actual_length = Some(length);
+                    });
+                if let Some(msg) = self.error_messages.get("string.too_long") {
+                    err = err.custom_message(msg.clone());
+                }
+                return Err(err);
+            }
+        }
+
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(s) {
+                let code = self.format.clone().unwrap_or(ErrorCode::PatternMismatch);
+                let mut err = ValidationError::new(code.clone());
+                if code == ErrorCode::PatternMismatch {
+                    err = err.with_details(|d| d.pattern = Some(pattern.as_str().to_string()));
+                }
+                let custom = self.error_messages.get(code.code());
+                return Err(err.message_or_default(custom));
+            }
+        }
+
+        if self.email && !email_regex().is_match(s) {
+            let mut err = ValidationError::new(ErrorCode::InvalidEmail);
+            if let Some(msg) = self.error_messages.get("string.email") {
+                err = err.custom_message(msg.clone());
+            }
+            return Err(err);
+        }
+
+        self.check_characters(s)?;
+
+        if let Some(policy) = self.numeric {
+            self.check_numeric(policy, s)?;
+        }
+
+        self.check_path(s)?;
+
+        if let Some(policy) = &self.password {
+            if let Some(violation) = policy.violations(s).into_iter().next() {
+                return Err(self.password_error(violation));
+            }
+        }
+
+        for (label, validator) in &self.custom_validators {
+            if let Err(issue) = validator(s) {
+                return Err(self.custom_error(label.as_deref(), issue));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl HasErrorMessages for StringSchemaImpl {
@@ -132,102 +625,164 @@ impl Transformable for WithTransform<StringSchemaImpl> {
 
 impl Schema for StringSchemaImpl {
     fn is_optional(&self) -> bool {
-        self.optional
+        self.presence.allows_missing()
     }
 
-    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
-        match value {
-            Value::Null if self.optional => Ok(value.clone()),
-            Value::String(s) => {
-                if let Some(min_len) = self.min_length {
-                    if s.len() < min_len {
-                        let mut err = ValidationError::new(ErrorCode::StringTooShort)
-                            .with_details(|d| {
-                                d.min_length = Some(min_len);
-                            });
-                        if let Some(msg) = self.error_messages.get("string.too_short") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message(format!("Minimum length is {}", min_len));
-                        }
-                        return Err(err);
-                    }
-                }
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
 
-                if let Some(max_len) = self.max_length {
-                    if s.len() > max_len {
-                        let mut err = ValidationError::new(ErrorCode::StringTooLong)
-                            .with_details(|d| {
-                                d.max_length = Some(max_len);
-                            });
-                        if let Some(msg) = self.error_messages.get("string.too_long") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message(format!("Maximum length is {}", max_len));
-                        }
-                        return Err(err);
-                    }
-                }
+    fn missing_message(&self) -> Option<String> {
+        self.error_messages.get("string.required").cloned()
+    }
 
-                if let Some(pattern) = &self.pattern {
-                    if !pattern.is_match(s) {
-                        let mut err = ValidationError::new(ErrorCode::PatternMismatch)
-                            .with_details(|d| {
-                                d.pattern = Some(pattern.as_str().to_string());
-                            });
-                        if let Some(msg) = self.error_messages.get("string.pattern") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message("Must be uppercase letters only".to_string());
-                        }
-                        return Err(err);
-                    }
-                }
+    fn check_satisfiable(&self) -> Vec<Unsatisfiable> {
+        satisfiable::check_bounds("length", self.min_length, self.max_length).into_iter().collect()
+    }
 
-                if self.email {
-                    let email_regex = Regex::new(r"^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}$").unwrap();
-                    if !email_regex.is_match(s) {
-                        let mut err = ValidationError::new(ErrorCode::InvalidEmail);
-                        if let Some(msg) = self.error_messages.get("string.email") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message("Invalid email address".to_string());
-                        }
-                        return Err(err);
-                    }
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("string", self.presence);
+        for rule in explain::bounds(self.min_length, self.max_length, "character") {
+            explanation.rule(rule);
+        }
+        if self.email {
+            explanation.rule("must be a valid email");
+        }
+        if let Some(pattern) = &self.pattern {
+            match &self.format {
+                Some(ErrorCode::InvalidUrl) => explanation.rule("must be a valid URL"),
+                Some(ErrorCode::InvalidUuid) => explanation.rule("must be a valid UUID"),
+                Some(ErrorCode::InvalidIp) => explanation.rule("must be a valid IP address"),
+                _ => explanation.rule(format!("must match `{}`", pattern.as_str())),
+            }
+        }
+        if let Some(policy) = self.numeric {
+            explanation.rule("digits only");
+            if policy.reject_leading_zeros {
+                explanation.rule("no leading zeros");
+            }
+            if self.as_number {
+                explanation.rule("output as a number");
+            }
+        }
+        if self.no_control_chars {
+            explanation.rule("no control characters");
+        }
+        if self.single_line {
+            explanation.rule("single line");
+        }
+        if let Some(policy) = &self.password {
+            explanation.rule(format!("password of at least {} characters", policy.min_len));
+            for (required, what) in [
+                (policy.require_upper, "an uppercase letter"),
+                (policy.require_lower, "a lowercase letter"),
+                (policy.require_digit, "a digit"),
+                (policy.require_symbol, "a symbol"),
+            ] {
+                if required {
+                    explanation.rule(format!("with {}", what));
                 }
+            }
+            if let Some(max) = policy.max_repeats {
+                explanation.rule(format!("no character repeated more than {} times", max));
+            }
+        }
+        if let Some(policy) = self.path {
+            explanation.rule("file path");
+            if policy.absolute {
+                explanation.rule("absolute");
+            }
+            if policy.no_traversal {
+                explanation.rule("no `..` segments");
+            }
+        }
+        if self.glob {
+            explanation.rule("glob pattern");
+        }
+        if let Some(max) = self.soft_max_length {
+            explanation.rule(format!("warns above {} characters", max));
+        }
+        for (label, _) in &self.custom_validators {
+            match label {
+                Some(label) => explanation.rule(format!("custom check `{}`", label)),
+                None => explanation.rule("custom check"),
+            }
+        }
+        vec![explanation]
+    }
 
-                for validator in &self.custom_validators {
-                    if let Err(msg) = validator(s) {
-                        let mut err = ValidationError::new(ErrorCode::Custom(msg.clone()));
-                        if let Some(msg) = self.error_messages.get("string.custom") {
-                            err = err.message(msg.clone());
-                        } else {
-                            err = err.message(msg.clone());
-                        }
-                        return Err(err);
-                    }
-                }
+    fn lint(&self) -> Vec<LintFinding> {
+        let mut findings = match &self.pattern {
+            Some(Pattern::Standard(re)) => lint::lint_pattern(re.as_str()),
+            #[cfg(feature = "fancy-regex")]
+            Some(Pattern::Fancy(re)) => vec![LintFinding::new(
+                LintKind::Backtracking,
+                format!("Pattern `{}` runs on a backtracking engine; keep its input short", re.as_str()),
+            )],
+            None => Vec::new(),
+        };
+        let bounded = match &self.pattern {
+            Some(Pattern::Standard(re)) => lint::bounds_length(re.as_str()),
+            _ => false,
+        };
+        if self.max_length.is_none() && !bounded {
+            findings.push(LintFinding::new(LintKind::UnboundedLength, "String has no max_length"));
+        }
+        findings
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        let mut output = PartialOutput::from_result(self.validate(value), value, invalid);
+        if let (Some(policy), Value::String(s), Some(first)) = (&self.password, value, output.errors.first()) {
+            if first.context.code.starts_with("string.password.") {
+                output.errors = policy.violations(s).into_iter().map(|v| self.password_error(v)).collect();
+            }
+        }
+        output
+    }
 
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        match value {
+            Value::Null if self.presence.allows_null() => Ok(value.clone()),
+            Value::String(s) => {
+                self.check_str(s)?;
+                if self.as_number {
+                    return self.numeric_output(s);
+                }
                 Ok(value.clone())
             }
-            Value::Null => Err(ValidationError::new(ErrorCode::RequiredField)),
+            Value::Null => {
+                let mut err = ValidationError::new(ErrorCode::StringRequired);
+                if let Some(msg) = self.error_messages.get("string.required") {
+                    err = err.custom_message(msg.clone());
+                }
+                Err(err)
+            }
             _ => {
-                let mut err = ValidationError::new(ErrorCode::InvalidType)
+                let mut err = ValidationError::new(ErrorCode::StringInvalidType)
                     .with_details(|d| {
                         d.expected_type = Some("string".to_string());
                         d.actual_type = Some(get_type_name(value).to_string());
                     });
                 if let Some(msg) = self.error_messages.get("string.invalid_type") {
-                    err = err.message(msg.clone());
-                } else {
-                    err = err.message("Must be a string".to_string());
+                    err = err.custom_message(msg.clone());
                 }
                 Err(err)
             }
         }
     }
 
+    fn validate_ref(&self, value: &Value) -> Result<(), ValidationError> {
+        match value {
+            Value::String(s) => self.check_str(s),
+            _ => self.validate(value).map(drop),
+        }
+    }
+
+    fn preserves_input(&self) -> bool {
+        !self.as_number
+    }
+
     fn into_schema_type(self) -> SchemaType {
         SchemaType::String(self)
     }
@@ -259,6 +814,29 @@ mod tests {
         assert!(err.to_string().contains("Maximum length is 5"));
     }
 
+    #[test]
+    fn test_count_chars() {
+        assert!(StringSchemaImpl::default().max_length(1).validate(&json!("é")).is_err());
+        let schema = StringSchemaImpl::default().min_length(2).max_length(2).count_chars();
+        assert!(schema.validate(&json!("né")).is_ok());
+        let err = schema.validate(&json!("日本語")).unwrap_err();
+        assert_eq!(err.context.details.actual_length, Some(3));
+        assert!(!schema.covers(&StringSchemaImpl::default().max_length(2)));
+    }
+
+    #[test]
+    fn test_string_len_range() {
+        let schema = StringSchemaImpl::default().len_range(3..=5);
+        assert!(schema.validate(&json!("abc")).is_ok());
+        assert!(schema.validate(&json!("abcde")).is_ok());
+        assert!(schema.validate(&json!("ab")).is_err());
+        assert!(schema.validate(&json!("abcdef")).is_err());
+
+        let schema = StringSchemaImpl::default().trim().len_range(..3);
+        assert!(schema.validate(&json!("  ab  ")).is_ok());
+        assert_eq!(schema.validate(&json!("abc")).unwrap_err().context.code, "string.too_long");
+    }
+
     #[test]
     fn test_string_pattern_validation() {
         let schema = StringSchemaImpl::default()
@@ -270,6 +848,42 @@ mod tests {
         let err = schema.validate(&json!("abc")).unwrap_err();
         assert_eq!(err.context.code, "string.pattern");
         assert!(err.to_string().contains("Must be uppercase letters only"));
+
+        let err = StringSchemaImpl::default().pattern(r"^\d+$").validate(&json!("abc")).unwrap_err();
+        assert_eq!(err.to_string(), r"String must match pattern: ^\d+$");
+    }
+
+    #[test]
+    fn test_pattern_full_match() {
+        let substring = StringSchemaImpl::default().pattern(r"\d{5}");
+        assert!(substring.validate(&json!("abc12345def")).is_ok());
+
+        let zip = StringSchemaImpl::default().pattern_full_match(r"\d{5}");
+        assert!(zip.validate(&json!("12345")).is_ok());
+        assert_eq!(zip.validate(&json!("abc12345def")).unwrap_err().context.code, "string.pattern");
+        assert!(zip.validate(&json!("123456")).is_err());
+
+        let either = StringSchemaImpl::default().pattern_full_match("cat|dog");
+        assert!(either.validate(&json!("dog")).is_ok());
+        assert!(either.validate(&json!("catfish")).is_err());
+        assert!(either.validate(&json!("hotdog")).is_err());
+    }
+
+    #[cfg(feature = "fancy-regex")]
+    #[test]
+    fn test_pattern_fancy_lookahead() {
+        let schema = StringSchemaImpl::default()
+            .pattern_fancy(r"^(?=.*[A-Z])(?=.*\d).{8,}$")
+            .error_message("string.pattern", "Needs an uppercase letter and a digit");
+        assert!(schema.validate(&json!("Passw0rd!")).is_ok());
+
+        let err = schema.validate(&json!("password1")).unwrap_err();
+        assert_eq!(err.context.code, "string.pattern");
+        assert_eq!(err.context.details.pattern.as_deref(), Some(r"^(?=.*[A-Z])(?=.*\d).{8,}$"));
+
+        let repeated = StringSchemaImpl::default().pattern_fancy(r"^(\w+) \1$");
+        assert!(repeated.validate(&json!("hey hey")).is_ok());
+        assert!(repeated.validate(&json!("hey you")).is_err());
     }
 
     #[test]
@@ -296,6 +910,15 @@ mod tests {
         assert!(schema.validate(&json!("ab")).is_err());
     }
 
+    #[test]
+    fn test_string_required_message() {
+        let schema = StringSchemaImpl::default().trim().required_message("Please provide your email");
+
+        let err = schema.validate(&json!(null)).unwrap_err();
+        assert_eq!(err.to_string(), "Please provide your email");
+        assert_eq!(schema.missing_message().as_deref(), Some("Please provide your email"));
+    }
+
     #[test]
     fn test_string_custom_validation() {
         let schema = StringSchemaImpl::default()
@@ -310,17 +933,43 @@ mod tests {
         assert!(schema.validate(&json!("123")).is_ok());
         
         let err = schema.validate(&json!("abc123")).unwrap_err();
-        assert_eq!(err.context.code, "custom");
+        assert_eq!(err.code(), ErrorCode::StringCustom);
         assert!(err.to_string().contains("Must contain only digits"));
     }
 
+    #[test]
+    fn test_string_custom_labeled() {
+        let schema = StringSchemaImpl::default()
+            .custom_labeled("no_profanity", |s| if s.contains("darn") { Err("No profanity".to_string()) } else { Ok(()) })
+            .custom_labeled("no_spaces", |s| if s.contains(' ') { Err("No spaces".to_string()) } else { Ok(()) })
+            .error_message("string.custom.no_spaces", "Spaces are not allowed");
+
+        assert!(schema.validate(&json!("hello")).is_ok());
+
+        let err = schema.validate(&json!("darn")).unwrap_err();
+        assert_eq!(err.context.code, "string.custom.no_profanity");
+        assert_eq!(err.to_string(), "No profanity");
+
+        let err = schema.validate(&json!("a b")).unwrap_err();
+        assert_eq!(err.context.code, "string.custom.no_spaces");
+        assert_eq!(err.to_string(), "Spaces are not allowed");
+    }
+
     #[test]
     fn test_string_url_validation() {
         let schema = StringSchemaImpl::default().url();
 
         assert!(schema.validate(&json!("https://example.com")).is_ok());
         assert!(schema.validate(&json!("http://sub.domain.com/path?q=1")).is_ok());
-        assert!(schema.validate(&json!("not-a-url")).is_err());
+        let err = schema.validate(&json!("not-a-url")).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidUrl);
+        assert_eq!(err.to_string(), "Invalid URL format");
+
+        let schema = schema.error_message("string.url", "Enter a website address");
+        assert_eq!(schema.validate(&json!("not-a-url")).unwrap_err().to_string(), "Enter a website address");
+        // A later pattern replaces the format, and reports as a pattern
+        let schema = StringSchemaImpl::default().url().pattern(r"^a");
+        assert_eq!(schema.validate(&json!("b")).unwrap_err().code(), ErrorCode::PatternMismatch);
     }
 
     #[test]
@@ -328,7 +977,9 @@ mod tests {
         let schema = StringSchemaImpl::default().uuid();
 
         assert!(schema.validate(&json!("550e8400-e29b-41d4-a716-446655440000")).is_ok());
-        assert!(schema.validate(&json!("not-a-uuid")).is_err());
+        let err = schema.validate(&json!("not-a-uuid")).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidUuid);
+        assert_eq!(err.to_string(), "Invalid UUID format");
     }
 
     #[test]
@@ -337,7 +988,22 @@ mod tests {
 
         assert!(schema.validate(&json!("192.168.1.1")).is_ok());
         assert!(schema.validate(&json!("256.1.2.3")).is_err());
-        assert!(schema.validate(&json!("not-an-ip")).is_err());
+        let err = schema.validate(&json!("not-an-ip")).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidIp);
+        assert_eq!(err.to_string(), "Invalid IP address format");
+    }
+
+    #[test]
+    fn test_patterns_are_shared() {
+        let same = |a: &StringSchemaImpl, b: &StringSchemaImpl| match (&a.pattern, &b.pattern) {
+            (Some(Pattern::Standard(a)), Some(Pattern::Standard(b))) => Arc::ptr_eq(a, b),
+            _ => false,
+        };
+        let schema = StringSchemaImpl::default().pattern(r"^[a-z]+$");
+        assert!(same(&schema, &schema.clone()));
+        assert!(!same(&schema, &StringSchemaImpl::default().pattern(r"^[a-z]+$")));
+        assert!(same(&StringSchemaImpl::default().url(), &StringSchemaImpl::default().url()));
+        assert!(same(&StringSchemaImpl::default().uuid(), &StringSchemaImpl::default().min_length(36).uuid()));
     }
 
     #[test]
@@ -372,4 +1038,144 @@ mod tests {
         let err = schema.validate(&json!("  hi  ")).unwrap_err();
         assert_eq!(err.context.code, "string.too_short");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_string_numeric() {
+        let schema = StringSchemaImpl::default().numeric().len_range(3..=6);
+
+        assert!(schema.validate(&json!("00123")).is_ok());
+        assert_eq!(schema.validate(&json!("12a4")).unwrap_err().context.code, "string.numeric");
+        assert_eq!(schema.validate(&json!("-123")).unwrap_err().context.code, "string.numeric");
+        assert_eq!(schema.validate(&json!("12")).unwrap_err().context.code, "string.too_short");
+
+        let schema = StringSchemaImpl::default().no_leading_zeros();
+        assert!(schema.validate(&json!("0")).is_ok());
+        assert_eq!(schema.validate(&json!("007")).unwrap_err().context.code, "string.leading_zero");
+        assert!(schema.validate(&json!("")).is_err());
+    }
+
+    #[test]
+    fn test_string_as_number() {
+        let schema = StringSchemaImpl::default().as_number();
+
+        assert_eq!(schema.validate(&json!("0042")).unwrap(), json!(42));
+        assert_eq!(schema.validate(&json!("4.2")).unwrap_err().context.code, "string.numeric");
+        assert_eq!(
+            schema.validate(&json!("99999999999999999999999")).unwrap_err().context.code,
+            "string.numeric_range"
+        );
+    }
+
+    #[test]
+    fn test_string_character_policies() {
+        let schema = StringSchemaImpl::default().no_control_chars().single_line();
+
+        assert!(schema.validate(&json!("Plain title\twith tab")).is_ok());
+        assert_eq!(schema.validate(&json!("two\nlines")).unwrap_err().context.code, "string.single_line");
+        assert_eq!(schema.validate(&json!("bell\u{7}")).unwrap_err().context.code, "string.control_chars");
+
+        let multi = StringSchemaImpl::default().no_control_chars();
+        assert!(multi.validate(&json!("two\r\nlines")).is_ok());
+    }
+
+    #[test]
+    fn test_string_collapse_whitespace() {
+        let schema = StringSchemaImpl::default().collapse_whitespace().max_length(11);
+
+        assert_eq!(schema.validate(&json!("  Hello \t\n  world ")).unwrap(), json!("Hello world"));
+    }
+
+    #[test]
+    fn test_string_password() {
+        let schema = StringSchemaImpl::default().password(PasswordPolicy {
+            min_len: 8,
+            require_upper: true,
+            require_lower: true,
+            require_digit: true,
+            require_symbol: true,
+            max_repeats: Some(2),
+        });
+
+        assert!(schema.validate(&json!("Tr0ub4dor&3")).is_ok());
+
+        let err = schema.validate(&json!("abc")).unwrap_err();
+        assert_eq!(err.context.code, "string.password.min_len");
+        assert_eq!(err.context.details.min_length, Some(8));
+
+        let codes: Vec<_> = schema.validate_partial(&json!("aaab")).errors
+            .into_iter()
+            .map(|e| e.context.code)
+            .collect();
+        assert_eq!(codes, vec![
+            "string.password.min_len",
+            "string.password.upper",
+            "string.password.digit",
+            "string.password.symbol",
+            "string.password.repeats",
+        ]);
+    }
+
+    #[test]
+    fn test_string_file_path() {
+        let schema = StringSchemaImpl::default().must_be_absolute().no_traversal();
+
+        assert!(schema.validate(&json!("/etc/app/config.toml")).is_ok());
+        assert!(schema.validate(&json!("C:\\Users\\app")).is_ok());
+        assert_eq!(schema.validate(&json!("config.toml")).unwrap_err().context.code, "string.path_absolute");
+        assert_eq!(schema.validate(&json!("/srv/../etc/passwd")).unwrap_err().context.code, "string.path_traversal");
+        assert_eq!(schema.validate(&json!("")).unwrap_err().context.code, "string.file_path");
+
+        let relative = StringSchemaImpl::default().file_path();
+        assert!(relative.validate(&json!("../shared/file")).is_ok());
+        assert!(relative.validate(&json!("a\u{0}b")).is_err());
+    }
+
+    #[test]
+    fn test_string_glob() {
+        let schema = StringSchemaImpl::default().glob();
+
+        for ok in ["*.rs", "src/**/*.{rs,toml}", "file[0-9].txt", "[]]", "[!a]x", "a\\*"] {
+            assert!(schema.validate(&json!(ok)).is_ok(), "{}", ok);
+        }
+        for bad in ["file[0-9.txt", "{a,b", "a}", "x]", "trailing\\", ""] {
+            assert_eq!(schema.validate(&json!(bad)).unwrap_err().context.code, "string.glob", "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_string_in_place_builders() {
+        let strict_names = true;
+        let mut schema = StringSchemaImpl::default();
+        if strict_names {
+            schema.set_min_length(3).set_error_message("string.too_short", "Too short");
+        }
+
+        assert_eq!(schema.validate(&json!("ab")).unwrap_err().to_string(), "Too short");
+        schema.set_presence(Presence::Nullable);
+        assert!(schema.validate(&json!(null)).is_ok());
+    }
+
+    #[test]
+    fn test_custom_issue_code_and_details() {
+        let schema = StringSchemaImpl::default().custom(|s| {
+            if s == "admin" {
+                Err(CustomIssue::new("username.reserved")
+                    .message("'{field}' is reserved")
+                    .with_details(|d| d.field_name = Some(s.to_string())))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(schema.validate(&json!("ada")).is_ok());
+        let err = schema.validate(&json!("admin")).unwrap_err();
+        assert_eq!(err.context.code, "username.reserved");
+        assert_eq!(err.to_string(), "'admin' is reserved");
+
+        let err = schema
+            .error_map(|code, _| (code.code() == "username.reserved").then(|| "Pick another name".to_string()))
+            .validate(&json!("admin"))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Pick another name");
+    }
+}
@@ -0,0 +1,53 @@
+use serde_json::Value;
+
+use super::SchemaType;
+
+/// Value of a string-only source such as a header or an environment
+/// variable, for a field of `schema`: arrays take every comma-separated
+/// item of every occurrence, other fields the occurrences joined by `", "`
+pub(crate) fn from_strings(schema: &SchemaType, values: &[String]) -> Value {
+    match value_schema(schema) {
+        SchemaType::Array(a) => Value::Array(
+            values
+                .iter()
+                .flat_map(|v| v.split(','))
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| coerce(a.item(), item))
+                .collect(),
+        ),
+        schema => coerce(schema, &values.join(", ")),
+    }
+}
+
+/// `raw` as the JSON type `schema` expects, if it parses as one
+pub(crate) fn coerce(schema: &SchemaType, raw: &str) -> Value {
+    let raw = raw.trim();
+    let coerced = match value_schema(schema) {
+        SchemaType::Number(_) => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| raw.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number)),
+        SchemaType::Boolean(_) if raw.eq_ignore_ascii_case("true") => Some(Value::Bool(true)),
+        SchemaType::Boolean(_) if raw.eq_ignore_ascii_case("false") => Some(Value::Bool(false)),
+        _ => None,
+    };
+    coerced.unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// The schema under wrappers that leave the expected input type as is
+pub(crate) fn value_schema(schema: &SchemaType) -> &SchemaType {
+    match schema {
+        SchemaType::Transform(t) => value_schema(&t.schema),
+        SchemaType::Sensitive(s) => value_schema(&s.schema),
+        SchemaType::ErrorMapped(e) => value_schema(&e.schema),
+        SchemaType::Cached(c) => value_schema(&c.schema),
+        SchemaType::Deprecated(d) => value_schema(&d.schema),
+        SchemaType::Catch(c) => value_schema(&c.schema),
+        SchemaType::Refined(r) => value_schema(&r.schema),
+        SchemaType::Pipe(p) => value_schema(&p.schema),
+        SchemaType::MapOutput(m) => value_schema(&m.schema),
+        other => other,
+    }
+}
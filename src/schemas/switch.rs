@@ -0,0 +1,209 @@
+use std::{collections::HashMap, sync::Arc};
+use serde_json::Value;
+
+use crate::error::{ValidationError, MessageIssue, ErrorCode, canonical_code};
+use crate::error::message_check::{check_templates, debug_check_template};
+use super::{Schema, Presence, SchemaType, HasErrorMessages, Explanation, explain, InvalidFields, PartialOutput, LintFinding, ValidationContext, canonical, validate_schema_type};
+
+type Selector = Arc<dyn Fn(&ValidationContext) -> Option<String> + Send + Sync>;
+
+/// Schema picked per call from the [`ValidationContext`], so tenant-specific
+/// or feature-flagged rules are built once and reused for every request
+#[derive(Clone)]
+pub struct SwitchSchema {
+    selector: Selector,
+    cases: Vec<(String, SchemaType)>,
+    otherwise: Option<Box<SchemaType>>,
+    presence: Presence,
+    error_messages: HashMap<String, String>,
+}
+
+impl SwitchSchema {
+    /// `selector` names the case to validate with, or `None` for the fallback
+    pub fn new<F, K>(selector: F) -> Self
+    where
+        F: Fn(&ValidationContext) -> Option<K> + Send + Sync + 'static,
+        K: Into<String>,
+    {
+        Self {
+            selector: Arc::new(move |ctx| selector(ctx).map(Into::into)),
+            cases: Vec::new(),
+            otherwise: None,
+            presence: Presence::Required,
+            error_messages: HashMap::new(),
+        }
+    }
+
+    pub fn case(mut self, key: impl Into<String>, schema: impl Schema) -> Self {
+        self.cases.push((key.into(), schema.into_schema_type()));
+        self
+    }
+
+    /// Schema used when the selector names no known case
+    pub fn otherwise(mut self, schema: impl Schema) -> Self {
+        self.otherwise = Some(Box::new(schema.into_schema_type()));
+        self
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.presence = Presence::OptionalNullable;
+        self
+    }
+
+    /// Whether the value may be missing and/or null
+    pub fn presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let (code, message) = (canonical_code(code.into()), message.into());
+        debug_check_template(&code, &message);
+        self.error_messages.insert(code, message);
+        self
+    }
+
+    /// Rewrite every case schema in place
+    pub(crate) fn map_cases(&mut self, f: impl Fn(SchemaType) -> SchemaType) {
+        for (_, schema) in &mut self.cases {
+            let taken = std::mem::replace(schema, SchemaType::Never(Default::default()));
+            *schema = f(taken);
+        }
+        if let Some(otherwise) = &mut self.otherwise {
+            let taken = std::mem::replace(otherwise.as_mut(), SchemaType::Never(Default::default()));
+            **otherwise = f(taken);
+        }
+    }
+
+    /// Schema chosen by the current context
+    fn select(&self) -> Result<&SchemaType, ValidationError> {
+        let key = ValidationContext::with_current(|ctx| (self.selector)(ctx));
+        let case = key.as_deref().and_then(|key| {
+            self.cases.iter().find(|(k, _)| k == key).map(|(_, schema)| schema)
+        });
+        case.or(self.otherwise.as_deref()).ok_or_else(|| {
+            let default_message = match &key {
+                Some(key) => format!("No schema configured for '{}'", key),
+                None => "No schema configured for this context".to_string(),
+            };
+            ValidationError::new(ErrorCode::SwitchNoCase)
+                .message_or(self.error_messages.get("switch.no_case"), default_message)
+        })
+    }
+}
+
+impl HasErrorMessages for SwitchSchema {
+    fn error_messages(&self) -> &HashMap<String, String> {
+        &self.error_messages
+    }
+
+    fn check_messages(&self) -> Vec<MessageIssue> {
+        let mut issues = check_templates("", &self.error_messages);
+        for (_, schema) in &self.cases {
+            issues.extend(schema.check_messages());
+        }
+        if let Some(otherwise) = &self.otherwise {
+            issues.extend(otherwise.check_messages());
+        }
+        issues
+    }
+}
+
+impl Schema for SwitchSchema {
+    fn is_optional(&self) -> bool {
+        self.presence.allows_missing()
+    }
+
+    fn get_presence(&self) -> Presence {
+        self.presence
+    }
+
+    fn explain(&self) -> Vec<Explanation> {
+        let mut explanation = Explanation::typed("chosen by the validation context", self.presence);
+        for (key, schema) in &self.cases {
+            explanation.rule(format!("for `{}`: {}", key, explain::root_summary(&schema.explain())));
+        }
+        if let Some(otherwise) = &self.otherwise {
+            explanation.rule(format!("otherwise: {}", explain::root_summary(&otherwise.explain())));
+        }
+        vec![explanation]
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value, ValidationError> {
+        if value.is_null() && self.presence.allows_null() {
+            return Ok(value.clone());
+        }
+        validate_schema_type(self.select()?, value)
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: InvalidFields) -> PartialOutput {
+        if value.is_null() && self.presence.allows_null() {
+            return PartialOutput::from_result(Ok(value.clone()), value, invalid);
+        }
+        match self.select() {
+            Ok(schema) => schema.validate_partial_with(value, invalid),
+            Err(err) => PartialOutput::from_result(Err(err), value, invalid),
+        }
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.select().map_or_else(|_| value.clone(), |schema| schema.redact(value))
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        match self.select() {
+            Ok(schema) => schema.canonical(output),
+            Err(_) => canonical::normalize(output),
+        }
+    }
+
+    /// Findings of every case, since any of them may be selected
+    fn lint(&self) -> Vec<LintFinding> {
+        self.cases.iter().map(|(_, schema)| schema).chain(self.otherwise.as_deref()).flat_map(Schema::lint).collect()
+    }
+
+    fn into_schema_type(self) -> SchemaType {
+        SchemaType::Switch(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::{string, number, object, feature_gated, StringSchema};
+
+    #[test]
+    fn test_switch_by_context_value() {
+        let schema = SwitchSchema::new(|ctx| ctx.get_str("tenant").map(String::from))
+            .case("acme", string().min_length(5))
+            .otherwise(string());
+
+        let acme = ValidationContext::new().value("tenant", "acme");
+        assert!(schema.validate_with_context(&json!("abc"), &acme).is_err());
+        assert!(schema.validate_with_context(&json!("abcdef"), &acme).is_ok());
+        assert!(schema.validate(&json!("abc")).is_ok());
+    }
+
+    #[test]
+    fn test_switch_without_fallback() {
+        let schema = SwitchSchema::new(|ctx| ctx.get_str("version").map(String::from)).case("v2", number());
+
+        let err = schema.validate(&json!(1)).unwrap_err();
+        assert_eq!(err.context.code, "switch.no_case");
+        let v3 = ValidationContext::new().value("version", "v3");
+        assert_eq!(schema.validate_with_context(&json!(1), &v3).unwrap_err().to_string(), "No schema configured for 'v3'");
+    }
+
+    #[test]
+    fn test_feature_gated_nested_field() {
+        let schema = object()
+            .field("name", string())
+            .field("nickname", feature_gated("nicknames", string().min_length(2), string().optional()));
+
+        let on = ValidationContext::new().flag("nicknames");
+        let err = schema.validate_with_context(&json!({"name": "a", "nickname": "x"}), &on).unwrap_err();
+        assert_eq!(err.context.path, "nickname");
+        assert!(schema.validate(&json!({"name": "a", "nickname": "x"})).is_ok());
+    }
+}
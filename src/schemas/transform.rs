@@ -12,6 +12,8 @@ pub enum Transform {
     ToUpperCase,
     /// Trim whitespace from string
     Trim,
+    /// Trim and replace each run of whitespace with a single space
+    CollapseWhitespace,
     /// Parse string as number
     ParseNumber,
     /// Convert number to integer
@@ -45,26 +47,35 @@ impl Transform {
                     value
                 }
             }
+            Transform::CollapseWhitespace => {
+                if let Value::String(s) = value {
+                    Value::String(s.split_whitespace().collect::<Vec<_>>().join(" "))
+                } else {
+                    value
+                }
+            }
             Transform::ParseNumber => {
                 if let Value::String(s) = &value {
-                    if let Ok(n) = s.trim().parse::<f64>() {
-                        Value::Number(serde_json::Number::from_f64(n).unwrap())
-                    } else {
-                        value
+                    match s.trim().parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+                        Some(n) => Value::Number(n),
+                        None => value,
                     }
                 } else {
                     value
                 }
             }
             Transform::ToInteger => {
-                if let Value::Number(n) = &value {
-                    if let Some(i) = n.as_i64() {
-                        Value::Number(i.into())
-                    } else {
-                        Value::Number(serde_json::Number::from_f64(n.as_f64().unwrap().floor()).unwrap())
+                let num = match &value {
+                    Value::Number(n) if n.is_i64() || n.is_u64() => return value,
+                    Value::Number(n) => n.as_f64(),
+                    Value::String(s) => s.trim().parse::<f64>().ok(),
+                    _ => None,
+                };
+                match num {
+                    Some(f) if f.is_finite() && f.abs() < i64::MAX as f64 => {
+                        Value::Number((f.floor() as i64).into())
                     }
-                } else {
-                    value
+                    _ => value,
                 }
             }
             Transform::ToString => {
@@ -78,6 +89,20 @@ impl Transform {
             }
         }
     }
+
+    /// The transformation in words, as listed by [`super::Schema::explain`]
+    pub(crate) fn describe(&self) -> &'static [&'static str] {
+        match self {
+            Transform::Custom(_) => &["transformed"],
+            Transform::ToLowerCase => &["trimmed", "lowercased"],
+            Transform::ToUpperCase => &["trimmed", "uppercased"],
+            Transform::Trim => &["trimmed"],
+            Transform::CollapseWhitespace => &["whitespace collapsed"],
+            Transform::ParseNumber => &["parsed as a number"],
+            Transform::ToInteger => &["rounded down to an integer"],
+            Transform::ToString => &["converted to a string"],
+        }
+    }
 }
 
 /// A trait for schemas that support transformations
@@ -105,6 +130,11 @@ pub trait Transformable: Sized {
         self.with_transform(Transform::Trim)
     }
 
+    /// Trim and replace each run of whitespace with a single space
+    fn collapse_whitespace(self) -> WithTransform<Self> {
+        self.with_transform(Transform::CollapseWhitespace)
+    }
+
     /// Parse string as number
     fn parse_number(self) -> WithTransform<Self> {
         self.with_transform(Transform::ParseNumber)
@@ -180,18 +210,43 @@ impl<S: super::StringSchema> WithTransform<S> {
         WithTransform::new(self.into_inner().optional()).with_transforms(transforms)
     }
 
+    pub fn presence(mut self, presence: super::Presence) -> Self {
+        let transforms = std::mem::take(&mut self.transforms);
+        WithTransform::new(self.into_inner().presence(presence)).with_transforms(transforms)
+    }
+
     pub fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
         let transforms = std::mem::take(&mut self.transforms);
         WithTransform::new(self.into_inner().error_message(code, message)).with_transforms(transforms)
     }
 
-    pub fn custom<F>(mut self, validator: F) -> Self
+    pub fn custom<F, E>(mut self, validator: F) -> Self
     where
-        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<crate::error::CustomIssue>,
     {
         let transforms = std::mem::take(&mut self.transforms);
         WithTransform::new(self.into_inner().custom(validator)).with_transforms(transforms)
     }
+
+    pub fn custom_labeled<F, E>(mut self, label: impl Into<String>, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<crate::error::CustomIssue>,
+    {
+        let transforms = std::mem::take(&mut self.transforms);
+        WithTransform::new(self.into_inner().custom_labeled(label, validator)).with_transforms(transforms)
+    }
+}
+
+impl<S: super::HasErrorMessages> super::HasErrorMessages for WithTransform<S> {
+    fn error_messages(&self) -> &std::collections::HashMap<String, String> {
+        self.schema.error_messages()
+    }
+
+    fn check_messages(&self) -> Vec<crate::error::MessageIssue> {
+        self.schema.check_messages()
+    }
 }
 
 impl<S: super::Schema> super::Schema for WithTransform<S> {
@@ -199,18 +254,66 @@ impl<S: super::Schema> super::Schema for WithTransform<S> {
         self.schema.is_optional()
     }
 
+    fn get_presence(&self) -> super::Presence {
+        self.schema.get_presence()
+    }
+
+    fn missing_message(&self) -> Option<String> {
+        self.schema.missing_message()
+    }
+
+    fn check_satisfiable(&self) -> Vec<super::Unsatisfiable> {
+        self.schema.check_satisfiable()
+    }
+
+    fn explain(&self) -> Vec<super::Explanation> {
+        let mut rules: Vec<String> = Vec::new();
+        for rule in self.transforms.iter().flat_map(Transform::describe) {
+            if !rules.iter().any(|r| r == rule) {
+                rules.push(rule.to_string());
+            }
+        }
+        super::explain::after_type(self.schema.explain(), rules)
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        self.schema.redact(value)
+    }
+
+    fn lint(&self) -> Vec<super::LintFinding> {
+        self.schema.lint()
+    }
+
+    fn canonical(&self, output: Value) -> Value {
+        self.schema.canonical(output)
+    }
+
+    fn validate_partial_with(&self, value: &Value, invalid: super::InvalidFields) -> super::PartialOutput {
+        let transformed = self.transforms.iter().fold(value.clone(), |v, t| t.apply(v));
+        self.schema.validate_partial_with(&transformed, invalid)
+    }
+
     fn validate(&self, value: &Value) -> Result<Value, crate::error::ValidationError> {
-        let mut value = value.clone();
+        self.validate_owned(value.clone())
+    }
+
+    fn validate_owned(&self, mut value: Value) -> Result<Value, crate::error::ValidationError> {
         // First apply transformations in the order they were added
         for transform in &self.transforms {
             value = transform.apply(value);
         }
         // Then validate the transformed value
-        self.schema.validate(&value)
+        self.schema.validate_owned(value)
     }
 
     fn into_schema_type(self) -> super::SchemaType {
-        self.schema.into_schema_type()
+        if self.transforms.is_empty() {
+            return self.schema.into_schema_type();
+        }
+        super::SchemaType::Transform(Box::new(WithTransform {
+            schema: self.schema.into_schema_type(),
+            transforms: self.transforms,
+        }))
     }
 }
 
@@ -250,6 +353,13 @@ impl<S: super::string::StringSchema> super::string::StringSchema for WithTransfo
         schema
     }
 
+    fn presence(mut self, presence: super::Presence) -> Self {
+        let transforms = std::mem::take(&mut self.transforms);
+        let mut schema = WithTransform::new(self.into_inner().presence(presence));
+        schema.transforms = transforms;
+        schema
+    }
+
     fn error_message(mut self, code: impl Into<String>, message: impl Into<String>) -> Self {
         let transforms = std::mem::take(&mut self.transforms);
         let mut schema = WithTransform::new(self.into_inner().error_message(code, message));
@@ -257,15 +367,27 @@ impl<S: super::string::StringSchema> super::string::StringSchema for WithTransfo
         schema
     }
 
-    fn custom<F>(mut self, validator: F) -> Self
+    fn custom<F, E>(mut self, validator: F) -> Self
     where
-        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<crate::error::CustomIssue>,
     {
         let transforms = std::mem::take(&mut self.transforms);
         let mut schema = WithTransform::new(self.into_inner().custom(validator));
         schema.transforms = transforms;
         schema
     }
+
+    fn custom_labeled<F, E>(mut self, label: impl Into<String>, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), E> + Send + Sync + 'static,
+        E: Into<crate::error::CustomIssue>,
+    {
+        let transforms = std::mem::take(&mut self.transforms);
+        let mut schema = WithTransform::new(self.into_inner().custom_labeled(label, validator));
+        schema.transforms = transforms;
+        schema
+    }
 }
 
 #[cfg(test)]
@@ -274,6 +396,16 @@ mod tests {
     use crate::{string, number, schemas::Schema};
     use serde_json::json;
 
+    #[test]
+    fn test_to_integer_parses_numeric_strings() {
+        assert_eq!(number().to_integer().validate(&json!(" 25 ")).unwrap(), json!(25));
+        assert_eq!(number().to_integer().validate(&json!("2.7")).unwrap(), json!(2));
+        assert!(number().to_integer().validate(&json!("abc")).is_err());
+        assert_eq!(number().to_integer().validate(&json!(-1.5)).unwrap(), json!(-2));
+        assert_eq!(number().to_integer().validate(&json!(u64::MAX)).unwrap(), json!(u64::MAX));
+        assert_eq!(Transform::ToInteger.apply(json!(1e300)), json!(1e300));
+    }
+
     #[test]
     fn test_string_transforms() {
         let schema = string()
@@ -0,0 +1,373 @@
+use std::fmt;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::error::{ParseError, ValidationError};
+use super::{nested, object::FieldEntry, spans, ObjectSchema, Schema};
+
+/// Parse JSON `input` and land it in `T`, validating it with `schema` as
+/// `T` is deserialized
+///
+/// Objects whose fields validate apart are handed to `T` field by field,
+/// each validated as `T` reads it and moved in without building the
+/// validated object first; other values are validated and read from their
+/// output. Validation errors carry source spans as with
+/// [`Schema::validate_str`], and are reported before anything `T` cannot
+/// hold that comes later in the document.
+///
+/// ```
+/// use rusty_zod::prelude::*;
+/// use rusty_zod::from_str_validated;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let schema = object().field("name", string().trim());
+/// let user: User = from_str_validated(r#"{"name": "  Ada "}"#, &schema).unwrap();
+/// assert_eq!(user.name, "Ada");
+/// ```
+pub fn from_str_validated<T, S>(input: &str, schema: &S) -> Result<T, ParseError>
+where
+    T: DeserializeOwned,
+    S: Schema + ?Sized,
+{
+    let value = serde_json::from_str(input).map_err(|e| spans::invalid_json(e, input))?;
+    deserialize(schema, value).map_err(|err| match err {
+        ParseError::Validation(err) => ParseError::Validation(spans::attach_span(err, input)),
+        err => err,
+    })
+}
+
+/// Land `value` in `T`, validating it with `schema` as `T` is deserialized,
+/// as [`from_str_validated`] does
+pub fn from_value_validated<T, S>(value: &Value, schema: &S) -> Result<T, ParseError>
+where
+    T: DeserializeOwned,
+    S: Schema + ?Sized,
+{
+    deserialize(schema, value.clone())
+}
+
+/// Deserialize a field as `Option<Option<T>>`: `None` when the field is
+/// missing, `Some(None)` when it is null
+///
+/// For PATCH-style structs, where a missing field keeps the stored value
+/// and a null one clears it. Needs `#[serde(default)]` so that a missing
+/// field is `None`:
+///
+/// ```
+/// use rusty_zod::prelude::*;
+/// use rusty_zod::double_option;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct UserPatch {
+///     #[serde(default, deserialize_with = "double_option")]
+///     nickname: Option<Option<String>>,
+/// }
+///
+/// let schema = object().field("nickname", string().optional());
+/// let patch: UserPatch = schema.parse(&serde_json::json!({"nickname": null})).unwrap();
+/// assert_eq!(patch.nickname, Some(None));
+/// let patch: UserPatch = schema.parse(&serde_json::json!({})).unwrap();
+/// assert_eq!(patch.nickname, None);
+/// ```
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn deserialize<T, S>(schema: &S, value: Value) -> Result<T, ParseError>
+where
+    T: DeserializeOwned,
+    S: Schema + ?Sized,
+{
+    T::deserialize(Validating { schema, value }).map_err(|e| match e {
+        Error::Validation(err) => ParseError::Validation(err),
+        Error::Deserialize(message) => ParseError::Parse(format!("Failed to deserialize validated output: {}", message)),
+    })
+}
+
+/// Failure of a [`Validating`] deserializer
+#[derive(Debug)]
+enum Error {
+    /// The schema rejected a value
+    Validation(ValidationError),
+    /// `T` cannot hold the validated output
+    Deserialize(String),
+}
+
+impl Error {
+    fn prefixed(self, field: &String) -> Self {
+        match self {
+            Error::Validation(err) => Error::Validation(err.with_path_prefix(field)),
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Validation(err) => err.fmt(f),
+            Error::Deserialize(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<M: fmt::Display>(message: M) -> Self {
+        Error::Deserialize(message.to_string())
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Deserialize(e.to_string())
+    }
+}
+
+/// Deserializer validating `value` with `schema` as it is read
+struct Validating<'s, S: ?Sized> {
+    schema: &'s S,
+    value: Value,
+}
+
+impl<'s, S: Schema + ?Sized> Validating<'s, S> {
+    /// The object schema, when `value` can be read and validated field by
+    /// field with the same outcome as validating it whole
+    fn fields(&self) -> Option<&'s ObjectSchema> {
+        let schema: &'s S = self.schema;
+        let object = schema.as_object().filter(|o| o.validates_fields_apart())?;
+        self.value.is_object().then_some(object)
+    }
+
+    fn output(self) -> Result<Value, Error> {
+        self.schema.validate_owned(self.value).map_err(Error::Validation)
+    }
+
+    fn visit_fields<'de, V: Visitor<'de>>(self, object: &'s ObjectSchema, visitor: V) -> Result<V::Value, Error> {
+        let Value::Object(obj) = self.value else { unreachable!("checked by `fields`") };
+        visitor.visit_map(Fields { entries: object.field_entries(obj), next: None })
+    }
+}
+
+/// Deserialize methods reading the validated output as a whole
+macro_rules! read_output {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Error> {
+                Ok(self.output()?.$method($($arg,)* visitor)?)
+            }
+        )*
+    };
+}
+
+impl<'de, S: Schema + ?Sized> Deserializer<'de> for Validating<'_, S> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.fields() {
+            Some(object) => self.visit_fields(object, visitor),
+            None => Ok(self.output()?.deserialize_any(visitor)?),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.fields() {
+            Some(object) => self.visit_fields(object, visitor),
+            None => Ok(self.output()?.deserialize_map(visitor)?),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.fields() {
+            Some(object) => self.visit_fields(object, visitor),
+            None => Ok(self.output()?.deserialize_struct(name, fields, visitor)?),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.fields() {
+            Some(_) => visitor.visit_some(self),
+            None => Ok(self.output()?.deserialize_option(visitor)?),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.output()?;
+        visitor.visit_unit()
+    }
+
+    read_output! {
+        deserialize_bool(), deserialize_i8(), deserialize_i16(), deserialize_i32(), deserialize_i64(),
+        deserialize_i128(), deserialize_u8(), deserialize_u16(), deserialize_u32(), deserialize_u64(),
+        deserialize_u128(), deserialize_f32(), deserialize_f64(), deserialize_char(), deserialize_str(),
+        deserialize_string(), deserialize_bytes(), deserialize_byte_buf(), deserialize_unit(),
+        deserialize_seq(), deserialize_identifier(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+    }
+}
+
+/// Fields of an object, each validated as its value is read
+struct Fields<'s, I> {
+    entries: I,
+    /// Entry whose key was read last
+    next: Option<FieldEntry<'s>>,
+}
+
+impl<'de, 's, I> MapAccess<'de> for Fields<'s, I>
+where
+    I: Iterator<Item = Result<FieldEntry<'s>, ValidationError>>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+        let Some(entry) = self.entries.next() else { return Ok(None) };
+        let entry = entry.map_err(Error::Validation)?;
+        let key = match &entry {
+            FieldEntry::Declared(field, ..) => (*field).clone(),
+            FieldEntry::Kept(field, _) => field.clone(),
+        };
+        self.next = Some(entry);
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.next.take() {
+            Some(FieldEntry::Declared(field, schema, value)) => {
+                nested(field, || seed.deserialize(Validating { schema, value })).map_err(|e| e.prefixed(field))
+            }
+            Some(FieldEntry::Kept(_, value)) => Ok(seed.deserialize(value)?),
+            None => Err(de::Error::custom("field value read before its key")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+    use crate::{number, object, string};
+    use crate::schemas::StringSchema;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Account {
+        email: String,
+        credits: u32,
+    }
+
+    fn schema() -> crate::ObjectSchema {
+        object().field("email", string().email().to_lowercase()).default_field("credits", number().integer(), 10)
+    }
+
+    #[test]
+    fn test_from_str_validated() {
+        let account: Account = from_str_validated(r#"{"email": "Ada@Example.com"}"#, &schema()).unwrap();
+        assert_eq!(account, Account { email: "ada@example.com".to_string(), credits: 10 });
+
+        let Err(ParseError::Validation(err)) = from_str_validated::<Account, _>(r#"{"email": "ada"}"#, &schema()) else {
+            panic!("expected a validation error");
+        };
+        assert!(err.context.details.span.is_some());
+        // Output the schema accepts but `T` cannot hold
+        let err = from_str_validated::<Account, _>(r#"{"email": "a@b.co", "credits": -1}"#, &schema()).unwrap_err();
+        assert!(matches!(err, ParseError::Parse(_)));
+    }
+
+    #[test]
+    fn test_from_value_validated() {
+        let account: Account = from_value_validated(&json!({"email": "a@b.co", "credits": 3}), &schema()).unwrap();
+        assert_eq!(account.credits, 3);
+        assert!(matches!(from_value_validated::<Account, _>(&json!({}), &schema()), Err(ParseError::Validation(_))));
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Order {
+        id: u32,
+        account: Account,
+        note: Option<Account>,
+    }
+
+    #[test]
+    fn test_validates_fields_as_read() {
+        let schema = object()
+            .field("id", number().integer())
+            .field("account", schema())
+            .optional_field("note", schema())
+            .strict();
+        let order: Order = from_str_validated(r#"{"id": 1, "account": {"email": "A@B.co"}, "note": {"email": "c@d.co"}}"#, &schema).unwrap();
+        assert_eq!(order.account, Account { email: "a@b.co".to_string(), credits: 10 });
+        assert_eq!(order.note.unwrap().credits, 10);
+
+        let Err(ParseError::Validation(err)) = from_value_validated::<Order, _>(&json!({"id": 1, "account": {"email": "a"}}), &schema) else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(err.context.path, "account.email");
+        let Err(ParseError::Validation(err)) = from_value_validated::<Order, _>(&json!({"id": 1, "account": {"email": "a@b.co"}, "x": 1}), &schema) else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(err.context.code, "object.unknown_field");
+        let Err(ParseError::Validation(err)) = from_value_validated::<Order, _>(&json!({"id": 1}), &schema) else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(err.context.path, "account");
+    }
+
+    #[test]
+    fn test_validates_whole_object_with_rules() {
+        let schema = schema().rule(["credits"], |obj| match obj["credits"].as_u64() {
+            Some(credits) if credits > 5 => Err("Too many credits"),
+            _ => Ok(()),
+        });
+        let account: Account = from_value_validated(&json!({"email": "A@b.co", "credits": 5}), &schema).unwrap();
+        assert_eq!(account.email, "a@b.co");
+        assert!(matches!(from_value_validated::<Account, _>(&json!({"email": "a@b.co"}), &schema), Err(ParseError::Validation(_))));
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct AccountPatch {
+        #[serde(default, deserialize_with = "double_option")]
+        nickname: Option<Option<String>>,
+        #[serde(default)]
+        credits: Option<u32>,
+        plan: Option<String>,
+    }
+
+    #[test]
+    fn test_double_option() {
+        let schema = object()
+            .field("nickname", string().trim().optional())
+            .optional_field("credits", number().integer())
+            .default_field("plan", string(), "free");
+        let patch = |value| schema.parse::<AccountPatch>(&value).unwrap();
+        assert_eq!(patch(json!({})), AccountPatch { nickname: None, credits: None, plan: Some("free".to_string()) });
+        assert_eq!(patch(json!({"nickname": null})).nickname, Some(None));
+        assert_eq!(patch(json!({"nickname": " ada ", "credits": 2})).nickname, Some(Some("ada".to_string())));
+        // `credits` may be missing but not null
+        assert!(schema.parse::<AccountPatch>(&json!({"credits": null})).is_err());
+
+        let patch: AccountPatch = from_str_validated(r#"{"nickname": null, "plan": "pro"}"#, &schema).unwrap();
+        assert_eq!((patch.nickname, patch.plan), (Some(None), Some("pro".to_string())));
+    }
+}
@@ -0,0 +1,130 @@
+use std::{cell::RefCell, sync::Arc};
+use serde_json::Value;
+
+use crate::error::{path_format::SharedFormatter, PathFormatter, PathSegment, ValidationError};
+
+/// Output of [`Schema::validate_with_report`](super::Schema::validate_with_report):
+/// the validated value plus non-fatal issues, such as deprecated fields or
+/// soft limits, that did not fail validation
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    pub value: Value,
+    pub warnings: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// [`ValidationError::with_path_formatter`] for every warning
+    pub fn with_path_formatter(mut self, formatter: impl PathFormatter + 'static) -> Self {
+        let formatter = SharedFormatter(Arc::new(formatter));
+        self.warnings = self.warnings.into_iter().map(|w| w.with_shared_formatter(formatter.clone())).collect();
+        self
+    }
+}
+
+thread_local! {
+    static SINKS: RefCell<Vec<Vec<ValidationError>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the innermost sink, also when validation panics
+struct SinkGuard;
+
+impl Drop for SinkGuard {
+    fn drop(&mut self) {
+        SINKS.with(|sinks| sinks.borrow_mut().pop());
+    }
+}
+
+/// Run `f`, collecting every warning raised on this thread meanwhile
+pub(crate) fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<ValidationError>) {
+    SINKS.with(|sinks| sinks.borrow_mut().push(Vec::new()));
+    let guard = SinkGuard;
+    let result = f();
+    let warnings = SINKS.with(|sinks| sinks.borrow_mut().last_mut().map(std::mem::take).unwrap_or_default());
+    drop(guard);
+    (result, warnings)
+}
+
+/// Record a warning; a no-op unless a report is being collected
+pub(crate) fn warn(warning: ValidationError) {
+    SINKS.with(|sinks| {
+        if let Some(sink) = sinks.borrow_mut().last_mut() {
+            sink.push(warning);
+        }
+    });
+}
+
+fn mark() -> usize {
+    SINKS.with(|sinks| sinks.borrow().last().map_or(0, Vec::len))
+}
+
+/// Validate a child value, prefixing the paths of its warnings with `segment`
+pub(crate) fn nested<R>(segment: impl Into<PathSegment>, f: impl FnOnce() -> R) -> R {
+    let start = mark();
+    let result = f();
+    SINKS.with(|sinks| {
+        if let Some(sink) = sinks.borrow_mut().last_mut() {
+            let segment = segment.into();
+            for warning in sink.iter_mut().skip(start) {
+                warning.context.path.prepend(segment.clone());
+            }
+        }
+    });
+    result
+}
+
+/// Validate a union branch, dropping its warnings if it fails
+pub(crate) fn branch<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let start = mark();
+    let result = f();
+    if result.is_err() {
+        SINKS.with(|sinks| {
+            if let Some(sink) = sinks.borrow_mut().last_mut() {
+                sink.truncate(start);
+            }
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::{array, number, object, string, union, Schema};
+
+    #[test]
+    fn test_report_collects_nested_warnings() {
+        let schema = object()
+            .field("name", string().soft_max_length(5))
+            .field("old_id", number().optional().deprecated("use id"))
+            .field("tags", array(string().soft_max_length(3)));
+
+        let report = schema
+            .validate_with_report(&json!({"name": "Bartholomew", "old_id": 7, "tags": ["ok", "toolong"]}))
+            .unwrap();
+        let warnings: Vec<_> = report.warnings.iter().map(|w| (w.context.path.to_string(), w.to_string())).collect();
+        assert_eq!(warnings, [
+            ("name".to_string(), "String is longer than the recommended 5 characters".to_string()),
+            ("old_id".to_string(), "use id".to_string()),
+            ("tags.1".to_string(), "String is longer than the recommended 3 characters".to_string()),
+        ]);
+
+        let report = schema.validate_with_report(&json!({"name": "Bart", "tags": []})).unwrap();
+        assert!(!report.has_warnings());
+        assert!(schema.validate_with_report(&json!({"name": 1, "tags": []})).is_err());
+    }
+
+    #[test]
+    fn test_failed_union_branch_drops_warnings() {
+        let schema = union((
+            object().field("a", string().deprecated("old")).field("b", number()),
+            object().field("a", string()),
+        ));
+        let report = schema.validate_with_report(&json!({"a": "x"})).unwrap();
+        assert!(report.warnings.is_empty());
+        assert!(schema.validate(&json!({"a": "x", "b": 1})).is_ok());
+    }
+}
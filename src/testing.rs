@@ -0,0 +1,168 @@
+//! Assertions for testing schemas, with every error listed on failure:
+//!
+//! ```
+//! use rusty_zod::prelude::*;
+//! use rusty_zod::{assert_invalid, assert_valid};
+//! use serde_json::json;
+//!
+//! let schema = object().field("user", object().field("email", string().email()));
+//! assert_valid!(schema, json!({"user": {"email": "ada@example.com"}}));
+//! assert_invalid!(schema, json!({"user": {"email": "ada"}}), code = "string.email", path = "user.email");
+//! ```
+
+use std::fmt::Write;
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::schemas::Schema;
+
+/// What [`assert_invalid!`](crate::assert_invalid) expects of one of the errors
+#[derive(Debug, Default, Clone)]
+pub struct Expected {
+    pub code: Option<String>,
+    pub path: Option<String>,
+}
+
+impl Expected {
+    fn matches(&self, err: &ValidationError) -> bool {
+        self.code.as_ref().is_none_or(|code| err.context.code == *code)
+            && self.path.as_ref().is_none_or(|path| err.context.path.to_string() == *path)
+    }
+}
+
+/// Every error `schema` reports for `value`, as partial validation finds them
+fn all_errors(schema: &impl Schema, value: &Value) -> Vec<ValidationError> {
+    let errors = schema.validate_partial(value).errors;
+    if errors.is_empty() {
+        // Checks that partial validation skips, e.g. custom ones
+        return schema.validate(value).err().into_iter().collect();
+    }
+    errors
+}
+
+fn describe(err: &ValidationError) -> String {
+    let path = err.context.path.to_string();
+    let path = if path.is_empty() { "(root)" } else { path.as_str() };
+    format!("{}: {} \"{}\"", path, err.context.code, err)
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Backs [`assert_valid!`](crate::assert_valid); returns the validated output
+#[track_caller]
+pub fn assert_valid(schema: &impl Schema, value: &Value) -> Value {
+    match schema.validate(value) {
+        Ok(output) => output,
+        Err(_) => {
+            let mut message = String::from("expected value to be valid\n\nvalue:\n");
+            message.push_str(&pretty(value));
+            message.push_str("\n\nerrors:\n");
+            for err in all_errors(schema, value) {
+                let _ = writeln!(message, "  {}", describe(&err));
+            }
+            panic!("{}", message);
+        }
+    }
+}
+
+/// Backs [`assert_invalid!`](crate::assert_invalid); returns the first error
+/// matching `expected`
+#[track_caller]
+pub fn assert_invalid(schema: &impl Schema, value: &Value, expected: Expected) -> ValidationError {
+    let errors = all_errors(schema, value);
+    if let Some(err) = errors.iter().find(|err| expected.matches(err)) {
+        return err.clone();
+    }
+
+    let mut message = String::from("expected value to be invalid\n\nvalue:\n");
+    message.push_str(&pretty(value));
+    if errors.is_empty() {
+        let output = schema.validate(value).map(|v| pretty(&v)).unwrap_or_default();
+        let _ = write!(message, "\n\nbut it passed, with output:\n{}", output);
+        panic!("{}", message);
+    }
+    let want = format!(
+        "{}: {}",
+        expected.path.as_deref().map_or("(any path)", |p| if p.is_empty() { "(root)" } else { p }),
+        expected.code.as_deref().unwrap_or("(any code)"),
+    );
+    let _ = write!(message, "\n\nno error matched:\n- {}\n", want);
+    for err in &errors {
+        let _ = writeln!(message, "+ {}", describe(err));
+    }
+    panic!("{}", message);
+}
+
+/// Assert that `schema` accepts `value`, listing every error otherwise;
+/// evaluates to the validated output
+#[macro_export]
+macro_rules! assert_valid {
+    ($schema:expr, $value:expr $(,)?) => {
+        $crate::testing::assert_valid(&$schema, &$value)
+    };
+}
+
+/// Assert that `schema` rejects `value`, optionally with an error of the
+/// given `code` and/or at the given `path`; evaluates to that error
+///
+/// `assert_invalid!(schema, json!(...), code = "string.email", path = "user.email")`
+#[macro_export]
+macro_rules! assert_invalid {
+    ($schema:expr, $value:expr $(, code = $code:expr)? $(, path = $path:expr)? $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut expected = $crate::testing::Expected::default();
+        $( expected.code = Some(::std::string::ToString::to_string(&$code)); )?
+        $( expected.path = Some(::std::string::ToString::to_string(&$path)); )?
+        $crate::testing::assert_invalid(&$schema, &$value, expected)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::{number, object, string};
+    use crate::schemas::StringSchema;
+
+    fn user() -> crate::ObjectSchema {
+        object()
+            .field("name", string().min_length(1))
+            .field("age", number().min(0.0))
+    }
+
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = std::panic::catch_unwind(f).unwrap_err();
+        payload.downcast_ref::<String>().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_assert_macros_pass() {
+        let output = assert_valid!(user(), json!({"name": "Ada", "age": 36}));
+        assert_eq!(output["name"], "Ada");
+
+        let err = assert_invalid!(user(), json!({"name": "", "age": -1}), path = "age");
+        assert_eq!(err.context.code, "number.too_small");
+        assert_invalid!(user(), json!({"name": "", "age": 1}), code = "string.too_short", path = "name");
+        assert_invalid!(user(), json!(null));
+    }
+
+    #[test]
+    fn test_assert_macros_report_every_error() {
+        let message = panic_message(|| {
+            assert_valid!(user(), json!({"name": "", "age": -1}));
+        });
+        assert!(message.contains("  name: string.too_short"), "{}", message);
+        assert!(message.contains("  age: number.too_small"), "{}", message);
+
+        let message = panic_message(|| {
+            assert_invalid!(user(), json!({"name": "", "age": 1}), code = "string.email");
+        });
+        assert!(message.contains("- (any path): string.email\n+ name: string.too_short"), "{}", message);
+
+        let message = panic_message(|| {
+            assert_invalid!(user(), json!({"name": "Ada", "age": 1}));
+        });
+        assert!(message.contains("but it passed"), "{}", message);
+    }
+}
@@ -0,0 +1,146 @@
+//! actix-web extractor validating JSON bodies:
+//!
+//! ```ignore
+//! use rusty_zod::web::actix::{BodySchema, ValidatedJson};
+//!
+//! impl BodySchema for NewUser {
+//!     fn schema() -> &'static SchemaType {
+//!         static SCHEMA: OnceLock<SchemaType> = OnceLock::new();
+//!         SCHEMA.get_or_init(|| object().field("name", string().min_length(1)).into_schema_type())
+//!     }
+//! }
+//!
+//! async fn create(user: ValidatedJson<NewUser>) -> impl Responder { ... }
+//! ```
+
+use std::{future::Future, pin::Pin};
+
+use actix_web::{dev::Payload, http::StatusCode, web, FromRequest, HttpRequest, HttpResponse, ResponseError};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::error::{ValidationError, ValidationErrors};
+use crate::schemas::{Schema, SchemaType};
+
+/// Schema a request body type is validated with by [`ValidatedJson`]
+pub trait BodySchema: DeserializeOwned {
+    /// Built once and reused, e.g. from a `OnceLock`
+    fn schema() -> &'static SchemaType;
+}
+
+/// A JSON body that passed `T`'s [`BodySchema`], deserialized from the
+/// validated output
+///
+/// Malformed JSON is rejected as `web::Json` would; invalid bodies get a
+/// `422` listing every issue.
+#[derive(Debug, Clone)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> ValidatedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for ValidatedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: BodySchema + 'static> FromRequest for ValidatedJson<T> {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<Value>::from_request(req, payload);
+        Box::pin(async move {
+            let body = json.await?.into_inner();
+            let data = T::schema().safe_parse::<T>(&body).into_result()?;
+            Ok(ValidatedJson(data))
+        })
+    }
+}
+
+impl ResponseError for ValidationErrors {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::UNPROCESSABLE_ENTITY)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .content_type("application/problem+json")
+            .body(self.to_problem_details().to_string())
+    }
+}
+
+impl ResponseError for ValidationError {
+    fn status_code(&self) -> StatusCode {
+        ValidationErrors::from(self.clone()).status_code()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        ValidationErrors::from(self.clone()).error_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::OnceLock;
+    use actix_web::{body::to_bytes, test::TestRequest};
+    use serde::Deserialize;
+    use crate::{number, object, string};
+
+    #[derive(Debug, Deserialize)]
+    struct NewUser {
+        name: String,
+        age: u32,
+    }
+
+    impl BodySchema for NewUser {
+        fn schema() -> &'static SchemaType {
+            static SCHEMA: OnceLock<SchemaType> = OnceLock::new();
+            SCHEMA.get_or_init(|| {
+                object()
+                    .field("name", string().trim().min_length(1))
+                    .field("age", number().integer().min(0.0))
+                    .into_schema_type()
+            })
+        }
+    }
+
+    async fn extract(body: &str) -> Result<ValidatedJson<NewUser>, actix_web::Error> {
+        let (req, mut payload) = TestRequest::post()
+            .insert_header(("content-type", "application/json"))
+            .set_payload(body.to_string())
+            .to_http_parts();
+        ValidatedJson::<NewUser>::from_request(&req, &mut payload).await
+    }
+
+    #[actix_web::test]
+    async fn test_validated_json() {
+        let user = extract(r#"{"name": " Ada ", "age": 36}"#).await.unwrap();
+        assert_eq!(user.name, "Ada");
+        assert_eq!(user.age, 36);
+
+        let response = extract(r#"{"name": "", "age": -1}"#).await.unwrap_err().error_response();
+        assert_eq!(response.status().as_u16(), 422);
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["errors"].as_array().unwrap().len(), 2);
+        assert_eq!(body["errors"][0]["pointer"], "/name");
+
+        let response = extract("{").await.unwrap_err().error_response();
+        assert_eq!(response.status().as_u16(), 400);
+    }
+
+    #[test]
+    fn test_unsupported_media_type_response() {
+        let errors = crate::validate_body_partial("text/plain", b"name=Ada", &string()).unwrap_err();
+        assert_eq!(errors.error_response().status().as_u16(), 415);
+        assert_eq!(errors.into_vec()[0].status_code().as_u16(), 415);
+    }
+}
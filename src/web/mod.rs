@@ -0,0 +1,15 @@
+//! Adapters validating request bodies in web frameworks, each behind the
+//! feature of the same name; failures are answered with `422` and an
+//! RFC 7807 body (see [`ValidationErrors::to_problem_details`](crate::ValidationErrors::to_problem_details)),
+//! or `415` when the body's content type cannot be validated
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "warp")]
+pub mod warp;
+#[cfg(feature = "tower")]
+pub mod tower;
+
+/// Largest request body the adapters read before answering `413`, unless
+/// configured otherwise; 2 MiB, as in axum and actix-web
+pub const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
@@ -0,0 +1,242 @@
+//! tower middleware validating request bodies, for any tower-based stack
+//! such as axum, tonic-web or hyper:
+//!
+//! ```ignore
+//! use rusty_zod::web::tower::{ValidateLayer, ValidatedBody};
+//!
+//! let app = Router::new()
+//!     .route("/users", post(|Extension(user): Extension<NewUser>| async move { ... }))
+//!     .layer(ValidateLayer::new(object().field("name", string().min_length(1))).parse::<NewUser>());
+//! ```
+
+use std::{future::Future, pin::Pin, sync::Arc, task::{Context, Poll}};
+
+use bytes::Bytes;
+use http::{header, Extensions, HeaderValue, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::error::{ErrorCode, ValidationError, ValidationErrors};
+use crate::schemas::{body::validate_body_partial, Schema, SchemaType};
+use super::DEFAULT_MAX_BODY_BYTES;
+
+/// Validated output of a request body, stored in the request extensions
+/// by [`ValidateService`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedBody(pub Value);
+
+type Parse = dyn Fn(&Value, &mut Extensions) -> Result<(), ValidationError> + Send + Sync;
+
+/// Layer wrapping services in a [`ValidateService`]
+#[derive(Clone)]
+pub struct ValidateLayer {
+    schema: Arc<SchemaType>,
+    parse: Option<Arc<Parse>>,
+    max_body_bytes: usize,
+}
+
+impl ValidateLayer {
+    pub fn new(schema: impl Schema) -> Self {
+        Self { schema: Arc::new(schema.into_schema_type()), parse: None, max_body_bytes: DEFAULT_MAX_BODY_BYTES }
+    }
+
+    /// Largest body read, [`DEFAULT_MAX_BODY_BYTES`] by default; longer
+    /// bodies are answered with `413` without being buffered
+    pub fn max_body_bytes(mut self, limit: usize) -> Self {
+        self.max_body_bytes = limit;
+        self
+    }
+
+    /// Also deserialize the validated output into `T`, stored in the
+    /// request extensions next to the [`ValidatedBody`]
+    ///
+    /// Output `T` cannot hold is answered like an invalid body, as
+    /// `deserialize_failed`.
+    pub fn parse<T>(mut self) -> Self
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        self.parse = Some(Arc::new(|value, extensions| {
+            let data = T::deserialize(value).map_err(|e| {
+                ValidationError::new(ErrorCode::DeserializeFailed).message(format!("Failed to deserialize: {}", e))
+            })?;
+            extensions.insert(data);
+            Ok(())
+        }));
+        self
+    }
+
+    /// Validate `body` by the request's content type, JSON if it has none,
+    /// and store the output in `extensions`
+    fn check(&self, body: &[u8], extensions: &mut Extensions, content_type: &str) -> Result<(), ValidationErrors> {
+        let value = validate_body_partial(content_type, body, self.schema.as_ref())?;
+        if let Some(parse) = &self.parse {
+            parse(&value, extensions)?;
+        }
+        extensions.insert(ValidatedBody(value));
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for ValidateLayer {
+    type Service = ValidateService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ValidateService { inner, layer: self.clone() }
+    }
+}
+
+/// Service buffering and validating request bodies before passing them on
+///
+/// The body reaches the inner service as received, with the validated
+/// output in its extensions as a [`ValidatedBody`]. Invalid bodies get a
+/// `422` with an RFC 7807 body listing every issue instead, bodies in a
+/// content type that cannot be validated a `415` with the same kind of
+/// body, bodies over [`ValidateLayer::max_body_bytes`] a `413`, and bodies
+/// that cannot be read a `400`.
+#[derive(Clone)]
+pub struct ValidateService<S> {
+    inner: S,
+    layer: ValidateLayer,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for ValidateService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: Body + From<Bytes> + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    ResBody: From<String>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // Call the service that was polled ready, leaving its clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let bytes = match Limited::new(body, layer.max_body_bytes).collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(e) if e.is::<LengthLimitError>() => return Ok(response(StatusCode::PAYLOAD_TOO_LARGE, String::new())),
+                Err(_) => return Ok(response(StatusCode::BAD_REQUEST, String::new())),
+            };
+            let content_type = parts.headers.get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("application/json");
+            match layer.check(&bytes, &mut parts.extensions, content_type) {
+                Ok(()) => inner.call(Request::from_parts(parts, B::from(bytes))).await,
+                Err(errors) => {
+                    let status = StatusCode::from_u16(errors.http_status()).unwrap_or(StatusCode::UNPROCESSABLE_ENTITY);
+                    let mut response = response(status, errors.to_problem_details().to_string());
+                    response.headers_mut().insert(header::CONTENT_TYPE, HeaderValue::from_static("application/problem+json"));
+                    Ok(response)
+                }
+            }
+        })
+    }
+}
+
+fn response<B: From<String>>(status: StatusCode, body: String) -> Response<B> {
+    let mut response = Response::new(B::from(body));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{convert::Infallible, future::{poll_fn, ready, Ready}};
+    use http_body_util::Full;
+    use serde::Deserialize;
+    use crate::{number, object, string};
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct NewUser {
+        name: String,
+    }
+
+    /// Answers with the extensions the layer stored
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Service<Request<Full<Bytes>>> for Echo {
+        type Response = Response<String>;
+        type Error = Infallible;
+        type Future = Ready<Result<Response<String>, Infallible>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<Full<Bytes>>) -> Self::Future {
+            let body = request.extensions().get::<ValidatedBody>().unwrap().0.to_string();
+            let user = request.extensions().get::<NewUser>().unwrap();
+            ready(Ok(Response::new(format!("{} {}", user.name, body))))
+        }
+    }
+
+    async fn send(body: &'static str) -> Response<String> {
+        send_to(ValidateLayer::new(object().field("name", string().trim().min_length(1)).field("age", number())), body).await
+    }
+
+    async fn send_to(layer: ValidateLayer, body: &'static str) -> Response<String> {
+        send_request(layer, Request::new(Full::from(body))).await
+    }
+
+    async fn send_request(layer: ValidateLayer, request: Request<Full<Bytes>>) -> Response<String> {
+        let mut service = layer.parse::<NewUser>().layer(Echo);
+        poll_fn(|cx| service.poll_ready(cx)).await.unwrap();
+        service.call(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validate_layer() {
+        let response = send(r#"{"name": " Ada ", "age": 36}"#).await;
+        assert_eq!(response.status(), 200);
+        let (name, body) = response.body().split_once(' ').unwrap();
+        assert_eq!(name, "Ada");
+        assert_eq!(serde_json::from_str::<Value>(body).unwrap(), serde_json::json!({"name": "Ada", "age": 36}));
+
+        let response = send(r#"{"name": "", "age": 36}"#).await;
+        assert_eq!(response.status(), 422);
+        assert_eq!(response.headers()["content-type"], "application/problem+json");
+        let body: Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(body["errors"][0]["pointer"], "/name");
+        assert_eq!(send("{").await.status(), 422);
+
+        let response = send(r#"{"name": "", "age": "x"}"#).await;
+        let body: Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(body["errors"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_validate_layer_body_limit() {
+        let layer = || ValidateLayer::new(object().field("name", string()));
+        assert_eq!(send_to(layer().max_body_bytes(8), r#"{"name": "Ada Lovelace"}"#).await.status(), 413);
+        assert_eq!(send_to(layer().max_body_bytes(32), r#"{"name": "Ada Lovelace"}"#).await.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_validate_layer_unsupported_media_type() {
+        let request = Request::builder()
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Full::from("name=Ada"))
+            .unwrap();
+        let response = send_request(ValidateLayer::new(object().field("name", string())), request).await;
+        assert_eq!(response.status(), 415);
+        assert_eq!(response.headers()["content-type"], "application/problem+json");
+        let body: Value = serde_json::from_str(response.body()).unwrap();
+        assert_eq!((&body["status"], &body["errors"][0]["code"]), (&serde_json::json!(415), &serde_json::json!("parse.unsupported_media_type")));
+    }
+}
@@ -0,0 +1,123 @@
+//! warp filter validating JSON bodies:
+//!
+//! ```ignore
+//! use rusty_zod::web::warp::{handle_rejection, with_schema};
+//!
+//! let route = warp::post()
+//!     .and(with_schema(object().field("name", string().min_length(1))))
+//!     .map(|body: serde_json::Value| warp::reply::json(&body))
+//!     .recover(handle_rejection);
+//! ```
+
+use std::sync::Arc;
+
+use ::warp::{http::StatusCode, reject::Reject, Filter, Rejection, Reply};
+use serde_json::Value;
+
+use crate::error::ValidationErrors;
+use crate::schemas::Schema;
+use super::DEFAULT_MAX_BODY_BYTES;
+
+impl Reject for ValidationErrors {}
+
+/// Extract the JSON body validated by `schema`, as its output
+///
+/// Bodies failing validation are rejected with the [`ValidationErrors`]
+/// listing every issue, which [`handle_rejection`] turns into a `422`.
+/// Bodies are limited to [`DEFAULT_MAX_BODY_BYTES`], see
+/// [`with_schema_limit`].
+pub fn with_schema<S>(schema: S) -> impl Filter<Extract = (Value,), Error = Rejection> + Clone
+where
+    S: Schema + Send + Sync + 'static,
+{
+    with_schema_limit(schema, DEFAULT_MAX_BODY_BYTES)
+}
+
+/// [`with_schema`] reading at most `max_body_bytes`
+///
+/// The limit is checked against `Content-Length` before the body is read:
+/// longer bodies are rejected as `413 Payload Too Large`, and bodies
+/// without the header as `411 Length Required`.
+pub fn with_schema_limit<S>(schema: S, max_body_bytes: usize) -> impl Filter<Extract = (Value,), Error = Rejection> + Clone
+where
+    S: Schema + Send + Sync + 'static,
+{
+    let schema = Arc::new(schema);
+    let limit = ::warp::body::content_length_limit(max_body_bytes as u64);
+    limit.and(::warp::body::json::<Value>()).and_then(move |body: Value| {
+        let schema = schema.clone();
+        async move {
+            let output = schema.validate_partial(&body);
+            match output.is_valid() {
+                true => Ok(output.value.unwrap_or(Value::Null)),
+                false => Err(::warp::reject::custom(ValidationErrors::from(output.errors))),
+            }
+        }
+    })
+}
+
+/// Recover [`with_schema`] rejections as `422` responses with an RFC 7807
+/// body; other rejections are passed on
+pub async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    let Some(errors) = rejection.find::<ValidationErrors>() else {
+        return Err(rejection);
+    };
+    let status = StatusCode::from_u16(errors.http_status()).unwrap_or(StatusCode::UNPROCESSABLE_ENTITY);
+    let reply = ::warp::reply::with_status(::warp::reply::json(&errors.to_problem_details()), status);
+    Ok(::warp::reply::with_header(reply, "content-type", "application/problem+json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{number, object, string};
+
+    fn route() -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+        let schema = object()
+            .field("name", string().trim().min_length(1))
+            .field("age", number().min(0.0));
+        with_schema(schema)
+            .map(|body: Value| ::warp::reply::json(&body))
+            .recover(handle_rejection)
+    }
+
+    #[tokio::test]
+    async fn test_with_schema() {
+        let response = ::warp::test::request()
+            .method("POST")
+            .json(&serde_json::json!({"name": " Ada ", "age": 36}))
+            .reply(&route())
+            .await;
+        assert_eq!(response.status(), 200);
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["name"], "Ada");
+
+        let response = ::warp::test::request()
+            .method("POST")
+            .json(&serde_json::json!({"name": "", "age": -1}))
+            .reply(&route())
+            .await;
+        assert_eq!(response.status(), 422);
+        assert_eq!(response.headers()["content-type"], "application/problem+json");
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["errors"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_with_schema_limit() {
+        let route = with_schema_limit(object().field("name", string()), 16).map(|body: Value| ::warp::reply::json(&body));
+        let response = ::warp::test::request()
+            .method("POST")
+            .json(&serde_json::json!({"name": "Ada Lovelace"}))
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), 413);
+
+        let response = ::warp::test::request()
+            .method("POST")
+            .json(&serde_json::json!({"name": "Ada"}))
+            .reply(&route)
+            .await;
+        assert_eq!(response.status(), 200);
+    }
+}